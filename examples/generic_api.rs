@@ -9,14 +9,14 @@ extern crate coinnect;
 use coinnect::coinnect::Coinnect;
 use coinnect::kraken::KrakenCreds;
 use coinnect::exchange::Exchange::*;
-use coinnect::types::Pair::*;
+use coinnect::types::Pair;
 
 fn main() {
     // We create a Coinnect Generic API
     // Since Kraken does not need customer_id field, we set it to None
     let my_creds = KrakenCreds::new("my_optionnal_name", "api_key", "api_secret");
-    let mut my_api = Coinnect::new(Kraken, my_creds).unwrap();
-    let ticker = my_api.ticker(ETC_BTC);
+    let my_api = Coinnect::new(Kraken, my_creds).unwrap();
+    let ticker = my_api.ticker(Pair::ETC_BTC);
 
     println!("ETC_BTC last trade price is {}.",
              ticker.unwrap().last_trade_price);
@@ -9,14 +9,13 @@ extern crate coinnect;
 use std::path::PathBuf;
 
 use coinnect::kraken::{KrakenApi, KrakenCreds};
-use std::error::Error;
 
 fn main() {
     // We create a KrakenApi by loading a json file containing API configuration
     // (see documentation for more info)
     let path = PathBuf::from("keys_real.json");
     let my_creds = KrakenCreds::new_from_file("account_kraken", path).unwrap();
-    let mut my_api = KrakenApi::new(my_creds).unwrap();
+    let my_api = KrakenApi::new(my_creds).unwrap();
 
 
 
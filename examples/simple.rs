@@ -8,7 +8,7 @@ fn main() {
     // We create a PoloniexApi by providing API key/secret
     // You can give an empty String if you only use public methods
     let creds = PoloniexCreds::new("my_optionnal_name", "api_key", "api_secret");
-    let mut my_api = PoloniexApi::new(creds).unwrap();
+    let my_api = PoloniexApi::new(creds).unwrap();
 
     // Let's look at the ticker!
     let list_coins = my_api.return_ticker().unwrap();
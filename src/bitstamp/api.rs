@@ -1,162 +1,72 @@
 //! Use this module to interact with Bitstamp exchange.
 //! Please see examples for more informations.
 
-
-use hyper_native_tls::NativeTlsClient;
-use hyper::Client;
-use hyper::header::ContentType;
-use hyper::net::HttpsConnector;
-
 use serde_json::Value;
 use serde_json::value::Map;
 
-use std::collections::HashMap;
-use std::io::Read;
-use std::thread;
-use std::time::Duration;
+use tokio::runtime::Runtime;
 
-use crate::coinnect::Credentials;
-use crate::exchange::Exchange;
+use std::fmt;
 
+use crate::client::ClientConfig;
+use crate::coinnect::Credentials;
 use crate::error::*;
 use crate::helpers;
-use crate::types::Pair;
-use crate::bitstamp::utils;
-use crate::types::*;
-
-header! {
-    #[doc(hidden)]
-    (KeyHeader, "Key") => [String]
-}
-
-header! {
-    #[doc(hidden)]
-    (SignHeader, "Sign") => [String]
-}
-
-header! {
-    #[doc(hidden)]
-    (ContentHeader, "Content-Type") => [String]
-}
-
-#[derive(Debug)]
+use crate::types::{Currency, Pair, Price, Volume};
+use crate::bitstamp::async_api::{AsyncBitstampApi, AuthMode};
+use crate::bitstamp::typed_api::BitstampTypedApi;
+use crate::transport::{BlockingTransport, HttpTransport, HyperTransport};
+
+/// Blocking client of the Bitstamp API. Every call runs the `AsyncBitstampApi` method of the
+/// same name to completion on a runtime owned by the client, so it must not be used from an
+/// asynchronous context.
 pub struct BitstampApi {
-    last_request: i64, // unix timestamp in ms, to avoid ban
-    api_key: String,
-    api_secret: String,
-    customer_id: String,
-    http_client: Client,
-    burst: bool,
+    pub(crate) inner: AsyncBitstampApi,
+    pub(crate) runtime: Runtime,
 }
 
-
 impl BitstampApi {
     /// Create a new BitstampApi by providing an API key & API secret
     pub fn new<C: Credentials>(creds: C) -> Result<BitstampApi> {
-        if creds.exchange() != Exchange::Bitstamp {
-            return Err(ErrorKind::InvalidConfigType(Exchange::Bitstamp, creds.exchange()).into());
-        }
-
-        //TODO: Handle correctly TLS errors with error_chain.
-        let ssl = match NativeTlsClient::new() {
-            Ok(res) => res,
-            Err(_) => return Err(ErrorKind::TlsError.into()),
-        };
-
-        let connector = HttpsConnector::new(ssl);
+        BitstampApi::new_with_transport(creds, Box::new(HyperTransport::new()?))
+    }
 
+    /// Create a new BitstampApi sending its requests through the given transport
+    pub fn new_with_transport<C: Credentials>(creds: C,
+                                              transport: Box<dyn HttpTransport>)
+                                              -> Result<BitstampApi> {
+        let transport = BlockingTransport::from(transport);
 
         Ok(BitstampApi {
-               last_request: 0,
-               api_key: creds.get("api_key").unwrap_or_default(),
-               api_secret: creds.get("api_secret").unwrap_or_default(),
-               customer_id: creds.get("customer_id").unwrap_or_default(),
-               http_client: Client::with_connector(connector),
-               burst: false, // No burst by default
+               inner: AsyncBitstampApi::new_with_transport(creds, Box::new(transport))?,
+               runtime: helpers::new_blocking_runtime()?,
            })
     }
 
-    /// The number of calls in a given period is limited. In order to avoid a ban we limit
-    /// by default the number of api requests.
-    /// This function sets or removes the limitation.
-    /// Burst false implies no block.
-    /// Burst true implies there is a control over the number of calls allowed to the exchange
-    pub fn set_burst(&mut self, burst: bool) {
-        self.burst = burst
+    /// Return the settings of the client: its RateLimiter, RetryPolicy and NonceProvider.
+    pub fn config(&self) -> &ClientConfig {
+        self.inner.config()
     }
 
-    fn block_or_continue(&self) {
-        if ! self.burst {
-            let threshold: u64 = 1000; // 600 requests per 10 mins = 1 request per second
-            let offset: u64 = helpers::get_unix_timestamp_ms() as u64 - self.last_request as u64;
-            if offset < threshold {
-                let wait_ms = Duration::from_millis(threshold - offset);
-                thread::sleep(wait_ms);
-            }
-        }
+    /// Change the settings of the client, ie. `api.config_mut().set_retry_policy(policy)`.
+    pub fn config_mut(&mut self) -> &mut ClientConfig {
+        self.inner.config_mut()
     }
 
-    fn public_query(&mut self, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
-
-        let method: &str = params
-            .get("method")
-            .ok_or_else(|| "Missing \"method\" field.")?;
-        let pair: &str = params.get("pair").ok_or_else(|| "Missing \"pair\" field.")?;
-        let url: String = utils::build_url(method, pair);
-
-        self.block_or_continue();
-        let mut response = self.http_client.get(&url).send()?;
-        self.last_request = helpers::get_unix_timestamp_ms();
-        let mut buffer = String::new();
-        response.read_to_string(&mut buffer)?;
-        utils::deserialize_json(&buffer)
+    pub fn auth_mode(&self) -> AuthMode {
+        self.inner.auth_mode()
     }
 
-    ///
-    ///
-    /// #Examples
-    ///
-    /// ```json
-    /// extern crate coinnect;
-    /// use coinnect::bitstamp::BitstampApi;
-    /// let mut api = BitstampApi::new("", "");
-    /// let  result = api.private_query("balance", "btcusd");
-    /// assert_eq!(true, true);
-    /// ```
-    fn private_query(&mut self, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
-
-        let method: &str = params
-            .get("method")
-            .ok_or_else(|| "Missing \"method\" field.")?;
-        let pair: &str = params.get("pair").ok_or_else(|| "Missing \"pair\" field.")?;
-        let url: String = utils::build_url(method, pair);
-
-        let nonce = utils::generate_nonce(None);
-        let signature =
-            utils::build_signature(&nonce, &self.customer_id, &self.api_key, &self.api_secret)?;
-
-        let copy_api_key = self.api_key.clone();
-        let mut post_params: &mut HashMap<&str, &str> = &mut HashMap::new();
-        post_params.insert("key", &copy_api_key);
-        post_params.insert("signature", &signature);
-        post_params.insert("nonce", &nonce);
-
-        // copy params into post_params .... bit of a hack but will do for now
-        params.iter().for_each(|(k,v)| {
-            post_params.insert(k,v);
-        });
-
-        helpers::strip_empties(&mut post_params);
-        let post_data = helpers::url_encode_hashmap(post_params);
-        let mut response = self.http_client
-            .post(&url)
-            .header(ContentType::form_url_encoded())
-            .body(&post_data)
-            .send()?;
+    /// Choose how the private requests are authenticated. The customer id is not used by
+    /// `AuthMode::Header`.
+    pub fn set_auth_mode(&mut self, auth_mode: AuthMode) {
+        self.inner.set_auth_mode(auth_mode)
+    }
 
-        let mut buffer = String::new();
-        response.read_to_string(&mut buffer)?;
-        utils::deserialize_json(&buffer)
+    /// Return a view of the client whose methods return the typed structs of the `responses`
+    /// module instead of JSON maps.
+    pub fn typed(&self) -> BitstampTypedApi<'_> {
+        BitstampTypedApi::new(self)
     }
 
     /// Sample output :
@@ -171,16 +81,8 @@ impl BitstampApi {
     /// "percentChange":"0.16701570","baseVolume":"0.45347489","quoteVolume":"9094"},
     /// ... }
     /// ```
-    pub fn return_ticker(&mut self, pair: Pair) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let mut params: HashMap<&str, &str> = HashMap::new();
-        params.insert("pair", pair_name);
-        params.insert("method", "ticker");
-        self.public_query(&params)
+    pub fn return_ticker(&self, pair: Pair) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_ticker(pair))
     }
 
     /// Sample output :
@@ -189,213 +91,219 @@ impl BitstampApi {
     /// {"asks":[[0.00007600,1164],[0.00007620,1300], ... ], "bids":[[0.00006901,200],
     /// [0.00006900,408], ... ], "timestamp": "1234567890"}
     /// ```
-    pub fn return_order_book(&mut self, pair: Pair) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-
-        };
-
-        let mut params: HashMap<&str, &str> = HashMap::new();
-        params.insert("method", "order_book");
-        params.insert("pair", pair_name);
-        self.public_query(&params)
+    pub fn return_order_book(&self, pair: Pair) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_order_book(pair))
     }
 
+    /// Returns the pairs listed by Bitstamp and their trading rules. The array is returned in the
+    /// "data" field of the map.
+    ///
     /// Sample output :
     ///
     /// ```json
-    /// [{"date":"2014-02-10 04:23:23","type":"buy","rate":"0.00007600","amount":"140",
-    /// "total":"0.01064"},
-    /// {"date":"2014-02-10 01:19:37","type":"buy","rate":"0.00007600","amount":"655",
-    /// "total":"0.04978"}, ... ]
+    /// [{"name": "LTC/USD", "url_symbol": "ltcusd", "base_decimals": 8, "counter_decimals": 2,
+    /// "instant_order_counter_decimals": 2, "minimum_order": "5.0 USD", "trading": "Enabled",
+    /// "instant_and_market_orders": "Enabled", "description": "Litecoin / U.S. dollar"}, ... ]
     /// ```
-    pub fn return_trade_history(&mut self, pair: Pair) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let mut params: HashMap<&str, &str> = HashMap::new();
-        params.insert("pair", pair_name);
-        params.insert("method", "transactions");
-        self.public_query(&params)
+    pub fn return_trading_pairs_info(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_trading_pairs_info())
     }
 
+    /// Returns the transactions of the last hour, most recent first ("type" is 0 for buy and 1
+    /// for sell). The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"date": "1509099072", "tid": "24311342", "price": "5812.99", "type": "0",
+    /// "amount": "0.04000000"},
+    /// {"date": "1509099060", "tid": "24311341", "price": "5812.98", "type": "1",
+    /// "amount": "0.22000000"}, ... ]
+    /// ```
+    pub fn return_trade_history(&self, pair: Pair) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_trade_history(pair))
+    }
 
-    /// Returns all of your available balances.
+    /// Returns your balances: the total, available and reserved amounts of each currency, the
+    /// withdrawal fee of each currency and your trading fee on each pair (in percent).
     ///
     /// Sample output:
     ///
     /// ```json
-    /// {"BTC":"0.59098578","LTC":"3.31117268", ... }
+    /// {"usd_balance": "102.50", "usd_available": "80.25", "usd_reserved": "22.25",
+    /// "btc_balance": "0.59098578", "btc_available": "0.59098578", "btc_reserved": "0.00000000",
+    /// "btc_withdrawal_fee": "0.00050000", "btcusd_fee": "0.250", ... }
     /// ```
-    pub fn return_balances(&mut self) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("method", "balance");
-        params.insert("pair", "");
-        self.private_query(&params)
+    pub fn return_balances(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_balances())
     }
 
     /// Add a buy limit order to the exchange
-    /// limit_price	: If the order gets executed, a new sell order will be placed,
+    /// limit_price    : If the order gets executed, a new sell order will be placed,
     /// with "limit_price" as its price.
     /// daily_order (Optional) : Opens buy limit order which will be canceled
     /// at 0:00 UTC unless it already has been executed. Possible value: True
-    pub fn buy_limit(&mut self,
+    pub fn buy_limit(&self,
                      pair: Pair,
                      amount: Volume,
                      price: Price,
                      price_limit: Option<Price>,
                      daily_order: Option<bool>)
                      -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let amount_string = amount.to_string();
-        let price_string = price.to_string();
-        let price_limit_string = match price_limit {
-            Some(limit) => limit.to_string(),
-            None => "".to_string(),
-        };
-
-        let mut params = HashMap::new();
-        params.insert("method", "buy");
-        params.insert("pair", pair_name);
-
-        params.insert("amount", &amount_string);
-        params.insert("price", &price_string);
-        params.insert("limit_price", &price_limit_string);
-        if let Some(order) = daily_order {
-            let daily_order_str = if order { "True" } else { "" }; // False is not a possible value
-            params.insert("daily_order", daily_order_str);
-        }
-
-        self.private_query(&params)
+        self.runtime.block_on(self.inner.buy_limit(pair, amount, price, price_limit, daily_order))
     }
 
     /// Add a sell limit order to the exchange
-    /// limit_price	: If the order gets executed, a new sell order will be placed,
+    /// limit_price    : If the order gets executed, a new sell order will be placed,
     /// with "limit_price" as its price.
     /// daily_order (Optional) : Opens sell limit order which will be canceled
     /// at 0:00 UTC unless it already has been executed. Possible value: True
-    pub fn sell_limit(&mut self,
+    pub fn sell_limit(&self,
                       pair: Pair,
                       amount: Volume,
                       price: Price,
                       price_limit: Option<Price>,
                       daily_order: Option<bool>)
                       -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let amount_string = amount.to_string();
-        let price_string = price.to_string();
-        let price_limit_string = match price_limit {
-            Some(limit) => limit.to_string(),
-            None => "".to_string(),
-        };
-
-        let mut params = HashMap::new();
-        params.insert("method", "sell");
-        params.insert("pair", pair_name);
-
-        params.insert("amount", &amount_string);
-        params.insert("price", &price_string);
-        params.insert("limit_price", &price_limit_string);
-        if let Some(order) = daily_order {
-            let daily_order_str = if order { "True" } else { "" }; // False is not a possible value
-            params.insert("daily_order", daily_order_str);
-        }
-
-        self.private_query(&params)
+        self.runtime.block_on(self.inner.sell_limit(pair, amount, price, price_limit, daily_order))
     }
 
     /// Add a market buy order to the exchange
     /// By placing a market order you acknowledge that the execution of your order depends
     /// on the market conditions and that these conditions may be subject to sudden changes
     /// that cannot be foreseen.
-    pub fn buy_market(&mut self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let amount_string = amount.to_string();
-
-        let mut params = HashMap::new();
-        params.insert("method", "buy/market");
-        params.insert("pair", pair_name);
-
-        params.insert("amount", &amount_string);
-
-        self.private_query(&params)
+    pub fn buy_market(&self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.buy_market(pair, amount))
     }
 
     /// Add a market sell order to the exchange
     /// By placing a market order you acknowledge that the execution of your order depends
     /// on the market conditions and that these conditions may be subject to sudden changes
     /// that cannot be foreseen.
-    pub fn sell_market(&mut self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let amount_string = amount.to_string();
-
-        let mut params = HashMap::new();
-        params.insert("method", "sell/market");
-        params.insert("pair", pair_name);
-
-        params.insert("amount", &amount_string);
-
-        self.private_query(&params)
+    pub fn sell_market(&self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.sell_market(pair, amount))
     }
-}
 
+    /// Cancel an order, identified by the "id" returned when it was placed.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"id": 1453282316, "amount": "0.02035278", "price": "2100.45", "type": 0}
+    /// ```
+    pub fn cancel_order(&self, id: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.cancel_order(id))
+    }
 
-#[cfg(test)]
-mod bitstamp_api_tests {
-    use super::*;
+    /// Returns your open orders, for a given Pair or for all of them if None is given.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// [{"id": "1453282316", "datetime": "2017-10-27 10:11:12", "type": "0",
+    /// "price": "2100.45", "amount": "0.02035278", "currency_pair": "BTC/USD"}, ...]
+    /// ```
+    pub fn return_open_orders(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_open_orders(pair))
+    }
 
-    #[test]
-    fn should_block_or_not_block_when_enabled_or_disabled() {
-        let mut api = BitstampApi {
-            last_request: helpers::get_unix_timestamp_ms(),
-            api_key: "".to_string(),
-            api_secret: "".to_string(),
-            customer_id: "".to_string(),
-            http_client: Client::new(),
-            burst: false,
-        };
+    /// Returns the status of an order, identified by the "id" returned when it was placed.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"id": 1453282316, "datetime": "2017-10-27 10:11:12", "type": "0", "status": "Finished",
+    /// "market": "BTC/USD", "amount_remaining": "0.00000000",
+    /// "transactions": [{"tid": 24311342, "price": "2100.45", "btc": "0.02035278",
+    /// "usd": "42.75", "fee": "0.11", "datetime": "2017-10-27 10:11:13", "type": 2}]}
+    /// ```
+    pub fn return_order_status(&self, id: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_order_status(id))
+    }
 
-        let mut counter = 0;
-        loop {
-            api.set_burst(false);
-            let start = helpers::get_unix_timestamp_ms();
-            api.block_or_continue();
-            api.last_request = helpers::get_unix_timestamp_ms();
+    /// Cancel all your open orders, for a given Pair or for all of them if None is given.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success": true, "canceled": [{"id": 1453282316, "amount": "0.02035278",
+    /// "price": "2100.45", "type": 0, "currency_pair": "BTC/USD"}, ...]}
+    /// ```
+    pub fn cancel_all_orders(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.cancel_all_orders(pair))
+    }
 
-            let difference = api.last_request - start;
-            assert!(difference >= 999);
-            assert!(difference < 10000);
+    /// Returns your transactions (deposits, withdrawals and trades), most recent first, for a
+    /// given Pair or for all of them if None is given. `offset` skips that many transactions,
+    /// `limit` is the number of transactions returned (100 by default, 1000 at most) and `sort`
+    /// is "desc" (default) or "asc". Empty parameters are not sent.
+    /// The "type" of a transaction is 0 for a deposit, 1 for a withdrawal, 2 for a trade and 14
+    /// for a sub account transfer. The amounts are given for each currency of the transaction,
+    /// the price of a trade is given for its market (ie. "btc_usd").
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// [{"id": 24311342, "datetime": "2017-10-27 10:11:13.123456", "type": "2",
+    /// "fee": "0.11", "order_id": 1453282316, "btc": "0.02035278", "usd": "-42.75",
+    /// "btc_usd": "2100.45", "eur": 0}, ...]
+    /// ```
+    pub fn return_user_transactions(&self,
+                                    pair: Option<Pair>,
+                                    offset: &str,
+                                    limit: &str,
+                                    sort: &str)
+                                    -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_user_transactions(pair, offset, limit, sort))
+    }
 
+    /// Returns your withdrawal requests of the last `timedelta` seconds (86400 by default, when
+    /// empty).
+    /// The "status" of a request is 0 (open), 1 (in process), 2 (finished), 3 (canceled) or 4
+    /// (failed).
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// [{"id": 1, "datetime": "2017-10-27 10:11:12", "type": 1, "currency": "BTC",
+    /// "amount": "0.50000000", "status": 2, "address": "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+    /// "transaction_id": "a2b5c6d1e4f8..."}, ...]
+    /// ```
+    pub fn return_withdrawal_requests(&self, timedelta: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_withdrawal_requests(timedelta))
+    }
 
-            api.set_burst(true);
-            let start = helpers::get_unix_timestamp_ms();
-            api.block_or_continue();
-            api.last_request = helpers::get_unix_timestamp_ms();
+    /// Returns the address to use to deposit a cryptocurrency. Some currencies also need a
+    /// "destination_tag" (ie. XRP) or a "memo_id" (ie. XLM).
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"address": "rDsbeomae4FXwgQTJp9Rs64Qg9vDiTCdBv", "destination_tag": 89250597}
+    /// ```
+    pub fn return_deposit_address(&self, currency: Currency) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_deposit_address(currency))
+    }
 
-            let difference = api.last_request - start;
-            assert!(difference < 10);
+    /// Returns your trading fees (in percent), for a given Pair or for all of them if None is
+    /// given. The fees of every pair are returned in the "data" field of the map.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"currency_pair": "btcusd", "market": "btcusd",
+    /// "fees": {"maker": "0.15000", "taker": "0.25000"}}
+    /// ```
+    pub fn return_trading_fees(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_trading_fees(pair))
+    }
+}
 
-            counter = counter + 1;
-            if counter >= 3 { break; }
-        }
+impl fmt::Debug for BitstampApi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("BitstampApi").field(&self.inner).finish()
     }
 }
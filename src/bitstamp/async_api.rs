@@ -0,0 +1,685 @@
+//! Use this module to interact asynchronously with Bitstamp exchange.
+//! `AsyncBitstampApi` is the implementation behind the blocking client of the `api` module.
+//! Please see examples for more informations.
+
+
+use serde_json::Value;
+use serde_json::value::Map;
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use uuid::Uuid;
+
+use crate::client::ClientConfig;
+use crate::coinnect::Credentials;
+use crate::exchange::Exchange;
+
+use crate::error::*;
+use crate::helpers;
+use crate::rate_limit::Scope;
+use crate::retry::{self, Idempotency};
+use crate::types::Pair;
+use crate::bitstamp::utils;
+use crate::bitstamp::typed_api::AsyncBitstampTypedApi;
+use crate::transport::{AsyncHttpTransport, HttpRequest, HttpResponse, Method, ReqwestTransport};
+use crate::types::*;
+
+/// The scheme authenticating the private requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthMode {
+    /// The `key`, `signature` and `nonce` fields posted with the parameters, the signature
+    /// covering `nonce + customer_id + api_key` (default).
+    Legacy,
+    /// The `X-Auth` headers of the v2 scheme: the signature covers the method, the URL, the
+    /// content type and the body of the request, with a UUID nonce and a timestamp. The
+    /// signature of the responses sent by the server is verified.
+    Header,
+}
+
+#[derive(Debug)]
+pub struct AsyncBitstampApi {
+    api_key: String,
+    api_secret: String,
+    customer_id: String,
+    auth_mode: AuthMode,
+    http_client: Box<dyn AsyncHttpTransport>,
+    config: ClientConfig,
+    /// Markets returned by `ExchangeApi::markets`, fetched on first use
+    pub(crate) markets: RwLock<Option<Markets>>,
+}
+
+
+impl AsyncBitstampApi {
+    /// Create a new AsyncBitstampApi by providing an API key & API secret
+    pub fn new<C: Credentials>(creds: C) -> Result<AsyncBitstampApi> {
+        AsyncBitstampApi::new_with_transport(creds, Box::new(ReqwestTransport::new()?))
+    }
+
+    /// Create a new AsyncBitstampApi sending its requests through the given transport
+    pub fn new_with_transport<C: Credentials>(creds: C,
+                                              transport: Box<dyn AsyncHttpTransport>)
+                                              -> Result<AsyncBitstampApi> {
+        if creds.exchange() != Exchange::Bitstamp {
+            return Err(ErrorKind::InvalidConfigType(Exchange::Bitstamp, creds.exchange()).into());
+        }
+
+        let api_key = creds.get("api_key").unwrap_or_default();
+        let config = ClientConfig::shared(Exchange::Bitstamp, &api_key);
+
+        Ok(AsyncBitstampApi {
+               api_key,
+               api_secret: creds.get("api_secret").unwrap_or_default(),
+               customer_id: creds.get("customer_id").unwrap_or_default(),
+               auth_mode: AuthMode::Legacy,
+               http_client: transport,
+               config,
+               markets: RwLock::new(None),
+           })
+    }
+
+    /// Return the settings of the client: its RateLimiter, RetryPolicy and NonceProvider.
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Change the settings of the client, ie. `api.config_mut().set_retry_policy(policy)`.
+    pub fn config_mut(&mut self) -> &mut ClientConfig {
+        &mut self.config
+    }
+
+    pub fn auth_mode(&self) -> AuthMode {
+        self.auth_mode
+    }
+
+    /// Choose how the private requests are authenticated. The customer id is not used by
+    /// `AuthMode::Header`.
+    pub fn set_auth_mode(&mut self, auth_mode: AuthMode) {
+        self.auth_mode = auth_mode;
+    }
+
+    /// Return a view of the client whose methods return the typed structs of the `responses`
+    /// module instead of JSON maps.
+    pub fn typed(&self) -> AsyncBitstampTypedApi<'_> {
+        AsyncBitstampTypedApi::new(self)
+    }
+
+    async fn public_query(&self, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
+        let method = params.get("method").cloned().unwrap_or("");
+        self.config
+            .retry_policy()
+            .run_checked(Idempotency::Idempotent,
+                         || self.send_public_query(params),
+                         |response| utils::parse_result(response, method).map(|_| ()))
+            .await
+            .map_err(|err| err.with_endpoint(Exchange::Bitstamp, method))
+    }
+
+    async fn private_query(&self, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
+        let method = params.get("method").cloned().unwrap_or("");
+        let idempotency = retry::get_idempotency(Exchange::Bitstamp, Scope::Private, method);
+        self.config
+            .retry_policy()
+            .run_checked(idempotency,
+                         || self.send_private_query(params),
+                         |response| utils::parse_result(response, method).map(|_| ()))
+            .await
+            .map_err(|err| err.with_endpoint(Exchange::Bitstamp, method))
+    }
+
+    async fn send_public_query(&self, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
+
+        let method: &str = params
+            .get("method")
+            .ok_or("Missing \"method\" field.")?;
+        let url: String = match params.get("pair") {
+            Some(pair) => utils::build_url(method, pair),
+            None => utils::build_method_url(method),
+        };
+
+        self.config.rate_limiter().acquire(Scope::Public, method).await?;
+        let request = HttpRequest::new(Method::Get, &url);
+        let response = self.http_client.send(request).await?.error_for_status()?;
+        let buffer = response.body;
+        if method == "transactions" || method == "trading-pairs-info" {
+            return utils::deserialize_json_array(&buffer);
+        }
+        utils::deserialize_json(&buffer)
+    }
+
+    /// Send a private request, authenticated following the `AuthMode` of the client. It is
+    /// used by the private methods of the client, ie. `return_balances`.
+    ///
+    /// #Examples
+    ///
+    /// ```json
+    /// extern crate coinnect;
+    /// use coinnect::bitstamp::{AsyncBitstampApi, BitstampCreds};
+    /// let api = AsyncBitstampApi::new(BitstampCreds::new("", "", "", "")).unwrap();
+    /// let result = api.return_balances().await;
+    /// ```
+    async fn send_private_query(&self, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
+
+        let method: &str = params
+            .get("method")
+            .ok_or("Missing \"method\" field.")?;
+        let url: String = match params.get("pair") {
+            Some(pair) => utils::build_url(method, pair),
+            None => utils::build_method_url(method),
+        };
+
+        self.config.rate_limiter().acquire(Scope::Private, method).await?;
+
+        let response = match self.auth_mode {
+            AuthMode::Legacy => self.send_legacy_request(&url, params).await?,
+            AuthMode::Header => self.send_header_request(&url, params).await?,
+        };
+
+        // Several endpoints return an array (ie. "open_orders" or "user_transactions")
+        utils::deserialize_json_array(&response.body)
+    }
+
+    async fn send_legacy_request(&self,
+                                 url: &str,
+                                 params: &HashMap<&str, &str>)
+                                 -> Result<HttpResponse> {
+        let nonce = self.config.nonce_provider().next_nonce()?.to_string();
+        let signature =
+            utils::build_signature(&nonce, &self.customer_id, &self.api_key, &self.api_secret)?;
+
+        let copy_api_key = self.api_key.clone();
+        let post_params: &mut HashMap<&str, &str> = &mut HashMap::new();
+        post_params.insert("key", &copy_api_key);
+        post_params.insert("signature", &signature);
+        post_params.insert("nonce", &nonce);
+
+        // copy params into post_params .... bit of a hack but will do for now
+        params.iter().for_each(|(k,v)| {
+            post_params.insert(k,v);
+        });
+
+        helpers::strip_empties(post_params);
+        let post_data = helpers::url_encode_hashmap(post_params);
+        let request = HttpRequest::new(Method::Post, url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(&post_data);
+        self.http_client.send(request).await?.error_for_status()
+    }
+
+    async fn send_header_request(&self,
+                                 url: &str,
+                                 params: &HashMap<&str, &str>)
+                                 -> Result<HttpResponse> {
+        // The nonce is a UUID, the nonce provider gives the timestamp in milliseconds
+        let nonce = Uuid::new_v4().to_string();
+        let timestamp = self.config.nonce_provider().next_nonce()?.to_string();
+
+        // The method and the pair are already in the URL
+        let mut post_params = params.clone();
+        post_params.remove("method");
+        post_params.remove("pair");
+        helpers::strip_empties(&mut post_params);
+        let post_data = helpers::url_encode_hashmap(&post_params);
+        let mut request = HttpRequest::new(Method::Post, url);
+        if !post_data.is_empty() {
+            request = request
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .body(&post_data);
+        }
+        let message = utils::build_v2_message(&self.api_key, &request, &nonce, &timestamp);
+        let signature = utils::build_v2_signature(&message, &self.api_secret);
+        let request = request
+            .header("X-Auth", &format!("BITSTAMP {}", self.api_key))
+            .header("X-Auth-Signature", &signature)
+            .header("X-Auth-Nonce", &nonce)
+            .header("X-Auth-Timestamp", &timestamp)
+            .header("X-Auth-Version", "v2");
+
+        let response = self.http_client.send(request).await?.error_for_status()?;
+        // The errors are not signed
+        if (200..300).contains(&response.status) {
+            utils::verify_v2_response(&response, &nonce, &timestamp, &self.api_secret)?;
+        }
+        Ok(response)
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// {
+    /// "BTC_LTC":{
+    /// "last":"0.0251","lowestAsk":"0.02589999","highestBid":"0.0251",
+    /// "percentChange":"0.02390438","baseVolume":"6.16485315","quoteVolume":"245.82513926"},
+    /// "BTC_NXT":{
+    /// "last":"0.00005730","lowestAsk":"0.00005710","highestBid":"0.00004903",
+    /// "percentChange":"0.16701570","baseVolume":"0.45347489","quoteVolume":"9094"},
+    /// ... }
+    /// ```
+    pub async fn return_ticker(&self, pair: Pair) -> Result<Map<String, Value>> {
+        let pair_name = match utils::get_pair_string(&pair) {
+            Some(name) => name,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+        };
+
+        let mut params: HashMap<&str, &str> = HashMap::new();
+        params.insert("pair", pair_name);
+        params.insert("method", "ticker");
+        self.public_query(&params).await
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"asks":[[0.00007600,1164],[0.00007620,1300], ... ], "bids":[[0.00006901,200],
+    /// [0.00006900,408], ... ], "timestamp": "1234567890"}
+    /// ```
+    pub async fn return_order_book(&self, pair: Pair) -> Result<Map<String, Value>> {
+        let pair_name = match utils::get_pair_string(&pair) {
+            Some(name) => name,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+
+        };
+
+        let mut params: HashMap<&str, &str> = HashMap::new();
+        params.insert("method", "order_book");
+        params.insert("pair", pair_name);
+        self.public_query(&params).await
+    }
+
+    /// Returns the pairs listed by Bitstamp and their trading rules. The array is returned in the
+    /// "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"name": "LTC/USD", "url_symbol": "ltcusd", "base_decimals": 8, "counter_decimals": 2,
+    /// "instant_order_counter_decimals": 2, "minimum_order": "5.0 USD", "trading": "Enabled",
+    /// "instant_and_market_orders": "Enabled", "description": "Litecoin / U.S. dollar"}, ... ]
+    /// ```
+    pub async fn return_trading_pairs_info(&self) -> Result<Map<String, Value>> {
+        let mut params: HashMap<&str, &str> = HashMap::new();
+        params.insert("method", "trading-pairs-info");
+        self.public_query(&params).await
+    }
+
+    /// Returns the transactions of the last hour, most recent first ("type" is 0 for buy and 1
+    /// for sell). The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"date": "1509099072", "tid": "24311342", "price": "5812.99", "type": "0",
+    /// "amount": "0.04000000"},
+    /// {"date": "1509099060", "tid": "24311341", "price": "5812.98", "type": "1",
+    /// "amount": "0.22000000"}, ... ]
+    /// ```
+    pub async fn return_trade_history(&self, pair: Pair) -> Result<Map<String, Value>> {
+        let pair_name = match utils::get_pair_string(&pair) {
+            Some(name) => name,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+        };
+
+        let mut params: HashMap<&str, &str> = HashMap::new();
+        params.insert("pair", pair_name);
+        params.insert("method", "transactions");
+        self.public_query(&params).await
+    }
+
+
+    /// Returns your balances: the total, available and reserved amounts of each currency, the
+    /// withdrawal fee of each currency and your trading fee on each pair (in percent).
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"usd_balance": "102.50", "usd_available": "80.25", "usd_reserved": "22.25",
+    /// "btc_balance": "0.59098578", "btc_available": "0.59098578", "btc_reserved": "0.00000000",
+    /// "btc_withdrawal_fee": "0.00050000", "btcusd_fee": "0.250", ... }
+    /// ```
+    pub async fn return_balances(&self) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("method", "balance");
+        self.private_query(&params).await
+    }
+
+    /// Add a buy limit order to the exchange
+    /// limit_price    : If the order gets executed, a new sell order will be placed,
+    /// with "limit_price" as its price.
+    /// daily_order (Optional) : Opens buy limit order which will be canceled
+    /// at 0:00 UTC unless it already has been executed. Possible value: True
+    pub async fn buy_limit(&self,
+                           pair: Pair,
+                           amount: Volume,
+                           price: Price,
+                           price_limit: Option<Price>,
+                           daily_order: Option<bool>)
+                           -> Result<Map<String, Value>> {
+        let pair_name = match utils::get_pair_string(&pair) {
+            Some(name) => name,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+        };
+
+        let amount_string = amount.to_string();
+        let price_string = price.to_string();
+        let price_limit_string = match price_limit {
+            Some(limit) => limit.to_string(),
+            None => "".to_string(),
+        };
+
+        let mut params = HashMap::new();
+        params.insert("method", "buy");
+        params.insert("pair", pair_name);
+
+        params.insert("amount", &amount_string);
+        params.insert("price", &price_string);
+        params.insert("limit_price", &price_limit_string);
+        if let Some(order) = daily_order {
+            let daily_order_str = if order { "True" } else { "" }; // False is not a possible value
+            params.insert("daily_order", daily_order_str);
+        }
+
+        self.private_query(&params).await
+    }
+
+    /// Add a sell limit order to the exchange
+    /// limit_price    : If the order gets executed, a new sell order will be placed,
+    /// with "limit_price" as its price.
+    /// daily_order (Optional) : Opens sell limit order which will be canceled
+    /// at 0:00 UTC unless it already has been executed. Possible value: True
+    pub async fn sell_limit(&self,
+                            pair: Pair,
+                            amount: Volume,
+                            price: Price,
+                            price_limit: Option<Price>,
+                            daily_order: Option<bool>)
+                            -> Result<Map<String, Value>> {
+        let pair_name = match utils::get_pair_string(&pair) {
+            Some(name) => name,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+        };
+
+        let amount_string = amount.to_string();
+        let price_string = price.to_string();
+        let price_limit_string = match price_limit {
+            Some(limit) => limit.to_string(),
+            None => "".to_string(),
+        };
+
+        let mut params = HashMap::new();
+        params.insert("method", "sell");
+        params.insert("pair", pair_name);
+
+        params.insert("amount", &amount_string);
+        params.insert("price", &price_string);
+        params.insert("limit_price", &price_limit_string);
+        if let Some(order) = daily_order {
+            let daily_order_str = if order { "True" } else { "" }; // False is not a possible value
+            params.insert("daily_order", daily_order_str);
+        }
+
+        self.private_query(&params).await
+    }
+
+    /// Add a market buy order to the exchange
+    /// By placing a market order you acknowledge that the execution of your order depends
+    /// on the market conditions and that these conditions may be subject to sudden changes
+    /// that cannot be foreseen.
+    pub async fn buy_market(&self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
+        let pair_name = match utils::get_pair_string(&pair) {
+            Some(name) => name,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+        };
+
+        let amount_string = amount.to_string();
+
+        let mut params = HashMap::new();
+        params.insert("method", "buy/market");
+        params.insert("pair", pair_name);
+
+        params.insert("amount", &amount_string);
+
+        self.private_query(&params).await
+    }
+
+    /// Add a market sell order to the exchange
+    /// By placing a market order you acknowledge that the execution of your order depends
+    /// on the market conditions and that these conditions may be subject to sudden changes
+    /// that cannot be foreseen.
+    pub async fn sell_market(&self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
+        let pair_name = match utils::get_pair_string(&pair) {
+            Some(name) => name,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+        };
+
+        let amount_string = amount.to_string();
+
+        let mut params = HashMap::new();
+        params.insert("method", "sell/market");
+        params.insert("pair", pair_name);
+
+        params.insert("amount", &amount_string);
+
+        self.private_query(&params).await
+    }
+
+    /// Cancel an order, identified by the "id" returned when it was placed.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"id": 1453282316, "amount": "0.02035278", "price": "2100.45", "type": 0}
+    /// ```
+    pub async fn cancel_order(&self, id: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("method", "cancel_order");
+        params.insert("id", id);
+        self.private_query(&params).await
+    }
+
+    /// Returns your open orders, for a given Pair or for all of them if None is given.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// [{"id": "1453282316", "datetime": "2017-10-27 10:11:12", "type": "0",
+    /// "price": "2100.45", "amount": "0.02035278", "currency_pair": "BTC/USD"}, ...]
+    /// ```
+    pub async fn return_open_orders(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        let pair_name = match pair {
+            Some(ref pair) => {
+                match utils::get_pair_string(pair) {
+                    Some(name) => name,
+                    None => return Err(ErrorKind::PairUnsupported.into()),
+                }
+            }
+            None => "all",
+        };
+
+        let mut params = HashMap::new();
+        params.insert("method", "open_orders");
+        params.insert("pair", pair_name);
+        self.private_query(&params).await
+    }
+
+    /// Returns the status of an order, identified by the "id" returned when it was placed.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"id": 1453282316, "datetime": "2017-10-27 10:11:12", "type": "0", "status": "Finished",
+    /// "market": "BTC/USD", "amount_remaining": "0.00000000",
+    /// "transactions": [{"tid": 24311342, "price": "2100.45", "btc": "0.02035278",
+    /// "usd": "42.75", "fee": "0.11", "datetime": "2017-10-27 10:11:13", "type": 2}]}
+    /// ```
+    pub async fn return_order_status(&self, id: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("method", "order_status");
+        params.insert("id", id);
+        self.private_query(&params).await
+    }
+
+    /// Cancel all your open orders, for a given Pair or for all of them if None is given.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success": true, "canceled": [{"id": 1453282316, "amount": "0.02035278",
+    /// "price": "2100.45", "type": 0, "currency_pair": "BTC/USD"}, ...]}
+    /// ```
+    pub async fn cancel_all_orders(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("method", "cancel_all_orders");
+        if let Some(ref pair) = pair {
+            params.insert("pair", utils::get_pair_string(pair).ok_or(ErrorKind::PairUnsupported)?);
+        }
+        self.private_query(&params).await
+    }
+
+    /// Returns your transactions (deposits, withdrawals and trades), most recent first, for a
+    /// given Pair or for all of them if None is given. `offset` skips that many transactions,
+    /// `limit` is the number of transactions returned (100 by default, 1000 at most) and `sort`
+    /// is "desc" (default) or "asc". Empty parameters are not sent.
+    /// The "type" of a transaction is 0 for a deposit, 1 for a withdrawal, 2 for a trade and 14
+    /// for a sub account transfer. The amounts are given for each currency of the transaction,
+    /// the price of a trade is given for its market (ie. "btc_usd").
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// [{"id": 24311342, "datetime": "2017-10-27 10:11:13.123456", "type": "2",
+    /// "fee": "0.11", "order_id": 1453282316, "btc": "0.02035278", "usd": "-42.75",
+    /// "btc_usd": "2100.45", "eur": 0}, ...]
+    /// ```
+    pub async fn return_user_transactions(&self,
+                                          pair: Option<Pair>,
+                                          offset: &str,
+                                          limit: &str,
+                                          sort: &str)
+                                          -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("method", "user_transactions");
+        if let Some(ref pair) = pair {
+            params.insert("pair", utils::get_pair_string(pair).ok_or(ErrorKind::PairUnsupported)?);
+        }
+        params.insert("offset", offset);
+        params.insert("limit", limit);
+        params.insert("sort", sort);
+        self.private_query(&params).await
+    }
+
+    /// Returns your withdrawal requests of the last `timedelta` seconds (86400 by default, when
+    /// empty).
+    /// The "status" of a request is 0 (open), 1 (in process), 2 (finished), 3 (canceled) or 4
+    /// (failed).
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// [{"id": 1, "datetime": "2017-10-27 10:11:12", "type": 1, "currency": "BTC",
+    /// "amount": "0.50000000", "status": 2, "address": "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+    /// "transaction_id": "a2b5c6d1e4f8..."}, ...]
+    /// ```
+    pub async fn return_withdrawal_requests(&self, timedelta: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("method", "withdrawal-requests");
+        params.insert("timedelta", timedelta);
+        self.private_query(&params).await
+    }
+
+    /// Returns the address to use to deposit a cryptocurrency. Some currencies also need a
+    /// "destination_tag" (ie. XRP) or a "memo_id" (ie. XLM).
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"address": "rDsbeomae4FXwgQTJp9Rs64Qg9vDiTCdBv", "destination_tag": 89250597}
+    /// ```
+    pub async fn return_deposit_address(&self, currency: Currency) -> Result<Map<String, Value>> {
+        let currency_name = match utils::get_currency_string(currency) {
+            Some(name) => name.to_lowercase(),
+            None => return Err(ErrorKind::InvalidArguments.into()),
+        };
+        let method = currency_name + "_address";
+
+        let mut params = HashMap::new();
+        params.insert("method", method.as_str());
+        self.private_query(&params).await
+    }
+
+    /// Returns your trading fees (in percent), for a given Pair or for all of them if None is
+    /// given. The fees of every pair are returned in the "data" field of the map.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"currency_pair": "btcusd", "market": "btcusd",
+    /// "fees": {"maker": "0.15000", "taker": "0.25000"}}
+    /// ```
+    pub async fn return_trading_fees(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("method", "fees/trading");
+        if let Some(ref pair) = pair {
+            params.insert("pair", utils::get_pair_string(pair).ok_or(ErrorKind::PairUnsupported)?);
+        }
+        self.private_query(&params).await
+    }
+}
+
+
+#[cfg(test)]
+mod bitstamp_api_tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::nonce::MemoryNonceProvider;
+    use crate::rate_limit::{Policy, RateLimiter};
+    use crate::retry::RetryPolicy;
+    use crate::nonce::Resolution;
+
+    #[tokio::test]
+    async fn should_block_or_not_block_when_enabled_or_disabled() {
+        let http_client = Box::new(ReqwestTransport::from(reqwest::Client::new()));
+        let api = AsyncBitstampApi {
+            api_key: "".to_string(),
+            api_secret: "".to_string(),
+            customer_id: "".to_string(),
+            auth_mode: AuthMode::Legacy,
+            http_client,
+            config: ClientConfig::new(RateLimiter::new(Exchange::Bitstamp),
+                                      RetryPolicy::never(),
+                                      Arc::new(MemoryNonceProvider::new(Resolution::Milliseconds))),
+            markets: RwLock::new(None),
+        };
+
+        // The bucket of the public requests is full: empty it
+        api.config.rate_limiter().set_policy(Policy::Burst);
+        for _ in 0..10 {
+            api.config.rate_limiter().acquire(Scope::Public, "ticker").await.unwrap();
+        }
+
+        let mut counter = 0;
+        loop {
+            api.config.rate_limiter().set_policy(Policy::Throttle);
+            let start = helpers::get_unix_timestamp_ms();
+            api.config.rate_limiter().acquire(Scope::Public, "ticker").await.unwrap();
+
+            let difference = helpers::get_unix_timestamp_ms() - start;
+            assert!(difference >= 990);
+            assert!(difference < 10000);
+
+            api.config.rate_limiter().set_policy(Policy::Burst);
+            let start = helpers::get_unix_timestamp_ms();
+            api.config.rate_limiter().acquire(Scope::Public, "ticker").await.unwrap();
+
+            let difference = helpers::get_unix_timestamp_ms() - start;
+            assert!(difference < 10);
+
+            counter += 1;
+            if counter >= 3 {
+                break;
+            }
+        }
+    }
+}
@@ -2,18 +2,24 @@
 //! This a more convenient and safe way to deal with the exchange since methods return a Result<>
 //! but this generic API does not provide all the functionnality that Bitstamp offers.
 
-use crate::exchange::ExchangeApi;
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::exchange::{AsyncExchangeApi, ExchangeApi};
 use crate::bitstamp::api::BitstampApi;
+use crate::bitstamp::async_api::AsyncBitstampApi;
 use crate::bitstamp::utils;
 
 use crate::error::*;
 use crate::types::*;
 use crate::helpers;
 
-impl ExchangeApi for BitstampApi {
-    fn ticker(&mut self, pair: Pair) -> Result<Ticker> {
+#[async_trait]
+impl AsyncExchangeApi for AsyncBitstampApi {
+    async fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        load_pair(self, &pair).await?;
 
-        let result = self.return_ticker(pair)?;
+        let result = Value::Object(self.return_ticker(pair).await?);
 
         let price = helpers::from_json_bigdecimal(&result["last"], "last")?;
         let ask = helpers::from_json_bigdecimal(&result["ask"], "ask")?;
@@ -22,7 +28,7 @@ impl ExchangeApi for BitstampApi {
 
         Ok(Ticker {
                timestamp: helpers::get_unix_timestamp_ms(),
-               pair: pair,
+               pair,
                last_trade_price: price,
                lowest_ask: ask,
                highest_bid: bid,
@@ -30,11 +36,12 @@ impl ExchangeApi for BitstampApi {
            })
     }
 
-    fn orderbook(&mut self, pair: Pair) -> Result<Orderbook> {
+    async fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        load_pair(self, &pair).await?;
 
-        let raw_response = self.return_order_book(pair)?;
+        let raw_response = self.return_order_book(pair).await?;
 
-        let result = utils::parse_result(&raw_response)?;
+        let result = Value::Object(utils::parse_result(&raw_response, "order_book")?);
 
         let mut ask_offers = Vec::new();
         let mut bid_offers = Vec::new();
@@ -42,11 +49,11 @@ impl ExchangeApi for BitstampApi {
         let ask_array =
             result["asks"]
                 .as_array()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat("asks".to_string()))?;
         let bid_array =
             result["bids"]
                 .as_array()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat("bids".to_string()))?;
 
         for ask in ask_array {
             let price = helpers::from_json_bigdecimal(&ask[0], "ask price")?;
@@ -64,75 +71,230 @@ impl ExchangeApi for BitstampApi {
 
         Ok(Orderbook {
             timestamp: helpers::get_unix_timestamp_ms(),
-            pair: pair,
+            pair,
             asks: ask_offers,
             bids: bid_offers,
         })
     }
 
-    fn add_order(&mut self,
-                 order_type: OrderType,
-                 pair: Pair,
-                 quantity: Volume,
-                 price: Option<Price>)
-                 -> Result<OrderInfo> {
-        //let pair_name = match utils::get_pair_string(&pair) {
-        //Some(name) => name,
-        //None => return Err(ErrorKind::PairUnsupported.into()),
-        //};
+    async fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        load_pair(self, &pair).await?;
+        let raw_response = self.return_trade_history(pair).await?;
+
+        let result = Value::Object(utils::parse_result(&raw_response, "transactions")?);
+
+        let trade_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        let (trades, cursor) = helpers::trades_after(utils::parse_trades(trade_array)?, since)?;
+
+        Ok(TradeHistory {
+               timestamp: helpers::get_unix_timestamp_ms(),
+               pair,
+               trades,
+               cursor,
+           })
+    }
+
+    async fn markets(&self) -> Result<Markets> {
+        if let Some(ref markets) = *self.markets.read().unwrap() {
+            return Ok(markets.clone());
+        }
+
+        let raw_response = self.return_trading_pairs_info().await?;
+        let result = Value::Object(utils::parse_result(&raw_response, "trading-pairs-info")?);
+
+        let pair_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        let markets = utils::parse_markets(pair_array)?;
+        *self.markets.write().unwrap() = Some(markets.clone());
+
+        Ok(markets)
+    }
+
+    async fn add_order(&self,
+                       order_type: OrderType,
+                       pair: Pair,
+                       quantity: Volume,
+                       price: Option<Price>)
+                       -> Result<OrderInfo> {
+        load_pair(self, &pair).await?;
 
         let result = match order_type {
             OrderType::BuyLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
-
-                // Unwrap safe here with the check above.
-                self.buy_limit(pair, quantity, price.unwrap(), None, None)
+                let price = price.clone().ok_or(ErrorKind::MissingPrice)?;
+                self.buy_limit(pair, quantity.clone(), price, None, None).await
             }
-            OrderType::BuyMarket => self.buy_market(pair, quantity),
+            OrderType::BuyMarket => self.buy_market(pair, quantity.clone()).await,
             OrderType::SellLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
-
-                // Unwrap safe here with the check above.
-                self.sell_limit(pair, quantity, price.unwrap(), None, None)
+                let price = price.clone().ok_or(ErrorKind::MissingPrice)?;
+                self.sell_limit(pair, quantity.clone(), price, None, None).await
             }
-            OrderType::SellMarket => self.sell_market(pair, quantity),
-        };
+            OrderType::SellMarket => self.sell_market(pair, quantity.clone()).await,
+        }?;
+        let result = Value::Object(result);
 
         Ok(OrderInfo {
                timestamp: helpers::get_unix_timestamp_ms(),
-               identifier: vec![result?["id"]
-                                    .as_str()
-                                    .ok_or_else(|| {
-                                                    ErrorKind::MissingField("id".to_string())
-                                                })?
-                                    .to_string()],
+               identifier: vec![helpers::get_json_id(&result, "id")?],
+               pair,
+               order_type,
+               quantity,
+               price,
+               fills: Vec::new(),
            })
     }
 
+    async fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        for id in &order.identifier {
+            let raw_response = AsyncBitstampApi::cancel_order(self, id).await?;
+            utils::parse_result(&raw_response, "cancel_order")?;
+        }
+
+        Ok(())
+    }
+
+    async fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        if let Some(ref pair) = pair {
+            load_pair(self, pair).await?;
+        }
+        let raw_response = self.return_open_orders(pair).await?;
+        let result = Value::Object(utils::parse_result(&raw_response, "open_orders")?);
+
+        let result_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        let mut orders = Vec::new();
+
+        for order in result_array {
+            let order_pair = match pair {
+                Some(pair) => pair,
+                None => {
+                    let market = helpers::get_json_string(order, "currency_pair")?;
+                    match get_pair_from_market(self, market).await? {
+                        Some(order_pair) => order_pair,
+                        None => continue,
+                    }
+                }
+            };
+
+            orders.push(utils::parse_open_order(order_pair, order)?);
+        }
+
+        Ok(orders)
+    }
+
+    async fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        let id = order.identifier.first().ok_or(ErrorKind::OrderNotFound)?;
+
+        let raw_response = self.return_order_status(id).await?;
+        let result = serde_json::Value::Object(utils::parse_result(&raw_response, "order_status")?);
+
+        let market = helpers::get_json_string(&result, "market")?;
+        let pair = match get_pair_from_market(self, market).await? {
+            Some(pair) => pair,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+        };
+
+        utils::parse_order_status(pair, &result)
+    }
+
     /// Return the balances for each currency on the account
-    fn balances(&mut self) -> Result<Balances> {
-        let raw_response = self.return_balances()?;
-        let result = utils::parse_result(&raw_response)?;
+    async fn balances(&self) -> Result<Balances> {
+        let raw_response = self.return_balances().await?;
+        let result = utils::parse_result(&raw_response, "balance")?;
 
         let mut balances = Balances::new();
 
         for (key, val) in result.iter() {
-            let currency = utils::get_currency_enum(key);
+            if let Some(currency) = utils::get_currency_enum(key) {
+                let amount = helpers::from_json_bigdecimal(val, "amount")?;
 
-            match currency {
-                Some(c) => {
-                    let amount = helpers::from_json_bigdecimal(&val, "amount")?;
-
-                    balances.insert(c, amount);
-                },
-                _ => ()
+                balances.insert(currency, amount);
             }
         }
 
         Ok(balances)
     }
 }
+
+/// Load the markets listed by Bitstamp if the pair is not known yet: the pairs listed since the
+/// last update of Coinnect are only known once the markets have been loaded.
+async fn load_pair(api: &AsyncBitstampApi, pair: &Pair) -> Result<()> {
+    helpers::find_pair(&api.markets, || api.markets(), || utils::get_pair_string(pair)).await?;
+
+    Ok(())
+}
+
+/// Return the Pair of a market name (ie. "BTC/USD"), loading the markets listed by Bitstamp if
+/// the market is not known yet.
+async fn get_pair_from_market(api: &AsyncBitstampApi, market: &str) -> Result<Option<Pair>> {
+    helpers::find_pair(&api.markets,
+                       || api.markets(),
+                       || utils::get_pair_enum_from_market(market))
+        .await
+}
+
+impl ExchangeApi for BitstampApi {
+    fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        self.runtime.block_on(self.inner.ticker(pair))
+    }
+
+    fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        self.runtime.block_on(self.inner.orderbook(pair))
+    }
+
+    fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        self.runtime.block_on(self.inner.trades(pair, since))
+    }
+
+    fn candles(&self,
+               pair: Pair,
+               interval: Interval,
+               since: Option<i64>)
+               -> Result<Vec<Candle>> {
+        self.runtime.block_on(self.inner.candles(pair, interval, since))
+    }
+
+    fn markets(&self) -> Result<Markets> {
+        self.runtime.block_on(self.inner.markets())
+    }
+
+    fn validate_order(&self,
+                      order_type: OrderType,
+                      pair: Pair,
+                      quantity: Volume,
+                      price: Option<Price>)
+                      -> Result<(Volume, Option<Price>)> {
+        self.runtime.block_on(self.inner.validate_order(order_type, pair, quantity, price))
+    }
+
+    fn add_order(&self,
+                 order_type: OrderType,
+                 pair: Pair,
+                 quantity: Volume,
+                 price: Option<Price>)
+                 -> Result<OrderInfo> {
+        self.runtime.block_on(self.inner.add_order(order_type, pair, quantity, price))
+    }
+
+    fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        self.runtime.block_on(AsyncExchangeApi::cancel_order(&self.inner, order))
+    }
+
+    fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        self.runtime.block_on(self.inner.open_orders(pair))
+    }
+
+    fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        self.runtime.block_on(self.inner.order_status(order))
+    }
+
+    fn balances(&self) -> Result<Balances> {
+        self.runtime.block_on(self.inner.balances())
+    }
+}
@@ -1,9 +1,14 @@
 //! Use this module to interact with Bitstamp exchange.
 
 pub mod api;
+pub mod async_api;
 pub mod generic_api;
 pub mod credentials;
 pub mod utils;
+pub mod responses;
+pub mod typed_api;
 
 pub use self::credentials::BitstampCreds;
 pub use self::api::BitstampApi;
+pub use self::async_api::{AsyncBitstampApi, AuthMode};
+pub use self::typed_api::{AsyncBitstampTypedApi, BitstampTypedApi};
@@ -0,0 +1,413 @@
+//! Typed results of the Bitstamp endpoints, returned by the `typed` API (see
+//! `AsyncBitstampApi::typed`).
+//!
+//! The fields are the ones documented by Bitstamp. Prices and amounts are `BigDecimal` parsed
+//! from the text sent by Bitstamp, dates and timestamps are `DateTime<Utc>`, and the "type" of
+//! the orders (0 for buy, 1 for sell) is an `OrderSide`. The identifiers sent either as strings
+//! or as numbers are strings. The raw responses are still returned by the methods of
+//! `AsyncBitstampApi`.
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{de, Deserialize, Deserializer};
+use serde_json::Value;
+use serde_json::value::Map;
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use crate::error::*;
+use crate::helpers;
+use crate::types::OrderSide;
+
+/// Result of `ticker`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Ticker {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub last: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub high: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub low: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub vwap: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub volume: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub bid: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub ask: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub open: Option<BigDecimal>,
+    #[serde(deserialize_with = "helpers::deserialize_timestamp")]
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Result of `order_book`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderBook {
+    #[serde(deserialize_with = "helpers::deserialize_timestamp")]
+    pub timestamp: DateTime<Utc>,
+    pub bids: Vec<OrderBookEntry>,
+    pub asks: Vec<OrderBookEntry>,
+}
+
+/// An order of `order_book`, sent as an array (<price>, <amount>).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderBookEntry {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+}
+
+/// An entry of the public `transactions`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Transaction {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub tid: String,
+    #[serde(deserialize_with = "helpers::deserialize_timestamp")]
+    pub date: DateTime<Utc>,
+    #[serde(rename = "type", deserialize_with = "deserialize_side")]
+    pub side: OrderSide,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+}
+
+/// The amounts of a currency in `balance`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Balance {
+    pub balance: BigDecimal,
+    pub available: BigDecimal,
+    pub reserved: BigDecimal,
+}
+
+/// Result of `balance`: the balances and the withdrawal fee of each currency (ie. "btc"), and
+/// the trading fee in percent on each pair (ie. "btcusd").
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountBalances {
+    pub balances: HashMap<String, Balance>,
+    pub withdrawal_fees: HashMap<String, BigDecimal>,
+    pub trading_fees: HashMap<String, BigDecimal>,
+}
+
+impl AccountBalances {
+    pub(crate) fn from_result(result: Map<String, Value>) -> Result<AccountBalances> {
+        let mut balances: HashMap<String, Balance> = HashMap::new();
+        let mut withdrawal_fees = HashMap::new();
+        let mut trading_fees = HashMap::new();
+
+        for (key, value) in result {
+            let amount = helpers::from_json_number_bigdecimal(&value, &key)?;
+
+            if let Some(currency) = key.strip_suffix("_withdrawal_fee") {
+                withdrawal_fees.insert(currency.to_string(), amount);
+            } else if let Some(currency) = key.strip_suffix("_balance") {
+                balances.entry(currency.to_string()).or_default().balance = amount;
+            } else if let Some(currency) = key.strip_suffix("_available") {
+                balances.entry(currency.to_string()).or_default().available = amount;
+            } else if let Some(currency) = key.strip_suffix("_reserved") {
+                balances.entry(currency.to_string()).or_default().reserved = amount;
+            } else if let Some(pair) = key.strip_suffix("_fee") {
+                trading_fees.insert(pair.to_string(), amount);
+            }
+        }
+
+        Ok(AccountBalances {
+               balances,
+               withdrawal_fees,
+               trading_fees,
+           })
+    }
+}
+
+/// Result of `buy`, `sell`, `buy/market` and `sell/market`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PlacedOrder {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub id: String,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub datetime: DateTime<Utc>,
+    #[serde(rename = "type", deserialize_with = "deserialize_side")]
+    pub side: OrderSide,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+}
+
+/// Result of `cancel_order`, and an entry of `cancel_all_orders`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CanceledOrder {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub id: String,
+    #[serde(rename = "type", deserialize_with = "deserialize_side")]
+    pub side: OrderSide,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(default)]
+    pub currency_pair: Option<String>,
+}
+
+/// Result of `cancel_all_orders`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CanceledOrders {
+    pub success: bool,
+    #[serde(default)]
+    pub canceled: Vec<CanceledOrder>,
+}
+
+/// An entry of `open_orders`. The amount is the remaining amount of the order.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OpenOrder {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub id: String,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub datetime: DateTime<Utc>,
+    #[serde(rename = "type", deserialize_with = "deserialize_side")]
+    pub side: OrderSide,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(default)]
+    pub currency_pair: Option<String>,
+}
+
+/// Result of `order_status`. The status is "Open", "In Queue", "Finished", "Canceled" or
+/// "Expired".
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderStatus {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub id: String,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_datetime")]
+    pub datetime: Option<DateTime<Utc>>,
+    #[serde(rename = "type", default, deserialize_with = "deserialize_option_side")]
+    pub side: Option<OrderSide>,
+    pub status: String,
+    #[serde(default)]
+    pub market: Option<String>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub amount_remaining: Option<BigDecimal>,
+    pub transactions: Vec<OrderTransaction>,
+}
+
+/// The type of a transaction of `user_transactions` and `order_status`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransactionType {
+    Deposit,
+    Withdrawal,
+    MarketTrade,
+    SubAccountTransfer,
+    /// A type that is not listed here, with the code sent by Bitstamp
+    Other(u32),
+}
+
+impl TransactionType {
+    fn from_value(value: &Value) -> Result<TransactionType> {
+        let code = match *value {
+            Value::Number(ref code) => code.to_string(),
+            Value::String(ref code) => code.clone(),
+            _ => return Err(ErrorKind::InvalidFieldFormat("type".to_string()).into()),
+        };
+        let code = code.parse::<u32>()
+            .chain_err(|| ErrorKind::InvalidFieldFormat("type".to_string()))?;
+
+        Ok(match code {
+               0 => TransactionType::Deposit,
+               1 => TransactionType::Withdrawal,
+               2 => TransactionType::MarketTrade,
+               14 => TransactionType::SubAccountTransfer,
+               other => TransactionType::Other(other),
+           })
+    }
+}
+
+/// A trade of `order_status`. The amounts are given for each currency of the trade (ie. "btc"
+/// and "usd").
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "Map<String, Value>")]
+pub struct OrderTransaction {
+    pub tid: String,
+    pub datetime: DateTime<Utc>,
+    pub transaction_type: TransactionType,
+    pub price: BigDecimal,
+    pub fee: BigDecimal,
+    pub amounts: HashMap<String, BigDecimal>,
+}
+
+impl TryFrom<Map<String, Value>> for OrderTransaction {
+    type Error = Error;
+
+    fn try_from(transaction: Map<String, Value>) -> Result<OrderTransaction> {
+        let mut amounts = HashMap::new();
+        for (key, value) in &transaction {
+            if !matches!(key.as_ref(), "tid" | "datetime" | "type" | "price" | "fee") {
+                amounts.insert(key.clone(), helpers::from_json_number_bigdecimal(value, key)?);
+            }
+        }
+
+        let transaction = Value::Object(transaction);
+        Ok(OrderTransaction {
+               tid: helpers::get_json_id(&transaction, "tid")?,
+               datetime: helpers::parse_datetime(helpers::get_json_string(&transaction,
+                                                                          "datetime")?,
+                                                 "datetime")?,
+               transaction_type: TransactionType::from_value(&transaction["type"])?,
+               price: helpers::from_json_number_bigdecimal(&transaction["price"], "price")?,
+               fee: helpers::from_json_number_bigdecimal(&transaction["fee"], "fee")?,
+               amounts,
+           })
+    }
+}
+
+/// An entry of `user_transactions`. The amounts are given for each currency (ie. "btc" and
+/// "usd", negative when spent) and the price of a trade for its market (ie. "btc_usd").
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "Map<String, Value>")]
+pub struct UserTransaction {
+    pub id: String,
+    pub datetime: DateTime<Utc>,
+    pub transaction_type: TransactionType,
+    pub fee: BigDecimal,
+    pub order_id: Option<String>,
+    pub amounts: HashMap<String, BigDecimal>,
+    pub prices: HashMap<String, BigDecimal>,
+}
+
+impl TryFrom<Map<String, Value>> for UserTransaction {
+    type Error = Error;
+
+    fn try_from(transaction: Map<String, Value>) -> Result<UserTransaction> {
+        let mut amounts = HashMap::new();
+        let mut prices = HashMap::new();
+        for (key, value) in &transaction {
+            if matches!(key.as_ref(), "id" | "datetime" | "type" | "fee" | "order_id") ||
+               value.is_null() {
+                continue;
+            }
+
+            let amount = helpers::from_json_number_bigdecimal(value, key)?;
+            if key.contains('_') {
+                prices.insert(key.clone(), amount);
+            } else {
+                amounts.insert(key.clone(), amount);
+            }
+        }
+
+        let transaction = Value::Object(transaction);
+        let order_id = match transaction.get("order_id") {
+            Some(Value::Null) | None => None,
+            Some(_) => Some(helpers::get_json_id(&transaction, "order_id")?),
+        };
+
+        Ok(UserTransaction {
+               id: helpers::get_json_id(&transaction, "id")?,
+               datetime: helpers::parse_datetime(helpers::get_json_string(&transaction,
+                                                                          "datetime")?,
+                                                 "datetime")?,
+               transaction_type: TransactionType::from_value(&transaction["type"])?,
+               fee: helpers::from_json_number_bigdecimal(&transaction["fee"], "fee")?,
+               order_id,
+               amounts,
+               prices,
+           })
+    }
+}
+
+/// The status of a withdrawal request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WithdrawalStatus {
+    Open,
+    InProcess,
+    Finished,
+    Canceled,
+    Failed,
+}
+
+/// An entry of `withdrawal-requests`. The type is the method of the withdrawal (ie. 0 for SEPA,
+/// 1 for bitcoin or 2 for a wire transfer).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WithdrawalRequest {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub id: String,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub datetime: DateTime<Utc>,
+    #[serde(rename = "type")]
+    pub withdrawal_type: u32,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "deserialize_withdrawal_status")]
+    pub status: WithdrawalStatus,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_id")]
+    pub transaction_id: Option<String>,
+}
+
+/// Result of `<currency>_address`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DepositAddress {
+    pub address: String,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_id")]
+    pub destination_tag: Option<String>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_id")]
+    pub memo_id: Option<String>,
+}
+
+/// An entry of `fees/trading`, the fees are in percent.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TradingFee {
+    pub currency_pair: String,
+    #[serde(default)]
+    pub market: Option<String>,
+    pub fees: Fees,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Fees {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub maker: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub taker: BigDecimal,
+}
+
+/// Deserialize the "type" of an order, 0 for buy and 1 for sell, sent as a number or a string.
+fn deserialize_side<'de, D>(deserializer: D) -> ::std::result::Result<OrderSide, D::Error>
+    where D: Deserializer<'de>
+{
+    let side = helpers::deserialize_id(deserializer)?;
+    match side.as_ref() {
+        "0" => Ok(OrderSide::Buy),
+        "1" => Ok(OrderSide::Sell),
+        _ => Err(de::Error::custom(format!("invalid order type: {}", side))),
+    }
+}
+
+fn deserialize_option_side<'de, D>(deserializer: D)
+                                   -> ::std::result::Result<Option<OrderSide>, D::Error>
+    where D: Deserializer<'de>
+{
+    deserialize_side(deserializer).map(Some)
+}
+
+fn deserialize_withdrawal_status<'de, D>(deserializer: D)
+                                         -> ::std::result::Result<WithdrawalStatus, D::Error>
+    where D: Deserializer<'de>
+{
+    let status = helpers::deserialize_id(deserializer)?;
+    match status.as_ref() {
+        "0" => Ok(WithdrawalStatus::Open),
+        "1" => Ok(WithdrawalStatus::InProcess),
+        "2" => Ok(WithdrawalStatus::Finished),
+        "3" => Ok(WithdrawalStatus::Canceled),
+        "4" => Ok(WithdrawalStatus::Failed),
+        _ => Err(de::Error::custom(format!("invalid withdrawal status: {}", status))),
+    }
+}
@@ -0,0 +1,242 @@
+//! Use this module to get the results of the Bitstamp endpoints as typed structs (see the
+//! `responses` module) instead of JSON maps.
+//!
+//! The typed API is a view of a client returned by `AsyncBitstampApi::typed` or
+//! `BitstampApi::typed`. Its methods have the names and the parameters of the raw methods, which
+//! still return the raw responses:
+//!
+//! ```no_run
+//! use coinnect::bitstamp::{BitstampApi, BitstampCreds};
+//! use coinnect::types::Pair;
+//!
+//! let api = BitstampApi::new(BitstampCreds::new("", "", "", "")).unwrap();
+//! let fees = api.typed().return_trading_fees(Some(Pair::BTC_USD)).unwrap();
+//! println!("{}", fees[0].fees.taker);
+//! ```
+
+use crate::bitstamp::{AsyncBitstampApi, BitstampApi};
+use crate::bitstamp::responses::*;
+use crate::bitstamp::utils;
+use crate::error::*;
+use crate::types::{Currency, Pair, Price, Volume};
+
+/// Typed view of an `AsyncBitstampApi`.
+#[derive(Debug, Clone, Copy)]
+pub struct AsyncBitstampTypedApi<'a> {
+    api: &'a AsyncBitstampApi,
+}
+
+impl<'a> AsyncBitstampTypedApi<'a> {
+    pub fn new(api: &'a AsyncBitstampApi) -> AsyncBitstampTypedApi<'a> {
+        AsyncBitstampTypedApi { api }
+    }
+
+    pub async fn return_ticker(&self, pair: Pair) -> Result<Ticker> {
+        let response = self.api.return_ticker(pair).await?;
+        utils::parse_response(&response, "ticker")
+    }
+
+    pub async fn return_order_book(&self, pair: Pair) -> Result<OrderBook> {
+        let response = self.api.return_order_book(pair).await?;
+        utils::parse_response(&response, "order_book")
+    }
+
+    pub async fn return_trade_history(&self, pair: Pair) -> Result<Vec<Transaction>> {
+        let response = self.api.return_trade_history(pair).await?;
+        utils::parse_array_response(&response, "transactions")
+    }
+
+    pub async fn return_balances(&self) -> Result<AccountBalances> {
+        let response = self.api.return_balances().await?;
+        AccountBalances::from_result(utils::parse_result(&response, "balance")?)
+    }
+
+    pub async fn buy_limit(&self,
+                           pair: Pair,
+                           amount: Volume,
+                           price: Price,
+                           price_limit: Option<Price>,
+                           daily_order: Option<bool>)
+                           -> Result<PlacedOrder> {
+        let response = self.api.buy_limit(pair, amount, price, price_limit, daily_order).await?;
+        utils::parse_response(&response, "buy")
+    }
+
+    pub async fn sell_limit(&self,
+                            pair: Pair,
+                            amount: Volume,
+                            price: Price,
+                            price_limit: Option<Price>,
+                            daily_order: Option<bool>)
+                            -> Result<PlacedOrder> {
+        let response = self.api.sell_limit(pair, amount, price, price_limit, daily_order).await?;
+        utils::parse_response(&response, "sell")
+    }
+
+    pub async fn buy_market(&self, pair: Pair, amount: Volume) -> Result<PlacedOrder> {
+        let response = self.api.buy_market(pair, amount).await?;
+        utils::parse_response(&response, "buy/market")
+    }
+
+    pub async fn sell_market(&self, pair: Pair, amount: Volume) -> Result<PlacedOrder> {
+        let response = self.api.sell_market(pair, amount).await?;
+        utils::parse_response(&response, "sell/market")
+    }
+
+    pub async fn cancel_order(&self, id: &str) -> Result<CanceledOrder> {
+        let response = self.api.cancel_order(id).await?;
+        utils::parse_response(&response, "cancel_order")
+    }
+
+    pub async fn return_open_orders(&self, pair: Option<Pair>) -> Result<Vec<OpenOrder>> {
+        let response = self.api.return_open_orders(pair).await?;
+        utils::parse_array_response(&response, "open_orders")
+    }
+
+    pub async fn return_order_status(&self, id: &str) -> Result<OrderStatus> {
+        let response = self.api.return_order_status(id).await?;
+        utils::parse_response(&response, "order_status")
+    }
+
+    pub async fn cancel_all_orders(&self, pair: Option<Pair>) -> Result<CanceledOrders> {
+        let response = self.api.cancel_all_orders(pair).await?;
+        utils::parse_response(&response, "cancel_all_orders")
+    }
+
+    pub async fn return_user_transactions(&self,
+                                          pair: Option<Pair>,
+                                          offset: &str,
+                                          limit: &str,
+                                          sort: &str)
+                                          -> Result<Vec<UserTransaction>> {
+        let response = self.api.return_user_transactions(pair, offset, limit, sort).await?;
+        utils::parse_array_response(&response, "user_transactions")
+    }
+
+    pub async fn return_withdrawal_requests(&self,
+                                            timedelta: &str)
+                                            -> Result<Vec<WithdrawalRequest>> {
+        let response = self.api.return_withdrawal_requests(timedelta).await?;
+        utils::parse_array_response(&response, "withdrawal-requests")
+    }
+
+    pub async fn return_deposit_address(&self, currency: Currency) -> Result<DepositAddress> {
+        let response = self.api.return_deposit_address(currency).await?;
+        utils::parse_response(&response, "deposit_address")
+    }
+
+    /// Return the fees of the Pair, or of every pair if None is given.
+    pub async fn return_trading_fees(&self, pair: Option<Pair>) -> Result<Vec<TradingFee>> {
+        let response = self.api.return_trading_fees(pair).await?;
+        if response.contains_key("data") {
+            utils::parse_array_response(&response, "fees/trading")
+        } else {
+            Ok(vec![utils::parse_response(&response, "fees/trading")?])
+        }
+    }
+}
+
+/// Typed view of a `BitstampApi`. Every call runs the `AsyncBitstampTypedApi` method of the same
+/// name to completion on the runtime of the client.
+#[derive(Debug, Clone, Copy)]
+pub struct BitstampTypedApi<'a> {
+    api: &'a BitstampApi,
+}
+
+impl<'a> BitstampTypedApi<'a> {
+    pub fn new(api: &'a BitstampApi) -> BitstampTypedApi<'a> {
+        BitstampTypedApi { api }
+    }
+
+    fn inner(&self) -> AsyncBitstampTypedApi<'a> {
+        self.api.inner.typed()
+    }
+
+    pub fn return_ticker(&self, pair: Pair) -> Result<Ticker> {
+        self.api.runtime.block_on(self.inner().return_ticker(pair))
+    }
+
+    pub fn return_order_book(&self, pair: Pair) -> Result<OrderBook> {
+        self.api.runtime.block_on(self.inner().return_order_book(pair))
+    }
+
+    pub fn return_trade_history(&self, pair: Pair) -> Result<Vec<Transaction>> {
+        self.api.runtime.block_on(self.inner().return_trade_history(pair))
+    }
+
+    pub fn return_balances(&self) -> Result<AccountBalances> {
+        self.api.runtime.block_on(self.inner().return_balances())
+    }
+
+    pub fn buy_limit(&self,
+                     pair: Pair,
+                     amount: Volume,
+                     price: Price,
+                     price_limit: Option<Price>,
+                     daily_order: Option<bool>)
+                     -> Result<PlacedOrder> {
+        self.api
+            .runtime
+            .block_on(self.inner().buy_limit(pair, amount, price, price_limit, daily_order))
+    }
+
+    pub fn sell_limit(&self,
+                      pair: Pair,
+                      amount: Volume,
+                      price: Price,
+                      price_limit: Option<Price>,
+                      daily_order: Option<bool>)
+                      -> Result<PlacedOrder> {
+        self.api
+            .runtime
+            .block_on(self.inner().sell_limit(pair, amount, price, price_limit, daily_order))
+    }
+
+    pub fn buy_market(&self, pair: Pair, amount: Volume) -> Result<PlacedOrder> {
+        self.api.runtime.block_on(self.inner().buy_market(pair, amount))
+    }
+
+    pub fn sell_market(&self, pair: Pair, amount: Volume) -> Result<PlacedOrder> {
+        self.api.runtime.block_on(self.inner().sell_market(pair, amount))
+    }
+
+    pub fn cancel_order(&self, id: &str) -> Result<CanceledOrder> {
+        self.api.runtime.block_on(self.inner().cancel_order(id))
+    }
+
+    pub fn return_open_orders(&self, pair: Option<Pair>) -> Result<Vec<OpenOrder>> {
+        self.api.runtime.block_on(self.inner().return_open_orders(pair))
+    }
+
+    pub fn return_order_status(&self, id: &str) -> Result<OrderStatus> {
+        self.api.runtime.block_on(self.inner().return_order_status(id))
+    }
+
+    pub fn cancel_all_orders(&self, pair: Option<Pair>) -> Result<CanceledOrders> {
+        self.api.runtime.block_on(self.inner().cancel_all_orders(pair))
+    }
+
+    pub fn return_user_transactions(&self,
+                                    pair: Option<Pair>,
+                                    offset: &str,
+                                    limit: &str,
+                                    sort: &str)
+                                    -> Result<Vec<UserTransaction>> {
+        self.api
+            .runtime
+            .block_on(self.inner().return_user_transactions(pair, offset, limit, sort))
+    }
+
+    pub fn return_withdrawal_requests(&self, timedelta: &str) -> Result<Vec<WithdrawalRequest>> {
+        self.api.runtime.block_on(self.inner().return_withdrawal_requests(timedelta))
+    }
+
+    pub fn return_deposit_address(&self, currency: Currency) -> Result<DepositAddress> {
+        self.api.runtime.block_on(self.inner().return_deposit_address(currency))
+    }
+
+    /// Return the fees of the Pair, or of every pair if None is given.
+    pub fn return_trading_fees(&self, pair: Option<Pair>) -> Result<Vec<TradingFee>> {
+        self.api.runtime.block_on(self.inner().return_trading_fees(pair))
+    }
+}
@@ -1,50 +1,64 @@
 use bidir_map::BidirMap;
+use std::sync::RwLock;
 
 use hmac::{Hmac, Mac, NewMac};
 use sha2::{Sha256};
 
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
 use serde_json;
 use serde_json::Value;
 use serde_json::value::Map;
 
+use bigdecimal::{BigDecimal, Zero};
+use data_encoding::HEXLOWER;
+
 use crate::error::*;
-use crate::helpers;
-use crate::types::Currency;
-use crate::types::Pair;
-use crate::types::Pair::*;
+use crate::exchange::Exchange;
+use crate::helpers::{self, PairNames};
+use crate::normalization;
+use crate::transport::{HttpRequest, HttpResponse, Method};
+use crate::types::*;
 
 lazy_static! {
-    static ref PAIRS_STRING: BidirMap<Pair, &'static str> = {
+    static ref PAIRS_STRING: PairNames = {
         let mut m = BidirMap::new();
-        m.insert(BTC_USD, "btcusd");
-        m.insert(BTC_EUR, "btceur");
-        m.insert(EUR_USD, "eurusd");
-        m.insert(XRP_USD, "xrpusd");
-        m.insert(XRP_EUR, "xrpeur");
-        m.insert(XRP_BTC, "xrpbtc");
-        m.insert(LTC_USD, "ltcusd");
-        m.insert(LTC_EUR, "ltceur");
-        m.insert(LTC_BTC, "ltcbtc");
-        m.insert(ETH_USD, "ethusd");
-        m.insert(ETH_EUR, "etheur");
-        m.insert(ETH_BTC, "ethbtc");
-        m.insert(BCH_USD, "bchusd");
-        m.insert(BCH_EUR, "bcheur");
-        m.insert(BCH_BTC, "bchbtc");
-        m
+        m.insert(Pair::BTC_USD, "btcusd");
+        m.insert(Pair::BTC_EUR, "btceur");
+        m.insert(Pair::EUR_USD, "eurusd");
+        m.insert(Pair::XRP_USD, "xrpusd");
+        m.insert(Pair::XRP_EUR, "xrpeur");
+        m.insert(Pair::XRP_BTC, "xrpbtc");
+        m.insert(Pair::LTC_USD, "ltcusd");
+        m.insert(Pair::LTC_EUR, "ltceur");
+        m.insert(Pair::LTC_BTC, "ltcbtc");
+        m.insert(Pair::ETH_USD, "ethusd");
+        m.insert(Pair::ETH_EUR, "etheur");
+        m.insert(Pair::ETH_BTC, "ethbtc");
+        m.insert(Pair::BCH_USD, "bchusd");
+        m.insert(Pair::BCH_EUR, "bcheur");
+        m.insert(Pair::BCH_BTC, "bchbtc");
+        RwLock::new(m)
     };
 }
 
 /// Return the name associated to pair used by Bitstamp
-/// If the Pair is not supported, None is returned.
-pub fn get_pair_string(pair: &Pair) -> Option<&&str> {
-    PAIRS_STRING.get_by_first(pair)
+/// If the Pair is not supported, None is returned. Pairs listed since the last update of this
+/// module are only known once `register_pair` has been called (see `ExchangeApi::markets`).
+pub fn get_pair_string(pair: &Pair) -> Option<&'static str> {
+    helpers::get_pair_name(&PAIRS_STRING, pair)
 }
 
 /// Return the Pair enum associated to the string used by Bitstamp
 /// If the Pair is not supported, None is returned.
-pub fn get_pair_enum(pair: &str) -> Option<&Pair> {
-    PAIRS_STRING.get_by_second(&pair)
+pub fn get_pair_enum(pair: &str) -> Option<Pair> {
+    helpers::get_pair_from_name(&PAIRS_STRING, pair)
+}
+
+/// Add a pair listed by Bitstamp and the name used by Bitstamp for it. Pairs and names that are
+/// already known are left untouched.
+pub fn register_pair(pair: Pair, name: &str) {
+    helpers::register_pair(&PAIRS_STRING, pair, name)
 }
 
 pub fn build_signature(nonce: &str,
@@ -52,7 +66,7 @@ pub fn build_signature(nonce: &str,
                        api_key: &str,
                        api_secret: &str)
                        -> Result<String> {
-    const C: &'static [u8] = b"0123456789ABCDEF";
+    const C: &[u8] = b"0123456789ABCDEF";
 
     let message = nonce.to_owned() + customer_id + api_key;
 
@@ -71,12 +85,89 @@ pub fn build_signature(nonce: &str,
     Ok(String::from_utf8(signature)?)
 }
 
+/// Return the message signed by the v2 header authentication:
+/// `"BITSTAMP " + api_key + method + host + path + query + content_type + nonce + timestamp + "v2"
+/// + body`. The content type is omitted when the request has no body.
+pub fn build_v2_message(api_key: &str,
+                        request: &HttpRequest,
+                        nonce: &str,
+                        timestamp: &str)
+                        -> String {
+    let method = match request.method {
+        Method::Get => "GET",
+        Method::Post => "POST",
+        Method::Delete => "DELETE",
+    };
+    let url = request.url.splitn(2, "://").last().unwrap_or("");
+    let (url, query) = match url.find('?') {
+        Some(index) => (&url[..index], &url[index + 1..]),
+        None => (url, ""),
+    };
+    let (host, path) = match url.find('/') {
+        Some(index) => (&url[..index], &url[index..]),
+        None => (url, "/"),
+    };
+    let content_type = if request.body.is_empty() {
+        ""
+    } else {
+        request.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("Content-Type"))
+            .map(|(_, value)| value.as_str())
+            .unwrap_or("")
+    };
+
+    format!("BITSTAMP {}{}{}{}{}{}{}{}v2{}",
+            api_key,
+            method,
+            host,
+            path,
+            query,
+            content_type,
+            nonce,
+            timestamp,
+            request.body)
+}
+
+/// Return the lowercase hexadecimal HMAC-SHA256 of the message, as sent in the
+/// `X-Auth-Signature` header and received in the `X-Server-Auth-Signature` header.
+pub fn build_v2_signature(message: &str, api_secret: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(api_secret.as_bytes()).unwrap();
+    mac.update(message.as_bytes());
+    HEXLOWER.encode(&mac.finalize().into_bytes())
+}
+
+/// Check the `X-Server-Auth-Signature` header of a response to a request authenticated with the
+/// v2 headers. The server signs `nonce + timestamp + content_type + body` with the API secret.
+pub fn verify_v2_response(response: &HttpResponse,
+                          nonce: &str,
+                          timestamp: &str,
+                          api_secret: &str)
+                          -> Result<()> {
+    let signature = match response.header("X-Server-Auth-Signature") {
+        Some(signature) => signature,
+        None => return Err(ErrorKind::MissingField("X-Server-Auth-Signature".to_string()).into()),
+    };
+    let content_type = response.header("Content-Type").unwrap_or("");
+    let message = format!("{}{}{}{}", nonce, timestamp, content_type, response.body);
+
+    if !build_v2_signature(&message, api_secret).eq_ignore_ascii_case(signature) {
+        return Err(ErrorKind::InvalidFieldValue("X-Server-Auth-Signature".to_string()).into());
+    }
+    Ok(())
+}
+
+/// Return the URL of a method that does not take a pair (ie. "cancel_order")
+pub fn build_method_url(method: &str) -> String {
+    "https://www.bitstamp.net/api/v2/".to_string() + method + "/"
+}
+
 pub fn build_url(method: &str, pair: &str) -> String {
     "https://www.bitstamp.net/api/v2/".to_string() + method + "/" + pair + "/"
 }
 
 pub fn deserialize_json(json_string: &str) -> Result<Map<String, Value>> {
-    let data: Value = match serde_json::from_str(json_string) {
+    let data: Value = match helpers::parse_json(json_string) {
         Ok(data) => data,
         Err(_) => return Err(ErrorKind::BadParse.into()),
     };
@@ -87,16 +178,30 @@ pub fn deserialize_json(json_string: &str) -> Result<Map<String, Value>> {
     }
 }
 
-pub fn generate_nonce(fixed_nonce: Option<String>) -> String {
-    match fixed_nonce {
-        Some(v) => v,
-        None => helpers::get_unix_timestamp_ms().to_string(),
+/// Convert a JSON array into a map containing a Vec for the "data" key
+/// A JSON object (ie. an error) is returned as is.
+pub fn deserialize_json_array(json_string: &str) -> Result<Map<String, Value>> {
+    let data: Value = match helpers::parse_json(json_string) {
+        Ok(data) => data,
+        Err(_) => return Err(ErrorKind::BadParse.into()),
+    };
+
+    if data.is_array() {
+        let mut map = Map::new();
+        map.insert("data".to_string(), data);
+        Ok(map)
+    } else if let Some(value) = data.as_object() {
+        Ok(value.clone())
+    } else {
+        Err(ErrorKind::BadParse.into())
     }
 }
 
 /// If error array is null, return the result (encoded in a json object)
 /// else return the error string found in array
-pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>> {
+pub fn parse_result(response: &Map<String, Value>,
+                    endpoint: &str)
+                    -> Result<Map<String, Value>> {
     let error_msg = match response.get("error") {
         Some(error) => {
             error
@@ -106,17 +211,239 @@ pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>>
         None => return Ok(response.clone()),
     };
 
-    match error_msg.as_ref() {
-        "Invalid command." => Err(ErrorKind::InvalidArguments.into()),
-        "Invalid API key/secret pair." => Err(ErrorKind::BadCredentials.into()),
-        "Total must be at least 0.0001." => Err(ErrorKind::InsufficientOrderSize.into()),
-        other => Err(ErrorKind::ExchangeSpecificError(other.to_string()).into()),
+    let kind = match error_msg {
+        "Invalid command." => ErrorKind::InvalidArguments,
+        "Invalid API key/secret pair." => ErrorKind::BadCredentials,
+        "Total must be at least 0.0001." => ErrorKind::InsufficientOrderSize,
+        "Order not found" => ErrorKind::OrderNotFound,
+        other => ErrorKind::ExchangeSpecificError(other.to_string()),
+    };
+    Err(ExchangeError::new(Exchange::Bitstamp, endpoint)
+            .message(error_msg)
+            .into_error(kind))
+}
+
+/// Check the response like `parse_result`, then deserialize it into the typed response of the
+/// endpoint (see the `responses` module).
+pub fn parse_response<T: DeserializeOwned>(response: &Map<String, Value>,
+                                           endpoint: &str)
+                                           -> Result<T> {
+    let result = parse_result(response, endpoint)?;
+    T::deserialize(Value::Object(result)).chain_err(|| ErrorKind::BadParse)
+}
+
+/// Same as `parse_response` for the endpoints returning an array, stored in the "data" field
+/// of the response (see `deserialize_json_array`).
+pub fn parse_array_response<T: DeserializeOwned>(response: &Map<String, Value>,
+                                                 endpoint: &str)
+                                                 -> Result<Vec<T>> {
+    let mut result = parse_result(response, endpoint)?;
+    let data = result
+        .remove("data")
+        .ok_or_else(|| ErrorKind::MissingField("data".to_string()))?;
+    Vec::<T>::deserialize(data).chain_err(|| ErrorKind::BadParse)
+}
+
+/// Return the Pair enum associated to a market name such as "BTC/USD", used by Bitstamp in
+/// its orders.
+/// If the Pair is not supported, None is returned.
+pub fn get_pair_enum_from_market(market: &str) -> Option<Pair> {
+    get_pair_enum(&market.replace("/", "").to_lowercase())
+}
+
+/// Convert the result of `trading-pairs-info` into Markets. The pairs that are not known yet are
+/// registered (see `register_pair`).
+/// The minimum notional is the amount of "minimum_order" (ie. "5.0 USD").
+pub fn parse_markets(pairs: &[Value]) -> Result<Markets> {
+    let mut markets = Markets::new();
+
+    for info in pairs {
+        let url_symbol = helpers::get_json_string(info, "url_symbol")?;
+        let pair = match get_pair_enum(url_symbol) {
+            Some(pair) => pair,
+            None => {
+                let name = helpers::get_json_string(info, "name")?;
+                let mut currencies = name
+                    .split('/')
+                    .map(|currency| {
+                             normalization::get_currency(Exchange::Bitstamp,
+                                                         &currency.to_uppercase())
+                         });
+                let pair = match (currencies.next(), currencies.next(), currencies.next()) {
+                    (Some(Some(quote)), Some(Some(base)), None) => Pair::new(quote, base),
+                    _ => return Err(ErrorKind::InvalidFieldValue("name".to_string()).into()),
+                };
+                register_pair(pair, url_symbol);
+                pair
+            }
+        };
+
+        let min_notional = helpers::get_json_string(info, "minimum_order")?
+            .split_whitespace()
+            .next()
+            .and_then(|amount| amount.parse::<BigDecimal>().ok())
+            .ok_or_else(|| ErrorKind::InvalidFieldValue("minimum_order".to_string()))?;
+        let tick_size = info["counter_decimals"]
+            .as_i64()
+            .map(|decimals| BigDecimal::new(1.into(), decimals));
+
+        markets.insert(pair,
+                       MarketInfo {
+                           pair,
+                           min_order_size: None,
+                           tick_size,
+                           lot_decimals: info["base_decimals"].as_u64().map(|d| d as u32),
+                           min_notional: Some(min_notional),
+                           active: info["trading"].as_str() == Some("Enabled"),
+                       });
+    }
+
+    Ok(markets)
+}
+
+/// Bitstamp uses 0 for buy and 1 for sell orders, either as a number or as a string
+fn parse_side(order: &Value) -> Result<OrderSide> {
+    let order_type = match order.get("type") {
+        Some(Value::String(order_type)) => order_type.to_string(),
+        Some(Value::Number(order_type)) => order_type.to_string(),
+        Some(_) => return Err(ErrorKind::InvalidFieldFormat("type".to_string()).into()),
+        None => return Err(ErrorKind::MissingField("type".to_string()).into()),
+    };
+
+    match order_type.as_ref() {
+        "0" => Ok(OrderSide::Buy),
+        "1" => Ok(OrderSide::Sell),
+        _ => Err(ErrorKind::InvalidFieldValue("type".to_string()).into()),
+    }
+}
+
+/// Convert the transactions returned by `transactions` (most recent first) into a list of
+/// Trade, from the oldest to the most recent.
+pub fn parse_trades(trades: &[Value]) -> Result<Vec<Trade>> {
+    let mut result = Vec::new();
+
+    for trade in trades.iter().rev() {
+        let date = helpers::get_json_id(trade, "date")?
+            .parse::<i64>()
+            .chain_err(|| ErrorKind::InvalidFieldFormat("date".to_string()))?;
+
+        result.push(Trade {
+                        identifier: helpers::get_json_id(trade, "tid")?,
+                        timestamp: date * 1000,
+                        price: helpers::from_json_number_bigdecimal(&trade["price"], "price")?,
+                        volume: helpers::from_json_number_bigdecimal(&trade["amount"], "amount")?,
+                        side: parse_side(trade)?,
+                    });
+    }
+
+    Ok(result)
+}
+
+/// Convert an order returned by `open_orders` into an Order.
+/// Bitstamp only reports the remaining amount of an open order, which is used as quantity.
+pub fn parse_open_order(pair: Pair, order: &Value) -> Result<Order> {
+    let side = parse_side(order)?;
+
+    Ok(Order {
+           timestamp: helpers::get_unix_timestamp_ms(),
+           identifier: helpers::get_json_id(order, "id")?,
+           pair,
+           side,
+           order_type: match side {
+               OrderSide::Buy => OrderType::BuyLimit,
+               OrderSide::Sell => OrderType::SellLimit,
+           },
+           price: Some(helpers::from_json_bigdecimal(&order["price"], "price")?),
+           quantity: helpers::from_json_bigdecimal(&order["amount"], "amount")?,
+           filled_quantity: BigDecimal::zero(),
+           average_price: None,
+           status: OrderStatus::Open,
+           opened_at: Some(helpers::parse_datetime_ms(helpers::get_json_string(order,
+                                                                               "datetime")?,
+                                                      "datetime")?),
+           closed_at: None,
+       })
+}
+
+/// Convert the response of `order_status` into an Order.
+/// The executed quantity and the average price are computed from the transactions of the
+/// order. Bitstamp does not report the limit price in this response so it is None.
+pub fn parse_order_status(pair: Pair, order: &Value) -> Result<Order> {
+    let side = parse_side(order)?;
+
+    // Transactions report the amounts in "<base>" and "<quote>" fields (ie. "btc" and "usd")
+    let pair_name = get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+    let (base, quote) = pair_name.split_at(3);
+
+    let transactions = order["transactions"]
+        .as_array()
+        .ok_or_else(|| ErrorKind::InvalidFieldFormat("transactions".to_string()))?;
+
+    let mut filled_quantity = BigDecimal::zero();
+    let mut total = BigDecimal::zero();
+    let mut closed_at = None;
+
+    for transaction in transactions {
+        filled_quantity += helpers::from_json_number_bigdecimal(&transaction[base], base)?;
+        total += helpers::from_json_number_bigdecimal(&transaction[quote], quote)?;
+
+        let date = helpers::parse_datetime_ms(helpers::get_json_string(transaction,
+                                                                       "datetime")?,
+                                              "datetime")?;
+        closed_at = Some(closed_at.map_or(date, |closed: i64| closed.max(date)));
     }
+
+    let remaining = match order.get("amount_remaining") {
+        Some(amount) => helpers::from_json_bigdecimal(amount, "amount_remaining")?,
+        None => BigDecimal::zero(),
+    };
+
+    let status = match helpers::get_json_string(order, "status")? {
+        "Open" | "In Queue" if filled_quantity.is_zero() => OrderStatus::Open,
+        "Open" | "In Queue" => OrderStatus::PartiallyFilled,
+        "Finished" => OrderStatus::Filled,
+        "Canceled" => OrderStatus::Canceled,
+        "Expired" => OrderStatus::Expired,
+        _ => return Err(ErrorKind::InvalidFieldValue("status".to_string()).into()),
+    };
+
+    Ok(Order {
+           timestamp: helpers::get_unix_timestamp_ms(),
+           identifier: helpers::get_json_id(order, "id")?,
+           pair,
+           side,
+           order_type: match side {
+               OrderSide::Buy => OrderType::BuyLimit,
+               OrderSide::Sell => OrderType::SellLimit,
+           },
+           price: None,
+           quantity: remaining + filled_quantity.clone(),
+           average_price: if filled_quantity.is_zero() {
+               None
+           } else {
+               Some(total / filled_quantity.clone())
+           },
+           filled_quantity,
+           opened_at: match order.get("datetime") {
+               Some(date) => {
+                   let date = date.as_str()
+                       .ok_or_else(|| ErrorKind::InvalidFieldFormat("datetime".to_string()))?;
+                   Some(helpers::parse_datetime_ms(date, "datetime")?)
+               }
+               None => None,
+           },
+           closed_at: match status {
+               OrderStatus::Filled => closed_at,
+               _ => None,
+           },
+           status,
+       })
 }
 
 /// Return the currency enum associated with the
-/// string used by Bitstamp. If no currency is found,
-/// return None
+/// balance key used by Bitstamp (ie. "usd_balance"), see
+/// the `normalization` module. If the string is not a
+/// balance key, return None
 /// # Examples
 ///
 /// ```
@@ -127,18 +454,14 @@ pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>>
 /// assert_eq!(Some(Currency::USD), currency);
 /// ```
 pub fn get_currency_enum(currency: &str) -> Option<Currency> {
-    match currency {
-        "usd_balance" => Some(Currency::USD),
-        "btc_balance" => Some(Currency::BTC),
-        "eur_balance" => Some(Currency::EUR),
-        "xrp_balance" => Some(Currency::XRP),
-        _ => None,
+    match currency.strip_suffix("_balance") {
+        Some(symbol) => normalization::get_currency(Exchange::Bitstamp, &symbol.to_uppercase()),
+        None => None,
     }
 }
 
-/// Return the currency string associated with the
-/// enum used by Bitstamp. If no currency is found,
-/// return None
+/// Return the name used by Bitstamp for the currency, see
+/// the `normalization` module
 /// # Examples
 ///
 /// ```
@@ -149,11 +472,5 @@ pub fn get_currency_enum(currency: &str) -> Option<Currency> {
 /// assert_eq!(currency, Some("USD".to_string()));
 /// ```
 pub fn get_currency_string(currency: Currency) -> Option<String> {
-    match currency {
-        Currency::USD => Some("USD".to_string()),
-        Currency::BTC => Some("BTC".to_string()),
-        Currency::EUR => Some("EUR".to_string()),
-        Currency::XRP => Some("XRP".to_string()),
-        _ => None,
-    }
+    Some(normalization::get_name(Exchange::Bitstamp, currency))
 }
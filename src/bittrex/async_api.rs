@@ -0,0 +1,516 @@
+//! Use this module to interact asynchronously with the raw-original API provided by Bittrex.
+//! `AsyncBittrexApi` is the implementation behind the blocking client of the `api` module.
+//! The requests are sent to the v3 API (see `utils::API_URL`).
+//! WARNING: Special attention should be paid to error management: parsing number, etc.
+
+use serde_json::Value;
+use serde_json::value::Map;
+
+use std::sync::RwLock;
+
+use crate::error::*;
+use crate::rate_limit::Scope;
+use crate::retry::{self, Idempotency};
+use crate::types::Markets;
+
+use crate::exchange::Exchange;
+use crate::client::ClientConfig;
+use crate::coinnect::Credentials;
+use crate::bittrex::utils;
+use crate::bittrex::typed_api::AsyncBittrexTypedApi;
+use crate::transport::{AsyncHttpTransport, HttpRequest, Method, ReqwestTransport};
+
+#[derive(Debug)]
+pub struct AsyncBittrexApi {
+    api_key: String,
+    api_secret: String,
+    http_client: Box<dyn AsyncHttpTransport>,
+    config: ClientConfig,
+    /// Markets returned by `ExchangeApi::markets`, fetched on first use
+    pub(crate) markets: RwLock<Option<Markets>>,
+}
+
+
+impl AsyncBittrexApi {
+    /// Create a new AsyncBittrexApi by providing an API key & API secret
+    pub fn new<C: Credentials>(creds: C) -> Result<AsyncBittrexApi> {
+        AsyncBittrexApi::new_with_transport(creds, Box::new(ReqwestTransport::new()?))
+    }
+
+    /// Create a new AsyncBittrexApi sending its requests through the given transport
+    pub fn new_with_transport<C: Credentials>(creds: C,
+                                              transport: Box<dyn AsyncHttpTransport>)
+                                              -> Result<AsyncBittrexApi> {
+        if creds.exchange() != Exchange::Bittrex {
+            return Err(ErrorKind::InvalidConfigType(Exchange::Bittrex, creds.exchange()).into());
+        }
+
+        let api_key = creds.get("api_key").unwrap_or_default();
+        let config = ClientConfig::shared(Exchange::Bittrex, &api_key);
+
+        Ok(AsyncBittrexApi {
+               api_key,
+               api_secret: creds.get("api_secret").unwrap_or_default(),
+               http_client: transport,
+               config,
+               markets: RwLock::new(None),
+           })
+    }
+
+    /// Return the settings of the client: its RateLimiter, RetryPolicy and NonceProvider.
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Change the settings of the client, ie. `api.config_mut().set_retry_policy(policy)`.
+    /// The nonces are sent as the Api-Timestamp of the requests, in ms.
+    pub fn config_mut(&mut self) -> &mut ClientConfig {
+        &mut self.config
+    }
+
+    /// Return a view of the client whose methods return the typed structs of the `responses`
+    /// module instead of JSON maps.
+    pub fn typed(&self) -> AsyncBittrexTypedApi<'_> {
+        AsyncBittrexTypedApi::new(self)
+    }
+
+    async fn public_query(&self, endpoint: &str, path: &str) -> Result<Map<String, Value>> {
+        self.config
+            .retry_policy()
+            .run_checked(Idempotency::Idempotent,
+                         || self.send_public_query(endpoint, path),
+                         |response| utils::parse_result(response, endpoint).map(|_| ()))
+            .await
+            .map_err(|err| err.with_endpoint(Exchange::Bittrex, endpoint))
+    }
+
+    async fn private_query(&self,
+                           method: Method,
+                           endpoint: &str,
+                           path: &str,
+                           body: Option<&Map<String, Value>>)
+                           -> Result<Map<String, Value>> {
+        let idempotency = retry::get_idempotency(Exchange::Bittrex, Scope::Private, endpoint);
+        self.config
+            .retry_policy()
+            .run_checked(idempotency,
+                         || self.send_private_query(method, endpoint, path, body),
+                         |response| utils::parse_result(response, endpoint).map(|_| ()))
+            .await
+            .map_err(|err| err.with_endpoint(Exchange::Bittrex, endpoint))
+    }
+
+    async fn send_public_query(&self, endpoint: &str, path: &str) -> Result<Map<String, Value>> {
+        self.config.rate_limiter().acquire(Scope::Public, endpoint).await?;
+
+        let request = HttpRequest::new(Method::Get, &utils::build_url(path));
+        let response = self.http_client.send(request).await?.error_for_status()?;
+
+        utils::deserialize_json_array(&response.body)
+    }
+
+    /// Send a signed request. `path` is the path of the endpoint with its query string (ie.
+    /// "/orders/open?marketSymbol=LTC-BTC"), the body is sent as JSON.
+    /// The signature covers the timestamp, the URL, the method and the hash of the body (see
+    /// `utils::build_signature`).
+    async fn send_private_query(&self,
+                                method: Method,
+                                endpoint: &str,
+                                path: &str,
+                                body: Option<&Map<String, Value>>)
+                                -> Result<Map<String, Value>> {
+        self.config.rate_limiter().acquire(Scope::Private, endpoint).await?;
+
+        let body = match body {
+            Some(body) => serde_json::to_string(body)?,
+            None => "".to_string(),
+        };
+        let method_name = match method {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Delete => "DELETE",
+        };
+
+        let url = utils::build_url(path);
+        let timestamp = self.config.nonce_provider().next_nonce()?.to_string();
+        let content_hash = utils::build_content_hash(&body);
+        let signature = utils::build_signature(&timestamp,
+                                               &url,
+                                               method_name,
+                                               &content_hash,
+                                               "",
+                                               &self.api_secret);
+
+        let mut request = HttpRequest::new(method, &url)
+            .header("Api-Key", &self.api_key)
+            .header("Api-Timestamp", &timestamp)
+            .header("Api-Content-Hash", &content_hash)
+            .header("Api-Signature", &signature);
+        if !body.is_empty() {
+            request = request.header("Content-Type", "application/json").body(&body);
+        }
+        let response = self.http_client.send(request).await?.error_for_status()?;
+
+        utils::deserialize_json_array(&response.body)
+    }
+
+    /// Returns the markets of Bittrex along with other meta data.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"symbol": "LTC-BTC", "baseCurrencySymbol": "LTC", "quoteCurrencySymbol": "BTC",
+    /// "minTradeSize": "0.01000000", "precision": 8, "status": "ONLINE",
+    /// "createdAt": "2014-02-13T00:00:00Z", "notice": "", "prohibitedIn": [],
+    /// "associatedTermsOfService": [], "tags": []}, ... ]
+    /// ```
+    pub async fn get_markets(&self) -> Result<Map<String, Value>> {
+        self.public_query("markets", "/markets").await
+    }
+
+    /// Returns a market of Bittrex, with the fields of `get_markets`.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    pub async fn get_market(&self, market: &str) -> Result<Map<String, Value>> {
+        self.public_query("market", &format!("/markets/{}", market)).await
+    }
+
+    /// Returns the currencies supported by Bittrex along with other meta data.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"symbol": "BTC", "name": "Bitcoin", "coinType": "BITCOIN", "status": "ONLINE",
+    /// "minConfirmations": 2, "notice": "", "txFee": "0.00030000",
+    /// "logoUrl": "https://bittrex.com/.../btc.png", "prohibitedIn": [],
+    /// "baseAddress": "1N52wHoVR79PMDishab2XmRHsbekCdGquK", "associatedTermsOfService": [],
+    /// "tags": []}, ... ]
+    /// ```
+    pub async fn get_currencies(&self) -> Result<Map<String, Value>> {
+        self.public_query("currencies", "/currencies").await
+    }
+
+    /// Returns a currency supported by Bittrex, with the fields of `get_currencies`.
+    /// "currency" required a string literal for the currency (ex: LTC)
+    pub async fn get_currency(&self, currency: &str) -> Result<Map<String, Value>> {
+        self.public_query("currency", &format!("/currencies/{}", currency)).await
+    }
+
+    /// Returns the last trade price and the best bid and ask of a market.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"symbol": "LTC-BTC", "lastTradeRate": "0.01349998", "bidRate": "0.01271001",
+    /// "askRate": "0.01291100"}
+    /// ```
+    pub async fn get_ticker(&self, market: &str) -> Result<Map<String, Value>> {
+        self.public_query("ticker", &format!("/markets/{}/ticker", market)).await
+    }
+
+    /// Returns the tickers of every market, with the fields of `get_ticker`.
+    /// The array is returned in the "data" field of the map.
+    pub async fn get_tickers(&self) -> Result<Map<String, Value>> {
+        self.public_query("tickers", "/markets/tickers").await
+    }
+
+    /// Returns the summaries of the last 24 hours of every market, with the fields of
+    /// `get_market_summary`.
+    /// The array is returned in the "data" field of the map.
+    pub async fn get_market_summaries(&self) -> Result<Map<String, Value>> {
+        self.public_query("market_summaries", "/markets/summaries").await
+    }
+
+    /// Returns the summary of the last 24 hours of a market.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"symbol": "LTC-BTC", "high": "0.01350000", "low": "0.01200000",
+    /// "volume": "3833.97619253", "quoteVolume": "47.03987026", "percentChange": "9.80",
+    /// "updatedAt": "2014-07-09T07:22:16.72Z"}
+    /// ```
+    pub async fn get_market_summary(&self, market: &str) -> Result<Map<String, Value>> {
+        self.public_query("market_summary", &format!("/markets/{}/summary", market)).await
+    }
+
+    /// Returns the orderbook of a market.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    /// "depth" required the number of entries of each side: 1, 25 or 500.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"bid": [{"quantity": "12.37000000", "rate": "0.02525000"}, ... ],
+    /// "ask": [{"quantity": "32.55412402", "rate": "0.02540000"}, ... ]}
+    /// ```
+    pub async fn get_order_book(&self, market: &str, depth: u32) -> Result<Map<String, Value>> {
+        let path = utils::build_path(&format!("/markets/{}/orderbook", market),
+                                     &[("depth", &depth.to_string())]);
+        self.public_query("orderbook", &path).await
+    }
+
+    /// Returns the latest trades of a market, most recent first ("takerSide" is the side of the
+    /// taker). The array is returned in the "data" field of the map.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "8f2e7cbd-7f6a-4d5b-9b0e-7b2b1b1f0b1c", "executedAt": "2014-07-09T03:21:20.08Z",
+    /// "quantity": "0.30802438", "rate": "0.01263400", "takerSide": "BUY"}, ... ]
+    /// ```
+    pub async fn get_trades(&self, market: &str) -> Result<Map<String, Value>> {
+        self.public_query("trades", &format!("/markets/{}/trades", market)).await
+    }
+
+    /// Returns the recent candles of a market, from the oldest to the most recent.
+    /// The array is returned in the "data" field of the map.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    /// "interval" required the duration of the candles: MINUTE_1, MINUTE_5, HOUR_1 or DAY_1.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"startsAt": "2014-07-09T03:00:00Z", "open": "0.01263400", "high": "0.01350000",
+    /// "low": "0.01200000", "close": "0.01349998", "volume": "383.39761925",
+    /// "quoteVolume": "4.70398702"}, ... ]
+    /// ```
+    pub async fn get_candles(&self, market: &str, interval: &str) -> Result<Map<String, Value>> {
+        let path = format!("/markets/{}/candles/{}/recent", market, interval);
+        self.public_query("candles", &path).await
+    }
+
+    /// Returns the balances of your account.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"currencySymbol": "BTC", "total": "14.21549076", "available": "14.21549076",
+    /// "updatedAt": "2014-07-09T04:01:00.667Z"}, ... ]
+    /// ```
+    pub async fn get_balances(&self) -> Result<Map<String, Value>> {
+        self.private_query(Method::Get, "balances", "/balances", None).await
+    }
+
+    /// Returns the balance of your account for a currency, with the fields of `get_balances`.
+    /// "currency" required a string literal for the currency (ex: LTC)
+    pub async fn get_balance(&self, currency: &str) -> Result<Map<String, Value>> {
+        let path = format!("/balances/{}", currency);
+        self.private_query(Method::Get, "balance", &path, None).await
+    }
+
+    /// Returns the deposit address of a currency. The address must have been requested with
+    /// `provision_deposit_address` first, its "status" is "REQUESTED" until it is available.
+    /// "currency" required a string literal for the currency (ex: LTC)
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"status": "PROVISIONED", "currencySymbol": "VTC",
+    /// "cryptoAddress": "Vy5SKeKGXUHKS2WVpJ76HYuKAu3URastUo", "cryptoAddressTag": null}
+    /// ```
+    pub async fn get_deposit_address(&self, currency: &str) -> Result<Map<String, Value>> {
+        let path = format!("/addresses/{}", currency);
+        self.private_query(Method::Get, "deposit_address", &path, None).await
+    }
+
+    /// Requests a deposit address for a currency, returned with the fields of
+    /// `get_deposit_address`.
+    /// "currency" required a string literal for the currency (ex: LTC)
+    pub async fn provision_deposit_address(&self, currency: &str) -> Result<Map<String, Value>> {
+        let mut body = Map::new();
+        body.insert("currencySymbol".to_string(), Value::String(currency.to_string()));
+        self.private_query(Method::Post, "provision_deposit_address", "/addresses", Some(&body))
+            .await
+    }
+
+    /// Place an order. The body is the JSON object documented by Bittrex (ie. with the
+    /// "marketSymbol", "direction", "type", "quantity", "limit" and "timeInForce" fields).
+    /// Make sure you have the proper permissions set on your API keys for this call to work.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"id": "e606d53c-8d70-11e3-94b5-425861b86ab6", "marketSymbol": "LTC-BTC",
+    /// "direction": "BUY", "type": "LIMIT", "quantity": "5.00000000", "limit": "0.01000000",
+    /// "timeInForce": "GOOD_TIL_CANCELLED", "fillQuantity": "0.00000000",
+    /// "commission": "0.00000000", "proceeds": "0.00000000", "status": "OPEN",
+    /// "createdAt": "2014-07-09T03:55:48.77Z", "updatedAt": "2014-07-09T03:55:48.77Z"}
+    /// ```
+    pub async fn place_order(&self, order: &Map<String, Value>) -> Result<Map<String, Value>> {
+        self.private_query(Method::Post, "place_order", "/orders", Some(order)).await
+    }
+
+    /// Place a limit order to buy "quantity" of a market at the "rate", kept until it is filled
+    /// or canceled. The order is returned with the fields of `place_order`.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    pub async fn buy_limit(&self,
+                           market: &str,
+                           quantity: &str,
+                           rate: &str)
+                           -> Result<Map<String, Value>> {
+        self.place_order(&build_order(market, "BUY", "LIMIT", quantity, Some(rate))).await
+    }
+
+    /// Place a limit order to sell "quantity" of a market at the "rate", kept until it is
+    /// filled or canceled. The order is returned with the fields of `place_order`.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    pub async fn sell_limit(&self,
+                            market: &str,
+                            quantity: &str,
+                            rate: &str)
+                            -> Result<Map<String, Value>> {
+        self.place_order(&build_order(market, "SELL", "LIMIT", quantity, Some(rate))).await
+    }
+
+    /// Place a market order to buy "quantity" of a market. The order is returned with the
+    /// fields of `place_order`.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    pub async fn buy_market(&self, market: &str, quantity: &str) -> Result<Map<String, Value>> {
+        self.place_order(&build_order(market, "BUY", "MARKET", quantity, None)).await
+    }
+
+    /// Place a market order to sell "quantity" of a market. The order is returned with the
+    /// fields of `place_order`.
+    /// "market" required a string literal for the market (ex: LTC-BTC)
+    pub async fn sell_market(&self, market: &str, quantity: &str) -> Result<Map<String, Value>> {
+        self.place_order(&build_order(market, "SELL", "MARKET", quantity, None)).await
+    }
+
+    /// Cancel an order, returned with the fields of `place_order`.
+    /// "uuid" required the id of the order
+    pub async fn cancel(&self, uuid: &str) -> Result<Map<String, Value>> {
+        let path = format!("/orders/{}", uuid);
+        self.private_query(Method::Delete, "cancel", &path, None).await
+    }
+
+    /// Cancel all your open orders, or the ones of a market.
+    /// "market" optional a string literal for the market (ie. LTC-BTC)
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "09aa5bb6-8232-41aa-9b78-a5a1093e0211", "statusCode": "SUCCESS",
+    /// "result": {"id": "09aa5bb6-8232-41aa-9b78-a5a1093e0211", ... }}, ... ]
+    /// ```
+    pub async fn cancel_open_orders(&self, market: &str) -> Result<Map<String, Value>> {
+        let path = utils::build_path("/orders/open", &[("marketSymbol", market)]);
+        self.private_query(Method::Delete, "cancel_open_orders", &path, None).await
+    }
+
+    /// Get all orders that you currently have opened, with the fields of `place_order`.
+    /// A specific market can be requested.
+    /// "market" optional a string literal for the market (ie. LTC-BTC)
+    /// The array is returned in the "data" field of the map.
+    pub async fn get_open_orders(&self, market: &str) -> Result<Map<String, Value>> {
+        let path = utils::build_path("/orders/open", &[("marketSymbol", market)]);
+        self.private_query(Method::Get, "open_orders", &path, None).await
+    }
+
+    /// Used to retrieve a single order by uuid, with the fields of `place_order` ("closedAt" is
+    /// set once the order is closed).
+    /// "uuid" required the id of the order
+    pub async fn get_order(&self, uuid: &str) -> Result<Map<String, Value>> {
+        let path = format!("/orders/{}", uuid);
+        self.private_query(Method::Get, "order", &path, None).await
+    }
+
+    /// Used to retrieve your closed orders, most recent first, with the fields of
+    /// `get_order`.
+    /// "market" optional a string literal for the market (ie. LTC-BTC).
+    /// If ommited, will return for all markets
+    /// The array is returned in the "data" field of the map.
+    pub async fn get_order_history(&self, market: &str) -> Result<Map<String, Value>> {
+        let path = utils::build_path("/orders/closed", &[("marketSymbol", market)]);
+        self.private_query(Method::Get, "order_history", &path, None).await
+    }
+
+    /// Used to withdraw funds from your account. note: please account for txfee.
+    /// "currency" required a string literal for the currency (ie. BTC)
+    /// "quantity" required the quantity of coins to withdraw
+    /// "address" required the address where to send the funds.
+    /// "paymentid" optional used for CryptoNotes/BitShareX/Nxt optional field (memo/paymentid)
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"id": "68b5a16c-92de-11e3-ba3b-425861b86ab6", "currencySymbol": "BTC",
+    /// "quantity": "17.00000000", "cryptoAddress": "1DeaaFBdbB5nrHj87x3NHS4onvw1GPNyAu",
+    /// "txCost": "0.00020000", "status": "REQUESTED", "createdAt": "2014-07-09T04:24:47.217Z"}
+    /// ```
+    pub async fn withdraw(&self,
+                          currency: &str,
+                          quantity: &str,
+                          address: &str,
+                          paymentid: &str)
+                          -> Result<Map<String, Value>> {
+        let mut body = Map::new();
+        body.insert("currencySymbol".to_string(), Value::String(currency.to_string()));
+        body.insert("quantity".to_string(), Value::String(quantity.to_string()));
+        body.insert("cryptoAddress".to_string(), Value::String(address.to_string()));
+        if !paymentid.is_empty() {
+            body.insert("cryptoAddressTag".to_string(), Value::String(paymentid.to_string()));
+        }
+        self.private_query(Method::Post, "withdraw", "/withdrawals", Some(&body)).await
+    }
+
+    /// Used to retrieve your closed withdrawals, with the fields of `withdraw` ("txId" and
+    /// "completedAt" are set once the withdrawal is completed).
+    /// "currency" optional a string literal for the currecy (ie. BTC).
+    /// If omitted, will return for all currencies
+    /// The array is returned in the "data" field of the map.
+    pub async fn get_withdrawal_history(&self, currency: &str) -> Result<Map<String, Value>> {
+        let path = utils::build_path("/withdrawals/closed", &[("currencySymbol", currency)]);
+        self.private_query(Method::Get, "withdrawal_history", &path, None).await
+    }
+
+    /// Used to retrieve your closed deposits.
+    /// "currency" optional a string literal for the currecy (ie. BTC).
+    /// If omitted, will return for all currencies
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "554ec664-8842-4fe9-b491-06225becbd59", "currencySymbol": "BTC",
+    /// "quantity": "0.00156121", "cryptoAddress": "1K37yQZaGrPKNTZ5KNP792xw8f7XbXxetE",
+    /// "confirmations": 2, "updatedAt": "2014-07-11T03:41:25.323Z",
+    /// "completedAt": "2014-07-11T03:41:25.323Z", "status": "COMPLETED",
+    /// "source": "BLOCKCHAIN",
+    /// "txId": "70cf6fdccb9bd38e1a930e13e4ae6299d678ed6902da710fa3cc8d164f9be126"}, ... ]
+    /// ```
+    pub async fn get_deposit_history(&self, currency: &str) -> Result<Map<String, Value>> {
+        let path = utils::build_path("/deposits/closed", &[("currencySymbol", currency)]);
+        self.private_query(Method::Get, "deposit_history", &path, None).await
+    }
+}
+
+/// Return the body of an order: limit orders are kept until they are filled or canceled, market
+/// orders are filled immediately as much as possible.
+fn build_order(market: &str,
+               direction: &str,
+               order_type: &str,
+               quantity: &str,
+               limit: Option<&str>)
+               -> Map<String, Value> {
+    let mut order = Map::new();
+    order.insert("marketSymbol".to_string(), Value::String(market.to_string()));
+    order.insert("direction".to_string(), Value::String(direction.to_string()));
+    order.insert("type".to_string(), Value::String(order_type.to_string()));
+    order.insert("quantity".to_string(), Value::String(quantity.to_string()));
+    let time_in_force = match limit {
+        Some(limit) => {
+            order.insert("limit".to_string(), Value::String(limit.to_string()));
+            "GOOD_TIL_CANCELLED"
+        }
+        None => "IMMEDIATE_OR_CANCEL",
+    };
+    order.insert("timeInForce".to_string(), Value::String(time_in_force.to_string()));
+    order
+}
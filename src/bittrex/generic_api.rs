@@ -2,174 +2,319 @@
 //! This a more convenient and safe way to deal with the exchange since methods return a Result<>
 //! but this generic API does not provide all the functionnality that Bittrex offers.
 
-use bigdecimal::BigDecimal;
-use std::str::FromStr;
+use async_trait::async_trait;
+use serde_json::Value;
 
-use crate::exchange::ExchangeApi;
+use crate::exchange::{AsyncExchangeApi, ExchangeApi};
 use crate::bittrex::api::BittrexApi;
+use crate::bittrex::async_api::AsyncBittrexApi;
 
 use crate::error::*;
 use crate::types::*;
 use crate::bittrex::utils;
 use crate::helpers;
 
-impl ExchangeApi for BittrexApi {
-    fn ticker(&mut self, pair: Pair) -> Result<Ticker> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
+#[async_trait]
+impl AsyncExchangeApi for AsyncBittrexApi {
+    async fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        let market = get_market_symbol(self, &pair).await?;
 
-        let raw_response = self.get_market_summary(pair_name)?;
+        // The volume is only sent with the summary of the market
+        let raw_response = self.get_ticker(market).await?;
+        let ticker = Value::Object(utils::parse_result(&raw_response, "ticker")?);
 
-        let result = utils::parse_result(&raw_response)?;
-        let result_array = result.as_array();
-        let result_obj = result_array.unwrap()[0].as_object().unwrap();
+        let raw_response = self.get_market_summary(market).await?;
+        let summary = Value::Object(utils::parse_result(&raw_response, "market_summary")?);
 
-        let price_str = result_obj.get("Last").unwrap().as_f64().unwrap().to_string();
-        let price = BigDecimal::from_str(&price_str).unwrap();
+        let price = helpers::from_json_number_bigdecimal(&ticker["lastTradeRate"],
+                                                         "lastTradeRate")?;
+        let ask = helpers::from_json_number_bigdecimal(&ticker["askRate"], "askRate")?;
+        let bid = helpers::from_json_number_bigdecimal(&ticker["bidRate"], "bidRate")?;
+        let vol = helpers::from_json_number_bigdecimal(&summary["volume"], "volume")?;
 
-        let ask_str = result_obj.get("Ask").unwrap().as_f64().unwrap().to_string();
-        let ask = BigDecimal::from_str(&ask_str).unwrap();
+        Ok(Ticker {
+               timestamp: helpers::get_unix_timestamp_ms(),
+               pair,
+               last_trade_price: price,
+               lowest_ask: ask,
+               highest_bid: bid,
+               volume: Some(vol),
+           })
+    }
 
-        let bid_str = result_obj.get("Bid").unwrap().as_f64().unwrap().to_string();
-        let bid = BigDecimal::from_str(&bid_str).unwrap();
+    async fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        let market = get_market_symbol(self, &pair).await?;
 
-        let volume_str = result_obj.get("Volume").unwrap().as_f64().unwrap().to_string();
-        let vol = BigDecimal::from_str(&volume_str).unwrap();
+        let raw_response = self.get_order_book(market, 500).await?;
 
-        Ok(Ticker {
-            timestamp: helpers::get_unix_timestamp_ms(),
-            pair: pair,
-            last_trade_price: price,
-            lowest_ask: ask,
-            highest_bid: bid,
-            volume: Some(vol),
-        })
+        let result = Value::Object(utils::parse_result(&raw_response, "orderbook")?);
 
-    }
+        let mut ask_offers = Vec::new();
+        let mut bid_offers = Vec::new();
 
-    fn orderbook(&mut self, pair: Pair) -> Result<Orderbook> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
+        let asks = result["ask"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("ask".to_string()))?;
 
-        let raw_response = self.get_order_book(pair_name, "both")?;
+        let bids = result["bid"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("bid".to_string()))?;
 
-        let result = utils::parse_result(&raw_response)?;
+        for ask in asks {
+            let price = helpers::from_json_number_bigdecimal(&ask["rate"], "rate")?;
+            let volume = helpers::from_json_number_bigdecimal(&ask["quantity"], "quantity")?;
 
-        let mut ask_offers = Vec::new();    // buy orders
-        let mut bid_offers = Vec::new();    // sell orders
+            ask_offers.push((price, volume));
+        }
 
-        let buy_orders = result["buy"].as_array()
-        .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["buy"])))?;
+        for bid in bids {
+            let price = helpers::from_json_number_bigdecimal(&bid["rate"], "rate")?;
+            let volume = helpers::from_json_number_bigdecimal(&bid["quantity"], "quantity")?;
 
-        let sell_orders = result["sell"].as_array()
-        .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["sell"])))?;
+            bid_offers.push((price, volume));
+        }
 
-        for ask in buy_orders {
-            let ask_obj = ask.as_object().unwrap();
+        Ok(Orderbook {
+               timestamp: helpers::get_unix_timestamp_ms(),
+               pair,
+               asks: ask_offers,
+               bids: bid_offers,
+           })
+    }
 
-            let price_str = ask_obj.get("Rate").unwrap().as_f64().unwrap().to_string();
-            let price = BigDecimal::from_str(&price_str).unwrap();
+    /// The identifiers of the trades are UUIDs, so the cursor is the timestamp (in ms) of the
+    /// last trade returned.
+    async fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        let market = get_market_symbol(self, &pair).await?;
 
+        let last_timestamp = match since {
+            Some(ref since) => {
+                Some(since.parse::<i64>().chain_err(|| ErrorKind::InvalidArguments)?)
+            }
+            None => None,
+        };
 
-            let volume_str = ask_obj.get("Quantity").unwrap().as_f64().unwrap().to_string();
-            let volume = BigDecimal::from_str(&volume_str).unwrap();
+        let raw_response = self.get_trades(market).await?;
 
-            ask_offers.push((price, volume));
+        let trade_array = utils::parse_array(&raw_response, "trades")?;
+
+        let mut trades = utils::parse_trades(&trade_array)?;
+        if let Some(last_timestamp) = last_timestamp {
+            trades.retain(|trade| trade.timestamp > last_timestamp);
         }
 
-        for bid in sell_orders {
-            let bid_obj = bid.as_object().unwrap();
+        let cursor = match trades.last() {
+            Some(trade) => Some(trade.timestamp.to_string()),
+            None => since,
+        };
 
-            let price_str = bid_obj.get("Rate").unwrap().as_f64().unwrap().to_string();
-            let price = BigDecimal::from_str(&price_str).unwrap();
+        Ok(TradeHistory {
+               timestamp: helpers::get_unix_timestamp_ms(),
+               pair,
+               trades,
+               cursor,
+           })
+    }
 
+    async fn candles(&self,
+                     pair: Pair,
+                     interval: Interval,
+                     since: Option<i64>)
+                     -> Result<Vec<Candle>> {
+        let market = get_market_symbol(self, &pair).await?;
+        let candle_interval = utils::get_candle_interval(interval)
+            .ok_or_else(|| ErrorKind::Unsupported(format!("{:?} candles", interval)))?;
 
-            let volume_str = bid_obj.get("Quantity").unwrap().as_f64().unwrap().to_string();
-            let volume = BigDecimal::from_str(&volume_str).unwrap();
+        let raw_response = self.get_candles(market, candle_interval).await?;
 
-            bid_offers.push((price, volume));
+        let candle_array = utils::parse_array(&raw_response, "candles")?;
+
+        let mut candles = utils::parse_candles(&candle_array)?;
+
+        // Bittrex only sends the recent candles
+        if let Some(since) = since {
+            candles.retain(|candle| candle.timestamp > since);
         }
 
-        Ok(Orderbook {
-            timestamp: helpers::get_unix_timestamp_ms(),
-            pair: pair,
-            asks: ask_offers,
-            bids: bid_offers,
-        })
+        Ok(candles)
     }
 
-    fn add_order(&mut self,
-                 order_type: OrderType,
-                 pair: Pair,
-                 quantity: Volume,
-                 price: Option<Price>)
-                 -> Result<OrderInfo> {
+    async fn markets(&self) -> Result<Markets> {
+        if let Some(ref markets) = *self.markets.read().unwrap() {
+            return Ok(markets.clone());
+        }
 
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(pair_str) => pair_str,
-            None => return Err(ErrorKind::PairUnsupported.into())
-        };
+        let raw_response = self.get_markets().await?;
+
+        let market_array = utils::parse_array(&raw_response, "markets")?;
+
+        let markets = utils::parse_markets(&market_array)?;
+        *self.markets.write().unwrap() = Some(markets.clone());
+
+        Ok(markets)
+    }
+
+    async fn add_order(&self,
+                       order_type: OrderType,
+                       pair: Pair,
+                       quantity: Volume,
+                       price: Option<Price>)
+                       -> Result<OrderInfo> {
+        let market = get_market_symbol(self, &pair).await?;
+        let quantity_str = quantity.to_string();
 
         let raw_response = match order_type {
             OrderType::BuyLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
-                self.buy_limit(pair_name, &quantity.to_string(), &price.unwrap().to_string())
-            }
-            OrderType::BuyMarket => {
-                let min_price = "0.000000001";
-                self.buy_limit(pair_name, &quantity.to_string(), min_price)
+                let price = price.as_ref().ok_or(ErrorKind::MissingPrice)?;
+                self.buy_limit(market, &quantity_str, &price.to_string()).await
             }
             OrderType::SellLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
-                self.sell_limit(pair_name, &quantity.to_string(), &price.unwrap().to_string())
-            }
-            OrderType::SellMarket => {
-                let max_price = "999999999.99";
-                self.buy_limit(pair_name, &quantity.to_string(), max_price)
+                let price = price.as_ref().ok_or(ErrorKind::MissingPrice)?;
+                self.sell_limit(market, &quantity_str, &price.to_string()).await
             }
+            OrderType::BuyMarket => self.buy_market(market, &quantity_str).await,
+            OrderType::SellMarket => self.sell_market(market, &quantity_str).await,
         }?;
 
-        let result = utils::parse_result(&raw_response)?;
-
-        let result_obj = result.as_object().unwrap();
+        let result = Value::Object(utils::parse_result(&raw_response, "place_order")?);
 
         Ok(OrderInfo {
                timestamp: helpers::get_unix_timestamp_ms(),
-               identifier: vec![result_obj.get("uuid").unwrap().as_str().unwrap().to_string()],
-        })
+               identifier: vec![helpers::get_json_string(&result, "id")?.to_string()],
+               pair,
+               order_type,
+               quantity,
+               price,
+               fills: Vec::new(),
+           })
     }
 
-    fn balances(&mut self) -> Result<Balances> {
-        let raw_response = self.get_balances()?;
+    async fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        for uuid in &order.identifier {
+            let raw_response = self.cancel(uuid).await?;
+            utils::parse_result(&raw_response, "cancel")?;
+        }
 
-        let result = utils::parse_result(&raw_response)?;
+        Ok(())
+    }
 
-        let result_array = result.as_array().unwrap();
+    async fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        let market = match pair {
+            Some(ref pair) => get_market_symbol(self, pair).await?,
+            None => "",
+        };
 
-        let mut balances = Balances::new();
+        let raw_response = self.get_open_orders(market).await?;
 
-        for currency in result_array {
-            let currency_obj = currency.as_object().unwrap();
-            let currency_str = currency_obj.get("Currency").unwrap().as_str().unwrap();
-            let currency = utils::get_currency_enum(&currency_str);
+        let order_array = utils::parse_array(&raw_response, "open_orders")?;
 
-            match currency {
-                Some(c) => {
-                    let amount_str = currency_obj.get("Available").unwrap().as_f64().unwrap().to_string();
-                    let amount = BigDecimal::from_str(&amount_str).unwrap();
-                    balances.insert(c, amount);
-                },
-                _ => ()
-            }
+        let mut orders = Vec::new();
+
+        for order in &order_array {
+            let market_name = helpers::get_json_string(order, "marketSymbol")?;
+            let order_pair = match get_pair_from_name(self, market_name).await? {
+                Some(order_pair) => order_pair,
+                None => continue,
+            };
+
+            orders.push(utils::parse_order(order_pair, order)?);
         }
-        Ok(balances)
+
+        Ok(orders)
+    }
+
+    async fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        let uuid = order.identifier.first().ok_or(ErrorKind::OrderNotFound)?;
+
+        let raw_response = self.get_order(uuid).await?;
+
+        let result = Value::Object(utils::parse_result(&raw_response, "order")?);
+
+        let market_name = helpers::get_json_string(&result, "marketSymbol")?;
+        let pair = get_pair_from_name(self, market_name).await?.ok_or(ErrorKind::PairUnsupported)?;
+
+        utils::parse_order(pair, &result)
+    }
+
+    async fn balances(&self) -> Result<Balances> {
+        let raw_response = self.get_balances().await?;
+
+        let balance_array = utils::parse_array(&raw_response, "balances")?;
+
+        utils::parse_balances(&balance_array)
+    }
+}
+
+/// Return the market symbol of the pair. The pairs listed since the last update of Coinnect are
+/// only known once the markets listed by Bittrex have been loaded.
+async fn get_market_symbol(api: &AsyncBittrexApi, pair: &Pair) -> Result<&'static str> {
+    helpers::find_pair(&api.markets, || api.markets(), || utils::get_pair_string(pair)).await?;
+
+    utils::get_market_symbol(pair)
+}
+
+/// Return the Pair of a market symbol used by Bittrex, loading the markets listed by Bittrex if
+/// the symbol is not known yet.
+async fn get_pair_from_name(api: &AsyncBittrexApi, name: &str) -> Result<Option<Pair>> {
+    helpers::find_pair(&api.markets, || api.markets(), || utils::get_pair_enum(name)).await
+}
+
+impl ExchangeApi for BittrexApi {
+    fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        self.runtime.block_on(self.inner.ticker(pair))
+    }
+
+    fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        self.runtime.block_on(self.inner.orderbook(pair))
+    }
+
+    fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        self.runtime.block_on(self.inner.trades(pair, since))
+    }
+
+    fn candles(&self,
+               pair: Pair,
+               interval: Interval,
+               since: Option<i64>)
+               -> Result<Vec<Candle>> {
+        self.runtime.block_on(self.inner.candles(pair, interval, since))
+    }
+
+    fn markets(&self) -> Result<Markets> {
+        self.runtime.block_on(self.inner.markets())
+    }
+
+    fn validate_order(&self,
+                      order_type: OrderType,
+                      pair: Pair,
+                      quantity: Volume,
+                      price: Option<Price>)
+                      -> Result<(Volume, Option<Price>)> {
+        self.runtime.block_on(self.inner.validate_order(order_type, pair, quantity, price))
+    }
+
+    fn add_order(&self,
+                 order_type: OrderType,
+                 pair: Pair,
+                 quantity: Volume,
+                 price: Option<Price>)
+                 -> Result<OrderInfo> {
+        self.runtime.block_on(self.inner.add_order(order_type, pair, quantity, price))
+    }
+
+    fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        self.runtime.block_on(self.inner.cancel_order(order))
+    }
+
+    fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        self.runtime.block_on(self.inner.open_orders(pair))
+    }
+
+    fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        self.runtime.block_on(self.inner.order_status(order))
+    }
+
+    fn balances(&self) -> Result<Balances> {
+        self.runtime.block_on(self.inner.balances())
     }
 }
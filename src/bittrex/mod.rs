@@ -2,9 +2,14 @@
 //! See examples for more informations.
 
 pub mod api;
+pub mod async_api;
 pub mod generic_api;
 pub mod credentials;
 pub mod utils;
+pub mod responses;
+pub mod typed_api;
 
 pub use self::credentials::BittrexCreds;
 pub use self::api::BittrexApi;
+pub use self::async_api::AsyncBittrexApi;
+pub use self::typed_api::{AsyncBittrexTypedApi, BittrexTypedApi};
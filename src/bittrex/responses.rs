@@ -0,0 +1,248 @@
+//! Typed results of the Bittrex endpoints, returned by the `typed` API (see
+//! `AsyncBittrexApi::typed`).
+//!
+//! The fields are the ones documented by Bittrex for the v3 API, in snake case. Prices and
+//! amounts are `BigDecimal` parsed from the text sent by Bittrex (never through a `f64`), dates
+//! are `DateTime<Utc>`. The raw responses are still returned by the methods of
+//! `AsyncBittrexApi`.
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::helpers;
+
+/// An entry of `markets`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Market {
+    pub symbol: String,
+    pub base_currency_symbol: String,
+    pub quote_currency_symbol: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub min_trade_size: BigDecimal,
+    pub precision: u32,
+    pub status: String,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_datetime")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub notice: Option<String>,
+}
+
+/// An entry of `currencies`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyInfo {
+    pub symbol: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub coin_type: String,
+    pub status: String,
+    pub min_confirmations: u32,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub tx_fee: BigDecimal,
+    #[serde(default)]
+    pub base_address: Option<String>,
+    #[serde(default)]
+    pub notice: Option<String>,
+}
+
+/// Result of `ticker` and entry of `tickers`. The prices are None when the market has no order
+/// or no trade.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ticker {
+    pub symbol: String,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub last_trade_rate: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub bid_rate: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub ask_rate: Option<BigDecimal>,
+}
+
+/// Result of `market_summary` and entry of `market_summaries`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketSummary {
+    pub symbol: String,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub high: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub low: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub volume: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub quote_volume: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub percent_change: Option<BigDecimal>,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Result of `orderbook`.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+pub struct OrderBook {
+    #[serde(default)]
+    pub bid: Vec<OrderBookEntry>,
+    #[serde(default)]
+    pub ask: Vec<OrderBookEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderBookEntry {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub quantity: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+}
+
+/// An entry of `trades`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarketTrade {
+    pub id: String,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub executed_at: DateTime<Utc>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub quantity: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    pub taker_side: String,
+}
+
+/// An entry of `candles`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Candle {
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub starts_at: DateTime<Utc>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub open: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub high: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub low: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub close: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub volume: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub quote_volume: Option<BigDecimal>,
+}
+
+/// Result of `balance` and entry of `balances`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub currency_symbol: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub total: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub available: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_datetime")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Result of `deposit_address` and `provision_deposit_address`. The address is None until its
+/// status is "PROVISIONED".
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositAddress {
+    pub status: String,
+    pub currency_symbol: String,
+    #[serde(default)]
+    pub crypto_address: Option<String>,
+    #[serde(default)]
+    pub crypto_address_tag: Option<String>,
+}
+
+/// Result of `place_order`, `cancel` and `order`, entry of `open_orders` and `order_history`.
+/// The quantity is None for the market orders placed with a ceiling (an amount of the quote
+/// currency).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub id: String,
+    pub market_symbol: String,
+    pub direction: String,
+    #[serde(rename = "type")]
+    pub order_type: String,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub quantity: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub limit: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub ceiling: Option<BigDecimal>,
+    pub time_in_force: String,
+    #[serde(default)]
+    pub client_order_id: Option<String>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fill_quantity: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub commission: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub proceeds: BigDecimal,
+    pub status: String,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_datetime")]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_datetime")]
+    pub closed_at: Option<DateTime<Utc>>,
+}
+
+/// An entry of `cancel_open_orders`. The order is None when it could not be canceled.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanceledOrder {
+    pub id: String,
+    pub status_code: String,
+    #[serde(default)]
+    pub result: Option<Order>,
+}
+
+/// Result of `withdraw` and entry of `withdrawal_history`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    pub id: String,
+    pub currency_symbol: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub quantity: BigDecimal,
+    pub crypto_address: String,
+    #[serde(default)]
+    pub crypto_address_tag: Option<String>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub tx_cost: Option<BigDecimal>,
+    #[serde(default)]
+    pub tx_id: Option<String>,
+    pub status: String,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub created_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_datetime")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// An entry of `deposit_history`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deposit {
+    pub id: String,
+    pub currency_symbol: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub quantity: BigDecimal,
+    pub crypto_address: String,
+    #[serde(default)]
+    pub crypto_address_tag: Option<String>,
+    #[serde(default)]
+    pub tx_id: Option<String>,
+    pub confirmations: u32,
+    pub status: String,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_datetime")]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_datetime")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,301 @@
+//! Use this module to get the results of the Bittrex endpoints as typed structs (see the
+//! `responses` module) instead of JSON maps.
+//!
+//! The typed API is a view of a client returned by `AsyncBittrexApi::typed` or
+//! `BittrexApi::typed`. Its methods have the names and the parameters of the raw methods, which
+//! still return the raw responses:
+//!
+//! ```no_run
+//! use coinnect::bittrex::{BittrexApi, BittrexCreds};
+//!
+//! let api = BittrexApi::new(BittrexCreds::new("", "", "")).unwrap();
+//! let summary = api.typed().get_market_summary("LTC-BTC").unwrap();
+//! println!("{:?}", summary.volume);
+//! ```
+
+use crate::bittrex::{AsyncBittrexApi, BittrexApi};
+use crate::bittrex::responses::*;
+use crate::bittrex::utils;
+use crate::error::*;
+
+/// Typed view of an `AsyncBittrexApi`.
+#[derive(Debug, Clone, Copy)]
+pub struct AsyncBittrexTypedApi<'a> {
+    api: &'a AsyncBittrexApi,
+}
+
+impl<'a> AsyncBittrexTypedApi<'a> {
+    pub fn new(api: &'a AsyncBittrexApi) -> AsyncBittrexTypedApi<'a> {
+        AsyncBittrexTypedApi { api }
+    }
+
+    pub async fn get_markets(&self) -> Result<Vec<Market>> {
+        let response = self.api.get_markets().await?;
+        utils::parse_array_response(&response, "markets")
+    }
+
+    pub async fn get_market(&self, market: &str) -> Result<Market> {
+        let response = self.api.get_market(market).await?;
+        utils::parse_response(&response, "market")
+    }
+
+    pub async fn get_currencies(&self) -> Result<Vec<CurrencyInfo>> {
+        let response = self.api.get_currencies().await?;
+        utils::parse_array_response(&response, "currencies")
+    }
+
+    pub async fn get_currency(&self, currency: &str) -> Result<CurrencyInfo> {
+        let response = self.api.get_currency(currency).await?;
+        utils::parse_response(&response, "currency")
+    }
+
+    pub async fn get_ticker(&self, market: &str) -> Result<Ticker> {
+        let response = self.api.get_ticker(market).await?;
+        utils::parse_response(&response, "ticker")
+    }
+
+    pub async fn get_tickers(&self) -> Result<Vec<Ticker>> {
+        let response = self.api.get_tickers().await?;
+        utils::parse_array_response(&response, "tickers")
+    }
+
+    pub async fn get_market_summaries(&self) -> Result<Vec<MarketSummary>> {
+        let response = self.api.get_market_summaries().await?;
+        utils::parse_array_response(&response, "market_summaries")
+    }
+
+    pub async fn get_market_summary(&self, market: &str) -> Result<MarketSummary> {
+        let response = self.api.get_market_summary(market).await?;
+        utils::parse_response(&response, "market_summary")
+    }
+
+    pub async fn get_order_book(&self, market: &str, depth: u32) -> Result<OrderBook> {
+        let response = self.api.get_order_book(market, depth).await?;
+        utils::parse_response(&response, "orderbook")
+    }
+
+    pub async fn get_trades(&self, market: &str) -> Result<Vec<MarketTrade>> {
+        let response = self.api.get_trades(market).await?;
+        utils::parse_array_response(&response, "trades")
+    }
+
+    pub async fn get_candles(&self, market: &str, interval: &str) -> Result<Vec<Candle>> {
+        let response = self.api.get_candles(market, interval).await?;
+        utils::parse_array_response(&response, "candles")
+    }
+
+    pub async fn get_balances(&self) -> Result<Vec<Balance>> {
+        let response = self.api.get_balances().await?;
+        utils::parse_array_response(&response, "balances")
+    }
+
+    pub async fn get_balance(&self, currency: &str) -> Result<Balance> {
+        let response = self.api.get_balance(currency).await?;
+        utils::parse_response(&response, "balance")
+    }
+
+    pub async fn get_deposit_address(&self, currency: &str) -> Result<DepositAddress> {
+        let response = self.api.get_deposit_address(currency).await?;
+        utils::parse_response(&response, "deposit_address")
+    }
+
+    pub async fn provision_deposit_address(&self, currency: &str) -> Result<DepositAddress> {
+        let response = self.api.provision_deposit_address(currency).await?;
+        utils::parse_response(&response, "provision_deposit_address")
+    }
+
+    pub async fn buy_limit(&self, market: &str, quantity: &str, rate: &str) -> Result<Order> {
+        let response = self.api.buy_limit(market, quantity, rate).await?;
+        utils::parse_response(&response, "place_order")
+    }
+
+    pub async fn sell_limit(&self, market: &str, quantity: &str, rate: &str) -> Result<Order> {
+        let response = self.api.sell_limit(market, quantity, rate).await?;
+        utils::parse_response(&response, "place_order")
+    }
+
+    pub async fn buy_market(&self, market: &str, quantity: &str) -> Result<Order> {
+        let response = self.api.buy_market(market, quantity).await?;
+        utils::parse_response(&response, "place_order")
+    }
+
+    pub async fn sell_market(&self, market: &str, quantity: &str) -> Result<Order> {
+        let response = self.api.sell_market(market, quantity).await?;
+        utils::parse_response(&response, "place_order")
+    }
+
+    pub async fn cancel(&self, uuid: &str) -> Result<Order> {
+        let response = self.api.cancel(uuid).await?;
+        utils::parse_response(&response, "cancel")
+    }
+
+    pub async fn cancel_open_orders(&self, market: &str) -> Result<Vec<CanceledOrder>> {
+        let response = self.api.cancel_open_orders(market).await?;
+        utils::parse_array_response(&response, "cancel_open_orders")
+    }
+
+    pub async fn get_open_orders(&self, market: &str) -> Result<Vec<Order>> {
+        let response = self.api.get_open_orders(market).await?;
+        utils::parse_array_response(&response, "open_orders")
+    }
+
+    pub async fn get_order(&self, uuid: &str) -> Result<Order> {
+        let response = self.api.get_order(uuid).await?;
+        utils::parse_response(&response, "order")
+    }
+
+    pub async fn get_order_history(&self, market: &str) -> Result<Vec<Order>> {
+        let response = self.api.get_order_history(market).await?;
+        utils::parse_array_response(&response, "order_history")
+    }
+
+    pub async fn withdraw(&self,
+                          currency: &str,
+                          quantity: &str,
+                          address: &str,
+                          paymentid: &str)
+                          -> Result<Withdrawal> {
+        let response = self.api.withdraw(currency, quantity, address, paymentid).await?;
+        utils::parse_response(&response, "withdraw")
+    }
+
+    pub async fn get_withdrawal_history(&self, currency: &str) -> Result<Vec<Withdrawal>> {
+        let response = self.api.get_withdrawal_history(currency).await?;
+        utils::parse_array_response(&response, "withdrawal_history")
+    }
+
+    pub async fn get_deposit_history(&self, currency: &str) -> Result<Vec<Deposit>> {
+        let response = self.api.get_deposit_history(currency).await?;
+        utils::parse_array_response(&response, "deposit_history")
+    }
+}
+
+/// Typed view of a `BittrexApi`. Every call runs the `AsyncBittrexTypedApi` method of the same
+/// name to completion on the runtime of the client.
+#[derive(Debug, Clone, Copy)]
+pub struct BittrexTypedApi<'a> {
+    api: &'a BittrexApi,
+}
+
+impl<'a> BittrexTypedApi<'a> {
+    pub fn new(api: &'a BittrexApi) -> BittrexTypedApi<'a> {
+        BittrexTypedApi { api }
+    }
+
+    fn inner(&self) -> AsyncBittrexTypedApi<'a> {
+        self.api.inner.typed()
+    }
+
+    pub fn get_markets(&self) -> Result<Vec<Market>> {
+        self.api.runtime.block_on(self.inner().get_markets())
+    }
+
+    pub fn get_market(&self, market: &str) -> Result<Market> {
+        self.api.runtime.block_on(self.inner().get_market(market))
+    }
+
+    pub fn get_currencies(&self) -> Result<Vec<CurrencyInfo>> {
+        self.api.runtime.block_on(self.inner().get_currencies())
+    }
+
+    pub fn get_currency(&self, currency: &str) -> Result<CurrencyInfo> {
+        self.api.runtime.block_on(self.inner().get_currency(currency))
+    }
+
+    pub fn get_ticker(&self, market: &str) -> Result<Ticker> {
+        self.api.runtime.block_on(self.inner().get_ticker(market))
+    }
+
+    pub fn get_tickers(&self) -> Result<Vec<Ticker>> {
+        self.api.runtime.block_on(self.inner().get_tickers())
+    }
+
+    pub fn get_market_summaries(&self) -> Result<Vec<MarketSummary>> {
+        self.api.runtime.block_on(self.inner().get_market_summaries())
+    }
+
+    pub fn get_market_summary(&self, market: &str) -> Result<MarketSummary> {
+        self.api.runtime.block_on(self.inner().get_market_summary(market))
+    }
+
+    pub fn get_order_book(&self, market: &str, depth: u32) -> Result<OrderBook> {
+        self.api.runtime.block_on(self.inner().get_order_book(market, depth))
+    }
+
+    pub fn get_trades(&self, market: &str) -> Result<Vec<MarketTrade>> {
+        self.api.runtime.block_on(self.inner().get_trades(market))
+    }
+
+    pub fn get_candles(&self, market: &str, interval: &str) -> Result<Vec<Candle>> {
+        self.api.runtime.block_on(self.inner().get_candles(market, interval))
+    }
+
+    pub fn get_balances(&self) -> Result<Vec<Balance>> {
+        self.api.runtime.block_on(self.inner().get_balances())
+    }
+
+    pub fn get_balance(&self, currency: &str) -> Result<Balance> {
+        self.api.runtime.block_on(self.inner().get_balance(currency))
+    }
+
+    pub fn get_deposit_address(&self, currency: &str) -> Result<DepositAddress> {
+        self.api.runtime.block_on(self.inner().get_deposit_address(currency))
+    }
+
+    pub fn provision_deposit_address(&self, currency: &str) -> Result<DepositAddress> {
+        self.api.runtime.block_on(self.inner().provision_deposit_address(currency))
+    }
+
+    pub fn buy_limit(&self, market: &str, quantity: &str, rate: &str) -> Result<Order> {
+        self.api.runtime.block_on(self.inner().buy_limit(market, quantity, rate))
+    }
+
+    pub fn sell_limit(&self, market: &str, quantity: &str, rate: &str) -> Result<Order> {
+        self.api.runtime.block_on(self.inner().sell_limit(market, quantity, rate))
+    }
+
+    pub fn buy_market(&self, market: &str, quantity: &str) -> Result<Order> {
+        self.api.runtime.block_on(self.inner().buy_market(market, quantity))
+    }
+
+    pub fn sell_market(&self, market: &str, quantity: &str) -> Result<Order> {
+        self.api.runtime.block_on(self.inner().sell_market(market, quantity))
+    }
+
+    pub fn cancel(&self, uuid: &str) -> Result<Order> {
+        self.api.runtime.block_on(self.inner().cancel(uuid))
+    }
+
+    pub fn cancel_open_orders(&self, market: &str) -> Result<Vec<CanceledOrder>> {
+        self.api.runtime.block_on(self.inner().cancel_open_orders(market))
+    }
+
+    pub fn get_open_orders(&self, market: &str) -> Result<Vec<Order>> {
+        self.api.runtime.block_on(self.inner().get_open_orders(market))
+    }
+
+    pub fn get_order(&self, uuid: &str) -> Result<Order> {
+        self.api.runtime.block_on(self.inner().get_order(uuid))
+    }
+
+    pub fn get_order_history(&self, market: &str) -> Result<Vec<Order>> {
+        self.api.runtime.block_on(self.inner().get_order_history(market))
+    }
+
+    pub fn withdraw(&self,
+                    currency: &str,
+                    quantity: &str,
+                    address: &str,
+                    paymentid: &str)
+                    -> Result<Withdrawal> {
+        self.api.runtime.block_on(self.inner().withdraw(currency, quantity, address, paymentid))
+    }
+
+    pub fn get_withdrawal_history(&self, currency: &str) -> Result<Vec<Withdrawal>> {
+        self.api.runtime.block_on(self.inner().get_withdrawal_history(currency))
+    }
+
+    pub fn get_deposit_history(&self, currency: &str) -> Result<Vec<Deposit>> {
+        self.api.runtime.block_on(self.inner().get_deposit_history(currency))
+    }
+}
@@ -0,0 +1,72 @@
+//! This module contains the settings shared by the clients of every exchange.
+
+use std::sync::Arc;
+
+use crate::exchange::Exchange;
+use crate::nonce::{MemoryNonceProvider, NonceProvider};
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+
+/// How a client sends its requests: the `RateLimiter` throttling them, the `RetryPolicy` of the
+/// failed ones and the `NonceProvider` of the private ones. Use the `config_mut` method of a
+/// client to change them.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    rate_limiter: RateLimiter,
+    retry_policy: RetryPolicy,
+    nonce_provider: Arc<dyn NonceProvider>,
+}
+
+impl ClientConfig {
+    pub fn new(rate_limiter: RateLimiter,
+               retry_policy: RetryPolicy,
+               nonce_provider: Arc<dyn NonceProvider>)
+               -> ClientConfig {
+        ClientConfig {
+            rate_limiter,
+            retry_policy,
+            nonce_provider,
+        }
+    }
+
+    /// Return the default settings of a client using this API key on the exchange: the
+    /// RateLimiter and the NonceProvider shared by all the clients of the process using this API
+    /// key, and a RetryPolicy sending the requests once.
+    pub fn shared(exchange: Exchange, api_key: &str) -> ClientConfig {
+        ClientConfig::new(RateLimiter::shared(exchange, api_key),
+                          RetryPolicy::never(),
+                          MemoryNonceProvider::shared(exchange, api_key))
+    }
+
+    /// Return the RateLimiter of the client. Its policy (see `RateLimiter::set_policy`) is the
+    /// policy of every client sharing it.
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+
+    /// Use another RateLimiter, ie. one created with the limits of your account or shared with
+    /// another client.
+    pub fn set_rate_limiter(&mut self, rate_limiter: RateLimiter) {
+        self.rate_limiter = rate_limiter;
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Send again the requests failing with a transient error, as allowed by the policy.
+    /// By default, the requests are sent once.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    pub fn nonce_provider(&self) -> &Arc<dyn NonceProvider> {
+        &self.nonce_provider
+    }
+
+    /// Replace the provider of the nonces, ie. by a `FileNonceProvider` when several processes
+    /// use the same API key.
+    pub fn set_nonce_provider(&mut self, nonce_provider: Arc<dyn NonceProvider>) {
+        self.nonce_provider = nonce_provider;
+    }
+}
@@ -2,12 +2,13 @@
 
 use std::path::PathBuf;
 
-use crate::exchange::{Exchange, ExchangeApi};
-use crate::bitstamp::{BitstampApi, BitstampCreds};
-use crate::kraken::{KrakenApi, KrakenCreds};
-use crate::poloniex::{PoloniexApi, PoloniexCreds};
-use crate::bittrex::{BittrexApi, BittrexCreds};
-use crate::gdax::{GdaxApi, GdaxCreds};
+use crate::exchange::{AsyncExchangeApi, Exchange, ExchangeApi};
+use crate::bitstamp::{AsyncBitstampApi, BitstampApi, BitstampCreds};
+use crate::kraken::{AsyncKrakenApi, KrakenApi, KrakenCreds};
+use crate::poloniex::{AsyncPoloniexApi, PoloniexApi, PoloniexCreds};
+use crate::bittrex::{AsyncBittrexApi, BittrexApi, BittrexCreds};
+use crate::gdax::{AsyncGdaxApi, GdaxApi, GdaxCreds};
+use crate::transport::{AsyncHttpTransport, HttpTransport};
 use crate::error::*;
 
 pub trait Credentials {
@@ -24,6 +25,7 @@ pub struct Coinnect;
 
 impl Coinnect {
     /// Create a new CoinnectApi by providing an API key & API secret
+    #[allow(clippy::new_ret_no_self)]
     pub fn new<C: Credentials>(exchange: Exchange, creds: C) -> Result<Box<dyn ExchangeApi>> {
         match exchange {
             Exchange::Bitstamp => Ok(Box::new(BitstampApi::new(creds)?)),
@@ -34,6 +36,55 @@ impl Coinnect {
         }
     }
 
+    /// Create a generic API sending its requests through the given transport instead of the
+    /// default hyper client.
+    pub fn new_with_transport<C: Credentials>(exchange: Exchange,
+                                              creds: C,
+                                              transport: Box<dyn HttpTransport>)
+                                              -> Result<Box<dyn ExchangeApi>> {
+        match exchange {
+            Exchange::Bitstamp => Ok(Box::new(BitstampApi::new_with_transport(creds, transport)?)),
+            Exchange::Kraken => Ok(Box::new(KrakenApi::new_with_transport(creds, transport)?)),
+            Exchange::Poloniex => Ok(Box::new(PoloniexApi::new_with_transport(creds, transport)?)),
+            Exchange::Bittrex => Ok(Box::new(BittrexApi::new_with_transport(creds, transport)?)),
+            Exchange::Gdax => Ok(Box::new(GdaxApi::new_with_transport(creds, transport)?)),
+        }
+    }
+
+    /// Create a new asynchronous generic API by providing an API key & API secret.
+    /// The requests are sent with reqwest, so the API must be used from a Tokio runtime.
+    pub fn new_async<C: Credentials>(exchange: Exchange,
+                                     creds: C)
+                                     -> Result<Box<dyn AsyncExchangeApi>> {
+        match exchange {
+            Exchange::Bitstamp => Ok(Box::new(AsyncBitstampApi::new(creds)?)),
+            Exchange::Kraken => Ok(Box::new(AsyncKrakenApi::new(creds)?)),
+            Exchange::Poloniex => Ok(Box::new(AsyncPoloniexApi::new(creds)?)),
+            Exchange::Bittrex => Ok(Box::new(AsyncBittrexApi::new(creds)?)),
+            Exchange::Gdax => Ok(Box::new(AsyncGdaxApi::new(creds)?)),
+        }
+    }
+
+    /// Create a new asynchronous generic API sending its requests through the given transport.
+    pub fn new_async_with_transport<C: Credentials>(exchange: Exchange,
+                                                    creds: C,
+                                                    transport: Box<dyn AsyncHttpTransport>)
+                                                    -> Result<Box<dyn AsyncExchangeApi>> {
+        match exchange {
+            Exchange::Bitstamp => {
+                Ok(Box::new(AsyncBitstampApi::new_with_transport(creds, transport)?))
+            }
+            Exchange::Kraken => Ok(Box::new(AsyncKrakenApi::new_with_transport(creds, transport)?)),
+            Exchange::Poloniex => {
+                Ok(Box::new(AsyncPoloniexApi::new_with_transport(creds, transport)?))
+            }
+            Exchange::Bittrex => {
+                Ok(Box::new(AsyncBittrexApi::new_with_transport(creds, transport)?))
+            }
+            Exchange::Gdax => Ok(Box::new(AsyncGdaxApi::new_with_transport(creds, transport)?)),
+        }
+    }
+
     /// Create a new CoinnectApi from a json configuration file. This file must follow this
     /// structure:
     ///
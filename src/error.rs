@@ -8,6 +8,12 @@ use hyper;
 use data_encoding;
 use crate::exchange::Exchange;
 
+use error_chain::State;
+
+use std::error;
+use std::fmt;
+use std::time::Duration;
+
 error_chain!{
     types {
         Error, ErrorKind, ResultExt, Result;
@@ -112,5 +118,167 @@ error_chain!{
             description("PermissionDenied")
                 display("The operation cannot be done with the provided credentials")
         }
+
+        OrderNotFound {
+            description("OrderNotFound")
+                display("The order could not be found.")
+        }
+
+        OrderAlreadyClosed {
+            description("OrderAlreadyClosed")
+                display("The order is already closed (filled or canceled).")
+        }
+
+        MarketInactive {
+            description("MarketInactive")
+                display("Trading is currently suspended on this market.")
+        }
+
+        Unsupported(feature: String) {
+            description("Unsupported")
+                display("This exchange does not support {}.", feature)
+        }
+    }
+}
+
+/// The HTTP response which caused an error, kept for diagnostics. It is the cause of the errors
+/// returned when the status of a response is not successful (see `HttpResponse::error_for_status`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpError {
+    pub status: u16,
+    /// How long the server asked to wait before sending a new request, from its `Retry-After`
+    /// header.
+    pub retry_after: Option<Duration>,
+    pub body: String,
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "HTTP status {}", self.status)
+    }
+}
+
+impl error::Error for HttpError {}
+
+/// Where an error comes from: the exchange and the endpoint of the request, and the error sent
+/// by the exchange, if any. It is the cause of the errors returned by the exchange APIs.
+#[derive(Debug)]
+pub struct ExchangeError {
+    pub exchange: Exchange,
+    /// The name of the endpoint, as for `rate_limit::get_cost` (ie. "AddOrder" for Kraken).
+    pub endpoint: String,
+    /// The error code sent by the exchange (ie. "EAPI:Invalid nonce" for Kraken or
+    /// "INSUFFICIENT_FUNDS" for Bittrex).
+    pub code: Option<String>,
+    /// The error message sent by the exchange.
+    pub message: Option<String>,
+    source: Option<Box<dyn error::Error + Send>>,
+}
+
+impl ExchangeError {
+    pub fn new(exchange: Exchange, endpoint: &str) -> ExchangeError {
+        ExchangeError {
+            exchange,
+            endpoint: endpoint.to_string(),
+            code: None,
+            message: None,
+            source: None,
+        }
+    }
+
+    pub fn code(mut self, code: &str) -> ExchangeError {
+        self.code = Some(code.to_string());
+        self
+    }
+
+    pub fn message(mut self, message: &str) -> ExchangeError {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    /// Return an error of kind `kind` caused by this one.
+    pub fn into_error(self, kind: ErrorKind) -> Error {
+        Error::with_chain(self, kind)
+    }
+}
+
+impl fmt::Display for ExchangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} {}", self.exchange, self.endpoint)?;
+        match (&self.code, &self.message) {
+            (Some(code), Some(message)) if code != message => write!(f, ": {} ({})", message, code),
+            (_, Some(text)) | (Some(text), None) => write!(f, ": {}", text),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl error::Error for ExchangeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.source.as_ref().map(|source| &**source as &(dyn error::Error + 'static))
+    }
+}
+
+impl ErrorKind {
+    /// Return true if the same request may succeed later: the exchange could not be reached, the
+    /// rate limit was exceeded or the nonce was rejected. The other errors will happen again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(*self,
+                 ErrorKind::ServiceUnavailable(_) | ErrorKind::RateLimitExceeded |
+                 ErrorKind::InvalidNonce)
+    }
+}
+
+impl Error {
+    /// Record the exchange and the endpoint of the request which failed, unless they are already
+    /// known.
+    pub fn with_endpoint(self, exchange: Exchange, endpoint: &str) -> Error {
+        if self.exchange_error().is_some() {
+            return self;
+        }
+
+        let Error(kind, state) = self;
+        let mut context = ExchangeError::new(exchange, endpoint);
+        context.source = state.next_error;
+        Error(kind,
+              State {
+                  next_error: Some(Box::new(context)),
+                  backtrace: state.backtrace,
+              })
+    }
+
+    /// Return where the error comes from, if it comes from an exchange.
+    pub fn exchange_error(&self) -> Option<&ExchangeError> {
+        self.1
+            .next_error
+            .as_ref()
+            .and_then(|cause| cause.downcast_ref::<ExchangeError>())
+    }
+
+    pub fn exchange(&self) -> Option<Exchange> {
+        self.exchange_error().map(|err| err.exchange)
+    }
+
+    pub fn endpoint(&self) -> Option<&str> {
+        self.exchange_error().map(|err| err.endpoint.as_str())
+    }
+
+    /// See `ErrorKind::is_retryable`.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Return the HTTP response which caused the error, if any.
+    pub fn http_error(&self) -> Option<&HttpError> {
+        let cause = match self.exchange_error() {
+            Some(err) => err.source.as_ref(),
+            None => self.1.next_error.as_ref(),
+        };
+        cause.and_then(|cause| cause.downcast_ref::<HttpError>())
+    }
+
+    /// Return how long the exchange asked to wait before sending a new request, if it did.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.http_error().and_then(|err| err.retry_after)
     }
 }
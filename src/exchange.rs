@@ -1,5 +1,7 @@
 //! This module contains Exchange enum.
 
+use async_trait::async_trait;
+
 use std::fmt::Debug;
 use std::convert::Into;
 use std::str::FromStr;
@@ -10,7 +12,7 @@ use crate::types::*;
 
 
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Exchange {
     Bitstamp,
     Kraken,
@@ -19,9 +21,9 @@ pub enum Exchange {
     Gdax,
 }
 
-impl Into<String> for Exchange {
-    fn into(self) -> String {
-        match self {
+impl From<Exchange> for String {
+    fn from(exchange: Exchange) -> String {
+        match exchange {
             Exchange::Bitstamp => "Bitstamp".to_string(),
             Exchange::Kraken => "Kraken".to_string(),
             Exchange::Poloniex => "Poloniex".to_string(),
@@ -46,12 +48,60 @@ impl FromStr for Exchange {
     }
 }
 
-pub trait ExchangeApi: Debug {
+/// The generic API of an exchange. Its methods take `&self` and every implementation is
+/// `Send + Sync`, so a single client can be shared between threads (ie. in an `Arc`).
+pub trait ExchangeApi: Debug + Send + Sync {
     /// Return a Ticker for the Pair specified.
-    fn ticker(&mut self, pair: Pair) -> Result<Ticker>;
+    fn ticker(&self, pair: Pair) -> Result<Ticker>;
 
     /// Return an Orderbook for the specified Pair.
-    fn orderbook(&mut self, pair: Pair) -> Result<Orderbook>;
+    fn orderbook(&self, pair: Pair) -> Result<Orderbook>;
+
+    /// Return the most recent public trades for the specified Pair.
+    /// `since` is the `cursor` of a previously returned TradeHistory, in which case only the
+    /// trades that happened after it are returned. Note that Bittrex, Bitstamp and Gdax cannot
+    /// page through their history: only their most recent trades are filtered with the cursor.
+    fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory>;
+
+    /// Return the OHLC candles of the specified Pair (from the oldest to the most recent).
+    /// `since` is a UNIX timestamp in ms: only the candles starting after it are returned. The
+    /// last candle may cover a period that is not over yet.
+    /// An `Unsupported` error is returned if the exchange does not provide candles or the
+    /// requested Interval.
+    fn candles(&self,
+               _pair: Pair,
+               _interval: Interval,
+               _since: Option<i64>)
+               -> Result<Vec<Candle>> {
+        Err(ErrorKind::Unsupported("candles".to_string()).into())
+    }
+
+    /// Return the trading rules (minimum order size, precision, ...) of the markets listed by the
+    /// exchange. The pairs listed since the last update of Coinnect are registered so that they
+    /// can be used with the other methods.
+    /// The result is fetched once and then cached by the client.
+    /// An `Unsupported` error is returned if the exchange does not publish them.
+    fn markets(&self) -> Result<Markets> {
+        Err(ErrorKind::Unsupported("markets".to_string()).into())
+    }
+
+    /// Check an order against the trading rules returned by `markets` without placing it.
+    /// Return the quantity and price rounded to the precision of the market (see
+    /// `MarketInfo::check_order`), to be passed to `add_order`.
+    /// An `InsufficientOrderSize` error is returned if the order is under the minimum size or
+    /// value of the market, and `MarketInactive` if trading is suspended.
+    /// Kraken also submits the rounded order with its `validate` flag, a server-side dry run that
+    /// requires valid credentials.
+    fn validate_order(&self,
+                      order_type: OrderType,
+                      pair: Pair,
+                      quantity: Volume,
+                      price: Option<Price>)
+                      -> Result<(Volume, Option<Price>)> {
+        let markets = self.markets()?;
+        let market = markets.get(&pair).ok_or(ErrorKind::PairUnsupported)?;
+        market.check_order(order_type, quantity, price)
+    }
 
     /// Place an order directly to the exchange.
     /// Quantity is in quote currency. So if you want to buy 1 Bitcoin for X€ (pair BTC_EUR),
@@ -61,14 +111,79 @@ pub trait ExchangeApi: Debug {
     ///
     /// A good practice is to store the return type (OrderInfo) somewhere since it can later be used
     /// to modify or cancel the order.
-    fn add_order(&mut self,
+    fn add_order(&self,
                  order_type: OrderType,
                  pair: Pair,
                  quantity: Volume,
                  price: Option<Price>)
                  -> Result<OrderInfo>;
 
+    /// Cancel an order previously placed with `add_order`.
+    /// Every identifier contained in the OrderInfo is canceled. An `OrderNotFound` error is
+    /// returned if the exchange does not know the order and `OrderAlreadyClosed` if it has
+    /// already been filled or canceled.
+    fn cancel_order(&self, order: &OrderInfo) -> Result<()>;
+
+    /// Return the orders that are still open on the account, optionally restricted to a Pair.
+    /// Orders on pairs whose name cannot be resolved are skipped.
+    fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>>;
+
+    /// Return the current state of an order previously placed with `add_order`.
+    /// The first identifier contained in the OrderInfo is queried. An `OrderNotFound` error is
+    /// returned if the exchange does not know the order.
+    fn order_status(&self, order: &OrderInfo) -> Result<Order>;
+
     /// Retrieve the current amounts of all the currencies that the account holds
     /// The amounts returned are available (not used to open an order)
-    fn balances(&mut self) -> Result<Balances>;
+    fn balances(&self) -> Result<Balances>;
+}
+
+/// Asynchronous counterpart of `ExchangeApi`, implemented by the `Async*Api` clients.
+/// Every method behaves like the `ExchangeApi` method of the same name.
+/// The returned futures must be run on a Tokio runtime with its I/O and time drivers enabled.
+#[async_trait]
+pub trait AsyncExchangeApi: Debug + Send + Sync {
+    async fn ticker(&self, pair: Pair) -> Result<Ticker>;
+
+    async fn orderbook(&self, pair: Pair) -> Result<Orderbook>;
+
+    async fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory>;
+
+    async fn candles(&self,
+                     _pair: Pair,
+                     _interval: Interval,
+                     _since: Option<i64>)
+                     -> Result<Vec<Candle>> {
+        Err(ErrorKind::Unsupported("candles".to_string()).into())
+    }
+
+    async fn markets(&self) -> Result<Markets> {
+        Err(ErrorKind::Unsupported("markets".to_string()).into())
+    }
+
+    async fn validate_order(&self,
+                            order_type: OrderType,
+                            pair: Pair,
+                            quantity: Volume,
+                            price: Option<Price>)
+                            -> Result<(Volume, Option<Price>)> {
+        let markets = self.markets().await?;
+        let market = markets.get(&pair).ok_or(ErrorKind::PairUnsupported)?;
+        market.check_order(order_type, quantity, price)
+    }
+
+    async fn add_order(&self,
+                       order_type: OrderType,
+                       pair: Pair,
+                       quantity: Volume,
+                       price: Option<Price>)
+                       -> Result<OrderInfo>;
+
+    async fn cancel_order(&self, order: &OrderInfo) -> Result<()>;
+
+    async fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>>;
+
+    async fn order_status(&self, order: &OrderInfo) -> Result<Order>;
+
+    async fn balances(&self) -> Result<Balances>;
 }
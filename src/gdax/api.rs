@@ -1,405 +1,297 @@
 //! Use this module to interact with Gdax exchange.
 //! Please see examples for more informations.
 
-
-use hyper_native_tls::NativeTlsClient;
-use hyper::Client;
-use hyper::header::{ContentType,UserAgent};
-use hyper::net::HttpsConnector;
-
 use serde_json::Value;
 use serde_json::value::Map;
 
-use std::collections::HashMap;
-use std::io::Read;
-use std::thread;
-use std::time::Duration;
+use tokio::runtime::Runtime;
 
-use crate::coinnect::Credentials;
-use crate::exchange::Exchange;
+use std::fmt;
 
+use crate::client::ClientConfig;
+use crate::coinnect::Credentials;
 use crate::error::*;
 use crate::helpers;
-use crate::types::Pair;
-use crate::gdax::utils;
-use crate::types::*;
+use crate::types::{Pair, Price, Volume};
+use crate::gdax::AsyncGdaxApi;
+use crate::transport::{BlockingTransport, HttpTransport, HyperTransport};
 
-header! {
-    #[doc(hidden)]
-    (KeyHeader, "Key") => [String]
-}
-
-header! {
-    #[doc(hidden)]
-    (SignHeader, "Sign") => [String]
-}
-
-header! {
-    #[doc(hidden)]
-    (ContentHeader, "Content-Type") => [String]
-}
-
-#[derive(Debug)]
+/// Blocking client of the Gdax API. Every call runs the `AsyncGdaxApi` method of the
+/// same name to completion on a runtime owned by the client, so it must not be used from an
+/// asynchronous context.
 pub struct GdaxApi {
-    last_request: i64, // unix timestamp in ms, to avoid ban
-    api_key: String,
-    api_secret: String,
-    customer_id: String,
-    http_client: Client,
-    burst: bool,
+    pub(crate) inner: AsyncGdaxApi,
+    pub(crate) runtime: Runtime,
 }
 
-
 impl GdaxApi {
     /// Create a new GdaxApi by providing an API key & API secret
     pub fn new<C: Credentials>(creds: C) -> Result<GdaxApi> {
-        if creds.exchange() != Exchange::Gdax {
-            return Err(ErrorKind::InvalidConfigType(Exchange::Gdax, creds.exchange()).into());
-        }
-
-        //TODO: Handle correctly TLS errors with error_chain.
-        let ssl = match NativeTlsClient::new() {
-            Ok(res) => res,
-            Err(_) => return Err(ErrorKind::TlsError.into()),
-        };
-
-        let connector = HttpsConnector::new(ssl);
+        GdaxApi::new_with_transport(creds, Box::new(HyperTransport::new()?))
+    }
 
+    /// Create a new GdaxApi sending its requests through the given transport
+    pub fn new_with_transport<C: Credentials>(creds: C,
+                                              transport: Box<dyn HttpTransport>)
+                                              -> Result<GdaxApi> {
+        let transport = BlockingTransport::from(transport);
 
         Ok(GdaxApi {
-               last_request: 0,
-               api_key: creds.get("api_key").unwrap_or_default(),
-               api_secret: creds.get("api_secret").unwrap_or_default(),
-               customer_id: creds.get("customer_id").unwrap_or_default(),
-               http_client: Client::with_connector(connector),
-               burst: false, // No burst by default
+               inner: AsyncGdaxApi::new_with_transport(creds, Box::new(transport))?,
+               runtime: helpers::new_blocking_runtime()?,
            })
     }
 
-    /// The number of calls in a given period is limited. In order to avoid a ban we limit
-    /// by default the number of api requests.
-    /// This function sets or removes the limitation.
-    /// Burst false implies no block.
-    /// Burst true implies there is a control over the number of calls allowed to the exchange
-    pub fn set_burst(&mut self, burst: bool) {
-        self.burst = burst
+    /// Return the settings of the client: its RateLimiter, RetryPolicy and NonceProvider.
+    pub fn config(&self) -> &ClientConfig {
+        self.inner.config()
     }
 
-    fn block_or_continue(&self) {
-        if ! self.burst {
-            let threshold: u64 = 334; // 3 requests/sec = 1/3*1000
-            let offset: u64 = helpers::get_unix_timestamp_ms() as u64 - self.last_request as u64;
-            if offset < threshold {
-                let wait_ms = Duration::from_millis(threshold - offset);
-                thread::sleep(wait_ms);
-            }
-        }
+    /// Change the settings of the client, ie. `api.config_mut().set_retry_policy(policy)`.
+    /// The nonces are sent as the timestamp of the requests: they must be in ms and close to the
+    /// current time.
+    pub fn config_mut(&mut self) -> &mut ClientConfig {
+        self.inner.config_mut()
     }
 
-    fn public_query(&mut self, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
-
-        let method: &str = params
-            .get("method")
-            .ok_or_else(|| "Missing \"method\" field.")?;
-        let pair: &str = params.get("pair").ok_or_else(|| "Missing \"pair\" field.")?;
-        let url: String = utils::build_url(method, pair);
+    /// Returns the products available for trading.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "BTC-USD", "base_currency": "BTC", "quote_currency": "USD",
+    /// "base_min_size": "0.001", "base_max_size": "280", "quote_increment": "0.01",
+    /// "base_increment": "0.00000001", "display_name": "BTC/USD", "min_market_funds": "10",
+    /// "post_only": false, "limit_only": false, "cancel_only": false,
+    /// "trading_disabled": false, "status": "online", "status_message": ""}, ... ]
+    /// ```
+    pub fn return_products(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_products())
+    }
 
-        self.block_or_continue();
-        let mut response = self.http_client
-            .get(&url)
-            .header(UserAgent("coinnect".to_string()))
-            .send()?;
+    /// Returns a single product, see `return_products` for the fields.
+    pub fn return_product(&self, pair: Pair) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_product(pair))
+    }
 
-        self.last_request = helpers::get_unix_timestamp_ms();
-        let mut buffer = String::new();
-        response.read_to_string(&mut buffer)?;
-        utils::deserialize_json(&buffer)
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"trade_id": 4729088, "price": "333.99", "size": "0.193", "bid": "333.98",
+    /// "ask": "333.99", "volume": "5957.11914015", "time": "2015-11-14T20:46:03.511254Z"}
+    /// ```
+    pub fn return_ticker(&self, pair: Pair) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_ticker(pair))
     }
 
+    /// Returns the 50 best bids and asks, aggregated by price (level 2).
+    ///
+    /// Sample output :
     ///
+    /// ```json
+    /// {"sequence": "3", "bids": [["295.96", "4.39088265", 2], ... ],
+    /// "asks": [["295.97", "25.23542881", 12], ... ]}
+    /// ```
+    pub fn return_order_book(&self, pair: Pair) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_order_book(pair))
+    }
+
+    /// Returns the latest trades, most recent first ("side" is the side of the maker).
+    /// The array is returned in the "data" field of the map.
     ///
-    /// #Examples
+    /// Sample output :
     ///
     /// ```json
-    /// extern crate coinnect;
-    /// use coinnect::gdax::GdaxApi;
-    /// let mut api = GdaxApi::new("", "");
-    /// let  result = api.private_query("balance", "btcusd");
-    /// assert_eq!(true, true);
+    /// [{"time": "2017-10-27T10:11:12.123Z", "trade_id": 74, "price": "5812.99",
+    /// "size": "0.04000000", "side": "buy"},
+    /// {"time": "2017-10-27T10:11:00.456Z", "trade_id": 73, "price": "5812.98",
+    /// "size": "0.22000000", "side": "sell"}, ... ]
     /// ```
-    fn private_query(&mut self, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
-
-        let method: &str = params
-            .get("method")
-            .ok_or_else(|| "Missing \"method\" field.")?;
-        let pair: &str = params.get("pair").ok_or_else(|| "Missing \"pair\" field.")?;
-        let url: String = utils::build_url(method, pair);
-
-        let nonce = utils::generate_nonce(None);
-        let signature =
-            utils::build_signature(&nonce, &self.customer_id, &self.api_key, &self.api_secret)?;
-
-        let copy_api_key = self.api_key.clone();
-        let mut post_params: &mut HashMap<&str, &str> = &mut HashMap::new();
-        post_params.insert("key", &copy_api_key);
-        post_params.insert("signature", &signature);
-        post_params.insert("nonce", &nonce);
-
-        // copy params into post_params .... bit of a hack but will do for now
-        params.iter().for_each(|(k,v)| {
-            post_params.insert(k,v);
-        });
-
-        helpers::strip_empties(&mut post_params);
-        let post_data = helpers::url_encode_hashmap(post_params);
-        let mut response = self.http_client
-            .post(&url)
-            .header(ContentType::form_url_encoded())
-            .body(&post_data)
-            .send()?;
-
-        let mut buffer = String::new();
-        response.read_to_string(&mut buffer)?;
-        utils::deserialize_json(&buffer)
+    pub fn return_trade_history(&self, pair: Pair) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_trade_history(pair))
     }
 
+    /// Returns at most 300 candles, most recent first. `granularity` is the duration of a
+    /// candle in seconds: 60, 300, 900, 3600, 21600 or 86400. `start` and `end` are ISO 8601
+    /// dates, both of them must be given (or left empty to get the latest candles).
+    /// The array is returned in the "data" field of the map.
+    ///
     /// Sample output :
     ///
     /// ```json
-    /// {
-    /// "BTC_LTC":{
-    /// "last":"0.0251","lowestAsk":"0.02589999","highestBid":"0.0251",
-    /// "percentChange":"0.02390438","baseVolume":"6.16485315","quoteVolume":"245.82513926"},
-    /// "BTC_NXT":{
-    /// "last":"0.00005730","lowestAsk":"0.00005710","highestBid":"0.00004903",
-    /// "percentChange":"0.16701570","baseVolume":"0.45347489","quoteVolume":"9094"},
-    /// ... }
+    /// [[1415398800, 0.32, 4.2, 0.35, 4.2, 12.3], [1415398740, 0.32, 4.2, 0.35, 4.2, 12.3], ...]
     /// ```
-    pub fn return_ticker(&mut self, pair: Pair) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let mut params: HashMap<&str, &str> = HashMap::new();
-        params.insert("pair", pair_name);
-        params.insert("method", "ticker");
-        self.public_query(&params)
+    /// Each candle is an array: [time, low, high, open, close, volume].
+    pub fn return_candles(&self,
+                          pair: Pair,
+                          granularity: i64,
+                          start: &str,
+                          end: &str)
+                          -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_candles(pair, granularity, start, end))
     }
 
+    /// Returns the statistics of the last 24 hours.
+    ///
     /// Sample output :
     ///
     /// ```json
-    /// {"asks":[[0.00007600,1164],[0.00007620,1300], ... ], "bids":[[0.00006901,200],
-    /// [0.00006900,408], ... ], "timestamp": "1234567890"}
+    /// {"open": "6745.61000000", "high": "7292.11000000", "low": "6650.00000000",
+    /// "volume": "26185.51325269", "last": "6813.19000000", "volume_30day": "1019451.11188405"}
     /// ```
-    pub fn return_order_book(&mut self, pair: Pair) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
+    pub fn return_product_stats(&self, pair: Pair) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_product_stats(pair))
+    }
 
-        };
+    /// Returns your accounts, one per currency.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "71452118-efc7-4cc4-8780-a5e22d4baa53", "currency": "BTC",
+    /// "balance": "0.0000000000000000", "available": "0.0000000000000000",
+    /// "hold": "0.0000000000000000", "profile_id": "75da88c5-05bf-4f54-bc85-5c775bd68254",
+    /// "trading_enabled": true}, ... ]
+    /// ```
+    pub fn return_accounts(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_accounts())
+    }
 
-        let mut params: HashMap<&str, &str> = HashMap::new();
-        params.insert("method", "order_book");
-        params.insert("pair", pair_name);
-        self.public_query(&params)
+    /// Returns a single account, identified by its "id" (see `return_accounts`).
+    pub fn return_account(&self, account_id: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_account(account_id))
     }
 
+    /// Returns the activity of an account (transfers, matches, fees, ...), most recent first.
+    /// The array is returned in the "data" field of the map.
+    ///
     /// Sample output :
     ///
     /// ```json
-    /// [{"date":"2014-02-10 04:23:23","type":"buy","rate":"0.00007600","amount":"140",
-    /// "total":"0.01064"},
-    /// {"date":"2014-02-10 01:19:37","type":"buy","rate":"0.00007600","amount":"655",
-    /// "total":"0.04978"}, ... ]
+    /// [{"id": "100", "created_at": "2014-11-07T08:19:27.028459Z", "amount": "0.001",
+    /// "balance": "239.669", "type": "fee", "details": {"order_id":
+    /// "d50ec984-77a8-460a-b958-66f114b0de9b", "trade_id": "74", "product_id": "BTC-USD"}},
+    /// ... ]
     /// ```
-    pub fn return_trade_history(&mut self, pair: Pair) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let mut params: HashMap<&str, &str> = HashMap::new();
-        params.insert("pair", pair_name);
-        params.insert("method", "transactions");
-        self.public_query(&params)
+    pub fn return_account_history(&self, account_id: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_account_history(account_id))
     }
 
+    /// Returns the funds of an account on hold for the open orders and pending withdrawals.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "82dcd140-c3c7-4507-8de4-2c529cd1a28f",
+    /// "account_id": "e0b3f39a-183d-453e-b754-0c13e5bab0b3",
+    /// "created_at": "2014-11-06T10:34:47.123456Z", "updated_at": "2014-11-06T10:40:47.123456Z",
+    /// "amount": "4.23", "type": "order", "ref": "0a205de4-dd35-4370-a285-fe8fc375a273"}, ... ]
+    /// ```
+    pub fn return_account_holds(&self, account_id: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_account_holds(account_id))
+    }
 
-    /// Returns all of your available balances.
+    /// Place an order described by the fields of the JSON body: "side" ("buy" or "sell"),
+    /// "product_id", "type" ("limit" or "market"), "size" and "price" for a limit order, "size"
+    /// or "funds" for a market order, and the optional fields documented by Gdax (ie.
+    /// "client_oid", "time_in_force" or "post_only").
     ///
-    /// Sample output:
+    /// Sample output :
     ///
     /// ```json
-    /// {"BTC":"0.59098578","LTC":"3.31117268", ... }
+    /// {"id": "d0c5340b-6d6c-49d9-b567-48c4bfca13d2", "price": "0.10000000",
+    /// "size": "0.01000000", "product_id": "BTC-USD", "side": "buy", "stp": "dc",
+    /// "type": "limit", "time_in_force": "GTC", "post_only": false,
+    /// "created_at": "2016-12-08T20:02:28.53864Z", "fill_fees": "0.0000000000000000",
+    /// "filled_size": "0.00000000", "executed_value": "0.0000000000000000",
+    /// "status": "pending", "settled": false}
     /// ```
-    pub fn return_balances(&mut self) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("method", "balance");
-        params.insert("pair", "");
-        self.private_query(&params)
+    pub fn place_order(&self, order: &Map<String, Value>) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.place_order(order))
     }
 
-    /// Add a buy limit order to the exchange
-    /// limit_price	: If the order gets executed, a new sell order will be placed,
-    /// with "limit_price" as its price.
-    /// daily_order (Optional) : Opens buy limit order which will be canceled
-    /// at 0:00 UTC unless it already has been executed. Possible value: True
-    pub fn buy_limit(&mut self,
+    /// Add a buy limit order to the exchange, good until canceled.
+    pub fn buy_limit(&self,
                      pair: Pair,
                      amount: Volume,
-                     price: Price,
-                     price_limit: Option<Price>,
-                     daily_order: Option<bool>)
+                     price: Price)
                      -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let amount_string = amount.to_string();
-        let price_string = price.to_string();
-        let price_limit_string = match price_limit {
-            Some(limit) => limit.to_string(),
-            None => "".to_string(),
-        };
-
-        let mut params = HashMap::new();
-        params.insert("method", "buy");
-        params.insert("pair", pair_name);
-
-        params.insert("amount", &amount_string);
-        params.insert("price", &price_string);
-        params.insert("limit_price", &price_limit_string);
-        if let Some(order) = daily_order {
-            let daily_order_str = if order { "True" } else { "" }; // False is not a possible value
-            params.insert("daily_order", daily_order_str);
-        }
-
-        self.private_query(&params)
+        self.runtime.block_on(self.inner.buy_limit(pair, amount, price))
     }
 
-    /// Add a sell limit order to the exchange
-    /// limit_price	: If the order gets executed, a new sell order will be placed,
-    /// with "limit_price" as its price.
-    /// daily_order (Optional) : Opens sell limit order which will be canceled
-    /// at 0:00 UTC unless it already has been executed. Possible value: True
-    pub fn sell_limit(&mut self,
+    /// Add a sell limit order to the exchange, good until canceled.
+    pub fn sell_limit(&self,
                       pair: Pair,
                       amount: Volume,
-                      price: Price,
-                      price_limit: Option<Price>,
-                      daily_order: Option<bool>)
+                      price: Price)
                       -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let amount_string = amount.to_string();
-        let price_string = price.to_string();
-        let price_limit_string = match price_limit {
-            Some(limit) => limit.to_string(),
-            None => "".to_string(),
-        };
-
-        let mut params = HashMap::new();
-        params.insert("method", "sell");
-        params.insert("pair", pair_name);
-
-        params.insert("amount", &amount_string);
-        params.insert("price", &price_string);
-        params.insert("limit_price", &price_limit_string);
-        if let Some(order) = daily_order {
-            let daily_order_str = if order { "True" } else { "" }; // False is not a possible value
-            params.insert("daily_order", daily_order_str);
-        }
-
-        self.private_query(&params)
+        self.runtime.block_on(self.inner.sell_limit(pair, amount, price))
     }
 
     /// Add a market buy order to the exchange
     /// By placing a market order you acknowledge that the execution of your order depends
     /// on the market conditions and that these conditions may be subject to sudden changes
     /// that cannot be foreseen.
-    pub fn buy_market(&mut self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-
-        let amount_string = amount.to_string();
-
-        let mut params = HashMap::new();
-        params.insert("method", "buy/market");
-        params.insert("pair", pair_name);
-
-        params.insert("amount", &amount_string);
-
-        self.private_query(&params)
+    pub fn buy_market(&self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.buy_market(pair, amount))
     }
 
     /// Add a market sell order to the exchange
     /// By placing a market order you acknowledge that the execution of your order depends
     /// on the market conditions and that these conditions may be subject to sudden changes
     /// that cannot be foreseen.
-    pub fn sell_market(&mut self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
+    pub fn sell_market(&self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.sell_market(pair, amount))
+    }
+
+    /// Cancel a previously placed order, identified by the "id" returned when it was placed.
+    /// The id of the canceled order is returned in the "data" field of the map.
+    pub fn cancel_order(&self, id: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.cancel_order(id))
+    }
 
-        let amount_string = amount.to_string();
+    /// Cancel all your open orders, for a given Pair or for all of them if None is given.
+    /// The ids of the canceled orders are returned in the "data" field of the map.
+    pub fn cancel_all_orders(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.cancel_all_orders(pair))
+    }
 
-        let mut params = HashMap::new();
-        params.insert("method", "sell/market");
-        params.insert("pair", pair_name);
+    /// List your open orders, for a given Pair or for all of them if None is given.
+    /// The array is returned in the "data" field of the map, see `place_order` for the fields.
+    pub fn return_open_orders(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_open_orders(pair))
+    }
 
-        params.insert("amount", &amount_string);
+    /// Get a single order, identified by the "id" returned when it was placed.
+    pub fn return_order_status(&self, id: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_order_status(id))
+    }
 
-        self.private_query(&params)
+    /// List your fills, for an order (identified by its "id") or a Pair. At least one of them
+    /// must be given.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"trade_id": 74, "product_id": "BTC-USD", "price": "10.00", "size": "0.01",
+    /// "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+    /// "created_at": "2014-11-07T22:19:28.578544Z", "liquidity": "T", "fee": "0.00025",
+    /// "settled": true, "side": "buy"}, ... ]
+    /// ```
+    pub fn return_fills(&self,
+                        order_id: Option<&str>,
+                        pair: Option<Pair>)
+                        -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_fills(order_id, pair))
     }
 }
 
-
-#[cfg(test)]
-mod gdax_api_tests {
-    use super::*;
-
-    #[test]
-    fn should_block_or_not_block_when_enabled_or_disabled() {
-        let mut api = GdaxApi {
-            last_request: helpers::get_unix_timestamp_ms(),
-            api_key: "".to_string(),
-            api_secret: "".to_string(),
-            customer_id: "".to_string(),
-            http_client: Client::new(),
-            burst: false,
-        };
-
-        let mut counter = 0;
-        loop {
-            api.set_burst(false);
-            let start = helpers::get_unix_timestamp_ms();
-            api.block_or_continue();
-            api.last_request = helpers::get_unix_timestamp_ms();
-
-            let difference = api.last_request - start;
-            assert!(difference >= 334);
-            assert!(difference < 10000);
-
-
-            api.set_burst(true);
-            let start = helpers::get_unix_timestamp_ms();
-            api.block_or_continue();
-            api.last_request = helpers::get_unix_timestamp_ms();
-
-            let difference = api.last_request - start;
-            assert!(difference < 10);
-
-            counter = counter + 1;
-            if counter >= 3 { break; }
-        }
+impl fmt::Debug for GdaxApi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("GdaxApi").field(&self.inner).finish()
     }
 }
@@ -0,0 +1,498 @@
+//! Use this module to interact asynchronously with Gdax exchange.
+//! `AsyncGdaxApi` is the implementation behind the blocking client of the `api` module.
+//! Please see examples for more informations.
+
+
+use serde_json::Value;
+use serde_json::value::Map;
+
+use std::sync::RwLock;
+
+use crate::client::ClientConfig;
+use crate::coinnect::Credentials;
+use crate::exchange::Exchange;
+
+use crate::error::*;
+use crate::rate_limit::Scope;
+use crate::retry::{self, Idempotency};
+use crate::types::Pair;
+use crate::gdax::utils;
+use crate::transport::{AsyncHttpTransport, HttpRequest, Method, ReqwestTransport};
+use crate::types::*;
+
+#[derive(Debug)]
+pub struct AsyncGdaxApi {
+    api_key: String,
+    api_secret: String,
+    passphrase: String,
+    http_client: Box<dyn AsyncHttpTransport>,
+    config: ClientConfig,
+    /// Markets returned by `ExchangeApi::markets`, fetched on first use
+    pub(crate) markets: RwLock<Option<Markets>>,
+}
+
+
+impl AsyncGdaxApi {
+    /// Create a new AsyncGdaxApi by providing an API key & API secret
+    pub fn new<C: Credentials>(creds: C) -> Result<AsyncGdaxApi> {
+        AsyncGdaxApi::new_with_transport(creds, Box::new(ReqwestTransport::new()?))
+    }
+
+    /// Create a new AsyncGdaxApi sending its requests through the given transport
+    pub fn new_with_transport<C: Credentials>(creds: C,
+                                              transport: Box<dyn AsyncHttpTransport>)
+                                              -> Result<AsyncGdaxApi> {
+        if creds.exchange() != Exchange::Gdax {
+            return Err(ErrorKind::InvalidConfigType(Exchange::Gdax, creds.exchange()).into());
+        }
+
+        let api_key = creds.get("api_key").unwrap_or_default();
+        let config = ClientConfig::shared(Exchange::Gdax, &api_key);
+
+        Ok(AsyncGdaxApi {
+               api_key,
+               api_secret: creds.get("api_secret").unwrap_or_default(),
+               passphrase: creds.get("passphrase").unwrap_or_default(),
+               http_client: transport,
+               config,
+               markets: RwLock::new(None),
+           })
+    }
+
+    /// Return the settings of the client: its RateLimiter, RetryPolicy and NonceProvider.
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Change the settings of the client, ie. `api.config_mut().set_retry_policy(policy)`.
+    /// The nonces are sent as the timestamp of the requests: they must be in ms and close to the
+    /// current time.
+    pub fn config_mut(&mut self) -> &mut ClientConfig {
+        &mut self.config
+    }
+
+    async fn public_query(&self, endpoint: &str, path: &str) -> Result<Map<String, Value>> {
+        self.config
+            .retry_policy()
+            .run_checked(Idempotency::Idempotent,
+                         || self.send_public_query(endpoint, path),
+                         |response| utils::parse_result(response, endpoint).map(|_| ()))
+            .await
+            .map_err(|err| err.with_endpoint(Exchange::Gdax, endpoint))
+    }
+
+    async fn private_query(&self,
+                           method: Method,
+                           endpoint: &str,
+                           path: &str,
+                           body: Option<&Map<String, Value>>)
+                           -> Result<Map<String, Value>> {
+        let idempotency = retry::get_idempotency(Exchange::Gdax, Scope::Private, endpoint);
+        self.config
+            .retry_policy()
+            .run_checked(idempotency,
+                         || self.send_private_query(method, endpoint, path, body),
+                         |response| utils::parse_result(response, endpoint).map(|_| ()))
+            .await
+            .map_err(|err| err.with_endpoint(Exchange::Gdax, endpoint))
+    }
+
+    async fn send_public_query(&self, endpoint: &str, path: &str) -> Result<Map<String, Value>> {
+        self.config.rate_limiter().acquire(Scope::Public, endpoint).await?;
+
+        let request = HttpRequest::new(Method::Get, &utils::build_url(path))
+            .header("User-Agent", "coinnect");
+        let response = self.http_client.send(request).await?.error_for_status()?;
+
+        utils::deserialize_json_array(&response.body)
+    }
+
+    /// Send a signed request. `path` is the path of the endpoint with its query string (ie.
+    /// "/orders?product_id=BTC-USD"), the body is sent as JSON.
+    /// The signature covers the timestamp, the method, the path and the body (see
+    /// `utils::build_signature`), the passphrase is the one chosen when the API key was created.
+    async fn send_private_query(&self,
+                                method: Method,
+                                endpoint: &str,
+                                path: &str,
+                                body: Option<&Map<String, Value>>)
+                                -> Result<Map<String, Value>> {
+        self.config.rate_limiter().acquire(Scope::Private, endpoint).await?;
+
+        let body = match body {
+            Some(body) => serde_json::to_string(body)?,
+            None => "".to_string(),
+        };
+        let method_name = match method {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Delete => "DELETE",
+        };
+
+        let timestamp = utils::build_timestamp(self.config.nonce_provider().next_nonce()?);
+        let signature =
+            utils::build_signature(&timestamp, method_name, path, &body, &self.api_secret)?;
+
+        let mut request = HttpRequest::new(method, &utils::build_url(path))
+            .header("User-Agent", "coinnect")
+            .header("CB-ACCESS-KEY", &self.api_key)
+            .header("CB-ACCESS-SIGN", &signature)
+            .header("CB-ACCESS-TIMESTAMP", &timestamp)
+            .header("CB-ACCESS-PASSPHRASE", &self.passphrase);
+        if !body.is_empty() {
+            request = request.header("Content-Type", "application/json").body(&body);
+        }
+        let response = self.http_client.send(request).await?.error_for_status()?;
+
+        utils::deserialize_json_array(&response.body)
+    }
+
+    /// Returns the products available for trading.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "BTC-USD", "base_currency": "BTC", "quote_currency": "USD",
+    /// "base_min_size": "0.001", "base_max_size": "280", "quote_increment": "0.01",
+    /// "base_increment": "0.00000001", "display_name": "BTC/USD", "min_market_funds": "10",
+    /// "post_only": false, "limit_only": false, "cancel_only": false,
+    /// "trading_disabled": false, "status": "online", "status_message": ""}, ... ]
+    /// ```
+    pub async fn return_products(&self) -> Result<Map<String, Value>> {
+        self.public_query("products", "/products").await
+    }
+
+    /// Returns a single product, see `return_products` for the fields.
+    pub async fn return_product(&self, pair: Pair) -> Result<Map<String, Value>> {
+        let product_id = utils::get_product_id(&pair)?;
+        self.public_query("product", &format!("/products/{}", product_id)).await
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"trade_id": 4729088, "price": "333.99", "size": "0.193", "bid": "333.98",
+    /// "ask": "333.99", "volume": "5957.11914015", "time": "2015-11-14T20:46:03.511254Z"}
+    /// ```
+    pub async fn return_ticker(&self, pair: Pair) -> Result<Map<String, Value>> {
+        let product_id = utils::get_product_id(&pair)?;
+        self.public_query("ticker", &format!("/products/{}/ticker", product_id)).await
+    }
+
+    /// Returns the 50 best bids and asks, aggregated by price (level 2).
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"sequence": "3", "bids": [["295.96", "4.39088265", 2], ... ],
+    /// "asks": [["295.97", "25.23542881", 12], ... ]}
+    /// ```
+    pub async fn return_order_book(&self, pair: Pair) -> Result<Map<String, Value>> {
+        let product_id = utils::get_product_id(&pair)?;
+        self.public_query("order_book", &format!("/products/{}/book?level=2", product_id)).await
+    }
+
+    /// Returns the latest trades, most recent first ("side" is the side of the maker).
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"time": "2017-10-27T10:11:12.123Z", "trade_id": 74, "price": "5812.99",
+    /// "size": "0.04000000", "side": "buy"},
+    /// {"time": "2017-10-27T10:11:00.456Z", "trade_id": 73, "price": "5812.98",
+    /// "size": "0.22000000", "side": "sell"}, ... ]
+    /// ```
+    pub async fn return_trade_history(&self, pair: Pair) -> Result<Map<String, Value>> {
+        let product_id = utils::get_product_id(&pair)?;
+        self.public_query("trades", &format!("/products/{}/trades", product_id)).await
+    }
+
+    /// Returns at most 300 candles, most recent first. `granularity` is the duration of a
+    /// candle in seconds: 60, 300, 900, 3600, 21600 or 86400. `start` and `end` are ISO 8601
+    /// dates, both of them must be given (or left empty to get the latest candles).
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [[1415398800, 0.32, 4.2, 0.35, 4.2, 12.3], [1415398740, 0.32, 4.2, 0.35, 4.2, 12.3], ...]
+    /// ```
+    /// Each candle is an array: [time, low, high, open, close, volume].
+    pub async fn return_candles(&self,
+                                pair: Pair,
+                                granularity: i64,
+                                start: &str,
+                                end: &str)
+                                -> Result<Map<String, Value>> {
+        let product_id = utils::get_product_id(&pair)?;
+        let granularity = granularity.to_string();
+        let path = utils::build_path(&format!("/products/{}/candles", product_id),
+                                     &[("granularity", &granularity),
+                                       ("start", start),
+                                       ("end", end)]);
+        self.public_query("candles", &path).await
+    }
+
+    /// Returns the statistics of the last 24 hours.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"open": "6745.61000000", "high": "7292.11000000", "low": "6650.00000000",
+    /// "volume": "26185.51325269", "last": "6813.19000000", "volume_30day": "1019451.11188405"}
+    /// ```
+    pub async fn return_product_stats(&self, pair: Pair) -> Result<Map<String, Value>> {
+        let product_id = utils::get_product_id(&pair)?;
+        self.public_query("stats", &format!("/products/{}/stats", product_id)).await
+    }
+
+    /// Returns your accounts, one per currency.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "71452118-efc7-4cc4-8780-a5e22d4baa53", "currency": "BTC",
+    /// "balance": "0.0000000000000000", "available": "0.0000000000000000",
+    /// "hold": "0.0000000000000000", "profile_id": "75da88c5-05bf-4f54-bc85-5c775bd68254",
+    /// "trading_enabled": true}, ... ]
+    /// ```
+    pub async fn return_accounts(&self) -> Result<Map<String, Value>> {
+        self.private_query(Method::Get, "accounts", "/accounts", None).await
+    }
+
+    /// Returns a single account, identified by its "id" (see `return_accounts`).
+    pub async fn return_account(&self, account_id: &str) -> Result<Map<String, Value>> {
+        let path = format!("/accounts/{}", account_id);
+        self.private_query(Method::Get, "account", &path, None).await
+    }
+
+    /// Returns the activity of an account (transfers, matches, fees, ...), most recent first.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "100", "created_at": "2014-11-07T08:19:27.028459Z", "amount": "0.001",
+    /// "balance": "239.669", "type": "fee", "details": {"order_id":
+    /// "d50ec984-77a8-460a-b958-66f114b0de9b", "trade_id": "74", "product_id": "BTC-USD"}},
+    /// ... ]
+    /// ```
+    pub async fn return_account_history(&self, account_id: &str) -> Result<Map<String, Value>> {
+        let path = format!("/accounts/{}/ledger", account_id);
+        self.private_query(Method::Get, "account_history", &path, None).await
+    }
+
+    /// Returns the funds of an account on hold for the open orders and pending withdrawals.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"id": "82dcd140-c3c7-4507-8de4-2c529cd1a28f",
+    /// "account_id": "e0b3f39a-183d-453e-b754-0c13e5bab0b3",
+    /// "created_at": "2014-11-06T10:34:47.123456Z", "updated_at": "2014-11-06T10:40:47.123456Z",
+    /// "amount": "4.23", "type": "order", "ref": "0a205de4-dd35-4370-a285-fe8fc375a273"}, ... ]
+    /// ```
+    pub async fn return_account_holds(&self, account_id: &str) -> Result<Map<String, Value>> {
+        let path = format!("/accounts/{}/holds", account_id);
+        self.private_query(Method::Get, "account_holds", &path, None).await
+    }
+
+    /// Place an order described by the fields of the JSON body: "side" ("buy" or "sell"),
+    /// "product_id", "type" ("limit" or "market"), "size" and "price" for a limit order, "size"
+    /// or "funds" for a market order, and the optional fields documented by Gdax (ie.
+    /// "client_oid", "time_in_force" or "post_only").
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"id": "d0c5340b-6d6c-49d9-b567-48c4bfca13d2", "price": "0.10000000",
+    /// "size": "0.01000000", "product_id": "BTC-USD", "side": "buy", "stp": "dc",
+    /// "type": "limit", "time_in_force": "GTC", "post_only": false,
+    /// "created_at": "2016-12-08T20:02:28.53864Z", "fill_fees": "0.0000000000000000",
+    /// "filled_size": "0.00000000", "executed_value": "0.0000000000000000",
+    /// "status": "pending", "settled": false}
+    /// ```
+    pub async fn place_order(&self, order: &Map<String, Value>) -> Result<Map<String, Value>> {
+        self.private_query(Method::Post, "orders", "/orders", Some(order)).await
+    }
+
+    async fn place_simple_order(&self,
+                                side: &str,
+                                order_type: &str,
+                                pair: Pair,
+                                amount: Volume,
+                                price: Option<Price>)
+                                -> Result<Map<String, Value>> {
+        let mut order = Map::new();
+        order.insert("side".to_string(), Value::String(side.to_string()));
+        order.insert("type".to_string(), Value::String(order_type.to_string()));
+        order.insert("product_id".to_string(), Value::String(utils::get_product_id(&pair)?));
+        order.insert("size".to_string(), Value::String(amount.to_string()));
+        if let Some(price) = price {
+            order.insert("price".to_string(), Value::String(price.to_string()));
+        }
+
+        self.place_order(&order).await
+    }
+
+    /// Add a buy limit order to the exchange, good until canceled.
+    pub async fn buy_limit(&self,
+                           pair: Pair,
+                           amount: Volume,
+                           price: Price)
+                           -> Result<Map<String, Value>> {
+        self.place_simple_order("buy", "limit", pair, amount, Some(price)).await
+    }
+
+    /// Add a sell limit order to the exchange, good until canceled.
+    pub async fn sell_limit(&self,
+                            pair: Pair,
+                            amount: Volume,
+                            price: Price)
+                            -> Result<Map<String, Value>> {
+        self.place_simple_order("sell", "limit", pair, amount, Some(price)).await
+    }
+
+    /// Add a market buy order to the exchange
+    /// By placing a market order you acknowledge that the execution of your order depends
+    /// on the market conditions and that these conditions may be subject to sudden changes
+    /// that cannot be foreseen.
+    pub async fn buy_market(&self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
+        self.place_simple_order("buy", "market", pair, amount, None).await
+    }
+
+    /// Add a market sell order to the exchange
+    /// By placing a market order you acknowledge that the execution of your order depends
+    /// on the market conditions and that these conditions may be subject to sudden changes
+    /// that cannot be foreseen.
+    pub async fn sell_market(&self, pair: Pair, amount: Volume) -> Result<Map<String, Value>> {
+        self.place_simple_order("sell", "market", pair, amount, None).await
+    }
+
+    /// Cancel a previously placed order, identified by the "id" returned when it was placed.
+    /// The id of the canceled order is returned in the "data" field of the map.
+    pub async fn cancel_order(&self, id: &str) -> Result<Map<String, Value>> {
+        let path = format!("/orders/{}", id);
+        self.private_query(Method::Delete, "cancel_order", &path, None).await
+    }
+
+    /// Cancel all your open orders, for a given Pair or for all of them if None is given.
+    /// The ids of the canceled orders are returned in the "data" field of the map.
+    pub async fn cancel_all_orders(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        let product_id = match pair {
+            Some(ref pair) => utils::get_product_id(pair)?,
+            None => "".to_string(),
+        };
+
+        let path = utils::build_path("/orders", &[("product_id", &product_id)]);
+        self.private_query(Method::Delete, "cancel_all_orders", &path, None).await
+    }
+
+    /// List your open orders, for a given Pair or for all of them if None is given.
+    /// The array is returned in the "data" field of the map, see `place_order` for the fields.
+    pub async fn return_open_orders(&self, pair: Option<Pair>) -> Result<Map<String, Value>> {
+        let product_id = match pair {
+            Some(ref pair) => utils::get_product_id(pair)?,
+            None => "".to_string(),
+        };
+
+        let path = utils::build_path("/orders", &[("product_id", &product_id)]);
+        self.private_query(Method::Get, "open_orders", &path, None).await
+    }
+
+    /// Get a single order, identified by the "id" returned when it was placed.
+    pub async fn return_order_status(&self, id: &str) -> Result<Map<String, Value>> {
+        let path = format!("/orders/{}", id);
+        self.private_query(Method::Get, "order_status", &path, None).await
+    }
+
+    /// List your fills, for an order (identified by its "id") or a Pair. At least one of them
+    /// must be given.
+    /// The array is returned in the "data" field of the map.
+    ///
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"trade_id": 74, "product_id": "BTC-USD", "price": "10.00", "size": "0.01",
+    /// "order_id": "d50ec984-77a8-460a-b958-66f114b0de9b",
+    /// "created_at": "2014-11-07T22:19:28.578544Z", "liquidity": "T", "fee": "0.00025",
+    /// "settled": true, "side": "buy"}, ... ]
+    /// ```
+    pub async fn return_fills(&self,
+                              order_id: Option<&str>,
+                              pair: Option<Pair>)
+                              -> Result<Map<String, Value>> {
+        let product_id = match pair {
+            Some(ref pair) => utils::get_product_id(pair)?,
+            None => "".to_string(),
+        };
+        if order_id.is_none() && product_id.is_empty() {
+            return Err(ErrorKind::InvalidArguments.into());
+        }
+
+        let path = utils::build_path("/fills",
+                                     &[("order_id", order_id.unwrap_or("")),
+                                       ("product_id", &product_id)]);
+        self.private_query(Method::Get, "fills", &path, None).await
+    }
+}
+
+
+#[cfg(test)]
+mod gdax_api_tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::nonce::MemoryNonceProvider;
+    use crate::rate_limit::{Policy, RateLimiter};
+    use crate::retry::RetryPolicy;
+    use crate::helpers;
+    use crate::nonce::Resolution;
+
+    #[tokio::test]
+    async fn should_block_or_not_block_when_enabled_or_disabled() {
+        let http_client = Box::new(ReqwestTransport::from(reqwest::Client::new()));
+        let api = AsyncGdaxApi {
+            api_key: "".to_string(),
+            api_secret: "".to_string(),
+            passphrase: "".to_string(),
+            http_client,
+            config: ClientConfig::new(RateLimiter::new(Exchange::Gdax),
+                                      RetryPolicy::never(),
+                                      Arc::new(MemoryNonceProvider::new(Resolution::Milliseconds))),
+            markets: RwLock::new(None),
+        };
+
+        // The bucket of the public requests is full: empty it
+        api.config.rate_limiter().set_policy(Policy::Burst);
+        for _ in 0..6 {
+            api.config.rate_limiter().acquire(Scope::Public, "ticker").await.unwrap();
+        }
+
+        let mut counter = 0;
+        loop {
+            api.config.rate_limiter().set_policy(Policy::Throttle);
+            let start = helpers::get_unix_timestamp_ms();
+            api.config.rate_limiter().acquire(Scope::Public, "ticker").await.unwrap();
+
+            let difference = helpers::get_unix_timestamp_ms() - start;
+            assert!(difference >= 325);
+            assert!(difference < 10000);
+
+            api.config.rate_limiter().set_policy(Policy::Burst);
+            let start = helpers::get_unix_timestamp_ms();
+            api.config.rate_limiter().acquire(Scope::Public, "ticker").await.unwrap();
+
+            let difference = helpers::get_unix_timestamp_ms() - start;
+            assert!(difference < 10);
+
+            counter += 1;
+            if counter >= 3 {
+                break;
+            }
+        }
+    }
+}
@@ -112,11 +112,7 @@ impl GdaxCreds {
 impl Credentials for GdaxCreds {
     /// Return a value from the credentials.
     fn get(&self, key: &str) -> Option<String> {
-        if let Some(res) = self.data.get(key) {
-            Some(res.clone())
-        } else {
-            None
-        }
+        self.data.get(key).cloned()
     }
 
     fn name(&self) -> String {
@@ -2,18 +2,24 @@
 //! This a more convenient and safe way to deal with the exchange since methods return a Result<>
 //! but this generic API does not provide all the functionnality that Gdax offers.
 
-use crate::exchange::ExchangeApi;
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::exchange::{AsyncExchangeApi, ExchangeApi};
 use crate::gdax::api::GdaxApi;
+use crate::gdax::async_api::AsyncGdaxApi;
 use crate::gdax::utils;
 
 use crate::error::*;
 use crate::types::*;
 use crate::helpers;
 
-impl ExchangeApi for GdaxApi {
-    fn ticker(&mut self, pair: Pair) -> Result<Ticker> {
+#[async_trait]
+impl AsyncExchangeApi for AsyncGdaxApi {
+    async fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        load_pair(self, &pair).await?;
 
-        let result = self.return_ticker(pair)?;
+        let result = Value::Object(self.return_ticker(pair).await?);
 
         let price = helpers::from_json_bigdecimal(&result["price"], "price")?;
         let ask = helpers::from_json_bigdecimal(&result["ask"], "ask")?;
@@ -30,11 +36,12 @@ impl ExchangeApi for GdaxApi {
            })
     }
 
-    fn orderbook(&mut self, pair: Pair) -> Result<Orderbook> {
+    async fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        load_pair(self, &pair).await?;
 
-        let raw_response = self.return_order_book(pair)?;
+        let raw_response = self.return_order_book(pair).await?;
 
-        let result = utils::parse_result(&raw_response)?;
+        let result = Value::Object(utils::parse_result(&raw_response, "order_book")?);
 
         let mut ask_offers = Vec::new();
         let mut bid_offers = Vec::new();
@@ -42,11 +49,11 @@ impl ExchangeApi for GdaxApi {
         let ask_array =
             result["asks"]
                 .as_array()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat("asks".to_string()))?;
         let bid_array =
             result["bids"]
                 .as_array()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat("bids".to_string()))?;
 
         for ask in ask_array {
             let price = helpers::from_json_bigdecimal(&ask[0], "ask price")?;
@@ -64,75 +71,251 @@ impl ExchangeApi for GdaxApi {
 
         Ok(Orderbook {
             timestamp: helpers::get_unix_timestamp_ms(),
-            pair: pair,
+            pair,
             asks: ask_offers,
             bids: bid_offers,
         })
     }
 
-    fn add_order(&mut self,
-                 order_type: OrderType,
-                 pair: Pair,
-                 quantity: Volume,
-                 price: Option<Price>)
-                 -> Result<OrderInfo> {
-        //let pair_name = match utils::get_pair_string(&pair) {
-        //Some(name) => name,
-        //None => return Err(ErrorKind::PairUnsupported.into()),
-        //};
+    async fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        load_pair(self, &pair).await?;
+        let raw_response = self.return_trade_history(pair).await?;
+
+        let result = Value::Object(utils::parse_result(&raw_response, "trades")?);
+
+        let trade_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        let (trades, cursor) = helpers::trades_after(utils::parse_trades(trade_array)?, since)?;
+
+        Ok(TradeHistory {
+               timestamp: helpers::get_unix_timestamp_ms(),
+               pair,
+               trades,
+               cursor,
+           })
+    }
+
+    async fn candles(&self,
+                     pair: Pair,
+                     interval: Interval,
+                     since: Option<i64>)
+                     -> Result<Vec<Candle>> {
+        let granularity = utils::get_interval_granularity(interval)
+            .ok_or_else(|| ErrorKind::Unsupported(format!("{:?} candles", interval)))?;
+
+        // Gdax returns at most 300 candles and needs both dates, or none for the latest candles
+        let (start, end) = match since {
+            Some(since) => {
+                let start = since / 1000;
+                (utils::format_date(start)?, utils::format_date(start + 300 * granularity)?)
+            }
+            None => ("".to_string(), "".to_string()),
+        };
+
+        load_pair(self, &pair).await?;
+        let raw_response = self.return_candles(pair, granularity, &start, &end).await?;
+
+        let result = Value::Object(utils::parse_result(&raw_response, "candles")?);
+
+        let candle_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        let mut candles = utils::parse_candles(candle_array)?;
 
+        // The start date is inclusive
+        if let Some(since) = since {
+            candles.retain(|candle| candle.timestamp > since);
+        }
+
+        Ok(candles)
+    }
+
+    async fn markets(&self) -> Result<Markets> {
+        if let Some(ref markets) = *self.markets.read().unwrap() {
+            return Ok(markets.clone());
+        }
+
+        let raw_response = self.return_products().await?;
+        let result = Value::Object(utils::parse_result(&raw_response, "products")?);
+
+        let product_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        let markets = utils::parse_markets(product_array)?;
+        *self.markets.write().unwrap() = Some(markets.clone());
+
+        Ok(markets)
+    }
+
+    async fn add_order(&self,
+                       order_type: OrderType,
+                       pair: Pair,
+                       quantity: Volume,
+                       price: Option<Price>)
+                       -> Result<OrderInfo> {
+        load_pair(self, &pair).await?;
         let result = match order_type {
             OrderType::BuyLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
-
-                // Unwrap safe here with the check above.
-                self.buy_limit(pair, quantity, price.unwrap(), None, None)
+                let price = price.clone().ok_or(ErrorKind::MissingPrice)?;
+                self.buy_limit(pair, quantity.clone(), price).await
             }
-            OrderType::BuyMarket => self.buy_market(pair, quantity),
+            OrderType::BuyMarket => self.buy_market(pair, quantity.clone()).await,
             OrderType::SellLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
-
-                // Unwrap safe here with the check above.
-                self.sell_limit(pair, quantity, price.unwrap(), None, None)
+                let price = price.clone().ok_or(ErrorKind::MissingPrice)?;
+                self.sell_limit(pair, quantity.clone(), price).await
             }
-            OrderType::SellMarket => self.sell_market(pair, quantity),
-        };
+            OrderType::SellMarket => self.sell_market(pair, quantity.clone()).await,
+        }?;
+        let result = Value::Object(result);
 
         Ok(OrderInfo {
                timestamp: helpers::get_unix_timestamp_ms(),
-               identifier: vec![result?["id"]
-                                    .as_str()
-                                    .ok_or_else(|| {
-                                                    ErrorKind::MissingField("id".to_string())
-                                                })?
-                                    .to_string()],
+               identifier: vec![helpers::get_json_id(&result, "id")?],
+               pair,
+               order_type,
+               quantity,
+               price,
+               fills: Vec::new(),
            })
     }
 
-    /// Return the balances for each currency on the account
-    fn balances(&mut self) -> Result<Balances> {
-        let raw_response = self.return_balances()?;
-        let result = utils::parse_result(&raw_response)?;
+    async fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        for id in &order.identifier {
+            let raw_response = AsyncGdaxApi::cancel_order(self, id).await?;
+            utils::parse_result(&raw_response, "cancel_order")?;
+        }
 
-        let mut balances = Balances::new();
+        Ok(())
+    }
 
-        for (key, val) in result.iter() {
-            let currency = utils::get_currency_enum(key);
+    async fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        if let Some(ref pair) = pair {
+            load_pair(self, pair).await?;
+        }
+        let raw_response = self.return_open_orders(pair).await?;
+        let result = Value::Object(utils::parse_result(&raw_response, "open_orders")?);
 
-            match currency {
-                Some(c) => {
-                    let amount = helpers::from_json_bigdecimal(&val, "amount")?;
+        let result_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
 
-                    balances.insert(c, amount);
-                },
-                _ => ()
-            }
+        let mut orders = Vec::new();
+
+        for order in result_array {
+            let product_id = helpers::get_json_string(order, "product_id")?.to_lowercase();
+            let order_pair = match get_pair_from_name(self, &product_id).await? {
+                Some(order_pair) => order_pair,
+                None => continue,
+            };
+
+            orders.push(utils::parse_order(order_pair, order)?);
         }
 
-        Ok(balances)
+        Ok(orders)
+    }
+
+    async fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        let id = order.identifier.first().ok_or(ErrorKind::OrderNotFound)?;
+
+        let raw_response = self.return_order_status(id).await?;
+        let result = serde_json::Value::Object(utils::parse_result(&raw_response, "order_status")?);
+
+        let product_id = helpers::get_json_string(&result, "product_id")?.to_lowercase();
+        let pair = match get_pair_from_name(self, &product_id).await? {
+            Some(pair) => pair,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+        };
+
+        utils::parse_order(pair, &result)
+    }
+
+    /// Return the balances for each currency on the account
+    async fn balances(&self) -> Result<Balances> {
+        let raw_response = self.return_accounts().await?;
+        let result = Value::Object(utils::parse_result(&raw_response, "accounts")?);
+
+        let account_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        utils::parse_balances(account_array)
+    }
+}
+
+/// Load the markets listed by Gdax if the pair is not known yet: the pairs listed since the last
+/// update of Coinnect are only known once the markets have been loaded.
+async fn load_pair(api: &AsyncGdaxApi, pair: &Pair) -> Result<()> {
+    helpers::find_pair(&api.markets, || api.markets(), || utils::get_pair_string(pair)).await?;
+
+    Ok(())
+}
+
+/// Return the Pair of a product id used by Gdax, loading the markets listed by Gdax if the id is
+/// not known yet.
+async fn get_pair_from_name(api: &AsyncGdaxApi, name: &str) -> Result<Option<Pair>> {
+    helpers::find_pair(&api.markets, || api.markets(), || utils::get_pair_enum(name)).await
+}
+
+impl ExchangeApi for GdaxApi {
+    fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        self.runtime.block_on(self.inner.ticker(pair))
+    }
+
+    fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        self.runtime.block_on(self.inner.orderbook(pair))
+    }
+
+    fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        self.runtime.block_on(self.inner.trades(pair, since))
+    }
+
+    fn candles(&self,
+               pair: Pair,
+               interval: Interval,
+               since: Option<i64>)
+               -> Result<Vec<Candle>> {
+        self.runtime.block_on(self.inner.candles(pair, interval, since))
+    }
+
+    fn markets(&self) -> Result<Markets> {
+        self.runtime.block_on(self.inner.markets())
+    }
+
+    fn validate_order(&self,
+                      order_type: OrderType,
+                      pair: Pair,
+                      quantity: Volume,
+                      price: Option<Price>)
+                      -> Result<(Volume, Option<Price>)> {
+        self.runtime.block_on(self.inner.validate_order(order_type, pair, quantity, price))
+    }
+
+    fn add_order(&self,
+                 order_type: OrderType,
+                 pair: Pair,
+                 quantity: Volume,
+                 price: Option<Price>)
+                 -> Result<OrderInfo> {
+        self.runtime.block_on(self.inner.add_order(order_type, pair, quantity, price))
+    }
+
+    fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        self.runtime.block_on(AsyncExchangeApi::cancel_order(&self.inner, order))
+    }
+
+    fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        self.runtime.block_on(self.inner.open_orders(pair))
+    }
+
+    fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        self.runtime.block_on(self.inner.order_status(order))
+    }
+
+    fn balances(&self) -> Result<Balances> {
+        self.runtime.block_on(self.inner.balances())
     }
 }
@@ -1,9 +1,11 @@
 //! Use this module to interact with Bitstamp exchange.
 
 pub mod api;
+pub mod async_api;
 pub mod generic_api;
 pub mod credentials;
 pub mod utils;
 
 pub use self::credentials::GdaxCreds;
 pub use self::api::GdaxApi;
+pub use self::async_api::AsyncGdaxApi;
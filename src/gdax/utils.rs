@@ -1,84 +1,195 @@
 use bidir_map::BidirMap;
+use std::sync::RwLock;
 
 use hmac::{Hmac, Mac, NewMac};
 use sha2::{Sha256};
 
+use data_encoding::BASE64;
+
 use serde_json;
 use serde_json::Value;
 use serde_json::value::Map;
 
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{TimeZone, Utc};
+
 use crate::error::*;
-use crate::helpers;
-use crate::types::Currency;
-use crate::types::Pair;
-use crate::types::Pair::*;
+use crate::exchange::Exchange;
+use crate::helpers::{self, PairNames};
+use crate::normalization;
+use crate::types::*;
 
 type HmacSha256 = Hmac<Sha256>;
 
+pub const API_URL: &str = "https://api.exchange.coinbase.com";
+
 lazy_static! {
-    static ref PAIRS_STRING: BidirMap<Pair, &'static str> = {
+    static ref PAIRS_STRING: PairNames = {
         let mut m = BidirMap::new();
-        m.insert(BCH_USD, "bch-usd");
-        m.insert(LTC_EUR, "ltc-eur");
-        m.insert(LTC_USD, "ltc-usd");
-        m.insert(LTC_BTC, "ltc-btc");
-        m.insert(ETH_EUR, "eth-eur");
-        m.insert(ETH_USD, "eth-usd");
-        m.insert(ETH_BTC, "eth-btc");
-        m.insert(BTC_GBP, "btc-gbp");
-        m.insert(BTC_EUR, "btc-eur");
-        m.insert(BTC_USD, "btc-usd");
-        m
+        m.insert(Pair::BCH_USD, "bch-usd");
+        m.insert(Pair::LTC_EUR, "ltc-eur");
+        m.insert(Pair::LTC_USD, "ltc-usd");
+        m.insert(Pair::LTC_BTC, "ltc-btc");
+        m.insert(Pair::ETH_EUR, "eth-eur");
+        m.insert(Pair::ETH_USD, "eth-usd");
+        m.insert(Pair::ETH_BTC, "eth-btc");
+        m.insert(Pair::BTC_GBP, "btc-gbp");
+        m.insert(Pair::BTC_EUR, "btc-eur");
+        m.insert(Pair::BTC_USD, "btc-usd");
+        RwLock::new(m)
     };
 }
 
-/// Return the name associated to pair used by Bitstamp
-/// If the Pair is not supported, None is returned.
-pub fn get_pair_string(pair: &Pair) -> Option<&&str> {
-    PAIRS_STRING.get_by_first(pair)
+/// Return the name associated to pair used by Gdax
+/// If the Pair is not supported, None is returned. Pairs listed since the last update of this
+/// module are only known once `register_pair` has been called (see `ExchangeApi::markets`).
+pub fn get_pair_string(pair: &Pair) -> Option<&'static str> {
+    helpers::get_pair_name(&PAIRS_STRING, pair)
 }
 
-/// Return the Pair enum associated to the string used by Bitstamp
+/// Return the Pair enum associated to the string used by Gdax
 /// If the Pair is not supported, None is returned.
-pub fn get_pair_enum(pair: &str) -> Option<&Pair> {
-    PAIRS_STRING.get_by_second(&pair)
+pub fn get_pair_enum(pair: &str) -> Option<Pair> {
+    helpers::get_pair_from_name(&PAIRS_STRING, pair)
+}
+
+/// Add a pair listed by Gdax and the name used by Gdax for it. Pairs and names that are already
+/// known are left untouched.
+pub fn register_pair(pair: Pair, name: &str) {
+    helpers::register_pair(&PAIRS_STRING, pair, name)
 }
 
-pub fn build_signature(nonce: &str,
-                       passphrase: &str,
-                       api_key: &str,
+/// Sign a private request: HMAC-SHA256 of the timestamp, the HTTP method in upper case, the path
+/// of the request (with its query string) and the JSON body, keyed with the base64-decoded API
+/// secret. The signature is sent base64-encoded in the CB-ACCESS-SIGN header.
+pub fn build_signature(timestamp: &str,
+                       method: &str,
+                       request_path: &str,
+                       body: &str,
                        api_secret: &str)
                        -> Result<String> {
-    const C: &'static [u8] = b"0123456789ABCDEF";
+    let message = timestamp.to_owned() + method + request_path + body;
 
-    let message = nonce.to_owned() + passphrase + api_key;
+    let hmac_key = BASE64.decode(api_secret.as_bytes())?;
+    let mut mac = HmacSha256::new_from_slice(&hmac_key[..]).unwrap();
+    mac.update(message.as_bytes());
 
-    let mut mac = HmacSha256::new_from_slice(api_secret.as_bytes()).unwrap();
+    Ok(BASE64.encode(&mac.finalize().into_bytes()))
+}
 
-    mac.update(message.as_bytes());
-    let result = mac.finalize();
+/// Convert a nonce in ms into the CB-ACCESS-TIMESTAMP header, a number of seconds with decimals.
+/// Gdax rejects the requests whose timestamp is more than 30 seconds away from its clock.
+///
+/// # Examples
+///
+/// ```
+/// use coinnect::gdax::utils::build_timestamp;
+///
+/// assert_eq!(build_timestamp(1509099072123), "1509099072.123");
+/// ```
+pub fn build_timestamp(nonce: i64) -> String {
+    format!("{}.{:03}", nonce / 1000, nonce % 1000)
+}
+
+/// Append the parameters to the path of a request, the ones with an empty value are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use coinnect::gdax::utils::build_path;
+///
+/// assert_eq!(build_path("/fills", &[("order_id", ""), ("product_id", "BTC-USD")]),
+///            "/fills?product_id=BTC-USD");
+/// ```
+pub fn build_path(path: &str, params: &[(&str, &str)]) -> String {
+    let query: Vec<String> = params
+        .iter()
+        .filter(|&&(_, value)| !value.is_empty())
+        .map(|&(name, value)| name.to_string() + "=" + value)
+        .collect();
 
-    let raw_signature = result.into_bytes();
-    let mut signature = Vec::with_capacity(raw_signature.len() * 2);
-    for &byte in &raw_signature {
-        signature.push(C[(byte >> 4) as usize]);
-        signature.push(C[(byte & 0xf) as usize]);
+    if query.is_empty() {
+        path.to_string()
+    } else {
+        path.to_string() + "?" + &query.join("&")
     }
-    // TODO: Handle correctly the from_utf8 errors with error_chain.
-    Ok(String::from_utf8(signature)?)
 }
 
-pub fn build_url(method: &str, pair: &str) -> String {
-    match method {
-        "ticker" => "https://api.gdax.com/products/".to_string() + pair + "/ticker",
-        "order_book" => "https://api.gdax.com/products/".to_string() + pair + "/book",
-        "transactions" => "https://api.gdax.com/accounts/".to_string() + pair + "/ledger",
-        _ => "not implemented yet".to_string(),
+/// Format a UNIX timestamp in seconds as an ISO 8601 date, ie. for the `start` and `end` of the
+/// candles.
+pub fn format_date(timestamp: i64) -> Result<String> {
+    let date = Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .ok_or(ErrorKind::InvalidArguments)?;
+    Ok(date.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+/// Convert the products returned by `products` into Markets. The pairs that are not known yet
+/// are registered (see `register_pair`).
+/// The lot decimals are the number of decimals of "base_increment".
+pub fn parse_markets(products: &[Value]) -> Result<Markets> {
+    let mut markets = Markets::new();
+
+    for product in products {
+        let id = helpers::get_json_string(product, "id")?.to_lowercase();
+        let pair = match get_pair_enum(&id) {
+            Some(pair) => pair,
+            None => {
+                // Gdax names "base" the left member of the pair
+                let quote = get_currency_enum(helpers::get_json_string(product, "base_currency")?)
+                    .ok_or_else(|| ErrorKind::InvalidFieldValue("base_currency".to_string()))?;
+                let base = get_currency_enum(helpers::get_json_string(product, "quote_currency")?)
+                    .ok_or_else(|| ErrorKind::InvalidFieldValue("quote_currency".to_string()))?;
+                let pair = Pair::new(quote, base);
+                register_pair(pair, &id);
+                pair
+            }
+        };
+
+        let base_increment = helpers::from_json_bigdecimal(&product["base_increment"],
+                                                           "base_increment")?;
+        let (_, decimals) = base_increment.normalized().as_bigint_and_exponent();
+        let tick_size = helpers::from_json_bigdecimal(&product["quote_increment"],
+                                                      "quote_increment")?;
+
+        markets.insert(pair,
+                       MarketInfo {
+                           pair,
+                           min_order_size: parse_optional_decimal(product, "base_min_size")?,
+                           tick_size: Some(tick_size),
+                           lot_decimals: Some(decimals.max(0) as u32),
+                           min_notional: parse_optional_decimal(product, "min_market_funds")?,
+                           active: product["status"].as_str() == Some("online") &&
+                                   product["trading_disabled"].as_bool() != Some(true),
+                       });
+    }
+
+    Ok(markets)
+}
+
+/// Parse a decimal field that Gdax does not send for every product.
+fn parse_optional_decimal(json_obj: &Value, key: &str) -> Result<Option<BigDecimal>> {
+    match json_obj[key] {
+        Value::Null => Ok(None),
+        ref value => Ok(Some(helpers::from_json_bigdecimal(value, key)?)),
+    }
+}
+
+pub fn build_url(path: &str) -> String {
+    API_URL.to_string() + path
+}
+
+/// Return the product id (ie. "BTC-USD") of a Pair, as used in the paths and bodies of the
+/// requests.
+pub fn get_product_id(pair: &Pair) -> Result<String> {
+    match get_pair_string(pair) {
+        Some(name) => Ok(name.to_uppercase()),
+        None => Err(ErrorKind::PairUnsupported.into()),
     }
 }
 
 pub fn deserialize_json(json_string: &str) -> Result<Map<String, Value>> {
-    let data: Value = match serde_json::from_str(json_string) {
+    let data: Value = match helpers::parse_json(json_string) {
         Ok(data) => data,
         Err(_) => return Err(ErrorKind::BadParse.into()),
     };
@@ -89,17 +200,62 @@ pub fn deserialize_json(json_string: &str) -> Result<Map<String, Value>> {
     }
 }
 
-pub fn generate_nonce(fixed_nonce: Option<String>) -> String {
-    match fixed_nonce {
-        Some(v) => v,
-        None => helpers::get_unix_timestamp_ms().to_string(),
+/// Convert a JSON array into a map containing a Vec for the "data" key. A JSON string (ie. the
+/// id of a canceled order) is returned in the "data" field as well.
+/// A JSON object (ie. an error) is returned as is.
+pub fn deserialize_json_array(json_string: &str) -> Result<Map<String, Value>> {
+    let data: Value = match helpers::parse_json(json_string) {
+        Ok(data) => data,
+        Err(_) => return Err(ErrorKind::BadParse.into()),
+    };
+
+    match data {
+        Value::Object(map) => Ok(map),
+        Value::Array(_) | Value::String(_) => {
+            let mut map = Map::new();
+            map.insert("data".to_string(), data);
+            Ok(map)
+        }
+        _ => Err(ErrorKind::BadParse.into()),
+    }
+}
+
+/// Return the ErrorKind matching an error message sent by Gdax in the "message" field. Some
+/// messages contain values (ie. "size is too small. Minimum size is 0.01"), they are matched on
+/// their constant part.
+pub fn get_error_kind(message: &str) -> ErrorKind {
+    match message {
+        "Invalid API Key" | "invalid signature" | "Invalid Passphrase" |
+        "CB-ACCESS-KEY header is required" | "CB-ACCESS-SIGN header is required" |
+        "CB-ACCESS-PASSPHRASE header is required" => ErrorKind::BadCredentials,
+        "request timestamp expired" | "invalid timestamp" => ErrorKind::InvalidNonce,
+        "Forbidden" => ErrorKind::PermissionDenied,
+        "Insufficient funds" => ErrorKind::InsufficientFunds,
+        "NotFound" | "order not found" => ErrorKind::OrderNotFound,
+        "Order already done" => ErrorKind::OrderAlreadyClosed,
+        "Product not found" | "Invalid product_id" => ErrorKind::PairUnsupported,
+        "Trading is disabled" | "Product is in cancel only mode" => ErrorKind::MarketInactive,
+        "Internal server error" => ErrorKind::ServiceUnavailable(message.to_string()),
+        message if message.to_lowercase().ends_with("rate limit exceeded") => {
+            ErrorKind::RateLimitExceeded
+        }
+        message if message.starts_with("size is too small") ||
+                   message.starts_with("funds is too small") => ErrorKind::InsufficientOrderSize,
+        message if message == "BadRequest" || message.starts_with("Invalid ") ||
+                   message.starts_with("price is too small") ||
+                   message.starts_with("size is too accurate") ||
+                   message.starts_with("price is too accurate") => ErrorKind::InvalidArguments,
+        other => ErrorKind::ExchangeSpecificError(other.to_string()),
     }
 }
 
 /// If error array is null, return the result (encoded in a json object)
 /// else return the error string found in array
-pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>> {
-    let error_msg = match response.get("error") {
+/// Gdax reports its errors in a "message" field, which is looked up as well.
+pub fn parse_result(response: &Map<String, Value>,
+                    endpoint: &str)
+                    -> Result<Map<String, Value>> {
+    let error_msg = match response.get("error").or_else(|| response.get("message")) {
         Some(error) => {
             error
                 .as_str()
@@ -108,42 +264,188 @@ pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>>
         None => return Ok(response.clone()),
     };
 
-    match error_msg.as_ref() {
-        "Invalid command." => Err(ErrorKind::InvalidArguments.into()),
-        "Invalid API key/secret pair." => Err(ErrorKind::BadCredentials.into()),
-        "Total must be at least 0.0001." => Err(ErrorKind::InsufficientOrderSize.into()),
-        other => Err(ErrorKind::ExchangeSpecificError(other.to_string()).into()),
+    Err(ExchangeError::new(Exchange::Gdax, endpoint)
+            .message(error_msg)
+            .into_error(get_error_kind(error_msg)))
+}
+
+/// Convert the trades returned by `trades` (most recent first) into a list of Trade, from the
+/// oldest to the most recent.
+/// Gdax reports the side of the maker, the taker side is the opposite one.
+pub fn parse_trades(trades: &[Value]) -> Result<Vec<Trade>> {
+    let mut result = Vec::new();
+
+    for trade in trades.iter().rev() {
+        let side = match helpers::get_json_string(trade, "side")? {
+            "buy" => OrderSide::Sell,
+            "sell" => OrderSide::Buy,
+            _ => return Err(ErrorKind::InvalidFieldValue("side".to_string()).into()),
+        };
+
+        let date = helpers::get_json_string(trade, "time")?;
+
+        result.push(Trade {
+                        identifier: helpers::get_json_id(trade, "trade_id")?,
+                        timestamp: helpers::parse_datetime_ms(date, "time")?,
+                        price: helpers::from_json_bigdecimal(&trade["price"], "price")?,
+                        volume: helpers::from_json_bigdecimal(&trade["size"], "size")?,
+                        side,
+                    });
+    }
+
+    Ok(result)
+}
+
+/// Return the granularity of the candles, in seconds, matching an Interval. Gdax only supports
+/// some of them.
+pub fn get_interval_granularity(interval: Interval) -> Option<i64> {
+    match interval {
+        Interval::OneMinute |
+        Interval::FiveMinutes |
+        Interval::FifteenMinutes |
+        Interval::OneHour |
+        Interval::OneDay => Some(interval.seconds()),
+        _ => None,
     }
 }
 
+/// Convert the candles returned by `candles` (most recent first) into a list of Candle, from the
+/// oldest to the most recent.
+/// Each candle is an array: [time, low, high, open, close, volume].
+pub fn parse_candles(candles: &[Value]) -> Result<Vec<Candle>> {
+    let mut result = Vec::new();
+
+    for candle in candles.iter().rev() {
+        let time = candle[0]
+            .as_i64()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("time".to_string()))?;
+
+        result.push(Candle {
+                        timestamp: time * 1000,
+                        open: helpers::from_json_number_bigdecimal(&candle[3], "open")?,
+                        high: helpers::from_json_number_bigdecimal(&candle[2], "high")?,
+                        low: helpers::from_json_number_bigdecimal(&candle[1], "low")?,
+                        close: helpers::from_json_number_bigdecimal(&candle[4], "close")?,
+                        volume: helpers::from_json_number_bigdecimal(&candle[5], "volume")?,
+                    });
+    }
+
+    Ok(result)
+}
+
+/// Convert the accounts returned by `accounts` into Balances, using the total "balance" of
+/// each account (available funds and holds). The unknown currencies are skipped.
+pub fn parse_balances(accounts: &[Value]) -> Result<Balances> {
+    let mut balances = Balances::new();
+
+    for account in accounts {
+        let currency = match get_currency_enum(helpers::get_json_string(account, "currency")?) {
+            Some(currency) => currency,
+            None => continue,
+        };
+
+        let amount = helpers::from_json_bigdecimal(&account["balance"], "balance")?;
+        balances.insert(currency, amount);
+    }
+
+    Ok(balances)
+}
+
+/// Convert an order returned by `orders` into an Order.
+/// The pair is resolved by the caller from the "product_id" field.
+pub fn parse_order(pair: Pair, order: &Value) -> Result<Order> {
+    let side = match helpers::get_json_string(order, "side")? {
+        "buy" => OrderSide::Buy,
+        "sell" => OrderSide::Sell,
+        _ => return Err(ErrorKind::InvalidFieldValue("side".to_string()).into()),
+    };
+
+    let is_market = helpers::get_json_string(order, "type")? == "market";
+    let order_type = match (side, is_market) {
+        (OrderSide::Buy, false) => OrderType::BuyLimit,
+        (OrderSide::Sell, false) => OrderType::SellLimit,
+        (OrderSide::Buy, true) => OrderType::BuyMarket,
+        (OrderSide::Sell, true) => OrderType::SellMarket,
+    };
+
+    let price = match order.get("price") {
+        Some(price) if !is_market => Some(helpers::from_json_bigdecimal(price, "price")?),
+        _ => None,
+    };
+
+    let filled_quantity = helpers::from_json_bigdecimal(&order["filled_size"], "filled_size")?;
+    // Market orders placed with "funds" have no size
+    let quantity = match order.get("size") {
+        Some(size) => helpers::from_json_bigdecimal(size, "size")?,
+        None => filled_quantity.clone(),
+    };
+
+    let average_price = if filled_quantity.is_zero() {
+        None
+    } else {
+        let executed_value = helpers::from_json_bigdecimal(&order["executed_value"],
+                                                           "executed_value")?;
+        Some(executed_value / filled_quantity.clone())
+    };
+
+    let status = match helpers::get_json_string(order, "status")? {
+        "pending" | "open" | "active" if filled_quantity.is_zero() => OrderStatus::Open,
+        "pending" | "open" | "active" => OrderStatus::PartiallyFilled,
+        "done" | "settled" => {
+            match order["done_reason"].as_str() {
+                Some("canceled") => OrderStatus::Canceled,
+                _ => OrderStatus::Filled,
+            }
+        }
+        _ => return Err(ErrorKind::InvalidFieldValue("status".to_string()).into()),
+    };
+
+    let opened_at = helpers::parse_datetime_ms(helpers::get_json_string(order, "created_at")?,
+                                               "created_at")?;
+    let closed_at = match order.get("done_at") {
+        Some(date) => {
+            let date = date.as_str()
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat("done_at".to_string()))?;
+            Some(helpers::parse_datetime_ms(date, "done_at")?)
+        }
+        None => None,
+    };
+
+    Ok(Order {
+           timestamp: helpers::get_unix_timestamp_ms(),
+           identifier: helpers::get_json_string(order, "id")?.to_string(),
+           pair,
+           side,
+           order_type,
+           price,
+           quantity,
+           filled_quantity,
+           average_price,
+           status,
+           opened_at: Some(opened_at),
+           closed_at,
+       })
+}
+
 /// Return the currency enum associated with the
-/// string used by Bitstamp. If no currency is found,
-/// return None
+/// name used by Gdax (ie. "BTC" in the accounts or "btc" in
+/// the pairs), see the `normalization` module. If the
+/// currency is unknown, return None
 /// # Examples
 ///
 /// ```
 /// use coinnect::gdax::utils::get_currency_enum;
 /// use coinnect::types::Currency;
 ///
-/// let currency = get_currency_enum("usd_balance");
+/// let currency = get_currency_enum("usd");
 /// assert_eq!(Some(Currency::USD), currency);
 /// ```
 pub fn get_currency_enum(currency: &str) -> Option<Currency> {
-    match currency {
-        "btc_balance" => Some(Currency::BTC),
-        "eur_balance" => Some(Currency::EUR),
-        "ltc_balance" => Some(Currency::LTC),
-        "gbp_balance" => Some(Currency::GBP),
-        "usd_balance" => Some(Currency::USD),
-        "eth_balance" => Some(Currency::ETH),
-        "bch_balance" => Some(Currency::BCH),
-        _ => None,
-    }
+    normalization::get_currency(Exchange::Gdax, &currency.to_uppercase())
 }
 
-/// Return the currency string associated with the
-/// enum used by Gdax. If no currency is found,
-/// return None
+/// Return the name used by Gdax for the currency, see
+/// the `normalization` module
 /// # Examples
 ///
 /// ```
@@ -154,14 +456,5 @@ pub fn get_currency_enum(currency: &str) -> Option<Currency> {
 /// assert_eq!(currency, Some("USD".to_string()));
 /// ```
 pub fn get_currency_string(currency: Currency) -> Option<String> {
-    match currency {
-        Currency::BTC => Some("BTC".to_string()),
-        Currency::EUR => Some("EUR".to_string()),
-        Currency::LTC => Some("LTC".to_string()),
-        Currency::GBP => Some("GBP".to_string()),
-        Currency::USD => Some("USD".to_string()),
-        Currency::ETH => Some("ETH".to_string()),
-        Currency::BCH => Some("BCH".to_string()),
-        _ => None,
-    }
+    Some(normalization::get_name(Exchange::Gdax, currency))
 }
@@ -1,10 +1,19 @@
+use serde::de::{self, Deserialize, Deserializer};
 use serde_json::Value;
+use serde_json::value::RawValue;
 use crate::error::*;
+use crate::types::{Markets, Pair, Trade};
+use bidir_map::BidirMap;
 use bigdecimal::BigDecimal;
 use std::str::FromStr;
 
+use std::cmp;
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicI64, Ordering};
 use chrono::prelude::*;
+use tokio::runtime::{Builder, Runtime};
 
 // Helper functions
 
@@ -20,6 +29,22 @@ pub fn url_encode_hashmap(hashmap: &HashMap<&str, &str>) -> String {
     acc
 }
 
+/// Return the runtime on which a blocking API runs its asynchronous client.
+pub fn new_blocking_runtime() -> Result<Runtime> {
+    let runtime = Builder::new_current_thread().enable_time().build()?;
+    Ok(runtime)
+}
+
+/// Return `now`, or the nonce following the last one stored in `last` if `now` is not greater,
+/// and store it. The nonces returned are strictly increasing, even between concurrent callers.
+pub fn next_nonce(last: &AtomicI64, now: i64) -> i64 {
+    let previous = last.fetch_update(Ordering::SeqCst,
+                                     Ordering::SeqCst,
+                                     |last| Some(cmp::max(now, last + 1)))
+        .unwrap_or_else(|previous| previous);
+    cmp::max(now, previous + 1)
+}
+
 pub fn get_unix_timestamp_ms() -> i64 {
     let now = Utc::now();
     let seconds: i64 = now.timestamp();
@@ -37,10 +62,45 @@ pub fn get_unix_timestamp_us() -> i64 {
 pub fn strip_empties(x: &mut HashMap<&str, &str>) {
     let empties: Vec<_> = x.iter()
         .filter(|&(_, &v)| v.is_empty())
-        .map(|(k, _)| (*k).clone())
+        .map(|(k, _)| *k)
         .collect();
     for empty in empties {
-        x.remove(&empty);
+        x.remove(empty);
+    }
+}
+
+/// Parse a JSON response without losing the precision of its numbers. serde_json keeps the
+/// decimals as `f64`, so a number which can not be read back from its `f64` (ie. more than 17
+/// significant digits) is kept as the JSON string of its text, parsed by the decimal helpers.
+pub fn parse_json(json: &str) -> serde_json::Result<Value> {
+    let raw: &RawValue = serde_json::from_str(json)?;
+    parse_raw_json(raw)
+}
+
+fn parse_raw_json(raw: &RawValue) -> serde_json::Result<Value> {
+    let text = raw.get();
+    match text.as_bytes().first() {
+        Some(b'{') => {
+            let fields: HashMap<String, &RawValue> = serde_json::from_str(text)?;
+            fields
+                .into_iter()
+                .map(|(key, value)| Ok((key, parse_raw_json(value)?)))
+                .collect()
+        }
+        Some(b'[') => {
+            let values: Vec<&RawValue> = serde_json::from_str(text)?;
+            values.into_iter().map(parse_raw_json).collect()
+        }
+        Some(b'-') | Some(b'0'..=b'9') => {
+            let value: Value = serde_json::from_str(text)?;
+            let read_back = BigDecimal::from_str(&value.to_string()).ok();
+            if read_back.is_some() && read_back == BigDecimal::from_str(text).ok() {
+                Ok(value)
+            } else {
+                Ok(Value::String(text.to_string()))
+            }
+        }
+        _ => serde_json::from_str(text),
     }
 }
 
@@ -52,10 +112,260 @@ pub fn get_json_string<'a>(json_obj: &'a Value, key: &str) -> Result<&'a str> {
            .ok_or_else(|| ErrorKind::InvalidFieldFormat(key.to_string()))?)
 }
 
+/// Return an identifier which can be sent either as a JSON string or as a JSON number.
+pub fn get_json_id(json_obj: &Value, key: &str) -> Result<String> {
+    match json_obj.get(key) {
+        Some(Value::String(id)) => Ok(id.to_string()),
+        Some(Value::Number(id)) => Ok(id.to_string()),
+        Some(_) => Err(ErrorKind::InvalidFieldFormat(key.to_string()).into()),
+        None => Err(ErrorKind::MissingField(key.to_string()).into()),
+    }
+}
+
+/// Parse a decimal sent as a JSON string. A null (ie. a missing field indexed with `value[key]`)
+/// is a `MissingField`, any other value an `InvalidFieldFormat`.
 pub fn from_json_bigdecimal(json_obj: &Value, key: &str) -> Result<BigDecimal> {
-    let num = json_obj
-        .as_str()
-        .ok_or_else(|| ErrorKind::MissingField(key.to_string()))?;
+    let num = match *json_obj {
+        Value::String(ref num) => num,
+        Value::Null => return Err(ErrorKind::MissingField(key.to_string()).into()),
+        _ => return Err(ErrorKind::InvalidFieldFormat(key.to_string()).into()),
+    };
+
+    BigDecimal::from_str(num).chain_err(|| ErrorKind::InvalidFieldFormat(key.to_string()))
+}
+
+/// Same as `from_json_bigdecimal` but also accepts JSON numbers, which some exchanges use for
+/// amounts and prices. The numbers are parsed from their shortest text, never from the digits of
+/// their `f64`; the ones whose precision does not fit in a `f64` are strings (see `parse_json`).
+pub fn from_json_number_bigdecimal(json_obj: &Value, key: &str) -> Result<BigDecimal> {
+    match *json_obj {
+        Value::Number(ref num) => {
+            Ok(BigDecimal::from_str(&num.to_string())
+                   .chain_err(|| ErrorKind::InvalidFieldFormat(key.to_string()))?)
+        }
+        _ => from_json_bigdecimal(json_obj, key),
+    }
+}
+
+/// Deserialize a decimal sent as a JSON string or number, without going through a `f64`.
+/// Use it with the `deserialize_with` attribute of the typed responses.
+pub fn deserialize_bigdecimal<'de, D>(deserializer: D)
+                                      -> ::std::result::Result<BigDecimal, D::Error>
+    where D: Deserializer<'de>
+{
+    let value = Value::deserialize(deserializer)?;
+    from_json_number_bigdecimal(&value, "")
+        .map_err(|_| de::Error::custom(format!("invalid decimal: {}", value)))
+}
+
+/// Same as `deserialize_bigdecimal`, for the optional decimals. A null, an empty string or
+/// `false` (sent by Kraken for "no limit") is None.
+pub fn deserialize_option_bigdecimal<'de, D>(deserializer: D)
+                                             -> ::std::result::Result<Option<BigDecimal>, D::Error>
+    where D: Deserializer<'de>
+{
+    let value = Value::deserialize(deserializer)?;
+    match value {
+        Value::Null | Value::Bool(false) => Ok(None),
+        Value::String(ref num) if num.is_empty() => Ok(None),
+        _ => {
+            from_json_number_bigdecimal(&value, "")
+                .map(Some)
+                .map_err(|_| de::Error::custom(format!("invalid decimal: {}", value)))
+        }
+    }
+}
+
+/// Deserialize an identifier sent either as a JSON string or as a JSON number (see
+/// `get_json_id`).
+pub fn deserialize_id<'de, D>(deserializer: D) -> ::std::result::Result<String, D::Error>
+    where D: Deserializer<'de>
+{
+    match Value::deserialize(deserializer)? {
+        Value::String(id) => Ok(id),
+        Value::Number(id) => Ok(id.to_string()),
+        other => Err(de::Error::custom(format!("invalid identifier: {}", other))),
+    }
+}
+
+/// Same as `deserialize_id`, for the optional identifiers. A null is None.
+pub fn deserialize_option_id<'de, D>(deserializer: D)
+                                     -> ::std::result::Result<Option<String>, D::Error>
+    where D: Deserializer<'de>
+{
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(None),
+        Value::String(id) => Ok(Some(id)),
+        Value::Number(id) => Ok(Some(id.to_string())),
+        other => Err(de::Error::custom(format!("invalid identifier: {}", other))),
+    }
+}
+
+/// Deserialize the amounts of a JSON object (ie. balances by currency), sent as JSON strings or
+/// numbers.
+pub fn deserialize_bigdecimal_map<'de, D>(deserializer: D)
+                                          -> ::std::result::Result<HashMap<String, BigDecimal>,
+                                                                   D::Error>
+    where D: Deserializer<'de>
+{
+    let values = HashMap::<String, Value>::deserialize(deserializer)?;
+    values
+        .into_iter()
+        .map(|(key, value)| match from_json_number_bigdecimal(&value, &key) {
+                 Ok(amount) => Ok((key, amount)),
+                 Err(_) => Err(de::Error::custom(format!("invalid decimal: {}", value))),
+             })
+        .collect()
+}
+
+/// Deserialize a flag sent as a JSON boolean, as 0 or 1, or as "0" or "1".
+pub fn deserialize_flag<'de, D>(deserializer: D) -> ::std::result::Result<bool, D::Error>
+    where D: Deserializer<'de>
+{
+    let value = Value::deserialize(deserializer)?;
+    match value {
+        Value::Bool(flag) => Ok(flag),
+        Value::Number(ref number) if number.to_string() == "0" => Ok(false),
+        Value::Number(ref number) if number.to_string() == "1" => Ok(true),
+        Value::String(ref flag) if flag == "0" => Ok(false),
+        Value::String(ref flag) if flag == "1" => Ok(true),
+        _ => Err(de::Error::custom(format!("invalid flag: {}", value))),
+    }
+}
+
+/// Deserialize a UTC date sent as a JSON string (see `parse_datetime_ms` for the formats).
+pub fn deserialize_datetime<'de, D>(deserializer: D)
+                                    -> ::std::result::Result<DateTime<Utc>, D::Error>
+    where D: Deserializer<'de>
+{
+    let date = String::deserialize(deserializer)?;
+    parse_datetime(&date, "").map_err(|_| de::Error::custom(format!("invalid date: {}", date)))
+}
+
+/// Same as `deserialize_datetime`, for the optional dates. A null is None.
+pub fn deserialize_option_datetime<'de, D>(deserializer: D)
+                                           -> ::std::result::Result<Option<DateTime<Utc>>,
+                                                                    D::Error>
+    where D: Deserializer<'de>
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(date) => {
+            parse_datetime(&date, "")
+                .map(Some)
+                .map_err(|_| de::Error::custom(format!("invalid date: {}", date)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Deserialize a UNIX timestamp in seconds sent as a JSON number or string.
+pub fn deserialize_timestamp<'de, D>(deserializer: D)
+                                     -> ::std::result::Result<DateTime<Utc>, D::Error>
+    where D: Deserializer<'de>
+{
+    let value = Value::deserialize(deserializer)?;
+    let timestamp = match value {
+        Value::Number(ref number) => number.to_string().parse::<i64>().ok(),
+        Value::String(ref number) => number.parse::<i64>().ok(),
+        _ => None,
+    };
+
+    timestamp
+        .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+        .ok_or_else(|| de::Error::custom(format!("invalid timestamp: {}", value)))
+}
+
+/// Convert a UTC date returned by an exchange into a UNIX timestamp in ms.
+/// RFC 3339 dates ("2017-10-27T10:11:12.123Z") and naive dates ("2017-10-27 10:11:12" or
+/// "2017-10-27T10:11:12.123") are supported.
+pub fn parse_datetime_ms(date: &str, key: &str) -> Result<i64> {
+    Ok(parse_datetime(date, key)?.timestamp_millis())
+}
+
+/// Same as `parse_datetime_ms`, returning the date.
+pub fn parse_datetime(date: &str, key: &str) -> Result<DateTime<Utc>> {
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(date) {
+        return Ok(datetime.with_timezone(&Utc));
+    }
+
+    for format in &["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(date, format) {
+            return Ok(Utc.from_utc_datetime(&datetime));
+        }
+    }
+
+    Err(ErrorKind::InvalidFieldFormat(key.to_string()).into())
+}
+
+/// The names used by an exchange for the pairs: the ones listed when its module was written,
+/// completed by the ones registered from the markets listed by the exchange.
+pub type PairNames = RwLock<BidirMap<Pair, &'static str>>;
+
+pub fn get_pair_name(names: &PairNames, pair: &Pair) -> Option<&'static str> {
+    names.read().unwrap().get_by_first(pair).cloned()
+}
+
+pub fn get_pair_from_name(names: &PairNames, name: &str) -> Option<Pair> {
+    names.read().unwrap().get_by_second(&name).cloned()
+}
+
+/// Add a pair listed by an exchange and the name used by the exchange for it. Pairs and names
+/// that are already known are left untouched.
+pub fn register_pair(names: &PairNames, pair: Pair, name: &str) {
+    let mut names = names.write().unwrap();
+
+    if names.contains_first_key(&pair) || names.contains_second_key(&name) {
+        return;
+    }
+
+    // The names are `&'static str`, like the ones listed in the modules, so that they can be
+    // returned without holding the lock. They are allocated once per pair listed by the exchange
+    // and never freed, like the symbols of Currency.
+    names.insert(pair, Box::leak(name.to_string().into_boxed_str()));
+}
+
+/// Return the pair (or its name) found by `find`, loading the markets listed by the exchange
+/// first if it is not found and the markets are not loaded yet: the pairs listed since the last
+/// update of Coinnect are only known once the markets have been loaded.
+pub async fn find_pair<T, F, L, Fut>(markets: &RwLock<Option<Markets>>,
+                                     load: L,
+                                     find: F)
+                                     -> Result<Option<T>>
+    where F: Fn() -> Option<T>,
+          L: FnOnce() -> Fut,
+          Fut: Future<Output = Result<Markets>>
+{
+    if let Some(found) = find() {
+        return Ok(Some(found));
+    }
+
+    if markets.read().unwrap().is_none() {
+        load().await?;
+    }
+
+    Ok(find())
+}
+
+/// Keep the trades whose numeric identifier is greater than `since`, for the exchanges which
+/// cannot filter their trade history themselves. Return the trades kept and the cursor to use
+/// for the next call (the identifier of the last trade, or `since` if no trade is kept).
+pub fn trades_after(trades: Vec<Trade>,
+                    since: Option<String>)
+                    -> Result<(Vec<Trade>, Option<String>)> {
+    let last_id = match since {
+        Some(ref since) => since.parse::<u64>().chain_err(|| ErrorKind::InvalidArguments)?,
+        None => 0,
+    };
+
+    let trades: Vec<Trade> = trades
+        .into_iter()
+        .filter(|trade| trade.identifier.parse::<u64>().unwrap_or(0) > last_id)
+        .collect();
+
+    let cursor = match trades.last() {
+        Some(trade) => Some(trade.identifier.clone()),
+        None => since,
+    };
 
-    Ok(BigDecimal::from_str(num).chain_err(|| ErrorKind::InvalidFieldFormat(key.to_string()))?)
+    Ok((trades, cursor))
 }
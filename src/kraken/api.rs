@@ -2,180 +2,66 @@
 //! It is recommended to use a nonce window setting of 5000 for your API key when sending requests in quick succession in order to avoid nonce errors.
 //! WARNING: Special attention should be paid to error management: parsing number, etc.
 
-use hmac::{Hmac, Mac, NewMac};
-use sha2::{Sha256, Sha512, Digest};
-
-use hyper_native_tls::NativeTlsClient;
-use hyper::Client;
-use hyper::header;
-use hyper::net::HttpsConnector;
-
-use data_encoding::BASE64;
-
 use serde_json::Value;
 use serde_json::value::Map;
 
-use std::collections::HashMap;
-use std::io::Read;
-use std::thread;
-use std::time::Duration;
-use std::str;
+use tokio::runtime::Runtime;
 
-use crate::error::*;
-use crate::helpers;
+use std::fmt;
 
-use crate::exchange::Exchange;
+use crate::client::ClientConfig;
 use crate::coinnect::Credentials;
-use crate::kraken::utils;
-
-header! {
-    #[doc(hidden)]
-    (KeyHeader, "API-Key") => [String]
-}
-
-header! {
-    #[doc(hidden)]
-    (SignHeader, "API-Sign") => [String]
-}
+use crate::error::*;
+use crate::helpers;
+use crate::kraken::AsyncKrakenApi;
+use crate::kraken::typed_api::KrakenTypedApi;
+use crate::transport::{BlockingTransport, HttpTransport, HyperTransport};
 
-#[derive(Debug)]
+/// Blocking client of the Kraken API. Every call runs the `AsyncKrakenApi` method of the
+/// same name to completion on a runtime owned by the client, so it must not be used from an
+/// asynchronous context.
 pub struct KrakenApi {
-    last_request: i64, // unix timestamp in ms, to avoid ban
-    api_key: String,
-    api_secret: String,
-    otp: Option<String>, // two-factor password (if two-factor enabled, otherwise not required)
-    http_client: Client,
-    burst: bool,
+    pub(crate) inner: AsyncKrakenApi,
+    pub(crate) runtime: Runtime,
 }
 
-
 impl KrakenApi {
     /// Create a new KrakenApi by providing an API key & API secret
     pub fn new<C: Credentials>(creds: C) -> Result<KrakenApi> {
-        if creds.exchange() != Exchange::Kraken {
-            return Err(ErrorKind::InvalidConfigType(Exchange::Kraken, creds.exchange()).into());
-        }
+        KrakenApi::new_with_transport(creds, Box::new(HyperTransport::new()?))
+    }
 
-        // TODO: implement correctly the TLS error in error_chain.
-        let ssl = match NativeTlsClient::new() {
-            Ok(res) => res,
-            Err(_) => return Err(ErrorKind::TlsError.into()),
-        };
-        let connector = HttpsConnector::new(ssl);
+    /// Create a new KrakenApi sending its requests through the given transport
+    pub fn new_with_transport<C: Credentials>(creds: C,
+                                              transport: Box<dyn HttpTransport>)
+                                              -> Result<KrakenApi> {
+        let transport = BlockingTransport::from(transport);
 
         Ok(KrakenApi {
-               last_request: 0,
-               api_key: creds.get("api_key").unwrap_or_default(),
-               api_secret: creds.get("api_secret").unwrap_or_default(),
-               otp: None,
-               http_client: Client::with_connector(connector),
-               burst: false,
+               inner: AsyncKrakenApi::new_with_transport(creds, Box::new(transport))?,
+               runtime: helpers::new_blocking_runtime()?,
            })
     }
 
-
     /// Use to provide your two-factor password (if two-factor enabled, otherwise not required)
-    pub fn set_two_pass_auth(&mut self, otp: String) {
-        self.otp = Some(otp);
+    pub fn set_two_pass_auth(&self, otp: String) {
+        self.inner.set_two_pass_auth(otp)
     }
 
-    /// The number of calls in a given period is limited. In order to avoid a ban we limit
-    /// by default the number of api requests.
-    /// This function sets or removes the limitation.
-    /// Burst false implies no block.
-    /// Burst true implies there is a control over the number of calls allowed to the exchange
-    pub fn set_burst(&mut self, burst: bool) {
-        self.burst = burst
+    /// Return the settings of the client: its RateLimiter, RetryPolicy and NonceProvider.
+    pub fn config(&self) -> &ClientConfig {
+        self.inner.config()
     }
 
-    pub fn block_or_continue(&self) {
-        if ! self.burst {
-            let threshold: u64 = 2000; // 1 request/2sec
-            let offset: u64 = helpers::get_unix_timestamp_ms() as u64 - self.last_request as u64;
-            if offset < threshold {
-                let wait_ms = Duration::from_millis(threshold - offset);
-                thread::sleep(wait_ms);
-            }
-        }
+    /// Change the settings of the client, ie. `api.config_mut().set_retry_policy(policy)`.
+    pub fn config_mut(&mut self) -> &mut ClientConfig {
+        self.inner.config_mut()
     }
 
-    fn public_query(&mut self,
-                    method: &str,
-                    params: &mut HashMap<&str, &str>)
-                    -> Result<Map<String, Value>> {
-        helpers::strip_empties(params);
-        let url = "https://api.kraken.com/0/public/".to_string() + method + "?" +
-                  &helpers::url_encode_hashmap(params);
-
-        self.block_or_continue();
-        //TODO: Handle correctly http errors with error_chain.
-        let mut response = match self.http_client.get(&url).send() {
-            Ok(response) => response,
-            Err(err) => return Err(ErrorKind::ServiceUnavailable(err.to_string()).into()),
-        };
-        self.last_request = helpers::get_unix_timestamp_ms();
-        let mut buffer = String::new();
-        response.read_to_string(&mut buffer)?;
-        utils::deserialize_json(&buffer)
-    }
-
-    fn private_query(&mut self,
-                     method: &str,
-                     mut params: &mut HashMap<&str, &str>)
-                     -> Result<Map<String, Value>> {
-        let url = "https://api.kraken.com/0/private/".to_string() + method;
-
-        let urlpath = "/0/private/".to_string() + method;
-
-        let nonce = helpers::get_unix_timestamp_ms().to_string();
-        helpers::strip_empties(&mut params);
-
-        let mut params = params.clone(); // TODO: Remove .clone()
-        params.insert("nonce", &nonce);
-
-        if let Some(ref password) = self.otp {
-            params.insert("otp", password);
-        }
-
-        let postdata = helpers::url_encode_hashmap(&params);
-
-        let signature = self.create_signature(urlpath, &postdata, &nonce)?;
-
-        let mut custom_header = header::Headers::new();
-        custom_header.set(KeyHeader(self.api_key.clone()));
-        custom_header.set(SignHeader(signature));
-
-        let mut res = match self.http_client
-                  .post(&url)
-                  .body(&postdata)
-                  .headers(custom_header)
-                  .send() {
-            Ok(res) => res,
-            Err(err) => return Err(ErrorKind::ServiceUnavailable(err.to_string()).into()),
-        };
-
-        let mut buffer = String::new();
-        res.read_to_string(&mut buffer)?;
-        utils::deserialize_json(&buffer)
-    }
-
-    fn create_signature(&self, urlpath: String, postdata: &str, nonce: &str) -> Result<String> {
-        let message_presha256 = nonce.to_string() + postdata;
-
-        let mut sha256 = Sha256::default();
-        sha256.update(&message_presha256.as_bytes());
-
-        let output = sha256.finalize();
-
-        let mut concatenated = urlpath.as_bytes().to_vec();
-        for elem in output {
-            concatenated.push(elem);
-        }
-
-        let hmac_key = BASE64.decode(self.api_secret.as_bytes())?;
-        let mut mac = Hmac::<Sha512>::new_from_slice(&hmac_key[..]).unwrap();
-        mac.update(&concatenated);
-        Ok(BASE64.encode(&mac.finalize().into_bytes()))
+    /// Return a view of the client whose methods return the typed structs of the `responses`
+    /// module instead of JSON maps.
+    pub fn typed(&self) -> KrakenTypedApi<'_> {
+        KrakenTypedApi::new(self)
     }
 
     /// Result: Server's time
@@ -185,9 +71,8 @@ impl KrakenApi {
     /// rfc1123 = as RFC 1123 time format
     /// ```
     /// Note: This is to aid in approximating the skew time between the server and client.
-    pub fn get_server_time(&mut self) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        self.public_query("Time", &mut params)
+    pub fn get_server_time(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_server_time())
     }
 
     /// Input:
@@ -209,16 +94,12 @@ impl KrakenApi {
     /// decimals = scaling decimal places for record keeping
     /// display_decimals = scaling decimal places for output display
     /// ```
-    pub fn get_asset_info(&mut self,
+    pub fn get_asset_info(&self,
                           info: &str,
                           aclass: &str,
                           asset: &str)
                           -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("info", info);
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        self.public_query("Assets", &mut params)
+        self.runtime.block_on(self.inner.get_asset_info(info, aclass, asset))
     }
 
     /// Input:
@@ -254,14 +135,11 @@ impl KrakenApi {
     ///     margin_call = margin call level
     ///     margin_stop = stop-out/liquidation margin level
     /// ```
-    pub fn get_tradable_asset_pairs(&mut self,
+    pub fn get_tradable_asset_pairs(&self,
                                     info: &str,
                                     pair: &str)
                                     -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("info", info);
-        params.insert("pair", pair);
-        self.public_query("AssetPairs", &mut params)
+        self.runtime.block_on(self.inner.get_tradable_asset_pairs(info, pair))
     }
 
     /// Input:
@@ -284,10 +162,8 @@ impl KrakenApi {
     /// h = high array(<today>, <last 24 hours>),
     /// o = today's opening price
     /// ```
-    pub fn get_ticker_information(&mut self, pair: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("pair", pair);
-        self.public_query("Ticker", &mut params)
+    pub fn get_ticker_information(&self, pair: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_ticker_information(pair))
     }
 
     /// Input:
@@ -310,16 +186,12 @@ impl KrakenApi {
     ///
     /// Note: the last entry in the OHLC array is for the current, not-yet-committed frame and will
     /// always be present, regardless of the value of "since".
-    pub fn get_ohlc_data(&mut self,
+    pub fn get_ohlc_data(&self,
                          pair: &str,
                          interval: &str,
                          since: &str)
                          -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("pair", pair);
-        params.insert("interval", interval);
-        params.insert("since", since);
-        self.public_query("OHLC", &mut params)
+        self.runtime.block_on(self.inner.get_ohlc_data(pair, interval, since))
     }
 
     /// Input:
@@ -335,14 +207,10 @@ impl KrakenApi {
     ///     asks = ask side array of array entries(<price>, <volume>, <timestamp>)
     ///     bids = bid side array of array entries(<price>, <volume>, <timestamp>)
     /// ```
-    pub fn get_order_book(&mut self, pair: &str, count: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("pair", pair);
-        params.insert("count", count);
-        self.public_query("Depth", &mut params)
+    pub fn get_order_book(&self, pair: &str, count: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_order_book(pair, count))
     }
 
-
     /// Input:
     ///
     /// ```json
@@ -357,11 +225,8 @@ impl KrakenApi {
     /// <miscellaneous>)
     /// last = id to be used as since when polling for new trade data
     /// ```
-    pub fn get_recent_trades(&mut self, pair: &str, since: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("pair", pair);
-        params.insert("since", since);
-        self.public_query("Trades", &mut params)
+    pub fn get_recent_trades(&self, pair: &str, since: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_recent_trades(pair, since))
     }
 
     /// Input:
@@ -380,20 +245,16 @@ impl KrakenApi {
     /// ```
     /// Note: "since" is inclusive so any returned data with the same time as the previous set
     /// should overwrite all of the previous set's entries at that time
-    pub fn get_recent_spread_data(&mut self,
+    pub fn get_recent_spread_data(&self,
                                   pair: &str,
                                   since: &str)
                                   -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("pair", pair);
-        params.insert("since", since);
-        self.public_query("Spread", &mut params)
+        self.runtime.block_on(self.inner.get_recent_spread_data(pair, since))
     }
 
     /// Result: array of asset names and balance amount
-    pub fn get_account_balance(&mut self) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        self.private_query("Balance", &mut params)
+    pub fn get_account_balance(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_account_balance())
     }
 
     /// Input:
@@ -417,11 +278,8 @@ impl KrakenApi {
     /// ml = margin level = (equity / initial margin) * 100
     /// ```
     /// Note: Rates used for the floating valuation is the midpoint of the best bid and ask prices
-    pub fn get_trade_balance(&mut self, aclass: &str, asset: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        self.private_query("TradeBalance", &mut params)
+    pub fn get_trade_balance(&self, aclass: &str, asset: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_trade_balance(aclass, asset))
     }
 
     /// Input:
@@ -480,11 +338,8 @@ impl KrakenApi {
     /// a scale of 2.
     /// Similarly, if the asset pair's pricing scale is 5, the scale will remain as 5, even if the
     /// underlying currency has a scale of 8.
-    pub fn get_open_orders(&mut self, trades: &str, userref: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("trades", trades);
-        params.insert("userref", userref);
-        self.private_query("OpenOrders", &mut params)
+    pub fn get_open_orders(&self, trades: &str, userref: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_open_orders(trades, userref))
     }
 
     /// Input:
@@ -511,7 +366,7 @@ impl KrakenApi {
     /// ```
     /// Note: Times given by order tx ids are more accurate than unix timestamps. If an order tx id
     /// is given for the time, the order's open time is used
-    pub fn get_closed_orders(&mut self,
+    pub fn get_closed_orders(&self,
                              trades: &str,
                              userref: &str,
                              start: &str,
@@ -519,14 +374,12 @@ impl KrakenApi {
                              ofs: &str,
                              closetime: &str)
                              -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("trades", trades);
-        params.insert("userref", userref);
-        params.insert("start", start);
-        params.insert("end", end);
-        params.insert("ofs", ofs);
-        params.insert("closetime", closetime);
-        self.private_query("OpenOrders", &mut params)
+        self.runtime.block_on(self.inner.get_closed_orders(trades,
+                                                           userref,
+                                                           start,
+                                                           end,
+                                                           ofs,
+                                                           closetime))
     }
 
     /// Input:
@@ -541,16 +394,12 @@ impl KrakenApi {
     /// ```json
     /// <order_txid> = order info.  See Get open orders/Get closed orders
     /// ```
-    pub fn query_orders_info(&mut self,
+    pub fn query_orders_info(&self,
                              trades: &str,
                              userref: &str,
                              txid: &str)
                              -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("trades", trades);
-        params.insert("userref", userref);
-        params.insert("txid", txid);
-        self.private_query("QueryOrders", &mut params)
+        self.runtime.block_on(self.inner.query_orders_info(trades, userref, txid))
     }
 
     /// Input:
@@ -603,20 +452,14 @@ impl KrakenApi {
     /// Unless otherwise stated, costs, fees, prices, and volumes are in the asset pair's scale,
     /// not the currency's scale.
     /// Times given by trade tx ids are more accurate than unix timestamps.
-    pub fn get_trades_history(&mut self,
+    pub fn get_trades_history(&self,
                               type_trade: &str,
                               trades: &str,
                               start: &str,
                               end: &str,
                               ofs: &str)
                               -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("type", type_trade);
-        params.insert("trades", trades);
-        params.insert("start", start);
-        params.insert("end", end);
-        params.insert("ofs", ofs);
-        self.private_query("TradesHistory", &mut params)
+        self.runtime.block_on(self.inner.get_trades_history(type_trade, trades, start, end, ofs))
     }
 
     /// Input:
@@ -626,17 +469,14 @@ impl KrakenApi {
     /// trades = whether or not to include trades related to position in output (optional.
     /// default = false)
     /// ```
-    // Result: associative array of trades info
     ///
     /// ```json
     /// <trade_txid> = trade info.  See Get trades history
     /// ```
-    pub fn query_trades_info(&mut self, txid: &str, trades: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("txid", txid);
-        params.insert("trades", trades);
-        self.private_query("QueryTrades", &mut params)
+    pub fn query_trades_info(&self, txid: &str, trades: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.query_trades_info(txid, trades))
     }
+
     /// Input:
     ///
     /// ```json
@@ -667,11 +507,8 @@ impl KrakenApi {
     ///
     /// Note: Unless otherwise stated, costs, fees, prices, and volumes are in the asset pair's
     /// scale, not the currency's scale.
-    pub fn get_open_positions(&mut self, txid: &str, docalcs: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("txid", txid);
-        params.insert("docalcs", docalcs);
-        self.private_query("OpenPositions", &mut params)
+    pub fn get_open_positions(&self, txid: &str, docalcs: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_open_positions(txid, docalcs))
     }
 
     /// Input:
@@ -704,7 +541,7 @@ impl KrakenApi {
     ///     balance = resulting balance
     /// ```
     /// Note: Times given by ledger ids are more accurate than unix timestamps.
-    pub fn get_ledgers_info(&mut self,
+    pub fn get_ledgers_info(&self,
                             aclass: &str,
                             asset: &str,
                             type_ledger: &str,
@@ -712,14 +549,12 @@ impl KrakenApi {
                             end: &str,
                             ofs: &str)
                             -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        params.insert("type_ledger", type_ledger);
-        params.insert("start", start);
-        params.insert("end", end);
-        params.insert("ofs", ofs);
-        self.private_query("Ledgers", &mut params)
+        self.runtime.block_on(self.inner.get_ledgers_info(aclass,
+                                                          asset,
+                                                          type_ledger,
+                                                          start,
+                                                          end,
+                                                          ofs))
     }
 
     /// Input:
@@ -732,10 +567,8 @@ impl KrakenApi {
     /// ```json
     /// <ledger_id> = ledger info.  See Get ledgers info
     /// ```
-    pub fn query_ledgers(&mut self, id: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("id", id);
-        self.private_query("QueryLedgers", &mut params)
+    pub fn query_ledgers(&self, id: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.query_ledgers(id))
     }
 
     /// Input:
@@ -768,14 +601,10 @@ impl KrakenApi {
     /// Note: If an asset pair is on a maker/taker fee schedule, the taker side is given in "fees"
     /// and maker side in "fees_maker". For pairs not on maker/taker, they will only be given in
     /// "fees".
-    pub fn get_trade_volume(&mut self, pair: &str, fee_info: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("pair", pair);
-        params.insert("fee-info", fee_info);
-        self.private_query("TradeVolume", &mut params)
+    pub fn get_trade_volume(&self, pair: &str, fee_info: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_trade_volume(pair, fee_info))
     }
 
-    // TODO: add optional closing order
     /// Input:
     ///
     /// ```json
@@ -858,7 +687,8 @@ impl KrakenApi {
     /// close out your position.
     /// If you receive the error "EOrder:Trading agreement required", refer to your API key
     /// management page for further details.
-    pub fn add_standard_order(&mut self,
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_standard_order(&self,
                               pair: &str,
                               type_order: &str,
                               ordertype: &str,
@@ -872,20 +702,18 @@ impl KrakenApi {
                               userref: &str,
                               validate: &str)
                               -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("pair", pair);
-        params.insert("type", type_order);
-        params.insert("ordertype", ordertype);
-        params.insert("price", price);
-        params.insert("price2", price2);
-        params.insert("volume", volume);
-        params.insert("leverage", leverage);
-        params.insert("oflags", oflags);
-        params.insert("starttm", starttm);
-        params.insert("expiretm", expiretm);
-        params.insert("userref", userref);
-        params.insert("validate", validate);
-        self.private_query("AddOrder", &mut params)
+        self.runtime.block_on(self.inner.add_standard_order(pair,
+                                                            type_order,
+                                                            ordertype,
+                                                            price,
+                                                            price2,
+                                                            volume,
+                                                            leverage,
+                                                            oflags,
+                                                            starttm,
+                                                            expiretm,
+                                                            userref,
+                                                            validate))
     }
 
     /// Input:
@@ -900,10 +728,8 @@ impl KrakenApi {
     /// pending = if set, order(s) is/are pending cancellation
     /// ```
     /// Note: txid may be a user reference id.
-    pub fn cancel_open_order(&mut self, txid: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("txid", txid);
-        self.private_query("CancelOrder", &mut params)
+    pub fn cancel_open_order(&self, txid: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.cancel_open_order(txid))
     }
 
     /// Input:
@@ -921,11 +747,8 @@ impl KrakenApi {
     /// fee = amount of fees that will be paid
     /// address-setup-fee = whether or not method has an address setup fee (optional)
     /// ```
-    pub fn get_deposit_methods(&mut self, aclass: &str, asset: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        self.private_query("DepositMethods", &mut params)
+    pub fn get_deposit_methods(&self, aclass: &str, asset: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_deposit_methods(aclass, asset))
     }
 
     /// Input:
@@ -944,18 +767,13 @@ impl KrakenApi {
     /// expiretm = expiration time in unix timestamp, or 0 if not expiring
     /// new = whether or not address has ever been used
     /// ```
-    pub fn get_deposit_addresses(&mut self,
+    pub fn get_deposit_addresses(&self,
                                  aclass: &str,
                                  asset: &str,
                                  method: &str,
                                  new: &str)
                                  -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        params.insert("method", method);
-        params.insert("new", new);
-        self.private_query("DepositAddresses", &mut params)
+        self.runtime.block_on(self.inner.get_deposit_addresses(aclass, asset, method, new))
     }
 
     /// Input:
@@ -984,16 +802,12 @@ impl KrakenApi {
     ///     onhold = deposit is on hold pending review
     /// ```
     /// For information about the status, please refer to the IFEX financial transaction states.
-    pub fn get_status_of_recent_deposits(&mut self,
+    pub fn get_status_of_recent_deposits(&self,
                                          aclass: &str,
                                          asset: &str,
                                          method: &str)
                                          -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        params.insert("method", method);
-        self.private_query("DepositStatus", &mut params)
+        self.runtime.block_on(self.inner.get_status_of_recent_deposits(aclass, asset, method))
     }
 
     /// Input:
@@ -1012,18 +826,13 @@ impl KrakenApi {
     /// limit = maximum net amount that can be withdrawn right now
     /// fee = amount of fees that will be paid
     /// ```
-    pub fn get_withdrawal_information(&mut self,
+    pub fn get_withdrawal_information(&self,
                                       aclass: &str,
                                       asset: &str,
                                       key: &str,
                                       amount: &str)
                                       -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        params.insert("key", key);
-        params.insert("amount", amount);
-        self.private_query("WithdrawInfo", &mut params)
+        self.runtime.block_on(self.inner.get_withdrawal_information(aclass, asset, key, amount))
     }
 
     /// Input:
@@ -1040,18 +849,13 @@ impl KrakenApi {
     /// ```json
     /// refid = reference id
     /// ```
-    pub fn withdraw_funds(&mut self,
+    pub fn withdraw_funds(&self,
                           aclass: &str,
                           asset: &str,
                           key: &str,
                           amount: &str)
                           -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        params.insert("key", key);
-        params.insert("amount", amount);
-        self.private_query("Withdraw", &mut params)
+        self.runtime.block_on(self.inner.withdraw_funds(aclass, asset, key, amount))
     }
 
     /// Input:
@@ -1083,16 +887,12 @@ impl KrakenApi {
     ///     onhold = withdrawal is on hold pending review
     /// ```
     /// For information about the status, please refer to the IFEX financial transaction states.
-    pub fn get_status_of_recent_withdrawals(&mut self,
+    pub fn get_status_of_recent_withdrawals(&self,
                                             aclass: &str,
                                             asset: &str,
                                             method: &str)
                                             -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        params.insert("method", method);
-        self.private_query("WithdrawStatus", &mut params)
+        self.runtime.block_on(self.inner.get_status_of_recent_withdrawals(aclass, asset, method))
     }
 
     /// Result: returns a token to be used when authenticating with private websockets API:
@@ -1101,9 +901,8 @@ impl KrakenApi {
     /// expires = time in seconds when token expires
     /// token = the token to be used for authentication
     /// ```
-    pub fn get_websockets_token(&mut self) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        self.private_query("GetWebSocketsToken", &mut params)
+    pub fn get_websockets_token(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_websockets_token())
     }
 
     /// Input:
@@ -1122,56 +921,17 @@ impl KrakenApi {
     /// Note: Cancelation cannot be guaranteed. This will put in a cancelation request. Depending
     /// upon how far along the withdrawal process is, it may not be possible to cancel the
     /// withdrawal.
-    pub fn request_withdrawal_cancelation(&mut self,
+    pub fn request_withdrawal_cancelation(&self,
                                           aclass: &str,
                                           asset: &str,
                                           refid: &str)
                                           -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("aclass", aclass);
-        params.insert("asset", asset);
-        params.insert("refid", refid);
-        self.private_query("WithdrawCancel", &mut params)
+        self.runtime.block_on(self.inner.request_withdrawal_cancelation(aclass, asset, refid))
     }
 }
 
-#[cfg(test)]
-mod kraken_api_tests {
-    use super::*;
-
-    #[test]
-    fn should_block_or_not_block_when_enabled_or_disabled() {
-        let mut api = KrakenApi {
-            last_request: helpers::get_unix_timestamp_ms(),
-            api_key: "".to_string(),
-            api_secret: "".to_string(),
-            otp: None,
-            http_client: Client::new(),
-            burst: false,
-        };
-
-        let mut counter = 0;
-        loop {
-            api.set_burst(false);
-            let start = helpers::get_unix_timestamp_ms();
-            api.block_or_continue();
-            api.last_request = helpers::get_unix_timestamp_ms();
-
-            let difference = api.last_request - start;
-            assert!(difference >= 1999);
-            assert!(difference < 10000);
-
-
-            api.set_burst(true);
-            let start = helpers::get_unix_timestamp_ms();
-            api.block_or_continue();
-            api.last_request = helpers::get_unix_timestamp_ms();
-
-            let difference = api.last_request - start;
-            assert!(difference < 10);
-
-            counter = counter + 1;
-            if counter >= 3 { break; }
-        }
+impl fmt::Debug for KrakenApi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("KrakenApi").field(&self.inner).finish()
     }
 }
@@ -103,11 +103,7 @@ impl KrakenCreds {
 impl Credentials for KrakenCreds {
     /// Return a value from the credentials.
     fn get(&self, key: &str) -> Option<String> {
-        if let Some(res) = self.data.get(key) {
-            Some(res.clone())
-        } else {
-            None
-        }
+        self.data.get(key).cloned()
     }
 
     fn name(&self) -> String {
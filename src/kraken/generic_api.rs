@@ -2,33 +2,38 @@
 //! This a more convenient and safe way to deal with the exchange since methods return a Result<>
 //! but this generic API does not provide all the functionnality that Kraken offers.
 
-use crate::exchange::ExchangeApi;
+use serde_json::Value;
+use serde_json::value::Map;
+
+use async_trait::async_trait;
+
+use crate::exchange::{AsyncExchangeApi, Exchange, ExchangeApi};
 use crate::kraken::api::KrakenApi;
+use crate::kraken::async_api::AsyncKrakenApi;
 
 use crate::error::*;
 use crate::types::*;
 use crate::kraken::utils;
 use crate::helpers;
+use crate::normalization;
 
-impl ExchangeApi for KrakenApi {
-    fn ticker(&mut self, pair: Pair) -> Result<Ticker> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
+#[async_trait]
+impl AsyncExchangeApi for AsyncKrakenApi {
+    async fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        let pair_name = get_pair_name(self, &pair).await?;
 
-        let raw_response = self.get_ticker_information(pair_name)?;
+        let raw_response = self.get_ticker_information(pair_name).await?;
 
-        let result = utils::parse_result(&raw_response)?;
+        let result = Value::Object(utils::parse_result(&raw_response, "Ticker")?);
 
-        let price = helpers::from_json_bigdecimal(&result[*pair_name]["c"][0], "c")?;
-        let ask = helpers::from_json_bigdecimal(&result[*pair_name]["a"][0], "a")?;
-        let bid = helpers::from_json_bigdecimal(&result[*pair_name]["b"][0], "b")?;
-        let vol = helpers::from_json_bigdecimal(&result[*pair_name]["v"][0], "v")?;
+        let price = helpers::from_json_bigdecimal(&result[pair_name]["c"][0], "c")?;
+        let ask = helpers::from_json_bigdecimal(&result[pair_name]["a"][0], "a")?;
+        let bid = helpers::from_json_bigdecimal(&result[pair_name]["b"][0], "b")?;
+        let vol = helpers::from_json_bigdecimal(&result[pair_name]["v"][0], "v")?;
 
         Ok(Ticker {
                timestamp: helpers::get_unix_timestamp_ms(),
-               pair: pair,
+               pair,
                last_trade_price: price,
                lowest_ask: ask,
                highest_bid: bid,
@@ -37,33 +42,22 @@ impl ExchangeApi for KrakenApi {
 
     }
 
-    fn orderbook(&mut self, pair: Pair) -> Result<Orderbook> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
+    async fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        let pair_name = get_pair_name(self, &pair).await?;
 
-        let raw_response = self.get_order_book(pair_name, "1000")?; // 1000 entries max
+        let raw_response = self.get_order_book(pair_name, "1000").await?; // 1000 entries max
 
-        let result = utils::parse_result(&raw_response)?;
+        let result = Value::Object(utils::parse_result(&raw_response, "Depth")?);
 
         let mut ask_offers = Vec::new();
         let mut bid_offers = Vec::new();
 
-        let ask_array =
-            result[*pair_name]["asks"]
-                .as_array()
-                .ok_or_else(|| {
-                                ErrorKind::InvalidFieldFormat(format!("{}.asks",
-                                                                      result[*pair_name]))
-                            })?;
-        let bid_array =
-            result[*pair_name]["bids"]
-                .as_array()
-                .ok_or_else(|| {
-                                ErrorKind::InvalidFieldFormat(format!("{}.bids",
-                                                                      result[*pair_name]))
-                            })?;
+        let ask_array = result[pair_name]["asks"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("asks".to_string()))?;
+        let bid_array = result[pair_name]["bids"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("bids".to_string()))?;
 
         for ask in ask_array {
             let price = helpers::from_json_bigdecimal(&ask[0], "ask price")?;
@@ -81,91 +75,309 @@ impl ExchangeApi for KrakenApi {
 
         Ok(Orderbook {
                timestamp: helpers::get_unix_timestamp_ms(),
-               pair: pair,
+               pair,
                asks: ask_offers,
                bids: bid_offers,
            })
     }
 
-    fn add_order(&mut self,
-                 order_type: OrderType,
-                 pair: Pair,
-                 quantity: Volume,
-                 price: Option<Price>)
-                 -> Result<OrderInfo> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
+    async fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        let pair_name = get_pair_name(self, &pair).await?;
 
-        let direction = match order_type {
-            OrderType::BuyLimit | OrderType::BuyMarket => "buy",
-            OrderType::SellLimit | OrderType::SellMarket => "sell",
-        };
+        let since_id = since.as_ref().map_or("", |s| s);
+        let raw_response = self.get_recent_trades(pair_name, since_id).await?;
 
-        let order_type_str = match order_type {
-            OrderType::BuyLimit | OrderType::SellLimit => "limit",
-            OrderType::BuyMarket | OrderType::SellMarket => "market",
-        };
+        let result = Value::Object(utils::parse_result(&raw_response, "Trades")?);
+
+        let trade_array = result[pair_name]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(pair_name.to_string()))?;
 
-        let mut price_str = "".to_string();
-        if price.is_some() {
-            price_str = price.unwrap().to_string()
+        // "last" is the id to be used as since to get the next trades
+        let cursor = match result.get("last") {
+            Some(Value::String(last)) => Some(last.to_string()),
+            Some(Value::Number(last)) => Some(last.to_string()),
+            _ => since,
         };
 
-        let raw_response = self.add_standard_order(pair_name,
-                                                   direction,
-                                                   order_type_str,
-                                                   &price_str,
-                                                   "",
-                                                   &quantity.to_string(),
-                                                   "",
-                                                   "",
-                                                   "",
-                                                   "",
-                                                   "",
-                                                   "")?;
-
-        let result = utils::parse_result(&raw_response)?;
+        Ok(TradeHistory {
+               timestamp: helpers::get_unix_timestamp_ms(),
+               pair,
+               trades: utils::parse_trades(trade_array)?,
+               cursor,
+           })
+    }
+
+    async fn candles(&self,
+                     pair: Pair,
+                     interval: Interval,
+                     since: Option<i64>)
+                     -> Result<Vec<Candle>> {
+        let pair_name = get_pair_name(self, &pair).await?;
+        let minutes = utils::get_interval_minutes(interval)
+            .ok_or_else(|| ErrorKind::Unsupported(format!("{:?} candles", interval)))?;
+        let since = since.map_or("".to_string(), |since| (since / 1000).to_string());
+
+        let raw_response = self.get_ohlc_data(pair_name, &minutes, &since).await?;
+
+        let result = Value::Object(utils::parse_result(&raw_response, "OHLC")?);
+
+        let candle_array = result[pair_name]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(pair_name.to_string()))?;
+
+        utils::parse_candles(candle_array)
+    }
+
+    async fn markets(&self) -> Result<Markets> {
+        if let Some(ref markets) = *self.markets.read().unwrap() {
+            return Ok(markets.clone());
+        }
+
+        let raw_response = self.get_tradable_asset_pairs("", "").await?;
+
+        let result = utils::parse_result(&raw_response, "AssetPairs")?;
+
+        let markets = utils::parse_markets(&result)?;
+        *self.markets.write().unwrap() = Some(markets.clone());
+
+        Ok(markets)
+    }
+
+    async fn validate_order(&self,
+                            order_type: OrderType,
+                            pair: Pair,
+                            quantity: Volume,
+                            price: Option<Price>)
+                            -> Result<(Volume, Option<Price>)> {
+        let markets = self.markets().await?;
+        let market = markets.get(&pair).ok_or(ErrorKind::PairUnsupported)?;
+        let (quantity, price) = market.check_order(order_type, quantity, price)?;
+
+        // Server-side dry run: Kraken checks the order but does not place it
+        let raw_response = submit_order(self, order_type, pair, &quantity, &price, true).await?;
+        utils::parse_result(&raw_response, "AddOrder")?;
+
+        Ok((quantity, price))
+    }
+
+    async fn add_order(&self,
+                       order_type: OrderType,
+                       pair: Pair,
+                       quantity: Volume,
+                       price: Option<Price>)
+                       -> Result<OrderInfo> {
+        let raw_response = submit_order(self, order_type, pair, &quantity, &price, false).await?;
+
+        let result = Value::Object(utils::parse_result(&raw_response, "AddOrder")?);
 
         let mut txids = Vec::new();
 
-        let list_id =
-            result["txid"]
-                .as_array()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["txid"])))?;
+        let list_id = result["txid"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("txid".to_string()))?;
 
         for id in list_id {
             txids.push(id.as_str()
-                           .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", id)))?
+                           .ok_or_else(|| ErrorKind::InvalidFieldFormat("txid".to_string()))?
                            .to_string());
         }
 
         Ok(OrderInfo {
                timestamp: helpers::get_unix_timestamp_ms(),
                identifier: txids,
+               pair,
+               order_type,
+               quantity,
+               price,
+               fills: Vec::new(),
            })
     }
 
-    fn balances(&mut self) -> Result<Balances> {
-        let raw_response = self.get_account_balance()?;
-        let result = utils::parse_result(&raw_response)?;
+    async fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        for txid in &order.identifier {
+            let raw_response = self.cancel_open_order(txid).await?;
+            utils::parse_result(&raw_response, "CancelOrder")?;
+        }
 
-        let mut balances = Balances::new();
+        Ok(())
+    }
+
+    async fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        let raw_response = self.get_open_orders("", "").await?;
+        let result = Value::Object(utils::parse_result(&raw_response, "OpenOrders")?);
+
+        let open = result["open"]
+            .as_object()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("open".to_string()))?;
 
-        for (key, val) in result.iter() {
-            let currency = utils::get_currency_enum(key);
+        let mut orders = Vec::new();
 
-            match currency {
-                Some(c) => {
-                    let amount = helpers::from_json_bigdecimal(&val, "amount")?;
+        for (txid, order) in open {
+            let altname = helpers::get_json_string(&order["descr"], "pair")?;
+            let order_pair = match get_pair_from_altname(self, altname).await? {
+                Some(order_pair) => order_pair,
+                None => continue,
+            };
 
-                    balances.insert(c, amount);
-                },
-                _ => ()
+            if pair.is_some() && pair != Some(order_pair) {
+                continue;
             }
+
+            orders.push(utils::parse_order(txid, order_pair, order)?);
+        }
+
+        Ok(orders)
+    }
+
+    async fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        let txid = order.identifier.first().ok_or(ErrorKind::OrderNotFound)?;
+
+        let raw_response = self.query_orders_info("", "", txid).await?;
+        let result = utils::parse_result(&raw_response, "QueryOrders")?;
+
+        let order = result.get(txid).ok_or(ErrorKind::OrderNotFound)?;
+
+        let altname = helpers::get_json_string(&order["descr"], "pair")?;
+        let pair = match get_pair_from_altname(self, altname).await? {
+            Some(pair) => pair,
+            None => return Err(ErrorKind::PairUnsupported.into()),
+        };
+
+        utils::parse_order(txid, pair, order)
+    }
+
+    async fn balances(&self) -> Result<Balances> {
+        let raw_response = self.get_account_balance().await?;
+        let result = utils::parse_result(&raw_response, "Balance")?;
+
+        let mut balances = Balances::new();
+
+        // Assets which are not listed in the normalization module are returned as new currencies
+        for (name, val) in result.iter() {
+            let currency = normalization::get_currency(Exchange::Kraken, name)
+                .ok_or_else(|| ErrorKind::InvalidFieldValue("asset".to_string()))?;
+            let amount = helpers::from_json_bigdecimal(val, name)?;
+
+            balances.insert(currency, amount);
         }
 
         Ok(balances)
     }
 }
+
+/// Send an order to `AddOrder`. If `validate` is true, Kraken only validates the order.
+async fn submit_order(api: &AsyncKrakenApi,
+                      order_type: OrderType,
+                      pair: Pair,
+                      quantity: &Volume,
+                      price: &Option<Price>,
+                      validate: bool)
+                      -> Result<Map<String, Value>> {
+    let pair_name = get_pair_name(api, &pair).await?;
+
+    let direction = match order_type {
+        OrderType::BuyLimit | OrderType::BuyMarket => "buy",
+        OrderType::SellLimit | OrderType::SellMarket => "sell",
+    };
+
+    let order_type_str = match order_type {
+        OrderType::BuyLimit | OrderType::SellLimit => "limit",
+        OrderType::BuyMarket | OrderType::SellMarket => "market",
+    };
+
+    let mut price_str = "".to_string();
+    if let Some(ref price) = *price {
+        price_str = price.to_string()
+    };
+
+    api.add_standard_order(pair_name,
+                           direction,
+                           order_type_str,
+                           &price_str,
+                           "",
+                           &quantity.to_string(),
+                           "",
+                           "",
+                           "",
+                           "",
+                           "",
+                           if validate { "true" } else { "" }).await
+}
+
+/// Return the name used by Kraken for the pair. The pairs listed since the last update of
+/// Coinnect are only known once the markets listed by Kraken have been loaded.
+async fn get_pair_name(api: &AsyncKrakenApi, pair: &Pair) -> Result<&'static str> {
+    helpers::find_pair(&api.markets, || api.markets(), || utils::get_pair_string(pair))
+        .await?
+        .ok_or_else(|| ErrorKind::PairUnsupported.into())
+}
+
+/// Return the Pair of an alternate name used by Kraken, loading the markets listed by Kraken if
+/// the name is not known yet.
+async fn get_pair_from_altname(api: &AsyncKrakenApi, altname: &str) -> Result<Option<Pair>> {
+    helpers::find_pair(&api.markets,
+                       || api.markets(),
+                       || utils::get_pair_enum_from_altname(altname))
+        .await
+}
+
+impl ExchangeApi for KrakenApi {
+    fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        self.runtime.block_on(self.inner.ticker(pair))
+    }
+
+    fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        self.runtime.block_on(self.inner.orderbook(pair))
+    }
+
+    fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        self.runtime.block_on(self.inner.trades(pair, since))
+    }
+
+    fn candles(&self,
+               pair: Pair,
+               interval: Interval,
+               since: Option<i64>)
+               -> Result<Vec<Candle>> {
+        self.runtime.block_on(self.inner.candles(pair, interval, since))
+    }
+
+    fn markets(&self) -> Result<Markets> {
+        self.runtime.block_on(self.inner.markets())
+    }
+
+    fn validate_order(&self,
+                      order_type: OrderType,
+                      pair: Pair,
+                      quantity: Volume,
+                      price: Option<Price>)
+                      -> Result<(Volume, Option<Price>)> {
+        self.runtime.block_on(self.inner.validate_order(order_type, pair, quantity, price))
+    }
+
+    fn add_order(&self,
+                 order_type: OrderType,
+                 pair: Pair,
+                 quantity: Volume,
+                 price: Option<Price>)
+                 -> Result<OrderInfo> {
+        self.runtime.block_on(self.inner.add_order(order_type, pair, quantity, price))
+    }
+
+    fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        self.runtime.block_on(self.inner.cancel_order(order))
+    }
+
+    fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        self.runtime.block_on(self.inner.open_orders(pair))
+    }
+
+    fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        self.runtime.block_on(self.inner.order_status(order))
+    }
+
+    fn balances(&self) -> Result<Balances> {
+        self.runtime.block_on(self.inner.balances())
+    }
+}
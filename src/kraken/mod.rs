@@ -2,9 +2,14 @@
 //! See examples for more informations.
 
 pub mod api;
+pub mod async_api;
 pub mod generic_api;
 pub mod credentials;
 pub mod utils;
+pub mod responses;
+pub mod typed_api;
 
 pub use self::credentials::KrakenCreds;
 pub use self::api::KrakenApi;
+pub use self::async_api::AsyncKrakenApi;
+pub use self::typed_api::{AsyncKrakenTypedApi, KrakenTypedApi};
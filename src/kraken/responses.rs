@@ -0,0 +1,538 @@
+//! Typed results of the Kraken endpoints, returned by the `typed` API (see
+//! `AsyncKrakenApi::typed`).
+//!
+//! The fields keep the names documented by Kraken when they are explicit, the one-letter fields
+//! (ie. "c" in the ticker) are renamed. Prices and amounts are `BigDecimal` parsed from the text
+//! sent by Kraken; times are UNIX timestamps in seconds, with a fractional part for most of the
+//! private endpoints. The raw responses are still returned by the methods of `AsyncKrakenApi`.
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_json::value::Map;
+
+use std::collections::HashMap;
+
+use crate::error::*;
+use crate::helpers;
+
+/// Result of `Time`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ServerTime {
+    pub unixtime: i64,
+    pub rfc1123: String,
+}
+
+/// Result of `Assets`, by asset name.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AssetInfo {
+    pub altname: String,
+    pub aclass: String,
+    pub decimals: u32,
+    pub display_decimals: u32,
+}
+
+/// Result of `AssetPairs`, by pair name. The fields which are not part of the `info` asked
+/// for are None or empty.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AssetPair {
+    #[serde(default)]
+    pub altname: Option<String>,
+    #[serde(default)]
+    pub wsname: Option<String>,
+    #[serde(default)]
+    pub aclass_base: Option<String>,
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub aclass_quote: Option<String>,
+    #[serde(default)]
+    pub quote: Option<String>,
+    #[serde(default)]
+    pub pair_decimals: Option<u32>,
+    #[serde(default)]
+    pub lot_decimals: Option<u32>,
+    #[serde(default)]
+    pub lot_multiplier: Option<u32>,
+    #[serde(default)]
+    pub leverage_buy: Vec<u32>,
+    #[serde(default)]
+    pub leverage_sell: Vec<u32>,
+    #[serde(default)]
+    pub fees: Vec<FeeTier>,
+    #[serde(default)]
+    pub fees_maker: Vec<FeeTier>,
+    #[serde(default)]
+    pub fee_volume_currency: Option<String>,
+    #[serde(default)]
+    pub margin_call: Option<u32>,
+    #[serde(default)]
+    pub margin_stop: Option<u32>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub ordermin: Option<BigDecimal>,
+}
+
+/// A fee schedule entry: the fee (in percent) applied from the volume.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FeeTier {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub volume: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+}
+
+/// Result of `Ticker`, by pair name.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TickerInfo {
+    #[serde(rename = "a")]
+    pub ask: TickerLevel,
+    #[serde(rename = "b")]
+    pub bid: TickerLevel,
+    #[serde(rename = "c")]
+    pub last_trade: LastTrade,
+    #[serde(rename = "v")]
+    pub volume: DecimalWindow,
+    #[serde(rename = "p")]
+    pub vwap: DecimalWindow,
+    #[serde(rename = "t")]
+    pub trades: CountWindow,
+    #[serde(rename = "l")]
+    pub low: DecimalWindow,
+    #[serde(rename = "h")]
+    pub high: DecimalWindow,
+    #[serde(rename = "o", deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub opening_price: BigDecimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TickerLevel {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub whole_lot_volume: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub lot_volume: BigDecimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LastTrade {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub lot_volume: BigDecimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DecimalWindow {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub today: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub last_24_hours: BigDecimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CountWindow {
+    pub today: u64,
+    pub last_24_hours: u64,
+}
+
+/// Result of the endpoints returning the data of a pair since a given id (`OHLC`, `Trades`
+/// and `Spread`). `last` is the id to use as `since` to poll for new data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PairHistory<T> {
+    pub pair: String,
+    pub entries: Vec<T>,
+    pub last: String,
+}
+
+impl<T: DeserializeOwned> PairHistory<T> {
+    /// Split the result into the "last" id and the entries of the (only) pair.
+    pub(crate) fn from_result(mut result: Map<String, Value>) -> Result<PairHistory<T>> {
+        let last = result
+            .remove("last")
+            .ok_or_else(|| ErrorKind::MissingField("last".to_string()))?;
+        let last = helpers::deserialize_id(&last)
+            .chain_err(|| ErrorKind::InvalidFieldFormat("last".to_string()))?;
+
+        let (pair, entries) = result
+            .into_iter()
+            .next()
+            .ok_or_else(|| ErrorKind::MissingField("pair".to_string()))?;
+        let entries = Vec::<T>::deserialize(entries).chain_err(|| ErrorKind::BadParse)?;
+
+        Ok(PairHistory {
+               pair,
+               entries,
+               last,
+           })
+    }
+}
+
+/// An entry of `OHLC`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Candle {
+    pub time: i64,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub open: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub high: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub low: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub close: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub vwap: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub volume: BigDecimal,
+    pub count: u64,
+}
+
+/// Result of `Depth`, by pair name.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderBook {
+    pub asks: Vec<OrderBookEntry>,
+    pub bids: Vec<OrderBookEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderBookEntry {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub volume: BigDecimal,
+    pub timestamp: i64,
+}
+
+/// An entry of `Trades`. `side` is "b" (buy) or "s" (sell), `order_type` "m" (market) or "l"
+/// (limit). The trade id is only sent by recent versions of the API.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RecentTrade {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub volume: BigDecimal,
+    pub time: f64,
+    pub side: String,
+    pub order_type: String,
+    pub misc: String,
+    #[serde(default)]
+    pub trade_id: Option<u64>,
+}
+
+/// An entry of `Spread`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SpreadEntry {
+    pub time: i64,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub bid: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub ask: BigDecimal,
+}
+
+/// Result of `TradeBalance`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TradeBalance {
+    #[serde(rename = "eb", deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub equivalent_balance: BigDecimal,
+    #[serde(rename = "tb", deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub trade_balance: BigDecimal,
+    #[serde(rename = "m", deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub margin: BigDecimal,
+    #[serde(rename = "n", deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub unrealized_net_profit: BigDecimal,
+    #[serde(rename = "c", deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub cost_basis: BigDecimal,
+    #[serde(rename = "v", deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub floating_valuation: BigDecimal,
+    #[serde(rename = "e", deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub equity: BigDecimal,
+    #[serde(rename = "mf", deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub free_margin: BigDecimal,
+    #[serde(rename = "ml", default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub margin_level: Option<BigDecimal>,
+}
+
+/// Result of `OpenOrders`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OpenOrders {
+    pub open: HashMap<String, OrderInfo>,
+}
+
+/// Result of `ClosedOrders`. `count` is the number of orders matching the criteria.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ClosedOrders {
+    pub closed: HashMap<String, OrderInfo>,
+    pub count: u64,
+}
+
+/// An order, by transaction id in `OpenOrders`, `ClosedOrders` and `QueryOrders`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderInfo {
+    #[serde(default)]
+    pub refid: Option<String>,
+    #[serde(default)]
+    pub userref: Option<i64>,
+    pub status: String,
+    pub opentm: f64,
+    #[serde(default)]
+    pub starttm: Option<f64>,
+    #[serde(default)]
+    pub expiretm: Option<f64>,
+    #[serde(default)]
+    pub closetm: Option<f64>,
+    pub descr: OrderDescription,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub vol: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub vol_exec: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub cost: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub stopprice: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub limitprice: Option<BigDecimal>,
+    pub misc: String,
+    pub oflags: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub trades: Vec<String>,
+}
+
+/// The description of an order. `side` is "buy" or "sell".
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderDescription {
+    pub pair: String,
+    #[serde(rename = "type")]
+    pub side: String,
+    pub ordertype: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price2: BigDecimal,
+    pub leverage: String,
+    pub order: String,
+    #[serde(default)]
+    pub close: Option<String>,
+}
+
+/// Result of `TradesHistory`. `count` is the number of trades matching the criteria.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TradesHistory {
+    pub trades: HashMap<String, TradeInfo>,
+    pub count: u64,
+}
+
+/// A trade of the account, by transaction id in `TradesHistory` and `QueryTrades`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TradeInfo {
+    pub ordertxid: String,
+    #[serde(default)]
+    pub postxid: Option<String>,
+    pub pair: String,
+    pub time: f64,
+    #[serde(rename = "type")]
+    pub side: String,
+    pub ordertype: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub cost: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub vol: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub margin: BigDecimal,
+    pub misc: String,
+    #[serde(default)]
+    pub posstatus: Option<String>,
+}
+
+/// An open position, by transaction id in `OpenPositions`. `value` and `net` are only sent
+/// when the calculations are asked for.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PositionInfo {
+    pub ordertxid: String,
+    #[serde(default)]
+    pub posstatus: Option<String>,
+    pub pair: String,
+    pub time: f64,
+    #[serde(rename = "type")]
+    pub side: String,
+    pub ordertype: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub cost: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub vol: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub vol_closed: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub margin: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub value: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub net: Option<BigDecimal>,
+    pub misc: String,
+    pub oflags: String,
+    #[serde(default)]
+    pub terms: Option<String>,
+    #[serde(default)]
+    pub rollovertm: Option<String>,
+}
+
+/// Result of `Ledgers`. `count` is the number of entries matching the criteria.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Ledgers {
+    pub ledger: HashMap<String, LedgerEntry>,
+    pub count: u64,
+}
+
+/// A ledger entry, by ledger id in `Ledgers` and `QueryLedgers`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LedgerEntry {
+    pub refid: String,
+    pub time: f64,
+    #[serde(rename = "type")]
+    pub ledger_type: String,
+    #[serde(default)]
+    pub subtype: Option<String>,
+    pub aclass: String,
+    pub asset: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub balance: BigDecimal,
+}
+
+/// Result of `TradeVolume`. The fees are only sent when asked for, by pair name.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TradeVolume {
+    pub currency: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub volume: BigDecimal,
+    #[serde(default)]
+    pub fees: HashMap<String, FeeInfo>,
+    #[serde(default)]
+    pub fees_maker: HashMap<String, FeeInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FeeInfo {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub minfee: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub maxfee: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub nextfee: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub nextvolume: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub tiervolume: Option<BigDecimal>,
+}
+
+/// Result of `AddOrder`. No transaction id is returned when the order is only validated.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AddedOrder {
+    pub descr: AddedOrderDescription,
+    #[serde(default)]
+    pub txid: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AddedOrderDescription {
+    pub order: String,
+    #[serde(default)]
+    pub close: Option<String>,
+}
+
+/// Result of `CancelOrder`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CanceledOrders {
+    pub count: u64,
+    #[serde(default)]
+    pub pending: Option<bool>,
+}
+
+/// An entry of `DepositMethods`. `limit` is None when there is no limit.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DepositMethod {
+    pub method: String,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub limit: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub fee: Option<BigDecimal>,
+    #[serde(rename = "address-setup-fee",
+            default,
+            deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub address_setup_fee: Option<BigDecimal>,
+    #[serde(rename = "gen-address", default)]
+    pub gen_address: Option<bool>,
+}
+
+/// An entry of `DepositAddresses`. `expiretm` is 0 if the address does not expire.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DepositAddress {
+    pub address: String,
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub expiretm: String,
+    #[serde(default)]
+    pub new: Option<bool>,
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+/// An entry of `DepositStatus` and `WithdrawStatus`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TransferStatus {
+    pub method: String,
+    pub aclass: String,
+    pub asset: String,
+    pub refid: String,
+    pub txid: String,
+    pub info: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub fee: Option<BigDecimal>,
+    pub time: i64,
+    pub status: String,
+    #[serde(rename = "status-prop", default)]
+    pub status_prop: Option<String>,
+}
+
+/// Result of `WithdrawInfo`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WithdrawalInfo {
+    pub method: String,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub limit: Option<BigDecimal>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+}
+
+/// Result of `Withdraw`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Withdrawal {
+    pub refid: String,
+}
+
+/// Result of `GetWebSocketsToken`. The token expires after `expires` seconds if it is not used.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WebSocketsToken {
+    pub token: String,
+    pub expires: u64,
+}
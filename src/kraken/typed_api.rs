@@ -0,0 +1,542 @@
+//! Use this module to get the results of the Kraken endpoints as typed structs (see the
+//! `responses` module) instead of JSON maps.
+//!
+//! The typed API is a view of a client returned by `AsyncKrakenApi::typed` or
+//! `KrakenApi::typed`. Its methods have the names and the parameters of the raw methods, which
+//! still return the raw responses:
+//!
+//! ```no_run
+//! use coinnect::kraken::{KrakenApi, KrakenCreds};
+//!
+//! let api = KrakenApi::new(KrakenCreds::new("", "", "")).unwrap();
+//! let tickers = api.typed().get_ticker_information("XXBTZEUR").unwrap();
+//! println!("{}", tickers["XXBTZEUR"].last_trade.price);
+//! ```
+
+use bigdecimal::BigDecimal;
+
+use std::collections::HashMap;
+
+use crate::error::*;
+use crate::helpers;
+use crate::kraken::{AsyncKrakenApi, KrakenApi};
+use crate::kraken::responses::*;
+use crate::kraken::utils;
+
+/// Typed view of an `AsyncKrakenApi`.
+#[derive(Debug, Clone, Copy)]
+pub struct AsyncKrakenTypedApi<'a> {
+    api: &'a AsyncKrakenApi,
+}
+
+impl<'a> AsyncKrakenTypedApi<'a> {
+    pub fn new(api: &'a AsyncKrakenApi) -> AsyncKrakenTypedApi<'a> {
+        AsyncKrakenTypedApi { api }
+    }
+
+    pub async fn get_server_time(&self) -> Result<ServerTime> {
+        let response = self.api.get_server_time().await?;
+        utils::parse_response(&response, "Time")
+    }
+
+    pub async fn get_asset_info(&self,
+                                info: &str,
+                                aclass: &str,
+                                asset: &str)
+                                -> Result<HashMap<String, AssetInfo>> {
+        let response = self.api.get_asset_info(info, aclass, asset).await?;
+        utils::parse_response(&response, "Assets")
+    }
+
+    pub async fn get_tradable_asset_pairs(&self,
+                                          info: &str,
+                                          pair: &str)
+                                          -> Result<HashMap<String, AssetPair>> {
+        let response = self.api.get_tradable_asset_pairs(info, pair).await?;
+        utils::parse_response(&response, "AssetPairs")
+    }
+
+    pub async fn get_ticker_information(&self, pair: &str) -> Result<HashMap<String, TickerInfo>> {
+        let response = self.api.get_ticker_information(pair).await?;
+        utils::parse_response(&response, "Ticker")
+    }
+
+    pub async fn get_ohlc_data(&self,
+                               pair: &str,
+                               interval: &str,
+                               since: &str)
+                               -> Result<PairHistory<Candle>> {
+        let response = self.api.get_ohlc_data(pair, interval, since).await?;
+        PairHistory::from_result(utils::parse_result(&response, "OHLC")?)
+    }
+
+    pub async fn get_order_book(&self,
+                                pair: &str,
+                                count: &str)
+                                -> Result<HashMap<String, OrderBook>> {
+        let response = self.api.get_order_book(pair, count).await?;
+        utils::parse_response(&response, "Depth")
+    }
+
+    pub async fn get_recent_trades(&self,
+                                   pair: &str,
+                                   since: &str)
+                                   -> Result<PairHistory<RecentTrade>> {
+        let response = self.api.get_recent_trades(pair, since).await?;
+        PairHistory::from_result(utils::parse_result(&response, "Trades")?)
+    }
+
+    pub async fn get_recent_spread_data(&self,
+                                        pair: &str,
+                                        since: &str)
+                                        -> Result<PairHistory<SpreadEntry>> {
+        let response = self.api.get_recent_spread_data(pair, since).await?;
+        PairHistory::from_result(utils::parse_result(&response, "Spread")?)
+    }
+
+    /// Return the balance of each asset, by asset name.
+    pub async fn get_account_balance(&self) -> Result<HashMap<String, BigDecimal>> {
+        let response = self.api.get_account_balance().await?;
+        let result = utils::parse_result(&response, "Balance")?;
+
+        let mut balances = HashMap::new();
+        for (asset, amount) in result {
+            let amount = helpers::from_json_bigdecimal(&amount, &asset)?;
+            balances.insert(asset, amount);
+        }
+        Ok(balances)
+    }
+
+    pub async fn get_trade_balance(&self, aclass: &str, asset: &str) -> Result<TradeBalance> {
+        let response = self.api.get_trade_balance(aclass, asset).await?;
+        utils::parse_response(&response, "TradeBalance")
+    }
+
+    pub async fn get_open_orders(&self, trades: &str, userref: &str) -> Result<OpenOrders> {
+        let response = self.api.get_open_orders(trades, userref).await?;
+        utils::parse_response(&response, "OpenOrders")
+    }
+
+    pub async fn get_closed_orders(&self,
+                                   trades: &str,
+                                   userref: &str,
+                                   start: &str,
+                                   end: &str,
+                                   ofs: &str,
+                                   closetime: &str)
+                                   -> Result<ClosedOrders> {
+        let response = self.api
+            .get_closed_orders(trades, userref, start, end, ofs, closetime)
+            .await?;
+        utils::parse_response(&response, "ClosedOrders")
+    }
+
+    pub async fn query_orders_info(&self,
+                                   trades: &str,
+                                   userref: &str,
+                                   txid: &str)
+                                   -> Result<HashMap<String, OrderInfo>> {
+        let response = self.api.query_orders_info(trades, userref, txid).await?;
+        utils::parse_response(&response, "QueryOrders")
+    }
+
+    pub async fn get_trades_history(&self,
+                                    type_trade: &str,
+                                    trades: &str,
+                                    start: &str,
+                                    end: &str,
+                                    ofs: &str)
+                                    -> Result<TradesHistory> {
+        let response = self.api
+            .get_trades_history(type_trade, trades, start, end, ofs)
+            .await?;
+        utils::parse_response(&response, "TradesHistory")
+    }
+
+    pub async fn query_trades_info(&self,
+                                   txid: &str,
+                                   trades: &str)
+                                   -> Result<HashMap<String, TradeInfo>> {
+        let response = self.api.query_trades_info(txid, trades).await?;
+        utils::parse_response(&response, "QueryTrades")
+    }
+
+    pub async fn get_open_positions(&self,
+                                    txid: &str,
+                                    docalcs: &str)
+                                    -> Result<HashMap<String, PositionInfo>> {
+        let response = self.api.get_open_positions(txid, docalcs).await?;
+        utils::parse_response(&response, "OpenPositions")
+    }
+
+    pub async fn get_ledgers_info(&self,
+                                  aclass: &str,
+                                  asset: &str,
+                                  type_ledger: &str,
+                                  start: &str,
+                                  end: &str,
+                                  ofs: &str)
+                                  -> Result<Ledgers> {
+        let response = self.api
+            .get_ledgers_info(aclass, asset, type_ledger, start, end, ofs)
+            .await?;
+        utils::parse_response(&response, "Ledgers")
+    }
+
+    pub async fn query_ledgers(&self, id: &str) -> Result<HashMap<String, LedgerEntry>> {
+        let response = self.api.query_ledgers(id).await?;
+        utils::parse_response(&response, "QueryLedgers")
+    }
+
+    pub async fn get_trade_volume(&self, pair: &str, fee_info: &str) -> Result<TradeVolume> {
+        let response = self.api.get_trade_volume(pair, fee_info).await?;
+        utils::parse_response(&response, "TradeVolume")
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn add_standard_order(&self,
+                                    pair: &str,
+                                    type_order: &str,
+                                    ordertype: &str,
+                                    price: &str,
+                                    price2: &str,
+                                    volume: &str,
+                                    leverage: &str,
+                                    oflags: &str,
+                                    starttm: &str,
+                                    expiretm: &str,
+                                    userref: &str,
+                                    validate: &str)
+                                    -> Result<AddedOrder> {
+        let response = self.api
+            .add_standard_order(pair, type_order, ordertype, price, price2, volume, leverage,
+                                oflags, starttm, expiretm, userref, validate)
+            .await?;
+        utils::parse_response(&response, "AddOrder")
+    }
+
+    pub async fn cancel_open_order(&self, txid: &str) -> Result<CanceledOrders> {
+        let response = self.api.cancel_open_order(txid).await?;
+        utils::parse_response(&response, "CancelOrder")
+    }
+
+    pub async fn get_deposit_methods(&self,
+                                     aclass: &str,
+                                     asset: &str)
+                                     -> Result<Vec<DepositMethod>> {
+        let response = self.api.get_deposit_methods(aclass, asset).await?;
+        utils::parse_response(&response, "DepositMethods")
+    }
+
+    pub async fn get_deposit_addresses(&self,
+                                       aclass: &str,
+                                       asset: &str,
+                                       method: &str,
+                                       new: &str)
+                                       -> Result<Vec<DepositAddress>> {
+        let response = self.api.get_deposit_addresses(aclass, asset, method, new).await?;
+        utils::parse_response(&response, "DepositAddresses")
+    }
+
+    pub async fn get_status_of_recent_deposits(&self,
+                                               aclass: &str,
+                                               asset: &str,
+                                               method: &str)
+                                               -> Result<Vec<TransferStatus>> {
+        let response = self.api
+            .get_status_of_recent_deposits(aclass, asset, method)
+            .await?;
+        utils::parse_response(&response, "DepositStatus")
+    }
+
+    pub async fn get_withdrawal_information(&self,
+                                            aclass: &str,
+                                            asset: &str,
+                                            key: &str,
+                                            amount: &str)
+                                            -> Result<WithdrawalInfo> {
+        let response = self.api
+            .get_withdrawal_information(aclass, asset, key, amount)
+            .await?;
+        utils::parse_response(&response, "WithdrawInfo")
+    }
+
+    pub async fn withdraw_funds(&self,
+                                aclass: &str,
+                                asset: &str,
+                                key: &str,
+                                amount: &str)
+                                -> Result<Withdrawal> {
+        let response = self.api.withdraw_funds(aclass, asset, key, amount).await?;
+        utils::parse_response(&response, "Withdraw")
+    }
+
+    pub async fn get_status_of_recent_withdrawals(&self,
+                                                  aclass: &str,
+                                                  asset: &str,
+                                                  method: &str)
+                                                  -> Result<Vec<TransferStatus>> {
+        let response = self.api
+            .get_status_of_recent_withdrawals(aclass, asset, method)
+            .await?;
+        utils::parse_response(&response, "WithdrawStatus")
+    }
+
+    pub async fn get_websockets_token(&self) -> Result<WebSocketsToken> {
+        let response = self.api.get_websockets_token().await?;
+        utils::parse_response(&response, "GetWebSocketsToken")
+    }
+
+    /// Return true if the cancelation has been accepted.
+    pub async fn request_withdrawal_cancelation(&self,
+                                                aclass: &str,
+                                                asset: &str,
+                                                refid: &str)
+                                                -> Result<bool> {
+        let response = self.api
+            .request_withdrawal_cancelation(aclass, asset, refid)
+            .await?;
+        utils::parse_response(&response, "WithdrawCancel")
+    }
+}
+
+/// Typed view of a `KrakenApi`. Every call runs the `AsyncKrakenTypedApi` method of the same
+/// name to completion on the runtime of the client.
+#[derive(Debug, Clone, Copy)]
+pub struct KrakenTypedApi<'a> {
+    api: &'a KrakenApi,
+}
+
+impl<'a> KrakenTypedApi<'a> {
+    pub fn new(api: &'a KrakenApi) -> KrakenTypedApi<'a> {
+        KrakenTypedApi { api }
+    }
+
+    fn inner(&self) -> AsyncKrakenTypedApi<'a> {
+        self.api.inner.typed()
+    }
+
+    pub fn get_server_time(&self) -> Result<ServerTime> {
+        self.api.runtime.block_on(self.inner().get_server_time())
+    }
+
+    pub fn get_asset_info(&self,
+                          info: &str,
+                          aclass: &str,
+                          asset: &str)
+                          -> Result<HashMap<String, AssetInfo>> {
+        self.api.runtime.block_on(self.inner().get_asset_info(info, aclass, asset))
+    }
+
+    pub fn get_tradable_asset_pairs(&self,
+                                    info: &str,
+                                    pair: &str)
+                                    -> Result<HashMap<String, AssetPair>> {
+        self.api.runtime.block_on(self.inner().get_tradable_asset_pairs(info, pair))
+    }
+
+    pub fn get_ticker_information(&self, pair: &str) -> Result<HashMap<String, TickerInfo>> {
+        self.api.runtime.block_on(self.inner().get_ticker_information(pair))
+    }
+
+    pub fn get_ohlc_data(&self,
+                         pair: &str,
+                         interval: &str,
+                         since: &str)
+                         -> Result<PairHistory<Candle>> {
+        self.api.runtime.block_on(self.inner().get_ohlc_data(pair, interval, since))
+    }
+
+    pub fn get_order_book(&self, pair: &str, count: &str) -> Result<HashMap<String, OrderBook>> {
+        self.api.runtime.block_on(self.inner().get_order_book(pair, count))
+    }
+
+    pub fn get_recent_trades(&self, pair: &str, since: &str) -> Result<PairHistory<RecentTrade>> {
+        self.api.runtime.block_on(self.inner().get_recent_trades(pair, since))
+    }
+
+    pub fn get_recent_spread_data(&self,
+                                  pair: &str,
+                                  since: &str)
+                                  -> Result<PairHistory<SpreadEntry>> {
+        self.api.runtime.block_on(self.inner().get_recent_spread_data(pair, since))
+    }
+
+    /// Return the balance of each asset, by asset name.
+    pub fn get_account_balance(&self) -> Result<HashMap<String, BigDecimal>> {
+        self.api.runtime.block_on(self.inner().get_account_balance())
+    }
+
+    pub fn get_trade_balance(&self, aclass: &str, asset: &str) -> Result<TradeBalance> {
+        self.api.runtime.block_on(self.inner().get_trade_balance(aclass, asset))
+    }
+
+    pub fn get_open_orders(&self, trades: &str, userref: &str) -> Result<OpenOrders> {
+        self.api.runtime.block_on(self.inner().get_open_orders(trades, userref))
+    }
+
+    pub fn get_closed_orders(&self,
+                             trades: &str,
+                             userref: &str,
+                             start: &str,
+                             end: &str,
+                             ofs: &str,
+                             closetime: &str)
+                             -> Result<ClosedOrders> {
+        self.api
+            .runtime
+            .block_on(self.inner().get_closed_orders(trades, userref, start, end, ofs, closetime))
+    }
+
+    pub fn query_orders_info(&self,
+                             trades: &str,
+                             userref: &str,
+                             txid: &str)
+                             -> Result<HashMap<String, OrderInfo>> {
+        self.api.runtime.block_on(self.inner().query_orders_info(trades, userref, txid))
+    }
+
+    pub fn get_trades_history(&self,
+                              type_trade: &str,
+                              trades: &str,
+                              start: &str,
+                              end: &str,
+                              ofs: &str)
+                              -> Result<TradesHistory> {
+        self.api
+            .runtime
+            .block_on(self.inner().get_trades_history(type_trade, trades, start, end, ofs))
+    }
+
+    pub fn query_trades_info(&self,
+                             txid: &str,
+                             trades: &str)
+                             -> Result<HashMap<String, TradeInfo>> {
+        self.api.runtime.block_on(self.inner().query_trades_info(txid, trades))
+    }
+
+    pub fn get_open_positions(&self,
+                              txid: &str,
+                              docalcs: &str)
+                              -> Result<HashMap<String, PositionInfo>> {
+        self.api.runtime.block_on(self.inner().get_open_positions(txid, docalcs))
+    }
+
+    pub fn get_ledgers_info(&self,
+                            aclass: &str,
+                            asset: &str,
+                            type_ledger: &str,
+                            start: &str,
+                            end: &str,
+                            ofs: &str)
+                            -> Result<Ledgers> {
+        self.api
+            .runtime
+            .block_on(self.inner().get_ledgers_info(aclass, asset, type_ledger, start, end, ofs))
+    }
+
+    pub fn query_ledgers(&self, id: &str) -> Result<HashMap<String, LedgerEntry>> {
+        self.api.runtime.block_on(self.inner().query_ledgers(id))
+    }
+
+    pub fn get_trade_volume(&self, pair: &str, fee_info: &str) -> Result<TradeVolume> {
+        self.api.runtime.block_on(self.inner().get_trade_volume(pair, fee_info))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_standard_order(&self,
+                              pair: &str,
+                              type_order: &str,
+                              ordertype: &str,
+                              price: &str,
+                              price2: &str,
+                              volume: &str,
+                              leverage: &str,
+                              oflags: &str,
+                              starttm: &str,
+                              expiretm: &str,
+                              userref: &str,
+                              validate: &str)
+                              -> Result<AddedOrder> {
+        self.api
+            .runtime
+            .block_on(self.inner()
+                          .add_standard_order(pair, type_order, ordertype, price, price2,
+                                              volume, leverage, oflags, starttm, expiretm,
+                                              userref, validate))
+    }
+
+    pub fn cancel_open_order(&self, txid: &str) -> Result<CanceledOrders> {
+        self.api.runtime.block_on(self.inner().cancel_open_order(txid))
+    }
+
+    pub fn get_deposit_methods(&self, aclass: &str, asset: &str) -> Result<Vec<DepositMethod>> {
+        self.api.runtime.block_on(self.inner().get_deposit_methods(aclass, asset))
+    }
+
+    pub fn get_deposit_addresses(&self,
+                                 aclass: &str,
+                                 asset: &str,
+                                 method: &str,
+                                 new: &str)
+                                 -> Result<Vec<DepositAddress>> {
+        self.api
+            .runtime
+            .block_on(self.inner().get_deposit_addresses(aclass, asset, method, new))
+    }
+
+    pub fn get_status_of_recent_deposits(&self,
+                                         aclass: &str,
+                                         asset: &str,
+                                         method: &str)
+                                         -> Result<Vec<TransferStatus>> {
+        self.api
+            .runtime
+            .block_on(self.inner().get_status_of_recent_deposits(aclass, asset, method))
+    }
+
+    pub fn get_withdrawal_information(&self,
+                                      aclass: &str,
+                                      asset: &str,
+                                      key: &str,
+                                      amount: &str)
+                                      -> Result<WithdrawalInfo> {
+        self.api
+            .runtime
+            .block_on(self.inner().get_withdrawal_information(aclass, asset, key, amount))
+    }
+
+    pub fn withdraw_funds(&self,
+                          aclass: &str,
+                          asset: &str,
+                          key: &str,
+                          amount: &str)
+                          -> Result<Withdrawal> {
+        self.api.runtime.block_on(self.inner().withdraw_funds(aclass, asset, key, amount))
+    }
+
+    pub fn get_status_of_recent_withdrawals(&self,
+                                            aclass: &str,
+                                            asset: &str,
+                                            method: &str)
+                                            -> Result<Vec<TransferStatus>> {
+        self.api
+            .runtime
+            .block_on(self.inner().get_status_of_recent_withdrawals(aclass, asset, method))
+    }
+
+    pub fn get_websockets_token(&self) -> Result<WebSocketsToken> {
+        self.api.runtime.block_on(self.inner().get_websockets_token())
+    }
+
+    /// Return true if the cancelation has been accepted.
+    pub fn request_withdrawal_cancelation(&self,
+                                          aclass: &str,
+                                          asset: &str,
+                                          refid: &str)
+                                          -> Result<bool> {
+        self.api
+            .runtime
+            .block_on(self.inner().request_withdrawal_cancelation(aclass, asset, refid))
+    }
+}
@@ -1,92 +1,125 @@
 use bidir_map::BidirMap;
+use std::sync::RwLock;
+use serde::de::DeserializeOwned;
 use serde_json;
 use serde_json::Value;
 use serde_json::value::Map;
 
+use bigdecimal::{BigDecimal, Zero};
+
 use crate::error::*;
-use crate::types::Currency;
-use crate::types::Pair;
-use crate::types::Pair::*;
+use crate::exchange::Exchange;
+use crate::helpers::{self, PairNames};
+use crate::normalization;
+use crate::types::*;
 
 lazy_static! {
-    static ref PAIRS_STRING: BidirMap<Pair, &'static str> = {
+    static ref PAIRS_STRING: PairNames = {
         let mut m = BidirMap::new();
-        m.insert(BCH_EUR, "BCHEUR");
-        m.insert(BCH_USD, "BCHUSD");
-        m.insert(BCH_BTC, "BCHXBT");
-        m.insert(DASH_EUR, "DASHEUR");
-        m.insert(DASH_USD, "DASHUSD");
-        m.insert(DASH_BTC, "DASHXBT");
-        m.insert(EOS_ETH, "EOSETH");
-        m.insert(EOS_BTC, "EOSXBT");
-        m.insert(GNO_ETH, "GNOETH");
-        m.insert(GNO_BTC, "GNOXBT");
-        m.insert(USDT_USD, "USDTZUSD");
-        m.insert(ETC_ETH, "XETCXETH");
-        m.insert(ETC_BTC, "XETCXXBT");
-        m.insert(ETC_EUR, "XETCZEUR");
-        m.insert(ETC_USD, "XETCZUSD");
-        m.insert(ETH_BTC, "XETHXXBT");
-        m.insert(ETH_BTC_d, "XETHXXBT.d");
-        m.insert(ETH_CAD, "XETHZCAD");
-        m.insert(ETH_CAD_d, "XETHZCAD.d");
-        m.insert(ETH_EUR, "XETHZEUR");
-        m.insert(ETH_EUR_d, "XETHZEUR.d");
-        m.insert(ETH_GBP, "XETHZGBP");
-        m.insert(ETH_GBP_d, "XETHZGBP.d");
-        m.insert(ETH_JPY, "XETHZJPY");
-        m.insert(ETH_JPY_d, "XETHZJPY.d");
-        m.insert(ETH_USD, "XETHZUSD");
-        m.insert(ETH_USD_d, "XETHZUSD.d");
-        m.insert(ICN_ETH, "XICNXETH");
-        m.insert(ICN_BTC, "XICNXXBT");
-        m.insert(LTC_BTC, "XLTCXXBT");
-        m.insert(LTC_EUR, "XLTCZEUR");
-        m.insert(LTC_USD, "XLTCZUSD");
-        m.insert(MLN_ETH, "XMLNXETH");
-        m.insert(MLN_BTC, "XMLNXXBT");
-        m.insert(REP_ETH, "XREPXETH");
-        m.insert(REP_BTC, "XREPXXBT");
-        m.insert(REP_EUR, "XREPZEUR");
-        m.insert(BTC_CAD, "XXBTZCAD");
-        m.insert(BTC_CAD_d, "XXBTZCAD.d");
-        m.insert(BTC_EUR, "XXBTZEUR");
-        m.insert(BTC_EUR_d, "XXBTZEUR.d");
-        m.insert(BTC_GBP, "XXBTZGBP");
-        m.insert(BTC_GBP_d, "XXBTZGBP.d");
-        m.insert(BTC_JPY, "XXBTZJPY");
-        m.insert(BTC_JPY_d, "XXBTZJPY.d");
-        m.insert(BTC_USD, "XXBTZUSD");
-        m.insert(BTC_USD_d, "XXBTZUSD.d");
-        m.insert(XDG_BTC, "XXDGXXBT");
-        m.insert(XLM_BTC, "XXLMXXBT");
-        m.insert(XMR_BTC, "XXMRXXBT");
-        m.insert(XMR_EUR, "XXMRZEUR");
-        m.insert(XMR_USD, "XXMRZUSD");
-        m.insert(XRP_BTC, "XXRPXXBT");
-        m.insert(XRP_EUR, "XXRPZEUR");
-        m.insert(XRP_USD, "XXRPZUSD");
-        m.insert(ZEC_BTC, "XZECXXBT");
-        m.insert(ZEC_EUR, "XZECZEUR");
-        m.insert(ZEC_USD, "XZECZUSD");
-        m
+        m.insert(Pair::BCH_EUR, "BCHEUR");
+        m.insert(Pair::BCH_USD, "BCHUSD");
+        m.insert(Pair::BCH_BTC, "BCHXBT");
+        m.insert(Pair::DASH_EUR, "DASHEUR");
+        m.insert(Pair::DASH_USD, "DASHUSD");
+        m.insert(Pair::DASH_BTC, "DASHXBT");
+        m.insert(Pair::EOS_ETH, "EOSETH");
+        m.insert(Pair::EOS_BTC, "EOSXBT");
+        m.insert(Pair::GNO_ETH, "GNOETH");
+        m.insert(Pair::GNO_BTC, "GNOXBT");
+        m.insert(Pair::USDT_USD, "USDTZUSD");
+        m.insert(Pair::ETC_ETH, "XETCXETH");
+        m.insert(Pair::ETC_BTC, "XETCXXBT");
+        m.insert(Pair::ETC_EUR, "XETCZEUR");
+        m.insert(Pair::ETC_USD, "XETCZUSD");
+        m.insert(Pair::ETH_BTC, "XETHXXBT");
+        m.insert(Pair::ETH_BTC_d, "XETHXXBT.d");
+        m.insert(Pair::ETH_CAD, "XETHZCAD");
+        m.insert(Pair::ETH_CAD_d, "XETHZCAD.d");
+        m.insert(Pair::ETH_EUR, "XETHZEUR");
+        m.insert(Pair::ETH_EUR_d, "XETHZEUR.d");
+        m.insert(Pair::ETH_GBP, "XETHZGBP");
+        m.insert(Pair::ETH_GBP_d, "XETHZGBP.d");
+        m.insert(Pair::ETH_JPY, "XETHZJPY");
+        m.insert(Pair::ETH_JPY_d, "XETHZJPY.d");
+        m.insert(Pair::ETH_USD, "XETHZUSD");
+        m.insert(Pair::ETH_USD_d, "XETHZUSD.d");
+        m.insert(Pair::ICN_ETH, "XICNXETH");
+        m.insert(Pair::ICN_BTC, "XICNXXBT");
+        m.insert(Pair::LTC_BTC, "XLTCXXBT");
+        m.insert(Pair::LTC_EUR, "XLTCZEUR");
+        m.insert(Pair::LTC_USD, "XLTCZUSD");
+        m.insert(Pair::MLN_ETH, "XMLNXETH");
+        m.insert(Pair::MLN_BTC, "XMLNXXBT");
+        m.insert(Pair::REP_ETH, "XREPXETH");
+        m.insert(Pair::REP_BTC, "XREPXXBT");
+        m.insert(Pair::REP_EUR, "XREPZEUR");
+        m.insert(Pair::BTC_CAD, "XXBTZCAD");
+        m.insert(Pair::BTC_CAD_d, "XXBTZCAD.d");
+        m.insert(Pair::BTC_EUR, "XXBTZEUR");
+        m.insert(Pair::BTC_EUR_d, "XXBTZEUR.d");
+        m.insert(Pair::BTC_GBP, "XXBTZGBP");
+        m.insert(Pair::BTC_GBP_d, "XXBTZGBP.d");
+        m.insert(Pair::BTC_JPY, "XXBTZJPY");
+        m.insert(Pair::BTC_JPY_d, "XXBTZJPY.d");
+        m.insert(Pair::BTC_USD, "XXBTZUSD");
+        m.insert(Pair::BTC_USD_d, "XXBTZUSD.d");
+        m.insert(Pair::XDG_BTC, "XXDGXXBT");
+        m.insert(Pair::XLM_BTC, "XXLMXXBT");
+        m.insert(Pair::XMR_BTC, "XXMRXXBT");
+        m.insert(Pair::XMR_EUR, "XXMRZEUR");
+        m.insert(Pair::XMR_USD, "XXMRZUSD");
+        m.insert(Pair::XRP_BTC, "XXRPXXBT");
+        m.insert(Pair::XRP_EUR, "XXRPZEUR");
+        m.insert(Pair::XRP_USD, "XXRPZUSD");
+        m.insert(Pair::ZEC_BTC, "XZECXXBT");
+        m.insert(Pair::ZEC_EUR, "XZECZEUR");
+        m.insert(Pair::ZEC_USD, "XZECZUSD");
+        RwLock::new(m)
     };
 }
 
 /// Return the name associated to pair used by Kraken
-/// If the Pair is not supported, None is returned.
-pub fn get_pair_string(pair: &Pair) -> Option<&&str> {
-    PAIRS_STRING.get_by_first(pair)
+/// If the Pair is not supported, None is returned. Pairs listed since the last update of this
+/// module are only known once `register_pair` has been called (see `ExchangeApi::markets`).
+pub fn get_pair_string(pair: &Pair) -> Option<&'static str> {
+    helpers::get_pair_name(&PAIRS_STRING, pair)
 }
 
 /// Return the Pair enum associated to the string used by Kraken
 /// If the Pair is not supported, None is returned.
-pub fn get_pair_enum(pair: &str) -> Option<&Pair> {
-    PAIRS_STRING.get_by_second(&pair)
+pub fn get_pair_enum(pair: &str) -> Option<Pair> {
+    helpers::get_pair_from_name(&PAIRS_STRING, pair)
+}
+
+/// Add a pair listed by Kraken and the name used by Kraken for it. Pairs and names that are already
+/// known are left untouched.
+pub fn register_pair(pair: Pair, name: &str) {
+    helpers::register_pair(&PAIRS_STRING, pair, name)
+}
+
+/// Return the Pair enum associated to the alternate name used by Kraken in order descriptions
+/// (ie. "XBTEUR" for "XXBTZEUR").
+/// If the Pair is not supported, None is returned.
+pub fn get_pair_enum_from_altname(altname: &str) -> Option<Pair> {
+    if let Some(pair) = get_pair_enum(altname) {
+        return Some(pair);
+    }
+
+    // Pairs prefixed with X/Z ("XXBTZEUR") drop both prefixes in their alternate name
+    PAIRS_STRING
+        .read()
+        .unwrap()
+        .iter()
+        .find(|(_, name)| {
+                  name.len() == 8 && name.is_ascii() && name.starts_with('X') &&
+                  (&name[4..5] == "X" || &name[4..5] == "Z") &&
+                  name[1..4].to_string() + &name[5..] == altname
+              })
+        .map(|(pair, _)| *pair)
 }
 
 pub fn deserialize_json(json_string: &str) -> Result<Map<String, Value>> {
-    let data: Value = match serde_json::from_str(json_string) {
+    let data: Value = match helpers::parse_json(json_string) {
         Ok(data) => data,
         Err(_) => return Err(ErrorKind::BadParse.into()),
     };
@@ -98,8 +131,22 @@ pub fn deserialize_json(json_string: &str) -> Result<Map<String, Value>> {
 }
 
 /// If error array is null, return the result (encoded in a json object)
-/// else return the error string found in array
-pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>> {
+/// else return the error string found in array, with the endpoint which sent it.
+/// Canceling an order which is already closed is an `OrderAlreadyClosed` error.
+pub fn parse_result(response: &Map<String, Value>,
+                    endpoint: &str)
+                    -> Result<Map<String, Value>> {
+    Ok(parse_result_value(response, endpoint)?
+           .as_object()
+           .ok_or_else(|| ErrorKind::InvalidFieldFormat("result".to_string()))?
+           .clone())
+}
+
+/// Same as `parse_result`, for the endpoints whose result is not a JSON object (ie. the array
+/// returned by `DepositMethods`).
+pub fn parse_result_value<'a>(response: &'a Map<String, Value>,
+                              endpoint: &str)
+                              -> Result<&'a Value> {
     let error_array = match response.get("error") {
         Some(array) => {
             array
@@ -109,38 +156,246 @@ pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>>
         None => return Err(ErrorKind::BadParse.into()),
     };
     if error_array.is_empty() {
-        return Ok(response
-                      .get("result")
-                      .ok_or_else(|| ErrorKind::MissingField("result".to_string()))?
-                      .as_object()
-                      .ok_or_else(|| ErrorKind::InvalidFieldFormat("result".to_string()))?
-                      .clone());
+        let result = response
+            .get("result")
+            .ok_or_else(|| ErrorKind::MissingField("result".to_string()))?;
+
+        // Kraken does not fail to cancel an order which is already closed, it cancels nothing
+        if endpoint == "CancelOrder" && result["count"].as_u64() == Some(0) &&
+           result["pending"].as_bool() != Some(true) {
+            return Err(ExchangeError::new(Exchange::Kraken, endpoint)
+                           .message("No order canceled")
+                           .into_error(ErrorKind::OrderAlreadyClosed));
+        }
+
+        return Ok(result);
     }
     let error_msg = error_array[0]
         .as_str()
         .ok_or_else(|| ErrorKind::InvalidFieldFormat(error_array[0].to_string()))?
         .to_string();
+    let messages: Vec<&str> = error_array.iter().filter_map(|error| error.as_str()).collect();
 
-    //TODO: Parse correctly the reason for "EService:Unavailable".
-    match error_msg.as_ref() {
-        "EService:Unavailable" => {
-            Err(ErrorKind::ServiceUnavailable("Unknown...".to_string()).into())
+    let kind = match error_msg.as_ref() {
+        "EService:Unavailable" | "EService:Busy" => {
+            ErrorKind::ServiceUnavailable(error_msg.clone())
         }
-        "EAPI:Invalid key" => Err(ErrorKind::BadCredentials.into()),
-        "EAPI:Invalid nonce" => Err(ErrorKind::InvalidNonce.into()),
-        "EOrder:Rate limit exceeded" => Err(ErrorKind::RateLimitExceeded.into()),
-        "EQuery:Unknown asset pair" => Err(ErrorKind::PairUnsupported.into()),
-        "EGeneral:Invalid arguments" => Err(ErrorKind::InvalidArguments.into()),
-        "EGeneral:Permission denied" => Err(ErrorKind::PermissionDenied.into()),
-        "EOrder:Insufficient funds" => Err(ErrorKind::InsufficientFunds.into()),
-        "EOrder:Order minimum not met" => Err(ErrorKind::InsufficientOrderSize.into()),
-        other => Err(ErrorKind::ExchangeSpecificError(other.to_string()).into()),
+        "EAPI:Invalid key" => ErrorKind::BadCredentials,
+        "EAPI:Invalid nonce" => ErrorKind::InvalidNonce,
+        "EOrder:Rate limit exceeded" => ErrorKind::RateLimitExceeded,
+        "EQuery:Unknown asset pair" => ErrorKind::PairUnsupported,
+        "EGeneral:Invalid arguments" => ErrorKind::InvalidArguments,
+        "EGeneral:Permission denied" => ErrorKind::PermissionDenied,
+        "EOrder:Insufficient funds" => ErrorKind::InsufficientFunds,
+        "EOrder:Order minimum not met" => ErrorKind::InsufficientOrderSize,
+        "EOrder:Unknown order" | "EOrder:Invalid order" => ErrorKind::OrderNotFound,
+        other => ErrorKind::ExchangeSpecificError(other.to_string()),
+    };
+    Err(ExchangeError::new(Exchange::Kraken, endpoint)
+            .code(&error_msg)
+            .message(&messages.join(", "))
+            .into_error(kind))
+}
+
+/// Check the response like `parse_result`, then deserialize its result into the typed response
+/// of the endpoint (see the `responses` module).
+pub fn parse_response<T: DeserializeOwned>(response: &Map<String, Value>,
+                                           endpoint: &str)
+                                           -> Result<T> {
+    let result = parse_result_value(response, endpoint)?;
+    T::deserialize(result).chain_err(|| ErrorKind::BadParse)
+}
+
+/// Convert the trades returned by `Trades` into a list of Trade.
+/// Each trade is an array (<price>, <volume>, <time>, <buy/sell>, <market/limit>,
+/// <miscellaneous>, <trade id>). The trade id is only sent by recent versions of the API, the
+/// time is used as identifier otherwise.
+pub fn parse_trades(trades: &[Value]) -> Result<Vec<Trade>> {
+    let mut result = Vec::new();
+
+    for trade in trades {
+        let time = trade[2]
+            .as_f64()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("time".to_string()))?;
+
+        let side = match trade[3].as_str() {
+            Some("b") => OrderSide::Buy,
+            Some("s") => OrderSide::Sell,
+            _ => return Err(ErrorKind::InvalidFieldValue("buy/sell".to_string()).into()),
+        };
+
+        let identifier = match trade.get(6) {
+            Some(id) => id.to_string(),
+            None => time.to_string(),
+        };
+
+        result.push(Trade {
+                        identifier,
+                        timestamp: (time * 1000.0) as i64,
+                        price: helpers::from_json_bigdecimal(&trade[0], "price")?,
+                        volume: helpers::from_json_bigdecimal(&trade[1], "volume")?,
+                        side,
+                    });
+    }
+
+    Ok(result)
+}
+
+/// Return the number of minutes expected by `OHLC` for the Interval, if Kraken supports it.
+pub fn get_interval_minutes(interval: Interval) -> Option<String> {
+    match interval {
+        Interval::TwoHours => None,
+        _ => Some((interval.seconds() / 60).to_string()),
+    }
+}
+
+/// Convert the candles returned by `OHLC` into a list of Candle.
+/// Each candle is an array (<time>, <open>, <high>, <low>, <close>, <vwap>, <volume>, <count>).
+pub fn parse_candles(candles: &[Value]) -> Result<Vec<Candle>> {
+    let mut result = Vec::new();
+
+    for candle in candles {
+        let time = candle[0]
+            .as_i64()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("time".to_string()))?;
+
+        result.push(Candle {
+                        timestamp: time * 1000,
+                        open: helpers::from_json_bigdecimal(&candle[1], "open")?,
+                        high: helpers::from_json_bigdecimal(&candle[2], "high")?,
+                        low: helpers::from_json_bigdecimal(&candle[3], "low")?,
+                        close: helpers::from_json_bigdecimal(&candle[4], "close")?,
+                        volume: helpers::from_json_bigdecimal(&candle[6], "volume")?,
+                    });
     }
+
+    Ok(result)
+}
+
+/// Convert the result of `AssetPairs` into Markets. The pairs that are not known yet are
+/// registered (see `register_pair`).
+/// Kraken only publishes "ordermin", "costmin", "tick_size" and "status" in recent versions of
+/// the API: the tick size falls back to "pair_decimals" and a market without status is active.
+pub fn parse_markets(asset_pairs: &Map<String, Value>) -> Result<Markets> {
+    let mut markets = Markets::new();
+
+    for (name, info) in asset_pairs {
+        let pair = match get_pair_enum(name) {
+            Some(pair) => pair,
+            None => {
+                // Kraken calls "base" the left member of the pair and "quote" the right one
+                let left = get_currency_enum(helpers::get_json_string(info, "base")?)
+                    .ok_or_else(|| ErrorKind::InvalidFieldValue("base".to_string()))?;
+                let right = get_currency_enum(helpers::get_json_string(info, "quote")?)
+                    .ok_or_else(|| ErrorKind::InvalidFieldValue("quote".to_string()))?;
+
+                let pair = if name.ends_with(".d") {
+                    Pair::new(left, right).dark_pool()
+                } else {
+                    Pair::new(left, right)
+                };
+                register_pair(pair, name);
+                pair
+            }
+        };
+
+        let min_order_size = match info.get("ordermin") {
+            Some(ordermin) => Some(helpers::from_json_bigdecimal(ordermin, "ordermin")?),
+            None => None,
+        };
+
+        let tick_size = match (info.get("tick_size"), info["pair_decimals"].as_u64()) {
+            (Some(tick_size), _) => Some(helpers::from_json_bigdecimal(tick_size, "tick_size")?),
+            (None, Some(decimals)) => Some(BigDecimal::new(1.into(), decimals as i64)),
+            (None, None) => None,
+        };
+
+        let min_notional = match info.get("costmin") {
+            Some(costmin) => Some(helpers::from_json_bigdecimal(costmin, "costmin")?),
+            None => None,
+        };
+
+        let active = match info.get("status") {
+            Some(Value::String(status)) => status == "online",
+            _ => true,
+        };
+
+        markets.insert(pair,
+                       MarketInfo {
+                           pair,
+                           min_order_size,
+                           tick_size,
+                           lot_decimals: info["lot_decimals"].as_u64().map(|d| d as u32),
+                           min_notional,
+                           active,
+                       });
+    }
+
+    Ok(markets)
+}
+
+/// Convert an order returned by `OpenOrders` or `QueryOrders` into an Order.
+/// The pair is resolved by the caller from the "descr" field.
+pub fn parse_order(txid: &str, pair: Pair, order: &Value) -> Result<Order> {
+    let descr = &order["descr"];
+
+    let side = match helpers::get_json_string(descr, "type")? {
+        "buy" => OrderSide::Buy,
+        "sell" => OrderSide::Sell,
+        _ => return Err(ErrorKind::InvalidFieldValue("type".to_string()).into()),
+    };
+
+    let is_market = helpers::get_json_string(descr, "ordertype")? == "market";
+    let order_type = match (side, is_market) {
+        (OrderSide::Buy, false) => OrderType::BuyLimit,
+        (OrderSide::Sell, false) => OrderType::SellLimit,
+        (OrderSide::Buy, true) => OrderType::BuyMarket,
+        (OrderSide::Sell, true) => OrderType::SellMarket,
+    };
+
+    let price = if is_market {
+        None
+    } else {
+        Some(helpers::from_json_bigdecimal(&descr["price"], "price")?)
+    };
+
+    let quantity = helpers::from_json_bigdecimal(&order["vol"], "vol")?;
+    let filled_quantity = helpers::from_json_bigdecimal(&order["vol_exec"], "vol_exec")?;
+
+    let average_price = if filled_quantity.is_zero() {
+        None
+    } else {
+        Some(helpers::from_json_bigdecimal(&order["price"], "price")?)
+    };
+
+    let status = match helpers::get_json_string(order, "status")? {
+        "pending" | "open" if filled_quantity.is_zero() => OrderStatus::Open,
+        "pending" | "open" => OrderStatus::PartiallyFilled,
+        "closed" => OrderStatus::Filled,
+        "canceled" => OrderStatus::Canceled,
+        "expired" => OrderStatus::Expired,
+        _ => return Err(ErrorKind::InvalidFieldValue("status".to_string()).into()),
+    };
+
+    Ok(Order {
+           timestamp: helpers::get_unix_timestamp_ms(),
+           identifier: txid.to_string(),
+           pair,
+           side,
+           order_type,
+           price,
+           quantity,
+           filled_quantity,
+           average_price,
+           status,
+           opened_at: order["opentm"].as_f64().map(|tm| (tm * 1000.0) as i64),
+           closed_at: order["closetm"].as_f64().map(|tm| (tm * 1000.0) as i64),
+       })
 }
 
 /// Return the currency enum associated with the
-/// string used by Kraken. If no currency is found,
-/// return None
+/// string used by Kraken, see the `normalization` module.
+/// If the string is empty, return None
 /// # Examples
 ///
 /// ```
@@ -151,34 +406,11 @@ pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>>
 /// assert_eq!(Some(Currency::USD), currency);
 /// ```
 pub fn get_currency_enum(currency: &str) -> Option<Currency> {
-    match currency {
-        "ZEUR" => Some(Currency::EUR),
-        "ZCAD" => Some(Currency::CAD),
-        "ZGBP" => Some(Currency::GBP),
-        "ZJPY" => Some(Currency::JPY),
-        "ZUSD" => Some(Currency::USD),
-        "XDASH" => Some(Currency::DASH),
-        "XETC" => Some(Currency::ETC),
-        "XETH" => Some(Currency::ETH),
-        "XGNO" => Some(Currency::GNO),
-        "XICN" => Some(Currency::ICN),
-        "XLTC" => Some(Currency::LTC),
-        "XMLN" => Some(Currency::MLN),
-        "XREP" => Some(Currency::REP),
-        "XUSDT" => Some(Currency::USDT),
-        "XXBT" => Some(Currency::BTC),
-        "XXDG" => Some(Currency::XDG),
-        "XXLM" => Some(Currency::XLM),
-        "XXMR" => Some(Currency::XMR),
-        "XXRP" => Some(Currency::XRP),
-        "XZEC" => Some(Currency::ZEC),
-        _ => None,
-    }
+    normalization::get_currency(Exchange::Kraken, currency)
 }
 
-/// Return the currency String associated with the
-/// string used by Kraken. If no currency is found,
-/// return None
+/// Return the name used by Kraken for the currency, see
+/// the `normalization` module
 /// # Examples
 ///
 /// ```
@@ -189,27 +421,5 @@ pub fn get_currency_enum(currency: &str) -> Option<Currency> {
 /// assert_eq!(currency, Some("XXBT".to_string()));
 /// ```
 pub fn get_currency_string(currency: Currency) -> Option<String> {
-    match currency {
-        Currency::EUR => Some("ZEUR".to_string()),
-        Currency::CAD => Some("ZCAD".to_string()),
-        Currency::GBP => Some("ZGBP".to_string()),
-        Currency::JPY => Some("ZJPY".to_string()),
-        Currency::USD => Some("ZUSD".to_string()),
-        Currency::DASH => Some("XDASH".to_string()),
-        Currency::ETC => Some("XETC".to_string()),
-        Currency::ETH => Some("XETH".to_string()),
-        Currency::GNO => Some("XGNO".to_string()),
-        Currency::ICN => Some("XICN".to_string()),
-        Currency::LTC => Some("XLTC".to_string()),
-        Currency::MLN => Some("XMLN".to_string()),
-        Currency::REP => Some("XREP".to_string()),
-        Currency::USDT => Some("XUSDT".to_string()),
-        Currency::BTC => Some("XXBT".to_string()),
-        Currency::XDG => Some("XXDG".to_string()),
-        Currency::XLM => Some("XXLM".to_string()),
-        Currency::XMR => Some("XXMR".to_string()),
-        Currency::XRP => Some("XXRP".to_string()),
-        Currency::ZEC => Some("XZEC".to_string()),
-        _ => None,
-    }
+    Some(normalization::get_name(Exchange::Kraken, currency))
 }
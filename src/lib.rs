@@ -4,6 +4,8 @@
 //! crypto-currencies exchanges (see below for a list of supported exchanges).
 //! All methods consume HTTPS api. The purpose of this crate is not
 //! to stream data (you should use websocket/FIX in that case).
+//! Each exchange has a blocking client (ie. `KrakenApi`) and an asynchronous one running on Tokio
+//! (ie. `AsyncKrakenApi`).
 //!
 //! For optional parameters, most methods require an empty str (`""`) or `Option` (`None`) if
 //! you don't want to specify them.
@@ -11,7 +13,7 @@
 //! ### Exchanges support:
 //! - [x] Poloniex
 //! - [x] Kraken
-//! - [x] Bitstamp (partial)
+//! - [x] Bitstamp
 //! - [x] Bittrex
 //! - [x] Gdax
 //!
@@ -30,13 +32,13 @@
 #![deny(clippy)]
 
 // Avoid warning for the Crypto-currency about quotes.
-#![allow(doc_markdown)]
+#![allow(clippy::doc_markdown)]
 
-#[macro_use]
 extern crate hyper;
 extern crate sha2;
 extern crate hmac;
 extern crate hyper_native_tls;
+extern crate serde;
 extern crate serde_json;
 extern crate chrono;
 #[macro_use]
@@ -46,11 +48,23 @@ extern crate data_encoding;
 #[macro_use]
 extern crate error_chain;
 extern crate bigdecimal;
+extern crate reqwest;
+extern crate tokio;
+extern crate async_trait;
+extern crate fastrand;
+extern crate fs2;
+extern crate uuid;
 
+pub mod client;
 pub mod coinnect;
 pub mod exchange;
 pub mod error;
 pub mod types;
+pub mod normalization;
+pub mod transport;
+pub mod rate_limit;
+pub mod retry;
+pub mod nonce;
 mod helpers;
 
 pub mod bitstamp;
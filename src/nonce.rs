@@ -0,0 +1,160 @@
+//! This module generates the nonces sent with the private requests.
+//!
+//! The exchanges reject a request whose nonce is not greater than the last one they received for
+//! the API key (`ErrorKind::InvalidNonce`). Timestamps are not enough: two requests can be sent in
+//! the same millisecond, the clock can step backwards, and several processes can use the same API
+//! key. A `NonceProvider` returns strictly increasing nonces, based on the current time:
+//!
+//! - `MemoryNonceProvider` (default) keeps the last nonce in memory. All the clients of a process
+//!   using the same API key on the same exchange share it (see `MemoryNonceProvider::shared`).
+//! - `FileNonceProvider` stores the last nonce in a file, locked while a nonce is generated, so
+//!   that several processes can share the API key.
+
+use fs2::FileExt;
+
+use std::cmp;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use crate::error::*;
+use crate::exchange::Exchange;
+use crate::helpers;
+
+lazy_static! {
+    static ref SHARED_PROVIDERS: Mutex<HashMap<(Exchange, String), Arc<MemoryNonceProvider>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// The unit of the timestamps used as nonces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Milliseconds,
+    Microseconds,
+}
+
+impl Resolution {
+    /// Return the current time in this unit.
+    pub fn now(self) -> i64 {
+        match self {
+            Resolution::Milliseconds => helpers::get_unix_timestamp_ms(),
+            Resolution::Microseconds => helpers::get_unix_timestamp_us(),
+        }
+    }
+}
+
+/// Return the unit of the nonces expected by the exchange.
+pub fn get_resolution(exchange: Exchange) -> Resolution {
+    match exchange {
+        Exchange::Poloniex => Resolution::Microseconds,
+        Exchange::Kraken | Exchange::Bitstamp | Exchange::Bittrex | Exchange::Gdax => {
+            Resolution::Milliseconds
+        }
+    }
+}
+
+/// Generate the nonces of the private requests.
+pub trait NonceProvider: fmt::Debug + Send + Sync {
+    /// Return a nonce greater than all the nonces previously returned, and at least the current
+    /// time (see `Resolution::now`).
+    fn next_nonce(&self) -> Result<i64>;
+}
+
+/// Keep the last nonce in memory.
+#[derive(Debug)]
+pub struct MemoryNonceProvider {
+    resolution: Resolution,
+    last: AtomicI64,
+}
+
+impl MemoryNonceProvider {
+    pub fn new(resolution: Resolution) -> MemoryNonceProvider {
+        MemoryNonceProvider {
+            resolution,
+            last: AtomicI64::new(0),
+        }
+    }
+
+    /// Return the provider shared by all the clients using this API key on the exchange,
+    /// creating it if needed. This is the provider used by the clients by default.
+    pub fn shared(exchange: Exchange, api_key: &str) -> Arc<MemoryNonceProvider> {
+        let mut providers = SHARED_PROVIDERS.lock().unwrap();
+        providers
+            .entry((exchange, api_key.to_string()))
+            .or_insert_with(|| Arc::new(MemoryNonceProvider::new(get_resolution(exchange))))
+            .clone()
+    }
+
+    pub fn last_nonce(&self) -> i64 {
+        self.last.load(Ordering::SeqCst)
+    }
+}
+
+impl NonceProvider for MemoryNonceProvider {
+    fn next_nonce(&self) -> Result<i64> {
+        Ok(helpers::next_nonce(&self.last, self.resolution.now()))
+    }
+}
+
+/// Store the last nonce in a file, so that it survives a restart and can be shared between
+/// processes. The file is locked while a nonce is generated.
+#[derive(Debug)]
+pub struct FileNonceProvider {
+    resolution: Resolution,
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl FileNonceProvider {
+    /// Open the file storing the last nonce, creating it if needed.
+    pub fn new<P: AsRef<Path>>(path: P, resolution: Resolution) -> Result<FileNonceProvider> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path.as_ref())?;
+
+        Ok(FileNonceProvider {
+               resolution,
+               path: path.as_ref().to_path_buf(),
+               file: Mutex::new(file),
+           })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn update(&self, file: &mut File) -> Result<i64> {
+        let mut content = String::new();
+        file.seek(SeekFrom::Start(0))?;
+        file.read_to_string(&mut content)?;
+
+        let last = match content.trim() {
+            "" => 0,
+            last => last.parse::<i64>().chain_err(|| ErrorKind::BadParse)?,
+        };
+        let nonce = cmp::max(self.resolution.now(), last + 1);
+
+        file.seek(SeekFrom::Start(0))?;
+        file.set_len(0)?;
+        file.write_all(nonce.to_string().as_bytes())?;
+        file.sync_data()?;
+        Ok(nonce)
+    }
+}
+
+impl NonceProvider for FileNonceProvider {
+    fn next_nonce(&self) -> Result<i64> {
+        let mut file = self.file.lock().unwrap();
+        FileExt::lock_exclusive(&*file)?;
+        let nonce = self.update(&mut file);
+        FileExt::unlock(&*file)?;
+        nonce
+    }
+}
@@ -0,0 +1,136 @@
+//! This module converts the asset names used by the exchanges into canonical Currencies.
+//!
+//! Some assets are known under several names: Kraken calls Bitcoin "XXBT" (or "XBT") and
+//! Dogecoin "XXDG", Bitcoin Cash used to be "BCC" on Bittrex and Poloniex calls Stellar "STR".
+//! Every name goes through this module so that the Balances and Pairs returned by different
+//! exchanges can be compared.
+//!
+//! Names used by a single exchange are kept in per-exchange alias tables, which can be changed at
+//! runtime with `set_alias` and `remove_alias`. Names used by several exchanges are kept in the
+//! canonical registry and are also applied by `Currency::new`.
+
+use bidir_map::BidirMap;
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::exchange::Exchange;
+use crate::types::Currency;
+
+lazy_static! {
+    static ref CANONICAL_SYMBOLS: HashMap<&'static str, &'static str> = {
+        let mut m = HashMap::new();
+        m.insert("XBT", "BTC");
+        m.insert("XDG", "DOGE");
+        m.insert("BCC", "BCH");
+        m
+    };
+
+    static ref ALIASES: RwLock<HashMap<Exchange, BidirMap<String, Currency>>> = {
+        let mut kraken = BidirMap::new();
+        kraken.insert("ZEUR".to_string(), Currency::EUR);
+        kraken.insert("ZCAD".to_string(), Currency::CAD);
+        kraken.insert("ZGBP".to_string(), Currency::GBP);
+        kraken.insert("ZJPY".to_string(), Currency::JPY);
+        kraken.insert("ZUSD".to_string(), Currency::USD);
+        kraken.insert("XDASH".to_string(), Currency::DASH);
+        kraken.insert("XETC".to_string(), Currency::ETC);
+        kraken.insert("XETH".to_string(), Currency::ETH);
+        kraken.insert("XGNO".to_string(), Currency::GNO);
+        kraken.insert("XICN".to_string(), Currency::ICN);
+        kraken.insert("XLTC".to_string(), Currency::LTC);
+        kraken.insert("XMLN".to_string(), Currency::MLN);
+        kraken.insert("XREP".to_string(), Currency::REP);
+        kraken.insert("XUSDT".to_string(), Currency::USDT);
+        kraken.insert("XXBT".to_string(), Currency::BTC);
+        kraken.insert("XXDG".to_string(), Currency::DOGE);
+        kraken.insert("XXLM".to_string(), Currency::XLM);
+        kraken.insert("XXMR".to_string(), Currency::XMR);
+        kraken.insert("XXRP".to_string(), Currency::XRP);
+        kraken.insert("XZEC".to_string(), Currency::ZEC);
+
+        let mut poloniex = BidirMap::new();
+        poloniex.insert("STR".to_string(), Currency::XLM);
+
+        let mut m = HashMap::new();
+        m.insert(Exchange::Kraken, kraken);
+        m.insert(Exchange::Bittrex, BidirMap::new());
+        m.insert(Exchange::Poloniex, poloniex);
+        m.insert(Exchange::Bitstamp, BidirMap::new());
+        m.insert(Exchange::Gdax, BidirMap::new());
+        RwLock::new(m)
+    };
+}
+
+/// Return the canonical symbol of an asset known under several names (ie. "BTC" for "XBT").
+/// Other symbols are returned untouched.
+pub fn get_canonical_symbol(symbol: &str) -> &str {
+    match CANONICAL_SYMBOLS.get(symbol) {
+        Some(canonical) => canonical,
+        None => symbol,
+    }
+}
+
+/// Return the Currency associated to the name used by the exchange. Names without alias are
+/// converted to the Currency of the same symbol. If the name is empty, None is returned.
+/// # Examples
+///
+/// ```
+/// use coinnect::exchange::Exchange;
+/// use coinnect::normalization::get_currency;
+/// use coinnect::types::Currency;
+///
+/// assert_eq!(get_currency(Exchange::Kraken, "XXDG"), Some(Currency::DOGE));
+/// assert_eq!(get_currency(Exchange::Bittrex, "BCC"), Some(Currency::BCH));
+/// ```
+pub fn get_currency(exchange: Exchange, name: &str) -> Option<Currency> {
+    if name.is_empty() {
+        return None;
+    }
+
+    let aliases = ALIASES.read().unwrap();
+    match aliases.get(&exchange).and_then(|table| table.get_by_first(name)) {
+        Some(currency) => Some(*currency),
+        None => Some(Currency::new(name)),
+    }
+}
+
+/// Return the name used by the exchange for the Currency.
+/// # Examples
+///
+/// ```
+/// use coinnect::exchange::Exchange;
+/// use coinnect::normalization::get_name;
+/// use coinnect::types::Currency;
+///
+/// assert_eq!(get_name(Exchange::Kraken, Currency::BTC), "XXBT");
+/// assert_eq!(get_name(Exchange::Poloniex, Currency::BTC), "BTC");
+/// ```
+pub fn get_name(exchange: Exchange, currency: Currency) -> String {
+    let aliases = ALIASES.read().unwrap();
+    match aliases.get(&exchange).and_then(|table| table.get_by_second(&currency)) {
+        Some(name) => name.clone(),
+        None => currency.symbol().to_string(),
+    }
+}
+
+/// Make the exchange use `name` for the Currency. The previous alias of the name or of the
+/// Currency on this exchange is replaced.
+/// Note that the pairs listed by each exchange module are not affected.
+pub fn set_alias(exchange: Exchange, name: &str, currency: Currency) {
+    let mut aliases = ALIASES.write().unwrap();
+    let table = aliases.entry(exchange).or_insert_with(BidirMap::new);
+
+    table.remove_by_first(name);
+    table.remove_by_second(&currency);
+    table.insert(name.to_string(), currency);
+}
+
+/// Remove the alias of `name` on the exchange, which is then converted to the Currency of the
+/// same symbol.
+pub fn remove_alias(exchange: Exchange, name: &str) {
+    let mut aliases = ALIASES.write().unwrap();
+    if let Some(table) = aliases.get_mut(&exchange) {
+        table.remove_by_first(name);
+    }
+}
@@ -1,187 +1,62 @@
 //! Use this module to interact with Poloniex exchange.
 //! See examples for more informations.
 
-use hmac::{Hmac, Mac, NewMac};
-use sha2::Sha512;
-
-use hyper_native_tls::NativeTlsClient;
-use hyper::Client;
-use hyper::header;
-use hyper::net::HttpsConnector;
-
-use data_encoding::HEXLOWER;
-
 use serde_json::Value;
 use serde_json::value::Map;
 
-use std::collections::HashMap;
-use std::io::Read;
-use std::thread;
-use std::time::Duration;
+use tokio::runtime::Runtime;
 
-use crate::error::*;
-use crate::helpers;
+use std::fmt;
 
-use crate::exchange::Exchange;
+use crate::client::ClientConfig;
 use crate::coinnect::Credentials;
-use crate::poloniex::utils;
-
-header! {
-    #[doc(hidden)]
-    (KeyHeader, "Key") => [String]
-}
-
-header! {
-    #[doc(hidden)]
-    (SignHeader, "Sign") => [String]
-}
-
-header! {
-    #[doc(hidden)]
-    (ContentHeader, "Content-Type") => [String]
-}
-
-#[derive(Debug, Copy, Clone)]
-pub enum PlaceOrderOption {
-    FillOrKill,
-    ImmediateOrCancel,
-    PostOnly,
-}
-impl PlaceOrderOption {
-    fn repr(&self) -> &'static str {
-        match self {
-            &PlaceOrderOption::FillOrKill => "fillOrKill",
-            &PlaceOrderOption::ImmediateOrCancel => "immediateOrCancel",
-            &PlaceOrderOption::PostOnly => "postOnly",
-        }
-    }
-}
-
-#[derive(Debug, Copy, Clone)]
-pub enum MoveOrderOption {
-    ImmediateOrCancel,
-    PostOnly,
-}
-impl MoveOrderOption {
-    fn repr(&self) -> &'static str {
-        match self {
-            &MoveOrderOption::ImmediateOrCancel => "immediateOrCancel",
-            &MoveOrderOption::PostOnly => "postOnly",
-        }
-    }
-}
-
-#[derive(Debug)]
+use crate::error::*;
+use crate::helpers;
+use crate::poloniex::AsyncPoloniexApi;
+use crate::poloniex::typed_api::PoloniexTypedApi;
+use crate::poloniex::async_api::{MoveOrderOption, PlaceOrderOption};
+use crate::transport::{BlockingTransport, HttpTransport, HyperTransport};
+
+/// Blocking client of the Poloniex API. Every call runs the `AsyncPoloniexApi` method of the
+/// same name to completion on a runtime owned by the client, so it must not be used from an
+/// asynchronous context.
 pub struct PoloniexApi {
-    last_request: i64, // unix timestamp in ms, to avoid ban
-    api_key: String,
-    api_secret: String,
-    http_client: Client,
-    burst: bool,
+    pub(crate) inner: AsyncPoloniexApi,
+    pub(crate) runtime: Runtime,
 }
 
 impl PoloniexApi {
     /// Create a new PoloniexApi by providing an API key & API secret
     pub fn new<C: Credentials>(creds: C) -> Result<PoloniexApi> {
-        if creds.exchange() != Exchange::Poloniex {
-            return Err(ErrorKind::InvalidConfigType(Exchange::Poloniex, creds.exchange()).into());
-        }
+        PoloniexApi::new_with_transport(creds, Box::new(HyperTransport::new()?))
+    }
 
-        //TODO: Handle correctly the TLS errors with error_chain.
-        let ssl = match NativeTlsClient::new() {
-            Ok(res) => res,
-            Err(_) => return Err(ErrorKind::TlsError.into()),
-        };
-        let connector = HttpsConnector::new(ssl);
+    /// Create a new PoloniexApi sending its requests through the given transport
+    pub fn new_with_transport<C: Credentials>(creds: C,
+                                              transport: Box<dyn HttpTransport>)
+                                              -> Result<PoloniexApi> {
+        let transport = BlockingTransport::from(transport);
 
         Ok(PoloniexApi {
-            last_request: 0,
-            api_key: creds.get("api_key").unwrap_or_default(),
-            api_secret: creds.get("api_secret").unwrap_or_default(),
-            http_client: Client::with_connector(connector),
-            burst: false,
-        })
-    }
-
-    /// The number of calls in a given period is limited. In order to avoid a ban we limit
-    /// by default the number of api requests.
-    /// This function sets or removes the limitation.
-    /// Burst false implies no block.
-    /// Burst true implies there is a control over the number of calls allowed to the exchange
-    pub fn set_burst(&mut self, burst: bool) {
-        self.burst = burst
-    }
-
-    fn block_or_continue(&self) {
-        if !self.burst {
-            let threshold: u64 = 167; // 6 requests/sec = 1/6*1000
-            let offset: u64 = helpers::get_unix_timestamp_ms() as u64 - self.last_request as u64;
-            if offset < threshold {
-                let wait_ms = Duration::from_millis(threshold - offset);
-                thread::sleep(wait_ms);
-            }
-        }
-    }
-
-    fn public_query(&mut self, method: &str, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
-        let mut params = params.clone();
-        helpers::strip_empties(&mut params);
-        let url = "https://poloniex.com/public?command=".to_string() + method + "&" + &helpers::url_encode_hashmap(&params);
-
-        self.block_or_continue();
-        let mut response = match self.http_client.get(&url).send() {
-            Ok(response) => response,
-            Err(err) => return Err(ErrorKind::ServiceUnavailable(err.to_string()).into()),
-        };
-        self.last_request = helpers::get_unix_timestamp_ms();
-        let mut buffer = String::new();
-        response.read_to_string(&mut buffer)?;
-
-        if method == "returnChartData" {
-            return utils::deserialize_json_array(&buffer);
-        }
-        utils::deserialize_json(&buffer)
-    }
-
-    fn private_query(&mut self, method: &str, params: &HashMap<&str, &str>) -> Result<Map<String, Value>> {
-        let unix_timestamp = helpers::get_unix_timestamp_us().to_string();
-        let mut post_params = params.clone();
-        post_params.insert("command", method);
-        post_params.insert("nonce", &unix_timestamp);
-        helpers::strip_empties(&mut post_params);
-        let post_data = helpers::url_encode_hashmap(&post_params);
-
-        let mut mac = Hmac::<Sha512>::new_from_slice(self.api_secret.as_bytes()).unwrap();
-        mac.update(post_data.as_bytes());
-
-        let sign = HEXLOWER.encode(&mac.finalize().into_bytes());
-
-        let mut custom_header = header::Headers::new();
-        custom_header.set(KeyHeader(self.api_key.to_owned()));
-        custom_header.set(SignHeader(sign));
-        custom_header.set(ContentHeader(
-            "application/x-www-form-urlencoded".to_owned(),
-        ));
-
-        self.block_or_continue();
-
-        let mut response = match self.http_client
-            .post("https://poloniex.com/tradingApi")
-            .body(&post_data)
-            .headers(custom_header)
-            .send()
-        {
-            Ok(response) => response,
-            Err(err) => return Err(ErrorKind::ServiceUnavailable(err.to_string()).into()),
-        };
-        self.last_request = helpers::get_unix_timestamp_ms();
-
-        let mut buffer = String::new();
-        response.read_to_string(&mut buffer)?;
-        if method == "returnChartData" {
-            return utils::deserialize_json_array(&buffer);
-        }
-        utils::deserialize_json(&buffer)
+               inner: AsyncPoloniexApi::new_with_transport(creds, Box::new(transport))?,
+               runtime: helpers::new_blocking_runtime()?,
+           })
+    }
+
+    /// Return the settings of the client: its RateLimiter, RetryPolicy and NonceProvider.
+    pub fn config(&self) -> &ClientConfig {
+        self.inner.config()
+    }
+
+    /// Change the settings of the client, ie. `api.config_mut().set_retry_policy(policy)`.
+    pub fn config_mut(&mut self) -> &mut ClientConfig {
+        self.inner.config_mut()
+    }
+
+    /// Return a view of the client whose methods return the typed structs of the `responses`
+    /// module instead of JSON maps.
+    pub fn typed(&self) -> PoloniexTypedApi<'_> {
+        PoloniexTypedApi::new(self)
     }
 
     /// Sample output :
@@ -196,9 +71,8 @@ impl PoloniexApi {
     /// "percentChange":"0.16701570","baseVolume":"0.45347489","quoteVolume":"9094"},
     /// ... }
     /// ```
-    pub fn return_ticker(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.public_query("returnTicker", &params)
+    pub fn return_ticker(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_ticker())
     }
 
     /// Sample output :
@@ -208,9 +82,8 @@ impl PoloniexApi {
     /// "NXT":"14145"},
     /// ... "totalBTC":"81.89657704","totalLTC":"78.52083806"}
     /// ```
-    pub fn return_24_volume(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.public_query("return24Volume", &params)
+    pub fn return_24_volume(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_24_volume())
     }
 
     /// Sample output :
@@ -219,11 +92,8 @@ impl PoloniexApi {
     /// {"asks":[[0.00007600,1164],[0.00007620,1300], ... ], "bids":[[0.00006901,200],
     /// [0.00006900,408], ... ], "isFrozen": 0, "seq": 18849}
     /// ```
-    pub fn return_order_book(&mut self, currency_pair: &str, depth: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        params.insert("depth", depth);
-        self.public_query("returnOrderBook", &params)
+    pub fn return_order_book(&self, currency_pair: &str, depth: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_order_book(currency_pair, depth))
     }
 
     /// Sample output :
@@ -234,12 +104,10 @@ impl PoloniexApi {
     /// {"date":"2014-02-10 01:19:37","type":"buy","rate":"0.00007600","amount":"655",
     /// "total":"0.04978"}, ... ]
     /// ```
-    pub fn return_trade_history(&mut self, currency_pair: &str, start: &str, end: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        params.insert("start", start);
-        params.insert("end", end);
-        self.public_query("returnTradeHistory", &params)
+    ///
+    /// The array is returned in the "data" field of the map.
+    pub fn return_trade_history(&self, currency_pair: &str, start: &str, end: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_trade_history(currency_pair, start, end))
     }
 
     /// Sample output :
@@ -248,13 +116,8 @@ impl PoloniexApi {
     /// {"data": {"date":1405699200,"high":0.0045388,"low":0.00403001,"open":0.00404545,"close":0.00427592,
     /// "volume":44.11655644,"quoteVolume":10259.29079097,"weightedAverage":0.00430015}, ...}
     /// ```
-    pub fn return_chart_data(&mut self, currency_pair: &str, start: &str, end: &str, period: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        params.insert("start", start);
-        params.insert("end", end);
-        params.insert("period", period);
-        self.public_query("returnChartData", &params)
+    pub fn return_chart_data(&self, currency_pair: &str, start: &str, end: &str, period: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_chart_data(currency_pair, start, end, period))
     }
 
     /// Sample output :
@@ -263,9 +126,8 @@ impl PoloniexApi {
     /// {"1CR":{"maxDailyWithdrawal":10000,"txFee":0.01,"minConf":3,"disabled":0},
     /// "ABY":{"maxDailyWithdrawal":10000000,"txFee":0.01,"minConf":8,"disabled":0}, ... }
     /// ```
-    pub fn return_currencies(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.public_query("returnCurrencies", &params)
+    pub fn return_currencies(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_currencies())
     }
 
     /// Sample output :
@@ -274,10 +136,8 @@ impl PoloniexApi {
     /// {"offers":[{"rate":"0.00200000","amount":"64.66305732","rangeMin":2,"rangeMax":8}, ... ],
     /// "demands":[{"rate":"0.00170000","amount":"26.54848841","rangeMin":2,"rangeMax":2}, ... ]}
     /// ```
-    pub fn return_loan_orders(&mut self, currency: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currency", currency);
-        self.public_query("returnLoanOrders", &params)
+    pub fn return_loan_orders(&self, currency: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_loan_orders(currency))
     }
 
     /// Returns all of your available balances.
@@ -287,9 +147,8 @@ impl PoloniexApi {
     /// ```json
     /// {"BTC":"0.59098578","LTC":"3.31117268", ... }
     /// ```
-    pub fn return_balances(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.private_query("returnBalances", &params)
+    pub fn return_balances(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_balances())
     }
 
     /// Returns all of your balances, including available balance, balance on orders,
@@ -302,10 +161,8 @@ impl PoloniexApi {
     /// ```json
     /// {"LTC":{"available":"5.015","onOrders":"1.0025","btcValue":"0.078"},"NXT":{...}, ... }
     /// ```
-    pub fn return_complete_balances(&mut self) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("account", "all");
-        self.private_query("returnCompleteBalances", &params)
+    pub fn return_complete_balances(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_complete_balances())
     }
 
     /// Returns all of your deposit addresses.
@@ -316,9 +173,8 @@ impl PoloniexApi {
     /// {"BTC":"19YqztHmspv2egyD6jQM3yn81x5t5krVdJ","LTC":"LPgf9kjv9H1Vuh4XSaKhzBe8JHdou1WgUB",
     /// ... "ITC":"Press Generate.." ... }
     /// ```
-    pub fn return_deposit_addresses(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.private_query("returnDepositAddresses", &params)
+    pub fn return_deposit_addresses(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_deposit_addresses())
     }
 
     /// Generates a new deposit address for the currency specified by the "currency" POST parameter.
@@ -328,10 +184,8 @@ impl PoloniexApi {
     /// ```json
     /// {"success":1,"response":"CKXbbs8FAVbtEa397gJHSutmrdrBrhUMxe"}
     /// ```
-    pub fn generate_new_address(&mut self, currency: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currency", currency);
-        self.private_query("generateNewAddress", &params)
+    pub fn generate_new_address(&self, currency: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.generate_new_address(currency))
     }
 
     /// Returns your deposit and withdrawal history within a range, specified by the "start" and
@@ -354,11 +208,8 @@ impl PoloniexApi {
     /// "status":"COMPLETE: 36e483efa6aff9fd53a235177579d98451c4eb237c210e66cd2b9a2d4a988f8e",
     /// "ipAddress":"..."}]}
     /// ```
-    pub fn return_deposits_withdrawals(&mut self, start: &str, end: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("start", start);
-        params.insert("end", end);
-        self.private_query("returnDepositsWithdrawals", &params)
+    pub fn return_deposits_withdrawals(&self, start: &str, end: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_deposits_withdrawals(start, end))
     }
 
     ///Returns your open orders for a given market, specified by the "currencyPair" POST parameter,
@@ -377,10 +228,8 @@ impl PoloniexApi {
     /// "amount":"100","total":"2.5"},
     /// {"orderNumber":"120467","type":"sell","rate":"0.04","amount":"100","total":"4"}], ... }
     /// ```
-    pub fn return_open_orders(&mut self, currency_pair: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        self.private_query("returnOpenOrders", &params)
+    pub fn return_open_orders(&self, currency_pair: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_open_orders(currency_pair))
     }
 
     /// Returns your trade history for a given market, specified by the "currencyPair" POST
@@ -414,12 +263,8 @@ impl PoloniexApi {
     /// "orderNumber": "12603319116", "type": "sell", "category": "marginTrade" }, ... ],
     /// "BTC_LTC":[ ... ] ... }
     /// ```
-    pub fn return_private_trade_history(&mut self, currency_pair: &str, start: &str, end: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        params.insert("start", start);
-        params.insert("end", end);
-        self.private_query("returnTradeHistory", &params)
+    pub fn return_private_trade_history(&self, currency_pair: &str, start: &str, end: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_private_trade_history(currency_pair, start, end))
     }
 
     /// Returns all trades involving a given order, specified by the "orderNumber" POST parameter.
@@ -433,10 +278,10 @@ impl PoloniexApi {
     /// "rate": "0.00018500", "amount": "455.34206390", "total": "0.08423828", "fee": "0.00200000",
     /// "date": "2016-03-14 01:04:36"}, ...]
     /// ```
-    pub fn return_order_trades(&mut self, order_number: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("orderNumber", order_number);
-        self.private_query("returnOrderTrades", &params)
+    ///
+    /// The array is returned in the "data" field of the map.
+    pub fn return_order_trades(&self, order_number: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_order_trades(order_number))
     }
 
     /// Places a limit buy order in a given market. Required POST parameters are "currencyPair",
@@ -450,39 +295,27 @@ impl PoloniexApi {
     /// "date":"2014-10-18 23:03:21", "rate":"0.00000173","total":"0.00058625","tradeID":"16164",
     /// "type":"buy"}]}
     /// ```
-    pub fn buy<O>(&mut self, currency_pair: &str, rate: &str, amount: &str, option: O) -> Result<Map<String, Value>>
+    pub fn buy<O>(&self, currency_pair: &str, rate: &str, amount: &str, option: O) -> Result<Map<String, Value>>
     where
         O: Into<Option<PlaceOrderOption>>,
     {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        params.insert("rate", rate);
-        params.insert("amount", amount);
-        option.into().map(|o| params.insert(o.repr(), "1"));
-        self.private_query("buy", &params)
+        self.runtime.block_on(self.inner.buy(currency_pair, rate, amount, option))
     }
 
     /// Places a sell order in a given market. Parameters and output are the same as for the buy
     /// method.
-    pub fn sell<O>(&mut self, currency_pair: &str, rate: &str, amount: &str, option: O) -> Result<Map<String, Value>>
+    pub fn sell<O>(&self, currency_pair: &str, rate: &str, amount: &str, option: O) -> Result<Map<String, Value>>
     where
         O: Into<Option<PlaceOrderOption>>,
     {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        params.insert("rate", rate);
-        params.insert("amount", amount);
-        option.into().map(|o| params.insert(o.repr(), "1"));
-        self.private_query("sell", &params)
+        self.runtime.block_on(self.inner.sell(currency_pair, rate, amount, option))
     }
 
     /// Cancels an order you have placed in a given market.
     /// Required POST parameter is "orderNumber". If successful, the method will return:
     /// {"success":1}
-    pub fn cancel_order(&mut self, order_number: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("orderNumber", order_number);
-        self.private_query("cancelOrder", &params)
+    pub fn cancel_order(&self, order_number: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.cancel_order(order_number))
     }
 
     /// Cancels an order and places a new one of the same type in a single atomic transaction,
@@ -497,16 +330,11 @@ impl PoloniexApi {
     /// ```json
     /// {"success":1,"orderNumber":"239574176","resultingTrades":{"BTC_BTS":[]}}
     /// ```
-    pub fn move_order<O>(&mut self, order_number: &str, rate: &str, option: O) -> Result<Map<String, Value>>
+    pub fn move_order<O>(&self, order_number: &str, rate: &str, option: O) -> Result<Map<String, Value>>
     where
         O: Into<Option<MoveOrderOption>>,
     {
-        // TODO: add optional parameters
-        let mut params = HashMap::new();
-        params.insert("orderNumber", order_number);
-        params.insert("rate", rate);
-        option.into().map(|o| params.insert(o.repr(), "1"));
-        self.private_query("moveOrder", &params)
+        self.runtime.block_on(self.inner.move_order(order_number, rate, option))
     }
 
     /// Immediately places a withdrawal for a given currency, with no email confirmation.
@@ -519,12 +347,8 @@ impl PoloniexApi {
     /// ```json
     /// {"response":"Withdrew 2398 NXT."}
     /// ```
-    pub fn withdraw(&mut self, currency: &str, amount: &str, address: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currency", currency);
-        params.insert("amount", amount);
-        params.insert("address", address);
-        self.private_query("withdraw", &params)
+    pub fn withdraw(&self, currency: &str, amount: &str, address: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.withdraw(currency, amount, address))
     }
 
     /// If you are enrolled in the maker-taker fee schedule, returns your current
@@ -537,9 +361,8 @@ impl PoloniexApi {
     /// {"makerFee": "0.00140000", "takerFee": "0.00240000", "thirtyDayVolume": "612.00248891",
     /// "nextTier": "1200.00000000"}
     /// ```
-    pub fn return_free_info(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.private_query("returnFeeInfo", &params)
+    pub fn return_free_info(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_free_info())
     }
 
     /// Returns your balances sorted by account. You may optionally specify the "account" POST
@@ -555,10 +378,8 @@ impl PoloniexApi {
     /// "margin":{"BTC":"3.90015637", "DASH":"250.00238240","XMR":"497.12028113"},
     /// "lending":{"DASH":"0.01174765","LTC":"11.99936230"}}
     /// ```
-    pub fn return_available_account_balances(&mut self, account: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("account", account);
-        self.private_query("returnAvailableAccountBalances", &params)
+    pub fn return_available_account_balances(&self, account: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_available_account_balances(account))
     }
 
     /// Returns your current tradable balances for each currency in each market for which
@@ -571,9 +392,8 @@ impl PoloniexApi {
     /// {"BTC_DASH":{"BTC":"8.50274777","DASH":"654.05752077"},"BTC_LTC":{"BTC":"8.50274777",
     /// "LTC":"1214.67825290"},"BTC_XMR":{"BTC":"8.50274777","XMR":"3696.84685650"}}
     /// ```
-    pub fn return_tradable_balances(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.private_query("returnTradableBalances", &params)
+    pub fn return_tradable_balances(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_tradable_balances())
     }
 
     /// Transfers funds from one account to another (e.g. from your exchange account to your
@@ -585,13 +405,11 @@ impl PoloniexApi {
     /// ```json
     /// {"success":1,"message":"Transferred 2 BTC from exchange to margin account."}
     /// ```
-    pub fn transfer_balance(&mut self, currency: &str, amount: &str, from_account: &str, to_account: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currency", currency);
-        params.insert("amount", amount);
-        params.insert("fromAccount", from_account);
-        params.insert("toAccount", to_account);
-        self.private_query("transferBalance", &params)
+    pub fn transfer_balance(&self, currency: &str, amount: &str, from_account: &str, to_account: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.transfer_balance(currency,
+                                                          amount,
+                                                          from_account,
+                                                          to_account))
     }
 
     /// Returns a summary of your entire margin account. This is the same information you will
@@ -603,9 +421,8 @@ impl PoloniexApi {
     /// {"totalValue": "0.00346561","pl": "-0.00001220","lendingFees": "0.00000000",
     /// "netValue": "0.00345341","totalBorrowedValue": "0.00123220","currentMargin": "2.80263755"}
     /// ```
-    pub fn return_margin_account_summary(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.private_query("returnMarginAccountSummary", &params)
+    pub fn return_margin_account_summary(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_margin_account_summary())
     }
 
     /// Places a margin buy order in a given market. Required POST parameters are
@@ -620,13 +437,8 @@ impl PoloniexApi {
     /// "resultingTrades":{"BTC_DASH":[{"amount":"1.00000000","date":"2015-05-10 22:47:05",
     /// "rate":"0.01383692","total":"0.01383692","tradeID":"1213556","type":"buy"}]}}
     /// ```
-    pub fn margin_buy(&mut self, currency_pair: &str, rate: &str, amount: &str, lending_rate: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        params.insert("rate", rate);
-        params.insert("amount", amount);
-        params.insert("lendingRate", lending_rate);
-        self.private_query("marginBuy", &params)
+    pub fn margin_buy(&self, currency_pair: &str, rate: &str, amount: &str, lending_rate: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.margin_buy(currency_pair, rate, amount, lending_rate))
     }
 
     /// Places a margin sell order in a given market. Required POST parameters are
@@ -641,13 +453,8 @@ impl PoloniexApi {
     /// "resultingTrades":{"BTC_DASH":[{"amount":"1.00000000","date":"2015-05-10 22:47:05",
     /// "rate":"0.01383692","total":"0.01383692","tradeID":"1213556","type":"sell"}]}}
     /// ```
-    pub fn margin_sell(&mut self, currency_pair: &str, rate: &str, amount: &str, lending_rate: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        params.insert("rate", rate);
-        params.insert("amount", amount);
-        params.insert("lendingRate", lending_rate);
-        self.private_query("marginSell", &params)
+    pub fn margin_sell(&self, currency_pair: &str, rate: &str, amount: &str, lending_rate: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.margin_sell(currency_pair, rate, amount, lending_rate))
     }
 
     /// Returns information about your margin position in a given market, specified by the
@@ -663,10 +470,8 @@ impl PoloniexApi {
     /// {"amount":"40.94717831","total":"-0.09671314","basePrice":"0.00236190",
     /// "liquidationPrice":-1,"pl":"-0.00058655", "lendingFees":"-0.00000038","type":"long"}
     /// ```
-    pub fn get_margin_position(&mut self, currency_pair: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        self.private_query("getMarginPosition", &params)
+    pub fn get_margin_position(&self, currency_pair: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.get_margin_position(currency_pair))
     }
 
     /// Closes your margin position in a given market (specified by the "currencyPair" POST
@@ -682,10 +487,8 @@ impl PoloniexApi {
     /// {"amount":"24.00289920","date":"2015-05-10 22:38:49","rate":"0.00235321",
     /// "total":"0.05648386","tradeID":"1213347","type":"sell"}]}}
     /// ```
-    pub fn close_margin_position(&mut self, currency_pair: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currencyPair", currency_pair);
-        self.private_query("closeMarginPosition", &params)
+    pub fn close_margin_position(&self, currency_pair: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.close_margin_position(currency_pair))
     }
 
     /// Creates a loan offer for a given currency. Required POST parameters are "currency",
@@ -696,14 +499,12 @@ impl PoloniexApi {
     /// ```json
     /// {"success":1,"message":"Loan order placed.","orderID":10590}
     /// ```
-    pub fn create_loan_offer(&mut self, currency: &str, amount: &str, duration: &str, auto_renew: &str, lending_rate: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("currency", currency);
-        params.insert("amount", amount);
-        params.insert("duration", duration);
-        params.insert("autoRenew", auto_renew);
-        params.insert("lendingRate", lending_rate);
-        self.private_query("createLoanOffer", &params)
+    pub fn create_loan_offer(&self, currency: &str, amount: &str, duration: &str, auto_renew: &str, lending_rate: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.create_loan_offer(currency,
+                                                           amount,
+                                                           duration,
+                                                           auto_renew,
+                                                           lending_rate))
     }
 
     /// Cancels a loan offer specified by the "orderNumber" POST parameter.
@@ -713,10 +514,8 @@ impl PoloniexApi {
     /// ```json
     /// {"success":1,"message":"Loan offer canceled."}
     /// ```
-    pub fn cancel_loan_offer(&mut self, order_number: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("orderNumber", order_number);
-        self.private_query("cancelLoanOffer", &params)
+    pub fn cancel_loan_offer(&self, order_number: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.cancel_loan_offer(order_number))
     }
 
     /// Returns your open loan offers for each currency.
@@ -728,9 +527,8 @@ impl PoloniexApi {
     /// "date":"2015-05-10 23:33:50"}],"LTC":[{"id":10598,"rate":"0.00002100",
     /// "amount":"10.00000000","duration":2,"autoRenew":1,"date":"2015-05-10 23:34:35"}]}
     /// ```
-    pub fn return_open_loan_offers(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.private_query("returnOpenLoanOffers", &params)
+    pub fn return_open_loan_offers(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_open_loan_offers())
     }
 
     /// Returns your active loans for each currency.
@@ -745,9 +543,8 @@ impl PoloniexApi {
     /// "used":[{"id":75238,"currency":"BTC","rate":"0.00020000","amount":"0.04843834","range":2,
     /// "date":"2015-05-10 23:51:12","fees":"-0.00000001"}]}
     /// ```
-    pub fn return_active_loans(&mut self) -> Result<Map<String, Value>> {
-        let params = HashMap::new();
-        self.private_query("returnActiveLoans", &params)
+    pub fn return_active_loans(&self) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_active_loans())
     }
 
     /// Returns your lending history within a time range specified by the "start" and "end" POST
@@ -761,12 +558,8 @@ impl PoloniexApi {
     /// "duration": "0.47610000", "interest": "0.00001196", "fee": "-0.00000179",
     /// "earned": "0.00001017", "open": "2016-09-28 06:47:26", "close": "2016-09-28 18:13:03" }]
     /// ```
-    pub fn return_lending_history(&mut self, start: &str, end: &str, limit: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("start", start);
-        params.insert("end", end);
-        params.insert("limit", limit);
-        self.private_query("returnLendingHistory", &params)
+    pub fn return_lending_history(&self, start: &str, end: &str, limit: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.return_lending_history(start, end, limit))
     }
 
     /// Toggles the autoRenew setting on an active loan, specified by the "orderNumber" POST
@@ -777,50 +570,13 @@ impl PoloniexApi {
     /// ```json
     /// {"success":1,"message":0}
     /// ```
-    pub fn toggle_auto_renew(&mut self, order_number: &str) -> Result<Map<String, Value>> {
-        let mut params = HashMap::new();
-        params.insert("orderNumber", order_number);
-        self.private_query("toggleAutoRenew", &params)
+    pub fn toggle_auto_renew(&self, order_number: &str) -> Result<Map<String, Value>> {
+        self.runtime.block_on(self.inner.toggle_auto_renew(order_number))
     }
 }
 
-#[cfg(test)]
-mod poloniex_api_tests {
-    use super::*;
-
-    #[test]
-    fn should_block_or_not_block_when_enabled_or_disabled() {
-        let mut api = PoloniexApi {
-            last_request: helpers::get_unix_timestamp_ms(),
-            api_key: "".to_string(),
-            api_secret: "".to_string(),
-            http_client: Client::new(),
-            burst: false,
-        };
-
-        let mut counter = 0;
-        loop {
-            api.set_burst(false);
-            let start = helpers::get_unix_timestamp_ms();
-            api.block_or_continue();
-            api.last_request = helpers::get_unix_timestamp_ms();
-
-            let difference = api.last_request - start;
-            assert!(difference >= 166);
-            assert!(difference < 1000);
-
-            api.set_burst(true);
-            let start = helpers::get_unix_timestamp_ms();
-            api.block_or_continue();
-            api.last_request = helpers::get_unix_timestamp_ms();
-
-            let difference = api.last_request - start;
-            assert!(difference < 10);
-
-            counter = counter + 1;
-            if counter >= 3 {
-                break;
-            }
-        }
+impl fmt::Debug for PoloniexApi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PoloniexApi").field(&self.inner).finish()
     }
 }
@@ -0,0 +1,905 @@
+//! Use this module to interact asynchronously with Poloniex exchange.
+//! `AsyncPoloniexApi` is the implementation behind the blocking client of the `api` module.
+//! See examples for more informations.
+
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha512;
+
+use data_encoding::HEXLOWER;
+
+use serde_json::Value;
+use serde_json::value::Map;
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crate::error::*;
+use crate::helpers;
+use crate::rate_limit::Scope;
+use crate::retry::{self, Idempotency};
+
+use crate::exchange::Exchange;
+use crate::client::ClientConfig;
+use crate::coinnect::Credentials;
+use crate::poloniex::utils;
+use crate::poloniex::typed_api::AsyncPoloniexTypedApi;
+use crate::transport::{AsyncHttpTransport, HttpRequest, Method, ReqwestTransport};
+use crate::types::Markets;
+
+#[derive(Debug, Copy, Clone)]
+pub enum PlaceOrderOption {
+    FillOrKill,
+    ImmediateOrCancel,
+    PostOnly,
+}
+impl PlaceOrderOption {
+    fn repr(&self) -> &'static str {
+        match *self {
+            PlaceOrderOption::FillOrKill => "fillOrKill",
+            PlaceOrderOption::ImmediateOrCancel => "immediateOrCancel",
+            PlaceOrderOption::PostOnly => "postOnly",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum MoveOrderOption {
+    ImmediateOrCancel,
+    PostOnly,
+}
+impl MoveOrderOption {
+    fn repr(&self) -> &'static str {
+        match *self {
+            MoveOrderOption::ImmediateOrCancel => "immediateOrCancel",
+            MoveOrderOption::PostOnly => "postOnly",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AsyncPoloniexApi {
+    api_key: String,
+    api_secret: String,
+    http_client: Box<dyn AsyncHttpTransport>,
+    config: ClientConfig,
+    /// Markets returned by `ExchangeApi::markets`, fetched on first use
+    pub(crate) markets: RwLock<Option<Markets>>,
+}
+
+impl AsyncPoloniexApi {
+    /// Create a new AsyncPoloniexApi by providing an API key & API secret
+    pub fn new<C: Credentials>(creds: C) -> Result<AsyncPoloniexApi> {
+        AsyncPoloniexApi::new_with_transport(creds, Box::new(ReqwestTransport::new()?))
+    }
+
+    /// Create a new AsyncPoloniexApi sending its requests through the given transport
+    pub fn new_with_transport<C: Credentials>(creds: C,
+                                              transport: Box<dyn AsyncHttpTransport>)
+                                              -> Result<AsyncPoloniexApi> {
+        if creds.exchange() != Exchange::Poloniex {
+            return Err(ErrorKind::InvalidConfigType(Exchange::Poloniex, creds.exchange()).into());
+        }
+
+        let api_key = creds.get("api_key").unwrap_or_default();
+        let config = ClientConfig::shared(Exchange::Poloniex, &api_key);
+
+        Ok(AsyncPoloniexApi {
+            api_key,
+            api_secret: creds.get("api_secret").unwrap_or_default(),
+            http_client: transport,
+            config,
+            markets: RwLock::new(None),
+        })
+    }
+
+    /// Return the settings of the client: its RateLimiter, RetryPolicy and NonceProvider.
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Change the settings of the client, ie. `api.config_mut().set_retry_policy(policy)`.
+    pub fn config_mut(&mut self) -> &mut ClientConfig {
+        &mut self.config
+    }
+
+    /// Return a view of the client whose methods return the typed structs of the `responses`
+    /// module instead of JSON maps.
+    pub fn typed(&self) -> AsyncPoloniexTypedApi<'_> {
+        AsyncPoloniexTypedApi::new(self)
+    }
+
+    async fn public_query(&self,
+                          method: &str,
+                          params: &HashMap<&str, &str>)
+                          -> Result<Map<String, Value>> {
+        self.config
+            .retry_policy()
+            .run_checked(Idempotency::Idempotent,
+                         || self.send_public_query(method, params),
+                         |response| utils::parse_result(response, method).map(|_| ()))
+            .await
+            .map_err(|err| err.with_endpoint(Exchange::Poloniex, method))
+    }
+
+    async fn private_query(&self,
+                           method: &str,
+                           params: &HashMap<&str, &str>)
+                           -> Result<Map<String, Value>> {
+        let idempotency = retry::get_idempotency(Exchange::Poloniex, Scope::Private, method);
+        self.config
+            .retry_policy()
+            .run_checked(idempotency,
+                         || self.send_private_query(method, params),
+                         |response| utils::parse_result(response, method).map(|_| ()))
+            .await
+            .map_err(|err| err.with_endpoint(Exchange::Poloniex, method))
+    }
+
+    async fn send_public_query(&self,
+                               method: &str,
+                               params: &HashMap<&str, &str>)
+                              -> Result<Map<String, Value>> {
+        let mut params = params.clone();
+        helpers::strip_empties(&mut params);
+        let url = "https://poloniex.com/public?command=".to_string() + method + "&" + &helpers::url_encode_hashmap(&params);
+
+        self.config.rate_limiter().acquire(Scope::Public, method).await?;
+        let request = HttpRequest::new(Method::Get, &url);
+        let response = self.http_client.send(request).await?.error_for_status()?;
+
+        if method == "returnChartData" || method == "returnTradeHistory" {
+            return utils::deserialize_json_array(&response.body);
+        }
+        utils::deserialize_json(&response.body)
+    }
+
+    async fn send_private_query(&self,
+                                method: &str,
+                                params: &HashMap<&str, &str>)
+                               -> Result<Map<String, Value>> {
+        self.config.rate_limiter().acquire(Scope::Private, method).await?;
+        let nonce = self.config.nonce_provider().next_nonce()?.to_string();
+        let mut post_params = params.clone();
+        post_params.insert("command", method);
+        post_params.insert("nonce", &nonce);
+        helpers::strip_empties(&mut post_params);
+        let post_data = helpers::url_encode_hashmap(&post_params);
+
+        let mut mac = Hmac::<Sha512>::new_from_slice(self.api_secret.as_bytes()).unwrap();
+        mac.update(post_data.as_bytes());
+
+        let sign = HEXLOWER.encode(&mac.finalize().into_bytes());
+
+        let request = HttpRequest::new(Method::Post, "https://poloniex.com/tradingApi")
+            .header("Key", &self.api_key)
+            .header("Sign", &sign)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(&post_data);
+
+        let response = self.http_client.send(request).await?.error_for_status()?;
+
+        match method {
+            "returnChartData" | "returnOrderTrades" | "returnOpenOrders" |
+            "returnTradeHistory" | "returnOpenLoanOffers" | "returnLendingHistory" => {
+                utils::deserialize_json_array(&response.body)
+            }
+            _ => utils::deserialize_json(&response.body),
+        }
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// {
+    /// "BTC_LTC":{
+    /// "last":"0.0251","lowestAsk":"0.02589999","highestBid":"0.0251",
+    /// "percentChange":"0.02390438","baseVolume":"6.16485315","quoteVolume":"245.82513926"},
+    /// "BTC_NXT":{
+    /// "last":"0.00005730","lowestAsk":"0.00005710","highestBid":"0.00004903",
+    /// "percentChange":"0.16701570","baseVolume":"0.45347489","quoteVolume":"9094"},
+    /// ... }
+    /// ```
+    pub async fn return_ticker(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.public_query("returnTicker", &params).await
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"BTC_LTC":{"BTC":"2.23248854","LTC":"87.10381314"},"BTC_NXT":{"BTC":"0.981616",
+    /// "NXT":"14145"},
+    /// ... "totalBTC":"81.89657704","totalLTC":"78.52083806"}
+    /// ```
+    pub async fn return_24_volume(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.public_query("return24Volume", &params).await
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"asks":[[0.00007600,1164],[0.00007620,1300], ... ], "bids":[[0.00006901,200],
+    /// [0.00006900,408], ... ], "isFrozen": 0, "seq": 18849}
+    /// ```
+    pub async fn return_order_book(&self,
+                                   currency_pair: &str,
+                                   depth: &str)
+                                  -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        params.insert("depth", depth);
+        self.public_query("returnOrderBook", &params).await
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// [{"date":"2014-02-10 04:23:23","type":"buy","rate":"0.00007600","amount":"140",
+    /// "total":"0.01064"},
+    /// {"date":"2014-02-10 01:19:37","type":"buy","rate":"0.00007600","amount":"655",
+    /// "total":"0.04978"}, ... ]
+    /// ```
+    ///
+    /// The array is returned in the "data" field of the map.
+    pub async fn return_trade_history(&self,
+                                      currency_pair: &str,
+                                      start: &str,
+                                      end: &str)
+                                     -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        params.insert("start", start);
+        params.insert("end", end);
+        self.public_query("returnTradeHistory", &params).await
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"data": {"date":1405699200,"high":0.0045388,"low":0.00403001,"open":0.00404545,"close":0.00427592,
+    /// "volume":44.11655644,"quoteVolume":10259.29079097,"weightedAverage":0.00430015}, ...}
+    /// ```
+    pub async fn return_chart_data(&self,
+                                   currency_pair: &str,
+                                   start: &str,
+                                   end: &str,
+                                   period: &str)
+                                  -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        params.insert("start", start);
+        params.insert("end", end);
+        params.insert("period", period);
+        self.public_query("returnChartData", &params).await
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"1CR":{"maxDailyWithdrawal":10000,"txFee":0.01,"minConf":3,"disabled":0},
+    /// "ABY":{"maxDailyWithdrawal":10000000,"txFee":0.01,"minConf":8,"disabled":0}, ... }
+    /// ```
+    pub async fn return_currencies(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.public_query("returnCurrencies", &params).await
+    }
+
+    /// Sample output :
+    ///
+    /// ```json
+    /// {"offers":[{"rate":"0.00200000","amount":"64.66305732","rangeMin":2,"rangeMax":8}, ... ],
+    /// "demands":[{"rate":"0.00170000","amount":"26.54848841","rangeMin":2,"rangeMax":2}, ... ]}
+    /// ```
+    pub async fn return_loan_orders(&self, currency: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currency", currency);
+        self.public_query("returnLoanOrders", &params).await
+    }
+
+    /// Returns all of your available balances.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"BTC":"0.59098578","LTC":"3.31117268", ... }
+    /// ```
+    pub async fn return_balances(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.private_query("returnBalances", &params).await
+    }
+
+    /// Returns all of your balances, including available balance, balance on orders,
+    /// and the estimated BTC value of your balance. By default, this call is limited to your
+    /// exchange account; set the "account" POST parameter to "all" to include your margin and
+    /// lending accounts.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"LTC":{"available":"5.015","onOrders":"1.0025","btcValue":"0.078"},"NXT":{...}, ... }
+    /// ```
+    pub async fn return_complete_balances(&self) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("account", "all");
+        self.private_query("returnCompleteBalances", &params).await
+    }
+
+    /// Returns all of your deposit addresses.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"BTC":"19YqztHmspv2egyD6jQM3yn81x5t5krVdJ","LTC":"LPgf9kjv9H1Vuh4XSaKhzBe8JHdou1WgUB",
+    /// ... "ITC":"Press Generate.." ... }
+    /// ```
+    pub async fn return_deposit_addresses(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.private_query("returnDepositAddresses", &params).await
+    }
+
+    /// Generates a new deposit address for the currency specified by the "currency" POST parameter.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success":1,"response":"CKXbbs8FAVbtEa397gJHSutmrdrBrhUMxe"}
+    /// ```
+    pub async fn generate_new_address(&self, currency: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currency", currency);
+        self.private_query("generateNewAddress", &params).await
+    }
+
+    /// Returns your deposit and withdrawal history within a range, specified by the "start" and
+    /// "end" POST parameters,
+    /// both of which should be given as UNIX timestamps.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"deposits":
+    /// [{"currency":"BTC","address":"...","amount":"0.01006132","confirmations":10,
+    /// "txid":"17f819a91369a9ff6c4a34216d434597cfc1b4a3d0489b46bd6f924137a47701",
+    /// "timestamp":1399305798,"status":"COMPLETE"},
+    /// {"currency":"BTC","address":"...","amount":"0.00404104","confirmations":10,
+    /// "txid":"7acb90965b252e55a894b535ef0b0b65f45821f2899e4a379d3e43799604695c",
+    /// "timestamp":1399245916,"status":"COMPLETE"}],
+    /// "withdrawals":[{"withdrawalNumber":134933,"currency":"BTC",
+    /// "address":"1N2i5n8DwTGzUq2Vmn9TUL8J1vdr1XBDFg","amount":"5.00010000",
+    /// "timestamp":1399267904,
+    /// "status":"COMPLETE: 36e483efa6aff9fd53a235177579d98451c4eb237c210e66cd2b9a2d4a988f8e",
+    /// "ipAddress":"..."}]}
+    /// ```
+    pub async fn return_deposits_withdrawals(&self,
+                                             start: &str,
+                                             end: &str)
+                                            -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("start", start);
+        params.insert("end", end);
+        self.private_query("returnDepositsWithdrawals", &params).await
+    }
+
+    ///Returns your open orders for a given market, specified by the "currencyPair" POST parameter,
+    /// e.g. "BTC_XCP". Set "currencyPair" to "all" to return open orders for all markets.
+    ///
+    /// Sample output for single market:
+    ///
+    /// ```json
+    /// [{"orderNumber":"120466","type":"sell","rate":"0.025","amount":"100","total":"2.5"},
+    /// {"orderNumber":"120467","type":"sell","rate":"0.04","amount":"100","total":"4"}, ... ]
+    /// ```
+    ///
+    /// ```json
+    /// Or, for all markets:
+    /// {"BTC_1CR":[],"BTC_AC":[{"orderNumber":"120466","type":"sell","rate":"0.025",
+    /// "amount":"100","total":"2.5"},
+    /// {"orderNumber":"120467","type":"sell","rate":"0.04","amount":"100","total":"4"}], ... }
+    /// ```
+    ///
+    /// The array of a single market is returned in the "data" field of the map.
+    pub async fn return_open_orders(&self, currency_pair: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        self.private_query("returnOpenOrders", &params).await
+    }
+
+    /// Returns your trade history for a given market, specified by the "currencyPair" POST
+    /// parameter.
+    /// You may specify "all" as the currencyPair to receive your trade history for all markets.
+    /// You may optionally specify a range via "start" and/or "end" POST parameters, given in UNIX
+    /// timestamp format;
+    /// if you do not specify a range, it will be limited to one day.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// [{ "globalTradeID": 25129732, "tradeID": "6325758", "date": "2016-04-05 08:08:40",
+    /// "rate": "0.02565498", "amount": "0.10000000", "total": "0.00256549", "fee": "0.00200000",
+    /// "orderNumber": "34225313575", "type": "sell", "category": "exchange" },
+    /// { "globalTradeID": 25129628, "tradeID": "6325741", "date": "2016-04-05 08:07:55",
+    /// "rate": "0.02565499", "amount": "0.10000000", "total": "0.00256549",
+    /// "fee": "0.00200000", "orderNumber": "34225195693", "type": "buy", "category": "exchange" },
+    /// ... ]
+    /// ```
+    ///
+    /// Or, for all markets:
+    ///
+    /// ```json
+    /// {"BTC_MAID": [ { "globalTradeID": 29251512, "tradeID": "1385888",
+    /// "date": "2016-05-03 01:29:55", "rate": "0.00014243", "amount": "353.74692925",
+    /// "total": "0.05038417", "fee": "0.00200000", "orderNumber": "12603322113", "type": "buy",
+    /// "category": "settlement" },
+    /// { "globalTradeID": 29251511, "tradeID": "1385887", "date": "2016-05-03 01:29:55",
+    /// "rate": "0.00014111", "amount": "311.24262497", "total": "0.04391944", "fee": "0.00200000",
+    /// "orderNumber": "12603319116", "type": "sell", "category": "marginTrade" }, ... ],
+    /// "BTC_LTC":[ ... ] ... }
+    /// ```
+    ///
+    /// The array of a single market is returned in the "data" field of the map.
+    pub async fn return_private_trade_history(&self,
+                                              currency_pair: &str,
+                                              start: &str,
+                                              end: &str)
+                                             -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        params.insert("start", start);
+        params.insert("end", end);
+        self.private_query("returnTradeHistory", &params).await
+    }
+
+    /// Returns all trades involving a given order, specified by the "orderNumber" POST parameter.
+    /// If no trades for the order have occurred or you specify an order that does not belong to
+    /// you, you will receive an error.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// [{"globalTradeID": 20825863, "tradeID": 147142, "currencyPair": "BTC_XVC", "type": "buy",
+    /// "rate": "0.00018500", "amount": "455.34206390", "total": "0.08423828", "fee": "0.00200000",
+    /// "date": "2016-03-14 01:04:36"}, ...]
+    /// ```
+    ///
+    /// The array is returned in the "data" field of the map.
+    pub async fn return_order_trades(&self, order_number: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("orderNumber", order_number);
+        self.private_query("returnOrderTrades", &params).await
+    }
+
+    /// Places a limit buy order in a given market. Required POST parameters are "currencyPair",
+    /// "rate", and "amount".
+    /// If successful, the method will return the order number.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"orderNumber":31226040,"resultingTrades":[{"amount":"338.8732",
+    /// "date":"2014-10-18 23:03:21", "rate":"0.00000173","total":"0.00058625","tradeID":"16164",
+    /// "type":"buy"}]}
+    /// ```
+    pub async fn buy<O>(&self, currency_pair: &str, rate: &str, amount: &str, option: O) -> Result<Map<String, Value>>
+          where
+              O: Into<Option<PlaceOrderOption>>,
+          {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        params.insert("rate", rate);
+        params.insert("amount", amount);
+        option.into().map(|o| params.insert(o.repr(), "1"));
+        self.private_query("buy", &params).await
+    }
+
+    /// Places a sell order in a given market. Parameters and output are the same as for the buy
+    /// method.
+    pub async fn sell<O>(&self, currency_pair: &str, rate: &str, amount: &str, option: O) -> Result<Map<String, Value>>
+          where
+              O: Into<Option<PlaceOrderOption>>,
+          {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        params.insert("rate", rate);
+        params.insert("amount", amount);
+        option.into().map(|o| params.insert(o.repr(), "1"));
+        self.private_query("sell", &params).await
+    }
+
+    /// Cancels an order you have placed in a given market.
+    /// Required POST parameter is "orderNumber". If successful, the method will return:
+    /// {"success":1}
+    pub async fn cancel_order(&self, order_number: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("orderNumber", order_number);
+        self.private_query("cancelOrder", &params).await
+    }
+
+    /// Cancels an order and places a new one of the same type in a single atomic transaction,
+    /// meaning either both operations will succeed or both will fail.
+    /// Required POST parameters are "orderNumber" and "rate"; you may optionally
+    /// specify "amount" if you wish to change the amount of the new order.
+    /// "postOnly" or "immediateOrCancel" may be specified for exchange orders, but will have no
+    /// effect on margin orders.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success":1,"orderNumber":"239574176","resultingTrades":{"BTC_BTS":[]}}
+    /// ```
+    pub async fn move_order<O>(&self, order_number: &str, rate: &str, option: O) -> Result<Map<String, Value>>
+          where
+              O: Into<Option<MoveOrderOption>>,
+          {
+        // TODO: add optional parameters
+        let mut params = HashMap::new();
+        params.insert("orderNumber", order_number);
+        params.insert("rate", rate);
+        option.into().map(|o| params.insert(o.repr(), "1"));
+        self.private_query("moveOrder", &params).await
+    }
+
+    /// Immediately places a withdrawal for a given currency, with no email confirmation.
+    /// In order to use this method, the withdrawal privilege must be enabled for your API key.
+    /// Required POST parameters are "currency", "amount", and "address".
+    /// For XMR withdrawals, you may optionally specify "paymentId".
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"response":"Withdrew 2398 NXT."}
+    /// ```
+    pub async fn withdraw(&self,
+                          currency: &str,
+                          amount: &str,
+                          address: &str)
+                         -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currency", currency);
+        params.insert("amount", amount);
+        params.insert("address", address);
+        self.private_query("withdraw", &params).await
+    }
+
+    /// If you are enrolled in the maker-taker fee schedule, returns your current
+    /// trading fees and trailing 30-day volume in BTC. This information is updated once every
+    /// 24 hours.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"makerFee": "0.00140000", "takerFee": "0.00240000", "thirtyDayVolume": "612.00248891",
+    /// "nextTier": "1200.00000000"}
+    /// ```
+    pub async fn return_free_info(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.private_query("returnFeeInfo", &params).await
+    }
+
+    /// Returns your balances sorted by account. You may optionally specify the "account" POST
+    /// parameter if you wish to fetch only the balances of one account. Please note that balances
+    /// in your margin account may not be accessible if you have any open margin positions or
+    /// orders.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"exchange":{"BTC":"1.19042859","BTM":"386.52379392","CHA":"0.50000000",
+    /// "DASH":"120.00000000", "STR":"3205.32958001", "VNL":"9673.22570147"},
+    /// "margin":{"BTC":"3.90015637", "DASH":"250.00238240","XMR":"497.12028113"},
+    /// "lending":{"DASH":"0.01174765","LTC":"11.99936230"}}
+    /// ```
+    pub async fn return_available_account_balances(&self,
+                                                   account: &str)
+                                                  -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("account", account);
+        self.private_query("returnAvailableAccountBalances", &params).await
+    }
+
+    /// Returns your current tradable balances for each currency in each market for which
+    /// margin trading is enabled. Please note that these balances may vary continually with
+    /// market conditions.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"BTC_DASH":{"BTC":"8.50274777","DASH":"654.05752077"},"BTC_LTC":{"BTC":"8.50274777",
+    /// "LTC":"1214.67825290"},"BTC_XMR":{"BTC":"8.50274777","XMR":"3696.84685650"}}
+    /// ```
+    pub async fn return_tradable_balances(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.private_query("returnTradableBalances", &params).await
+    }
+
+    /// Transfers funds from one account to another (e.g. from your exchange account to your
+    /// margin account). Required POST parameters are "currency", "amount", "fromAccount",
+    /// and "toAccount".
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success":1,"message":"Transferred 2 BTC from exchange to margin account."}
+    /// ```
+    pub async fn transfer_balance(&self,
+                                  currency: &str,
+                                  amount: &str,
+                                  from_account: &str,
+                                  to_account: &str)
+                                 -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currency", currency);
+        params.insert("amount", amount);
+        params.insert("fromAccount", from_account);
+        params.insert("toAccount", to_account);
+        self.private_query("transferBalance", &params).await
+    }
+
+    /// Returns a summary of your entire margin account. This is the same information you will
+    /// find in the Margin Account section of the Margin Trading page, under the Markets list.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"totalValue": "0.00346561","pl": "-0.00001220","lendingFees": "0.00000000",
+    /// "netValue": "0.00345341","totalBorrowedValue": "0.00123220","currentMargin": "2.80263755"}
+    /// ```
+    pub async fn return_margin_account_summary(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.private_query("returnMarginAccountSummary", &params).await
+    }
+
+    /// Places a margin buy order in a given market. Required POST parameters are
+    /// "currencyPair", "rate", and "amount". You may optionally specify a maximum lending
+    /// rate using the "lendingRate" parameter. If successful, the method will return the order
+    /// number and any trades immediately resulting from your order.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success":1,"message":"Margin order placed.","orderNumber":"154407998",
+    /// "resultingTrades":{"BTC_DASH":[{"amount":"1.00000000","date":"2015-05-10 22:47:05",
+    /// "rate":"0.01383692","total":"0.01383692","tradeID":"1213556","type":"buy"}]}}
+    /// ```
+    pub async fn margin_buy(&self,
+                            currency_pair: &str,
+                            rate: &str,
+                            amount: &str,
+                            lending_rate: &str)
+                           -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        params.insert("rate", rate);
+        params.insert("amount", amount);
+        params.insert("lendingRate", lending_rate);
+        self.private_query("marginBuy", &params).await
+    }
+
+    /// Places a margin sell order in a given market. Required POST parameters are
+    /// "currencyPair", "rate", and "amount". You may optionally specify a maximum lending
+    /// rate using the "lendingRate" parameter. If successful, the method will return the order
+    /// number and any trades immediately resulting from your order.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success":1,"message":"Margin order placed.","orderNumber":"154407998",
+    /// "resultingTrades":{"BTC_DASH":[{"amount":"1.00000000","date":"2015-05-10 22:47:05",
+    /// "rate":"0.01383692","total":"0.01383692","tradeID":"1213556","type":"sell"}]}}
+    /// ```
+    pub async fn margin_sell(&self,
+                             currency_pair: &str,
+                             rate: &str,
+                             amount: &str,
+                             lending_rate: &str)
+                            -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        params.insert("rate", rate);
+        params.insert("amount", amount);
+        params.insert("lendingRate", lending_rate);
+        self.private_query("marginSell", &params).await
+    }
+
+    /// Returns information about your margin position in a given market, specified by the
+    /// "currencyPair" POST parameter. You may set "currencyPair" to "all" if you wish to fetch all
+    /// of your margin positions at once. If you have no margin position in the specified market,
+    /// "type" will be set to "none". "liquidationPrice" is an estimate, and does not necessarily
+    /// represent the price at which an actual forced liquidation will occur. If you have no
+    /// liquidation price, the value will be -1.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"amount":"40.94717831","total":"-0.09671314","basePrice":"0.00236190",
+    /// "liquidationPrice":-1,"pl":"-0.00058655", "lendingFees":"-0.00000038","type":"long"}
+    /// ```
+    pub async fn get_margin_position(&self, currency_pair: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        self.private_query("getMarginPosition", &params).await
+    }
+
+    /// Closes your margin position in a given market (specified by the "currencyPair" POST
+    /// parameter) using a market order. This call will also return success if you do not have an
+    /// open position in the specified market.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success":1,"message":"Successfully closed margin position.",
+    /// "resultingTrades":{"BTC_XMR":[{"amount":"7.09215901","date":"2015-05-10 22:38:49",
+    /// "rate":"0.00235337","total":"0.01669047","tradeID":"1213346","type":"sell"},
+    /// {"amount":"24.00289920","date":"2015-05-10 22:38:49","rate":"0.00235321",
+    /// "total":"0.05648386","tradeID":"1213347","type":"sell"}]}}
+    /// ```
+    pub async fn close_margin_position(&self,
+                                       currency_pair: &str)
+                                      -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currencyPair", currency_pair);
+        self.private_query("closeMarginPosition", &params).await
+    }
+
+    /// Creates a loan offer for a given currency. Required POST parameters are "currency",
+    /// "amount", "duration", "autoRenew" (0 or 1), and "lendingRate".
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success":1,"message":"Loan order placed.","orderID":10590}
+    /// ```
+    pub async fn create_loan_offer(&self,
+                                   currency: &str,
+                                   amount: &str,
+                                   duration: &str,
+                                   auto_renew: &str,
+                                   lending_rate: &str)
+                                  -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("currency", currency);
+        params.insert("amount", amount);
+        params.insert("duration", duration);
+        params.insert("autoRenew", auto_renew);
+        params.insert("lendingRate", lending_rate);
+        self.private_query("createLoanOffer", &params).await
+    }
+
+    /// Cancels a loan offer specified by the "orderNumber" POST parameter.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success":1,"message":"Loan offer canceled."}
+    /// ```
+    pub async fn cancel_loan_offer(&self, order_number: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("orderNumber", order_number);
+        self.private_query("cancelLoanOffer", &params).await
+    }
+
+    /// Returns your open loan offers for each currency.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"BTC":[{"id":10595,"rate":"0.00020000","amount":"3.00000000","duration":2,"autoRenew":1,
+    /// "date":"2015-05-10 23:33:50"}],"LTC":[{"id":10598,"rate":"0.00002100",
+    /// "amount":"10.00000000","duration":2,"autoRenew":1,"date":"2015-05-10 23:34:35"}]}
+    /// ```
+    ///
+    /// The empty array sent when there is no offer is returned in the "data" field of the map.
+    pub async fn return_open_loan_offers(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.private_query("returnOpenLoanOffers", &params).await
+    }
+
+    /// Returns your active loans for each currency.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"provided":[{"id":75073,"currency":"LTC","rate":"0.00020000","amount":"0.72234880",
+    /// "range":2,"autoRenew":0,"date":"2015-05-10 23:45:05","fees":"0.00006000"},
+    /// {"id":74961,"currency":"LTC","rate":"0.00002000","amount":"4.43860711","range":2,
+    /// "autoRenew":0,"date":"2015-05-10 23:45:05","fees":"0.00006000"}],
+    /// "used":[{"id":75238,"currency":"BTC","rate":"0.00020000","amount":"0.04843834","range":2,
+    /// "date":"2015-05-10 23:51:12","fees":"-0.00000001"}]}
+    /// ```
+    pub async fn return_active_loans(&self) -> Result<Map<String, Value>> {
+        let params = HashMap::new();
+        self.private_query("returnActiveLoans", &params).await
+    }
+
+    /// Returns your lending history within a time range specified by the "start" and "end" POST
+    /// parameters as UNIX timestamps. "limit" may also be specified to limit the number of rows
+    /// returned.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// [{ "id": 175589553, "currency": "BTC", "rate": "0.00057400", "amount": "0.04374404",
+    /// "duration": "0.47610000", "interest": "0.00001196", "fee": "-0.00000179",
+    /// "earned": "0.00001017", "open": "2016-09-28 06:47:26", "close": "2016-09-28 18:13:03" }]
+    /// ```
+    ///
+    /// The array is returned in the "data" field of the map.
+    pub async fn return_lending_history(&self,
+                                        start: &str,
+                                        end: &str,
+                                        limit: &str)
+                                       -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("start", start);
+        params.insert("end", end);
+        params.insert("limit", limit);
+        self.private_query("returnLendingHistory", &params).await
+    }
+
+    /// Toggles the autoRenew setting on an active loan, specified by the "orderNumber" POST
+    /// parameter. If successful, "message" will indicate the new autoRenew setting.
+    ///
+    /// Sample output:
+    ///
+    /// ```json
+    /// {"success":1,"message":0}
+    /// ```
+    pub async fn toggle_auto_renew(&self, order_number: &str) -> Result<Map<String, Value>> {
+        let mut params = HashMap::new();
+        params.insert("orderNumber", order_number);
+        self.private_query("toggleAutoRenew", &params).await
+    }
+}
+
+#[cfg(test)]
+mod poloniex_api_tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::nonce::MemoryNonceProvider;
+    use crate::rate_limit::{Policy, RateLimiter};
+    use crate::retry::RetryPolicy;
+    use crate::nonce::Resolution;
+
+    #[tokio::test]
+    async fn should_block_or_not_block_when_enabled_or_disabled() {
+        let http_client = Box::new(ReqwestTransport::from(reqwest::Client::new()));
+        let api = AsyncPoloniexApi {
+            api_key: "".to_string(),
+            api_secret: "".to_string(),
+            http_client,
+            config: ClientConfig::new(RateLimiter::new(Exchange::Poloniex),
+                                      RetryPolicy::never(),
+                                      Arc::new(MemoryNonceProvider::new(Resolution::Microseconds))),
+            markets: RwLock::new(None),
+        };
+
+        // The bucket of the public requests is full: empty it
+        api.config.rate_limiter().set_policy(Policy::Burst);
+        for _ in 0..6 {
+            api.config.rate_limiter().acquire(Scope::Public, "returnTicker").await.unwrap();
+        }
+
+        let mut counter = 0;
+        loop {
+            api.config.rate_limiter().set_policy(Policy::Throttle);
+            let start = helpers::get_unix_timestamp_ms();
+            api.config.rate_limiter().acquire(Scope::Public, "returnTicker").await.unwrap();
+
+            let difference = helpers::get_unix_timestamp_ms() - start;
+            assert!(difference >= 160);
+            assert!(difference < 10000);
+
+            api.config.rate_limiter().set_policy(Policy::Burst);
+            let start = helpers::get_unix_timestamp_ms();
+            api.config.rate_limiter().acquire(Scope::Public, "returnTicker").await.unwrap();
+
+            let difference = helpers::get_unix_timestamp_ms() - start;
+            assert!(difference < 10);
+
+            counter += 1;
+            if counter >= 3 {
+                break;
+            }
+        }
+    }
+}
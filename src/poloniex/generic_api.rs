@@ -2,35 +2,35 @@
 //! This a more convenient and safe way to deal with the exchange since methods return a Result<>
 //! but this generic API does not provide all the functionnality that Poloniex offers.
 
-use crate::exchange::ExchangeApi;
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::exchange::{AsyncExchangeApi, ExchangeApi};
 use crate::poloniex::api::PoloniexApi;
+use crate::poloniex::async_api::AsyncPoloniexApi;
 
-use bigdecimal::BigDecimal;
-use std::str::FromStr;
 
 use crate::error::*;
 use crate::types::*;
 use crate::poloniex::utils;
 use crate::helpers;
 
-impl ExchangeApi for PoloniexApi {
-    fn ticker(&mut self, pair: Pair) -> Result<Ticker> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-        let raw_response = self.return_ticker()?;
+#[async_trait]
+impl AsyncExchangeApi for AsyncPoloniexApi {
+    async fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        let pair_name = get_pair_name(self, &pair).await?;
+        let raw_response = self.return_ticker().await?;
 
-        let result = utils::parse_result(&raw_response)?;
+        let result = Value::Object(utils::parse_result(&raw_response, "returnTicker")?);
 
-        let price = helpers::from_json_bigdecimal(&result[*pair_name]["last"], "last")?;
-        let ask = helpers::from_json_bigdecimal(&result[*pair_name]["lowestAsk"], "lowestAsk")?;
-        let bid = helpers::from_json_bigdecimal(&result[*pair_name]["highestBid"], "highestBid")?;
-        let vol = helpers::from_json_bigdecimal(&result[*pair_name]["quoteVolume"], "quoteVolume")?;
+        let price = helpers::from_json_bigdecimal(&result[pair_name]["last"], "last")?;
+        let ask = helpers::from_json_bigdecimal(&result[pair_name]["lowestAsk"], "lowestAsk")?;
+        let bid = helpers::from_json_bigdecimal(&result[pair_name]["highestBid"], "highestBid")?;
+        let vol = helpers::from_json_bigdecimal(&result[pair_name]["quoteVolume"], "quoteVolume")?;
 
         Ok(Ticker {
             timestamp: helpers::get_unix_timestamp_ms(),
-            pair: pair,
+            pair,
             last_trade_price: price,
             lowest_ask: ask,
             highest_bid: bid,
@@ -38,118 +38,324 @@ impl ExchangeApi for PoloniexApi {
         })
     }
 
-    fn orderbook(&mut self, pair: Pair) -> Result<Orderbook> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
-        };
-        let raw_response = self.return_order_book(pair_name, "1000")?; // 1000 entries max
+    async fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        let pair_name = get_pair_name(self, &pair).await?;
+        let raw_response = self.return_order_book(pair_name, "1000").await?; // 1000 entries max
 
-        let result = utils::parse_result(&raw_response)?;
+        let result = Value::Object(utils::parse_result(&raw_response, "returnOrderBook")?);
 
         let mut ask_offers = Vec::new();
         let mut bid_offers = Vec::new();
 
         let ask_array = result["asks"]
             .as_array()
-            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("asks".to_string()))?;
         let bid_array = result["bids"]
             .as_array()
-            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("bids".to_string()))?;
 
         for ask in ask_array {
             let price = helpers::from_json_bigdecimal(&ask[0], "ask price")?;
-            let volume_str = ask[1].as_f64().unwrap().to_string();
-            let volume = BigDecimal::from_str(&volume_str).unwrap();
+            let volume = helpers::from_json_number_bigdecimal(&ask[1], "ask volume")?;
 
             ask_offers.push((price, volume));
         }
 
         for bid in bid_array {
             let price = helpers::from_json_bigdecimal(&bid[0], "bid price")?;
-            let volume_str = bid[1].as_f64().unwrap().to_string();
-            let volume = BigDecimal::from_str(&volume_str).unwrap();
+            let volume = helpers::from_json_number_bigdecimal(&bid[1], "bid volume")?;
 
             bid_offers.push((price, volume));
         }
 
         Ok(Orderbook {
             timestamp: helpers::get_unix_timestamp_ms(),
-            pair: pair,
+            pair,
             asks: ask_offers,
             bids: bid_offers,
         })
     }
 
-    fn add_order(&mut self, order_type: OrderType, pair: Pair, quantity: Volume, price: Option<Price>) -> Result<OrderInfo> {
-        let pair_name = match utils::get_pair_string(&pair) {
-            Some(name) => name,
-            None => return Err(ErrorKind::PairUnsupported.into()),
+    async fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        let pair_name = get_pair_name(self, &pair).await?;
+
+        let (start, last_id) = match since {
+            Some(ref cursor) => utils::parse_trade_cursor(cursor)?,
+            None => ("".to_string(), 0),
+        };
+
+        let raw_response = self.return_trade_history(pair_name, &start, "").await?;
+
+        let result = Value::Object(utils::parse_result(&raw_response, "returnTradeHistory")?);
+
+        let trade_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        // The start timestamp is inclusive, trades already returned are removed
+        let trades: Vec<Trade> = utils::parse_trades(trade_array)?
+            .into_iter()
+            .filter(|trade| trade.identifier.parse::<u64>().unwrap_or(0) > last_id)
+            .collect();
+
+        let cursor = match trades.last() {
+            Some(trade) => Some(utils::build_trade_cursor(trade)),
+            None => since,
         };
 
+        Ok(TradeHistory {
+               timestamp: helpers::get_unix_timestamp_ms(),
+               pair,
+               trades,
+               cursor,
+           })
+    }
+
+    async fn candles(&self, pair: Pair, interval: Interval, since: Option<i64>) -> Result<Vec<Candle>> {
+        let pair_name = get_pair_name(self, &pair).await?;
+        let period = utils::get_interval_period(interval)
+            .ok_or_else(|| ErrorKind::Unsupported(format!("{:?} candles", interval)))?;
+
+        // A start date is mandatory, default to the 720 last candles like Kraken does
+        let start = match since {
+            Some(since) => since / 1000,
+            None => helpers::get_unix_timestamp_ms() / 1000 - 720 * interval.seconds(),
+        };
+
+        let raw_response = self.return_chart_data(pair_name, &start.to_string(), "9999999999", &period).await?;
+
+        let result = Value::Object(utils::parse_result(&raw_response, "returnChartData")?);
+
+        let candle_array = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        let mut candles = utils::parse_candles(candle_array)?;
+
+        // The start date is inclusive
+        if let Some(since) = since {
+            candles.retain(|candle| candle.timestamp > since);
+        }
+
+        Ok(candles)
+    }
+
+    async fn markets(&self) -> Result<Markets> {
+        if let Some(ref markets) = *self.markets.read().unwrap() {
+            return Ok(markets.clone());
+        }
+
+        let raw_response = self.return_ticker().await?;
+
+        let result = utils::parse_result(&raw_response, "returnTicker")?;
+
+        let markets = utils::parse_markets(&result)?;
+        *self.markets.write().unwrap() = Some(markets.clone());
+
+        Ok(markets)
+    }
+
+    async fn add_order(&self, order_type: OrderType, pair: Pair, quantity: Volume, price: Option<Price>) -> Result<OrderInfo> {
+        let pair_name = get_pair_name(self, &pair).await?;
+
         // The trick is to use minimal (0.0) and "maximum" (999..) price to simulate market order
+        let endpoint = match order_type {
+            OrderType::BuyLimit | OrderType::BuyMarket => "buy",
+            OrderType::SellLimit | OrderType::SellMarket => "sell",
+        };
+
         let raw_response = match order_type {
-            // Unwrap safe here with the check above.
             OrderType::BuyLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
+                let price = price.as_ref().ok_or(ErrorKind::MissingPrice)?;
 
                 self.buy(
                     pair_name,
-                    &price.unwrap().to_string(),
+                    &price.to_string(),
                     &quantity.to_string(),
                     None,
-                )
+                ).await
             }
             OrderType::BuyMarket => self.buy(
                 pair_name,
                 "9999999999999999999",
                 &quantity.to_string(),
                 None,
-            ),
+            ).await,
             OrderType::SellLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
+                let price = price.as_ref().ok_or(ErrorKind::MissingPrice)?;
 
                 self.sell(
                     pair_name,
-                    &price.unwrap().to_string(),
+                    &price.to_string(),
                     &quantity.to_string(),
                     None,
-                )
+                ).await
             }
-            OrderType::SellMarket => self.sell(pair_name, "0.0", &quantity.to_string(), None),
+            OrderType::SellMarket => self.sell(pair_name, "0.0", &quantity.to_string(), None).await,
         }?;
 
-        let result = utils::parse_result(&raw_response)?;
+        let result = utils::parse_result(&raw_response, endpoint)?;
 
         Ok(OrderInfo {
             timestamp: helpers::get_unix_timestamp_ms(),
-            identifier: vec![
-                result["orderNumber"]
-                    .as_f64()
-                    .ok_or_else(|| ErrorKind::MissingField("orderNumber".to_string()))?
-                    .to_string(),
-            ],
+            identifier: vec![helpers::get_json_id(&Value::Object(result.clone()), "orderNumber")?],
+            pair,
+            order_type,
+            quantity,
+            price,
+            fills: utils::parse_resulting_trades(&result)?,
         })
     }
 
-    fn balances(&mut self) -> Result<Balances> {
-        let raw_response = self.return_balances()?;
-        let result = utils::parse_result(&raw_response)?;
+    async fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        for order_number in &order.identifier {
+            let raw_response = AsyncPoloniexApi::cancel_order(self, order_number).await?;
+            utils::parse_result(&raw_response, "cancelOrder")?;
+        }
+
+        Ok(())
+    }
+
+    async fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        // A single market returns an array, so we always ask for all markets
+        let raw_response = self.return_open_orders("all").await?;
+        let result = utils::parse_result(&raw_response, "returnOpenOrders")?;
+
+        let mut orders = Vec::new();
+
+        for (pair_name, pair_orders) in result.iter() {
+            let order_pair = match get_pair_from_name(self, pair_name).await? {
+                Some(order_pair) => order_pair,
+                None => continue,
+            };
+
+            if pair.is_some() && pair != Some(order_pair) {
+                continue;
+            }
+
+            let pair_orders = pair_orders
+                .as_array()
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat(pair_name.to_string()))?;
+
+            for order in pair_orders {
+                orders.push(utils::parse_open_order(order_pair, order)?);
+            }
+        }
+
+        Ok(orders)
+    }
+
+    async fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        let order_number = order.identifier.first().ok_or(ErrorKind::OrderNotFound)?;
+
+        for open_order in self.open_orders(None).await? {
+            if open_order.identifier == *order_number {
+                return Ok(open_order);
+            }
+        }
+
+        // Not open anymore: the trades tell how much of the order has been filled. A canceled
+        // order without any trade is reported as not found by Poloniex.
+        let raw_response = self.return_order_trades(order_number).await?;
+        let result = match utils::parse_result(&raw_response, "returnOrderTrades") {
+            Ok(result) => Value::Object(result),
+            Err(Error(ErrorKind::OrderNotFound, _)) => return utils::parse_order_trades(order, &[]),
+            Err(err) => return Err(err),
+        };
+
+        let trades = result["data"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("data".to_string()))?;
+
+        utils::parse_order_trades(order, trades)
+    }
+
+    async fn balances(&self) -> Result<Balances> {
+        let raw_response = self.return_balances().await?;
+        let result = utils::parse_result(&raw_response, "returnBalances")?;
 
         let mut balances = Balances::new();
 
         for (key, val) in result.iter() {
-            let currency = utils::get_currency_enum(key);
-
-            if currency.is_some() {
-                let amount = helpers::from_json_bigdecimal(&val, "amount")?;
-                balances.insert(currency.unwrap(), amount);
+            if let Some(currency) = utils::get_currency_enum(key) {
+                let amount = helpers::from_json_bigdecimal(val, "amount")?;
+                balances.insert(currency, amount);
             }
         }
         Ok(balances)
     }
 }
+
+/// Return the name used by Poloniex for the pair. The pairs listed since the last update of
+/// Coinnect are only known once the markets listed by Poloniex have been loaded.
+async fn get_pair_name(api: &AsyncPoloniexApi, pair: &Pair) -> Result<&'static str> {
+    helpers::find_pair(&api.markets, || api.markets(), || utils::get_pair_string(pair))
+        .await?
+        .ok_or_else(|| ErrorKind::PairUnsupported.into())
+}
+
+/// Return the Pair of a name used by Poloniex, loading the markets listed by Poloniex if the
+/// name is not known yet.
+async fn get_pair_from_name(api: &AsyncPoloniexApi, name: &str) -> Result<Option<Pair>> {
+    helpers::find_pair(&api.markets, || api.markets(), || utils::get_pair_enum(name)).await
+}
+
+impl ExchangeApi for PoloniexApi {
+    fn ticker(&self, pair: Pair) -> Result<Ticker> {
+        self.runtime.block_on(self.inner.ticker(pair))
+    }
+
+    fn orderbook(&self, pair: Pair) -> Result<Orderbook> {
+        self.runtime.block_on(self.inner.orderbook(pair))
+    }
+
+    fn trades(&self, pair: Pair, since: Option<String>) -> Result<TradeHistory> {
+        self.runtime.block_on(self.inner.trades(pair, since))
+    }
+
+    fn candles(&self,
+               pair: Pair,
+               interval: Interval,
+               since: Option<i64>)
+               -> Result<Vec<Candle>> {
+        self.runtime.block_on(self.inner.candles(pair, interval, since))
+    }
+
+    fn markets(&self) -> Result<Markets> {
+        self.runtime.block_on(self.inner.markets())
+    }
+
+    fn validate_order(&self,
+                      order_type: OrderType,
+                      pair: Pair,
+                      quantity: Volume,
+                      price: Option<Price>)
+                      -> Result<(Volume, Option<Price>)> {
+        self.runtime.block_on(self.inner.validate_order(order_type, pair, quantity, price))
+    }
+
+    fn add_order(&self,
+                 order_type: OrderType,
+                 pair: Pair,
+                 quantity: Volume,
+                 price: Option<Price>)
+                 -> Result<OrderInfo> {
+        self.runtime.block_on(self.inner.add_order(order_type, pair, quantity, price))
+    }
+
+    fn cancel_order(&self, order: &OrderInfo) -> Result<()> {
+        self.runtime.block_on(AsyncExchangeApi::cancel_order(&self.inner, order))
+    }
+
+    fn open_orders(&self, pair: Option<Pair>) -> Result<Vec<Order>> {
+        self.runtime.block_on(self.inner.open_orders(pair))
+    }
+
+    fn order_status(&self, order: &OrderInfo) -> Result<Order> {
+        self.runtime.block_on(self.inner.order_status(order))
+    }
+
+    fn balances(&self) -> Result<Balances> {
+        self.runtime.block_on(self.inner.balances())
+    }
+}
@@ -1,10 +1,15 @@
 //! Use this module to interact with Poloniex exchange.
 
 pub mod api;
+pub mod async_api;
 pub mod generic_api;
 pub mod credentials;
 pub mod utils;
+pub mod responses;
+pub mod typed_api;
 
 pub use self::credentials::PoloniexCreds;
 pub use self::api::PoloniexApi;
-pub use self::api::{MoveOrderOption, PlaceOrderOption};
+pub use self::async_api::AsyncPoloniexApi;
+pub use self::typed_api::{AsyncPoloniexTypedApi, PoloniexTypedApi};
+pub use self::async_api::{MoveOrderOption, PlaceOrderOption};
@@ -0,0 +1,535 @@
+//! Typed results of the Poloniex endpoints, returned by the `typed` API (see
+//! `AsyncPoloniexApi::typed`).
+//!
+//! The fields are the ones documented by Poloniex, in snake case. Prices and amounts are
+//! `BigDecimal` parsed from the text sent by Poloniex, dates and timestamps are `DateTime<Utc>`.
+//! The identifiers sent either as strings or as numbers (ie. "orderNumber") are strings. The raw
+//! responses are still returned by the methods of `AsyncPoloniexApi`.
+
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::value::Map;
+
+use std::collections::HashMap;
+
+use crate::error::*;
+use crate::helpers;
+
+/// An entry of `returnTicker`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TickerInfo {
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub last: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub lowest_ask: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub highest_bid: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub percent_change: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub base_volume: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub quote_volume: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_flag")]
+    pub is_frozen: bool,
+    #[serde(rename = "high24hr",
+            default,
+            deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub high_24h: Option<BigDecimal>,
+    #[serde(rename = "low24hr",
+            default,
+            deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub low_24h: Option<BigDecimal>,
+}
+
+/// Result of `return24hVolume`: the volumes of each pair, by currency, and the total volume of
+/// the main currencies (ie. "totalBTC" is stored under "BTC").
+#[derive(Debug, Clone, PartialEq)]
+pub struct Volumes {
+    pub pairs: HashMap<String, HashMap<String, BigDecimal>>,
+    pub totals: HashMap<String, BigDecimal>,
+}
+
+impl Volumes {
+    pub(crate) fn from_result(result: Map<String, Value>) -> Result<Volumes> {
+        let mut pairs = HashMap::new();
+        let mut totals = HashMap::new();
+
+        for (key, value) in result {
+            if let Some(currency) = key.strip_prefix("total") {
+                let total = helpers::from_json_number_bigdecimal(&value, &key)?;
+                totals.insert(currency.to_string(), total);
+            } else if value.is_object() {
+                let volumes = helpers::deserialize_bigdecimal_map(value)
+                    .chain_err(|| ErrorKind::InvalidFieldFormat(key.clone()))?;
+                pairs.insert(key, volumes);
+            }
+        }
+
+        Ok(Volumes { pairs, totals })
+    }
+}
+
+/// Result of `returnOrderBook` for a pair.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderBook {
+    pub asks: Vec<OrderBookEntry>,
+    pub bids: Vec<OrderBookEntry>,
+    #[serde(default, deserialize_with = "helpers::deserialize_flag")]
+    pub is_frozen: bool,
+    #[serde(default)]
+    pub seq: Option<u64>,
+}
+
+/// An order of `returnOrderBook`, sent as an array (<price>, <amount>).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct OrderBookEntry {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub price: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+}
+
+/// An entry of the public `returnTradeHistory`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicTrade {
+    #[serde(rename = "globalTradeID", default)]
+    pub global_trade_id: Option<u64>,
+    #[serde(rename = "tradeID", default)]
+    pub trade_id: Option<u64>,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub date: DateTime<Utc>,
+    #[serde(rename = "type")]
+    pub side: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub total: BigDecimal,
+}
+
+/// An entry of `returnChartData`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChartEntry {
+    #[serde(deserialize_with = "helpers::deserialize_timestamp")]
+    pub date: DateTime<Utc>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub high: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub low: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub open: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub close: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub volume: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub quote_volume: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub weighted_average: BigDecimal,
+}
+
+/// An entry of `returnCurrencies`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyInfo {
+    #[serde(default)]
+    pub id: Option<u64>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub tx_fee: BigDecimal,
+    pub min_conf: u32,
+    #[serde(default)]
+    pub deposit_address: Option<String>,
+    #[serde(deserialize_with = "helpers::deserialize_flag")]
+    pub disabled: bool,
+    #[serde(default, deserialize_with = "helpers::deserialize_flag")]
+    pub delisted: bool,
+    #[serde(default, deserialize_with = "helpers::deserialize_flag")]
+    pub frozen: bool,
+}
+
+/// Result of `returnLoanOrders`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LoanOrders {
+    pub offers: Vec<LoanOrder>,
+    pub demands: Vec<LoanOrder>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanOrder {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    pub range_min: u32,
+    pub range_max: u32,
+}
+
+/// An entry of `returnCompleteBalances`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompleteBalance {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub available: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub on_orders: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub btc_value: BigDecimal,
+}
+
+/// Result of `generateNewAddress`. `response` is the new address.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct NewAddress {
+    #[serde(deserialize_with = "helpers::deserialize_flag")]
+    pub success: bool,
+    pub response: String,
+}
+
+/// Result of `returnDepositsWithdrawals`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DepositsWithdrawals {
+    #[serde(default)]
+    pub deposits: Vec<Deposit>,
+    #[serde(default)]
+    pub withdrawals: Vec<Withdrawal>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Deposit {
+    pub currency: String,
+    pub address: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    pub confirmations: u32,
+    pub txid: String,
+    #[serde(deserialize_with = "helpers::deserialize_timestamp")]
+    pub timestamp: DateTime<Utc>,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Withdrawal {
+    pub withdrawal_number: u64,
+    pub currency: String,
+    pub address: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_timestamp")]
+    pub timestamp: DateTime<Utc>,
+    pub status: String,
+    #[serde(default)]
+    pub ip_address: Option<String>,
+}
+
+/// An entry of `returnOpenOrders`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenOrder {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub order_number: String,
+    #[serde(rename = "type")]
+    pub side: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub total: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub starting_amount: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_datetime")]
+    pub date: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "helpers::deserialize_flag")]
+    pub margin: bool,
+}
+
+/// An entry of the private `returnTradeHistory`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateTrade {
+    #[serde(rename = "globalTradeID", default)]
+    pub global_trade_id: Option<u64>,
+    #[serde(rename = "tradeID", deserialize_with = "helpers::deserialize_id")]
+    pub trade_id: String,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub date: DateTime<Utc>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub total: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub order_number: String,
+    #[serde(rename = "type")]
+    pub side: String,
+    pub category: String,
+}
+
+/// An entry of `returnOrderTrades`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderTrade {
+    #[serde(rename = "globalTradeID", default)]
+    pub global_trade_id: Option<u64>,
+    #[serde(rename = "tradeID", deserialize_with = "helpers::deserialize_id")]
+    pub trade_id: String,
+    pub currency_pair: String,
+    #[serde(rename = "type")]
+    pub side: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub total: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub date: DateTime<Utc>,
+}
+
+/// Result of `buy` and `sell`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacedOrder {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub order_number: String,
+    #[serde(default)]
+    pub resulting_trades: Vec<ResultingTrade>,
+}
+
+/// A trade resulting from an order.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ResultingTrade {
+    #[serde(rename = "tradeID", deserialize_with = "helpers::deserialize_id")]
+    pub trade_id: String,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub date: DateTime<Utc>,
+    #[serde(rename = "type")]
+    pub side: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub total: BigDecimal,
+}
+
+/// Result of the endpoints only telling whether they succeeded (`cancelOrder`,
+/// `transferBalance`, `cancelLoanOffer`, ...).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Success {
+    #[serde(deserialize_with = "helpers::deserialize_flag")]
+    pub success: bool,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Result of `moveOrder`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MovedOrder {
+    #[serde(deserialize_with = "helpers::deserialize_flag")]
+    pub success: bool,
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub order_number: String,
+    #[serde(default)]
+    pub resulting_trades: HashMap<String, Vec<ResultingTrade>>,
+}
+
+/// Result of `withdraw`. `response` is the message of Poloniex (ie. "Withdrew 2398 NXT.").
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct WithdrawalResponse {
+    pub response: String,
+}
+
+/// Result of `returnFeeInfo`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeInfo {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub maker_fee: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub taker_fee: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub thirty_day_volume: BigDecimal,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_bigdecimal")]
+    pub next_tier: Option<BigDecimal>,
+}
+
+/// Result of `returnAvailableAccountBalances`: the balances of each account, by currency.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AccountBalances {
+    #[serde(default, deserialize_with = "helpers::deserialize_bigdecimal_map")]
+    pub exchange: HashMap<String, BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_bigdecimal_map")]
+    pub margin: HashMap<String, BigDecimal>,
+    #[serde(default, deserialize_with = "helpers::deserialize_bigdecimal_map")]
+    pub lending: HashMap<String, BigDecimal>,
+}
+
+/// Result of `returnMarginAccountSummary`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginAccountSummary {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub total_value: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub pl: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub lending_fees: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub net_value: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub total_borrowed_value: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub current_margin: BigDecimal,
+}
+
+/// Result of `marginBuy`, `marginSell` and `closeMarginPosition` (which has no order number).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginOrder {
+    #[serde(deserialize_with = "helpers::deserialize_flag")]
+    pub success: bool,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default, deserialize_with = "helpers::deserialize_option_id")]
+    pub order_number: Option<String>,
+    #[serde(default)]
+    pub resulting_trades: HashMap<String, Vec<ResultingTrade>>,
+}
+
+/// Result of `getMarginPosition`. `side` is "none" if there is no position, the
+/// `liquidation_price` is None if there is no liquidation price (sent as -1).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginPosition {
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub total: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub base_price: BigDecimal,
+    #[serde(deserialize_with = "deserialize_liquidation_price")]
+    pub liquidation_price: Option<BigDecimal>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub pl: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub lending_fees: BigDecimal,
+    #[serde(rename = "type")]
+    pub side: String,
+}
+
+fn deserialize_liquidation_price<'de, D>(deserializer: D)
+                                         -> ::std::result::Result<Option<BigDecimal>, D::Error>
+    where D: serde::Deserializer<'de>
+{
+    let price = helpers::deserialize_option_bigdecimal(deserializer)?;
+    Ok(price.filter(|price| *price != BigDecimal::from(-1)))
+}
+
+/// Result of `createLoanOffer`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CreatedLoanOffer {
+    #[serde(deserialize_with = "helpers::deserialize_flag")]
+    pub success: bool,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(rename = "orderID", deserialize_with = "helpers::deserialize_id")]
+    pub order_id: String,
+}
+
+/// An entry of `returnOpenLoanOffers`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanOffer {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub id: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    pub duration: u32,
+    #[serde(deserialize_with = "helpers::deserialize_flag")]
+    pub auto_renew: bool,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub date: DateTime<Utc>,
+}
+
+/// Result of `returnActiveLoans`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ActiveLoans {
+    #[serde(default)]
+    pub provided: Vec<ActiveLoan>,
+    #[serde(default)]
+    pub used: Vec<ActiveLoan>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveLoan {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub id: String,
+    pub currency: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    pub range: u32,
+    #[serde(default, deserialize_with = "helpers::deserialize_flag")]
+    pub auto_renew: bool,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub date: DateTime<Utc>,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fees: BigDecimal,
+}
+
+/// An entry of `returnLendingHistory`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LendingHistoryEntry {
+    #[serde(deserialize_with = "helpers::deserialize_id")]
+    pub id: String,
+    pub currency: String,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub rate: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub amount: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub duration: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub interest: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub fee: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_bigdecimal")]
+    pub earned: BigDecimal,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub open: DateTime<Utc>,
+    #[serde(deserialize_with = "helpers::deserialize_datetime")]
+    pub close: DateTime<Utc>,
+}
+
+/// Result of `toggleAutoRenew`. `auto_renew` is the new setting of the loan.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ToggledAutoRenew {
+    #[serde(deserialize_with = "helpers::deserialize_flag")]
+    pub success: bool,
+    #[serde(rename = "message", deserialize_with = "helpers::deserialize_flag")]
+    pub auto_renew: bool,
+}
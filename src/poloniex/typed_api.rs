@@ -0,0 +1,576 @@
+//! Use this module to get the results of the Poloniex endpoints as typed structs (see the
+//! `responses` module) instead of JSON maps.
+//!
+//! The typed API is a view of a client returned by `AsyncPoloniexApi::typed` or
+//! `PoloniexApi::typed`. Its methods have the names and the parameters of the raw methods, which
+//! still return the raw responses:
+//!
+//! ```no_run
+//! use coinnect::poloniex::{PoloniexApi, PoloniexCreds};
+//!
+//! let api = PoloniexApi::new(PoloniexCreds::new("", "", "")).unwrap();
+//! let book = api.typed().return_order_book("BTC_ETH", "10").unwrap();
+//! println!("{}", book.asks[0].price);
+//! ```
+
+use bigdecimal::BigDecimal;
+use serde_json::Value;
+use serde_json::value::Map;
+
+use std::collections::HashMap;
+
+use crate::error::*;
+use crate::helpers;
+use crate::poloniex::{AsyncPoloniexApi, MoveOrderOption, PlaceOrderOption, PoloniexApi};
+use crate::poloniex::responses::*;
+use crate::poloniex::utils;
+
+/// Typed view of an `AsyncPoloniexApi`.
+#[derive(Debug, Clone, Copy)]
+pub struct AsyncPoloniexTypedApi<'a> {
+    api: &'a AsyncPoloniexApi,
+}
+
+impl<'a> AsyncPoloniexTypedApi<'a> {
+    pub fn new(api: &'a AsyncPoloniexApi) -> AsyncPoloniexTypedApi<'a> {
+        AsyncPoloniexTypedApi { api }
+    }
+
+    pub async fn return_ticker(&self) -> Result<HashMap<String, TickerInfo>> {
+        let response = self.api.return_ticker().await?;
+        utils::parse_response(&response, "returnTicker")
+    }
+
+    pub async fn return_24_volume(&self) -> Result<Volumes> {
+        let response = self.api.return_24_volume().await?;
+        Volumes::from_result(utils::parse_result(&response, "return24Volume")?)
+    }
+
+    pub async fn return_order_book(&self, currency_pair: &str, depth: &str) -> Result<OrderBook> {
+        let response = self.api.return_order_book(currency_pair, depth).await?;
+        utils::parse_response(&response, "returnOrderBook")
+    }
+
+    pub async fn return_trade_history(&self,
+                                      currency_pair: &str,
+                                      start: &str,
+                                      end: &str)
+                                      -> Result<Vec<PublicTrade>> {
+        let response = self.api.return_trade_history(currency_pair, start, end).await?;
+        utils::parse_array_response(&response, "returnTradeHistory")
+    }
+
+    pub async fn return_chart_data(&self,
+                                   currency_pair: &str,
+                                   start: &str,
+                                   end: &str,
+                                   period: &str)
+                                   -> Result<Vec<ChartEntry>> {
+        let response = self.api
+            .return_chart_data(currency_pair, start, end, period)
+            .await?;
+        utils::parse_array_response(&response, "returnChartData")
+    }
+
+    pub async fn return_currencies(&self) -> Result<HashMap<String, CurrencyInfo>> {
+        let response = self.api.return_currencies().await?;
+        utils::parse_response(&response, "returnCurrencies")
+    }
+
+    pub async fn return_loan_orders(&self, currency: &str) -> Result<LoanOrders> {
+        let response = self.api.return_loan_orders(currency).await?;
+        utils::parse_response(&response, "returnLoanOrders")
+    }
+
+    /// Return the available balance of each currency, by currency name.
+    pub async fn return_balances(&self) -> Result<HashMap<String, BigDecimal>> {
+        let response = self.api.return_balances().await?;
+        let result = utils::parse_result(&response, "returnBalances")?;
+        parse_amounts(result)
+    }
+
+    pub async fn return_complete_balances(&self) -> Result<HashMap<String, CompleteBalance>> {
+        let response = self.api.return_complete_balances().await?;
+        utils::parse_response(&response, "returnCompleteBalances")
+    }
+
+    /// Return the deposit address of each currency, by currency name.
+    pub async fn return_deposit_addresses(&self) -> Result<HashMap<String, String>> {
+        let response = self.api.return_deposit_addresses().await?;
+        utils::parse_response(&response, "returnDepositAddresses")
+    }
+
+    pub async fn generate_new_address(&self, currency: &str) -> Result<NewAddress> {
+        let response = self.api.generate_new_address(currency).await?;
+        utils::parse_response(&response, "generateNewAddress")
+    }
+
+    pub async fn return_deposits_withdrawals(&self,
+                                             start: &str,
+                                             end: &str)
+                                             -> Result<DepositsWithdrawals> {
+        let response = self.api.return_deposits_withdrawals(start, end).await?;
+        utils::parse_response(&response, "returnDepositsWithdrawals")
+    }
+
+    /// Return the open orders by market name, including when a single market is requested.
+    pub async fn return_open_orders(&self,
+                                    currency_pair: &str)
+                                    -> Result<HashMap<String, Vec<OpenOrder>>> {
+        let response = self.api.return_open_orders(currency_pair).await?;
+        utils::parse_market_response(&response, "returnOpenOrders", currency_pair)
+    }
+
+    /// Return the trades by market name, including when a single market is requested.
+    pub async fn return_private_trade_history(&self,
+                                              currency_pair: &str,
+                                              start: &str,
+                                              end: &str)
+                                              -> Result<HashMap<String, Vec<PrivateTrade>>> {
+        let response = self.api
+            .return_private_trade_history(currency_pair, start, end)
+            .await?;
+        utils::parse_market_response(&response, "returnTradeHistory", currency_pair)
+    }
+
+    pub async fn return_order_trades(&self, order_number: &str) -> Result<Vec<OrderTrade>> {
+        let response = self.api.return_order_trades(order_number).await?;
+        utils::parse_array_response(&response, "returnOrderTrades")
+    }
+
+    pub async fn buy<O>(&self,
+                        currency_pair: &str,
+                        rate: &str,
+                        amount: &str,
+                        option: O)
+                        -> Result<PlacedOrder>
+        where O: Into<Option<PlaceOrderOption>>
+    {
+        let response = self.api.buy(currency_pair, rate, amount, option).await?;
+        utils::parse_response(&response, "buy")
+    }
+
+    pub async fn sell<O>(&self,
+                         currency_pair: &str,
+                         rate: &str,
+                         amount: &str,
+                         option: O)
+                         -> Result<PlacedOrder>
+        where O: Into<Option<PlaceOrderOption>>
+    {
+        let response = self.api.sell(currency_pair, rate, amount, option).await?;
+        utils::parse_response(&response, "sell")
+    }
+
+    pub async fn cancel_order(&self, order_number: &str) -> Result<Success> {
+        let response = self.api.cancel_order(order_number).await?;
+        utils::parse_response(&response, "cancelOrder")
+    }
+
+    pub async fn move_order<O>(&self,
+                               order_number: &str,
+                               rate: &str,
+                               option: O)
+                               -> Result<MovedOrder>
+        where O: Into<Option<MoveOrderOption>>
+    {
+        let response = self.api.move_order(order_number, rate, option).await?;
+        utils::parse_response(&response, "moveOrder")
+    }
+
+    pub async fn withdraw(&self,
+                          currency: &str,
+                          amount: &str,
+                          address: &str)
+                          -> Result<WithdrawalResponse> {
+        let response = self.api.withdraw(currency, amount, address).await?;
+        utils::parse_response(&response, "withdraw")
+    }
+
+    pub async fn return_free_info(&self) -> Result<FeeInfo> {
+        let response = self.api.return_free_info().await?;
+        utils::parse_response(&response, "returnFeeInfo")
+    }
+
+    pub async fn return_available_account_balances(&self,
+                                                   account: &str)
+                                                   -> Result<AccountBalances> {
+        let response = self.api.return_available_account_balances(account).await?;
+        utils::parse_response(&response, "returnAvailableAccountBalances")
+    }
+
+    /// Return the tradable balances of each market, by market name then by currency name.
+    pub async fn return_tradable_balances(&self)
+                                          -> Result<HashMap<String, HashMap<String, BigDecimal>>> {
+        let response = self.api.return_tradable_balances().await?;
+        let result = utils::parse_result(&response, "returnTradableBalances")?;
+
+        let mut balances = HashMap::new();
+        for (market, amounts) in result {
+            let amounts = amounts
+                .as_object()
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat(market.clone()))?;
+            balances.insert(market, parse_amounts(amounts.clone())?);
+        }
+        Ok(balances)
+    }
+
+    pub async fn transfer_balance(&self,
+                                  currency: &str,
+                                  amount: &str,
+                                  from_account: &str,
+                                  to_account: &str)
+                                  -> Result<Success> {
+        let response = self.api
+            .transfer_balance(currency, amount, from_account, to_account)
+            .await?;
+        utils::parse_response(&response, "transferBalance")
+    }
+
+    pub async fn return_margin_account_summary(&self) -> Result<MarginAccountSummary> {
+        let response = self.api.return_margin_account_summary().await?;
+        utils::parse_response(&response, "returnMarginAccountSummary")
+    }
+
+    pub async fn margin_buy(&self,
+                            currency_pair: &str,
+                            rate: &str,
+                            amount: &str,
+                            lending_rate: &str)
+                            -> Result<MarginOrder> {
+        let response = self.api
+            .margin_buy(currency_pair, rate, amount, lending_rate)
+            .await?;
+        utils::parse_response(&response, "marginBuy")
+    }
+
+    pub async fn margin_sell(&self,
+                             currency_pair: &str,
+                             rate: &str,
+                             amount: &str,
+                             lending_rate: &str)
+                             -> Result<MarginOrder> {
+        let response = self.api
+            .margin_sell(currency_pair, rate, amount, lending_rate)
+            .await?;
+        utils::parse_response(&response, "marginSell")
+    }
+
+    /// Return the margin positions by market name, including when a single market is requested.
+    pub async fn get_margin_position(&self,
+                                     currency_pair: &str)
+                                     -> Result<HashMap<String, MarginPosition>> {
+        let response = self.api.get_margin_position(currency_pair).await?;
+        if currency_pair == "all" {
+            return utils::parse_response(&response, "getMarginPosition");
+        }
+
+        let position = utils::parse_response(&response, "getMarginPosition")?;
+        let mut positions = HashMap::new();
+        positions.insert(currency_pair.to_string(), position);
+        Ok(positions)
+    }
+
+    pub async fn close_margin_position(&self, currency_pair: &str) -> Result<MarginOrder> {
+        let response = self.api.close_margin_position(currency_pair).await?;
+        utils::parse_response(&response, "closeMarginPosition")
+    }
+
+    pub async fn create_loan_offer(&self,
+                                   currency: &str,
+                                   amount: &str,
+                                   duration: &str,
+                                   auto_renew: &str,
+                                   lending_rate: &str)
+                                   -> Result<CreatedLoanOffer> {
+        let response = self.api
+            .create_loan_offer(currency, amount, duration, auto_renew, lending_rate)
+            .await?;
+        utils::parse_response(&response, "createLoanOffer")
+    }
+
+    pub async fn cancel_loan_offer(&self, order_number: &str) -> Result<Success> {
+        let response = self.api.cancel_loan_offer(order_number).await?;
+        utils::parse_response(&response, "cancelLoanOffer")
+    }
+
+    /// Return the open loan offers by currency name.
+    pub async fn return_open_loan_offers(&self) -> Result<HashMap<String, Vec<LoanOffer>>> {
+        let response = self.api.return_open_loan_offers().await?;
+        utils::parse_market_response(&response, "returnOpenLoanOffers", "all")
+    }
+
+    pub async fn return_active_loans(&self) -> Result<ActiveLoans> {
+        let response = self.api.return_active_loans().await?;
+        utils::parse_response(&response, "returnActiveLoans")
+    }
+
+    pub async fn return_lending_history(&self,
+                                        start: &str,
+                                        end: &str,
+                                        limit: &str)
+                                        -> Result<Vec<LendingHistoryEntry>> {
+        let response = self.api.return_lending_history(start, end, limit).await?;
+        utils::parse_array_response(&response, "returnLendingHistory")
+    }
+
+    pub async fn toggle_auto_renew(&self, order_number: &str) -> Result<ToggledAutoRenew> {
+        let response = self.api.toggle_auto_renew(order_number).await?;
+        utils::parse_response(&response, "toggleAutoRenew")
+    }
+}
+
+/// Typed view of a `PoloniexApi`. Every call runs the `AsyncPoloniexTypedApi` method of the same
+/// name to completion on the runtime of the client.
+#[derive(Debug, Clone, Copy)]
+pub struct PoloniexTypedApi<'a> {
+    api: &'a PoloniexApi,
+}
+
+impl<'a> PoloniexTypedApi<'a> {
+    pub fn new(api: &'a PoloniexApi) -> PoloniexTypedApi<'a> {
+        PoloniexTypedApi { api }
+    }
+
+    fn inner(&self) -> AsyncPoloniexTypedApi<'a> {
+        self.api.inner.typed()
+    }
+
+    pub fn return_ticker(&self) -> Result<HashMap<String, TickerInfo>> {
+        self.api.runtime.block_on(self.inner().return_ticker())
+    }
+
+    pub fn return_24_volume(&self) -> Result<Volumes> {
+        self.api.runtime.block_on(self.inner().return_24_volume())
+    }
+
+    pub fn return_order_book(&self, currency_pair: &str, depth: &str) -> Result<OrderBook> {
+        self.api.runtime.block_on(self.inner().return_order_book(currency_pair, depth))
+    }
+
+    pub fn return_trade_history(&self,
+                                currency_pair: &str,
+                                start: &str,
+                                end: &str)
+                                -> Result<Vec<PublicTrade>> {
+        self.api.runtime.block_on(self.inner().return_trade_history(currency_pair, start, end))
+    }
+
+    pub fn return_chart_data(&self,
+                             currency_pair: &str,
+                             start: &str,
+                             end: &str,
+                             period: &str)
+                             -> Result<Vec<ChartEntry>> {
+        self.api.runtime.block_on(self.inner().return_chart_data(currency_pair, start, end, period))
+    }
+
+    pub fn return_currencies(&self) -> Result<HashMap<String, CurrencyInfo>> {
+        self.api.runtime.block_on(self.inner().return_currencies())
+    }
+
+    pub fn return_loan_orders(&self, currency: &str) -> Result<LoanOrders> {
+        self.api.runtime.block_on(self.inner().return_loan_orders(currency))
+    }
+
+    /// Return the available balance of each currency, by currency name.
+    pub fn return_balances(&self) -> Result<HashMap<String, BigDecimal>> {
+        self.api.runtime.block_on(self.inner().return_balances())
+    }
+
+    pub fn return_complete_balances(&self) -> Result<HashMap<String, CompleteBalance>> {
+        self.api.runtime.block_on(self.inner().return_complete_balances())
+    }
+
+    /// Return the deposit address of each currency, by currency name.
+    pub fn return_deposit_addresses(&self) -> Result<HashMap<String, String>> {
+        self.api.runtime.block_on(self.inner().return_deposit_addresses())
+    }
+
+    pub fn generate_new_address(&self, currency: &str) -> Result<NewAddress> {
+        self.api.runtime.block_on(self.inner().generate_new_address(currency))
+    }
+
+    pub fn return_deposits_withdrawals(&self,
+                                       start: &str,
+                                       end: &str)
+                                       -> Result<DepositsWithdrawals> {
+        self.api.runtime.block_on(self.inner().return_deposits_withdrawals(start, end))
+    }
+
+    /// Return the open orders by market name, including when a single market is requested.
+    pub fn return_open_orders(&self,
+                              currency_pair: &str)
+                              -> Result<HashMap<String, Vec<OpenOrder>>> {
+        self.api.runtime.block_on(self.inner().return_open_orders(currency_pair))
+    }
+
+    /// Return the trades by market name, including when a single market is requested.
+    pub fn return_private_trade_history(&self,
+                                        currency_pair: &str,
+                                        start: &str,
+                                        end: &str)
+                                        -> Result<HashMap<String, Vec<PrivateTrade>>> {
+        self.api
+            .runtime
+            .block_on(self.inner().return_private_trade_history(currency_pair, start, end))
+    }
+
+    pub fn return_order_trades(&self, order_number: &str) -> Result<Vec<OrderTrade>> {
+        self.api.runtime.block_on(self.inner().return_order_trades(order_number))
+    }
+
+    pub fn buy<O>(&self,
+                  currency_pair: &str,
+                  rate: &str,
+                  amount: &str,
+                  option: O)
+                  -> Result<PlacedOrder>
+        where O: Into<Option<PlaceOrderOption>>
+    {
+        self.api.runtime.block_on(self.inner().buy(currency_pair, rate, amount, option))
+    }
+
+    pub fn sell<O>(&self,
+                   currency_pair: &str,
+                   rate: &str,
+                   amount: &str,
+                   option: O)
+                   -> Result<PlacedOrder>
+        where O: Into<Option<PlaceOrderOption>>
+    {
+        self.api.runtime.block_on(self.inner().sell(currency_pair, rate, amount, option))
+    }
+
+    pub fn cancel_order(&self, order_number: &str) -> Result<Success> {
+        self.api.runtime.block_on(self.inner().cancel_order(order_number))
+    }
+
+    pub fn move_order<O>(&self,
+                         order_number: &str,
+                         rate: &str,
+                         option: O)
+                         -> Result<MovedOrder>
+        where O: Into<Option<MoveOrderOption>>
+    {
+        self.api.runtime.block_on(self.inner().move_order(order_number, rate, option))
+    }
+
+    pub fn withdraw(&self,
+                    currency: &str,
+                    amount: &str,
+                    address: &str)
+                    -> Result<WithdrawalResponse> {
+        self.api.runtime.block_on(self.inner().withdraw(currency, amount, address))
+    }
+
+    pub fn return_free_info(&self) -> Result<FeeInfo> {
+        self.api.runtime.block_on(self.inner().return_free_info())
+    }
+
+    pub fn return_available_account_balances(&self, account: &str) -> Result<AccountBalances> {
+        self.api.runtime.block_on(self.inner().return_available_account_balances(account))
+    }
+
+    /// Return the tradable balances of each market, by market name then by currency name.
+    pub fn return_tradable_balances(&self) -> Result<HashMap<String, HashMap<String, BigDecimal>>> {
+        self.api.runtime.block_on(self.inner().return_tradable_balances())
+    }
+
+    pub fn transfer_balance(&self,
+                            currency: &str,
+                            amount: &str,
+                            from_account: &str,
+                            to_account: &str)
+                            -> Result<Success> {
+        self.api
+            .runtime
+            .block_on(self.inner().transfer_balance(currency, amount, from_account, to_account))
+    }
+
+    pub fn return_margin_account_summary(&self) -> Result<MarginAccountSummary> {
+        self.api.runtime.block_on(self.inner().return_margin_account_summary())
+    }
+
+    pub fn margin_buy(&self,
+                      currency_pair: &str,
+                      rate: &str,
+                      amount: &str,
+                      lending_rate: &str)
+                      -> Result<MarginOrder> {
+        self.api
+            .runtime
+            .block_on(self.inner().margin_buy(currency_pair, rate, amount, lending_rate))
+    }
+
+    pub fn margin_sell(&self,
+                       currency_pair: &str,
+                       rate: &str,
+                       amount: &str,
+                       lending_rate: &str)
+                       -> Result<MarginOrder> {
+        self.api
+            .runtime
+            .block_on(self.inner().margin_sell(currency_pair, rate, amount, lending_rate))
+    }
+
+    /// Return the margin positions by market name, including when a single market is requested.
+    pub fn get_margin_position(&self,
+                               currency_pair: &str)
+                               -> Result<HashMap<String, MarginPosition>> {
+        self.api.runtime.block_on(self.inner().get_margin_position(currency_pair))
+    }
+
+    pub fn close_margin_position(&self, currency_pair: &str) -> Result<MarginOrder> {
+        self.api.runtime.block_on(self.inner().close_margin_position(currency_pair))
+    }
+
+    pub fn create_loan_offer(&self,
+                             currency: &str,
+                             amount: &str,
+                             duration: &str,
+                             auto_renew: &str,
+                             lending_rate: &str)
+                             -> Result<CreatedLoanOffer> {
+        self.api
+            .runtime
+            .block_on(self.inner()
+                          .create_loan_offer(currency, amount, duration, auto_renew, lending_rate))
+    }
+
+    pub fn cancel_loan_offer(&self, order_number: &str) -> Result<Success> {
+        self.api.runtime.block_on(self.inner().cancel_loan_offer(order_number))
+    }
+
+    /// Return the open loan offers by currency name.
+    pub fn return_open_loan_offers(&self) -> Result<HashMap<String, Vec<LoanOffer>>> {
+        self.api.runtime.block_on(self.inner().return_open_loan_offers())
+    }
+
+    pub fn return_active_loans(&self) -> Result<ActiveLoans> {
+        self.api.runtime.block_on(self.inner().return_active_loans())
+    }
+
+    pub fn return_lending_history(&self,
+                                  start: &str,
+                                  end: &str,
+                                  limit: &str)
+                                  -> Result<Vec<LendingHistoryEntry>> {
+        self.api.runtime.block_on(self.inner().return_lending_history(start, end, limit))
+    }
+
+    pub fn toggle_auto_renew(&self, order_number: &str) -> Result<ToggledAutoRenew> {
+        self.api.runtime.block_on(self.inner().toggle_auto_renew(order_number))
+    }
+}
+
+
+/// Convert the amounts of a result, by currency name.
+fn parse_amounts(result: Map<String, Value>) -> Result<HashMap<String, BigDecimal>> {
+    let mut amounts = HashMap::new();
+    for (currency, amount) in result {
+        let amount = helpers::from_json_number_bigdecimal(&amount, &currency)?;
+        amounts.insert(currency, amount);
+    }
+    Ok(amounts)
+}
@@ -1,136 +1,151 @@
 use bidir_map::BidirMap;
+use std::sync::RwLock;
 use serde_json;
 use serde_json::Value;
 use serde_json::value::Map;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use bigdecimal::{BigDecimal, Zero};
+
+use std::collections::HashMap;
 
 use crate::error::*;
-use crate::types::Currency;
-use crate::types::Pair;
-use crate::types::Pair::*;
+use crate::exchange::Exchange;
+use crate::helpers::{self, PairNames};
+use crate::normalization;
+use crate::types::*;
 
 lazy_static! {
-    static ref PAIRS_STRING: BidirMap<Pair, &'static str> = {
+    static ref PAIRS_STRING: PairNames = {
         let mut m = BidirMap::new();
-        m.insert(BCN_BTC, "BTC_BCN");
-        m.insert(BELA_BTC, "BTC_BELA");
-        m.insert(BLK_BTC, "BTC_BLK");
-        m.insert(BTCD_BTC, "BTC_BTCD");
-        m.insert(BTM_BTC, "BTC_BTM");
-        m.insert(BTS_BTC, "BTC_BTS");
-        m.insert(BURST_BTC, "BTC_BURST");
-        m.insert(CLAM_BTC, "BTC_CLAM");
-        m.insert(DASH_BTC, "BTC_DASH");
-        m.insert(DGB_BTC, "BTC_DGB");
-        m.insert(DOGE_BTC, "BTC_DOGE");
-        m.insert(EMC2_BTC, "BTC_EMC2");
-        m.insert(FLDC_BTC, "BTC_FLDC");
-        m.insert(FLO_BTC, "BTC_FLO");
-        m.insert(GAME_BTC, "BTC_GAME");
-        m.insert(GRC_BTC, "BTC_GRC");
-        m.insert(HUC_BTC, "BTC_HUC");
-        m.insert(LTC_BTC, "BTC_LTC");
-        m.insert(MAID_BTC, "BTC_MAID");
-        m.insert(OMNI_BTC, "BTC_OMNI");
-        m.insert(NAUT_BTC, "BTC_NAUT");
-        m.insert(NAV_BTC, "BTC_NAV");
-        m.insert(NEOS_BTC, "BTC_NEOS");
-        m.insert(NMC_BTC, "BTC_NMC");
-        m.insert(NOTE_BTC, "BTC_NOTE");
-        m.insert(NXT_BTC, "BTC_NXT");
-        m.insert(PINK_BTC, "BTC_PINK");
-        m.insert(POT_BTC, "BTC_POT");
-        m.insert(PPC_BTC, "BTC_PPC");
-        m.insert(RIC_BTC, "BTC_RIC");
-        m.insert(SJCX_BTC, "BTC_SJCX");
-        m.insert(STR_BTC, "BTC_STR");
-        m.insert(SYS_BTC, "BTC_SYS");
-        m.insert(VIA_BTC, "BTC_VIA");
-        m.insert(XVC_BTC, "BTC_XVC");
-        m.insert(VRC_BTC, "BTC_VRC");
-        m.insert(VTC_BTC, "BTC_VTC");
-        m.insert(XBC_BTC, "BTC_XBC");
-        m.insert(XCP_BTC, "BTC_XCP");
-        m.insert(XEM_BTC, "BTC_XEM");
-        m.insert(XMR_BTC, "BTC_XMR");
-        m.insert(XPM_BTC, "BTC_XPM");
-        m.insert(XRP_BTC, "BTC_XRP");
-        m.insert(BTC_USDT, "USDT_BTC");
-        m.insert(DASH_USDT, "USDT_DASH");
-        m.insert(LTC_USDT, "USDT_LTC");
-        m.insert(NXT_USDT, "USDT_NXT");
-        m.insert(STR_USDT, "USDT_STR");
-        m.insert(XMR_USDT, "USDT_XMR");
-        m.insert(XRP_USDT, "USDT_XRP");
-        m.insert(BCN_XMR, "XMR_BCN");
-        m.insert(BLK_XMR, "XMR_BLK");
-        m.insert(BTCD_XMR, "XMR_BTCD");
-        m.insert(DASH_XMR, "XMR_DASH");
-        m.insert(LTC_XMR, "XMR_LTC");
-        m.insert(MAID_XMR, "XMR_MAID");
-        m.insert(NXT_XMR, "XMR_NXT");
-        m.insert(ETH_BTC, "BTC_ETH");
-        m.insert(ETH_USDT, "USDT_ETH");
-        m.insert(SC_BTC, "BTC_SC");
-        m.insert(BCY_BTC, "BTC_BCY");
-        m.insert(EXP_BTC, "BTC_EXP");
-        m.insert(FCT_BTC, "BTC_FCT");
-        m.insert(RADS_BTC, "BTC_RADS");
-        m.insert(AMP_BTC, "BTC_AMP");
-        m.insert(DCR_BTC, "BTC_DCR");
-        m.insert(LSK_BTC, "BTC_LSK");
-        m.insert(LSK_ETH, "ETH_LSK");
-        m.insert(LBC_BTC, "BTC_LBC");
-        m.insert(STEEM_BTC, "BTC_STEEM");
-        m.insert(STEEM_ETH, "ETH_STEEM");
-        m.insert(SBD_BTC, "BTC_SBD");
-        m.insert(ETC_BTC, "BTC_ETC");
-        m.insert(ETC_ETH, "ETH_ETC");
-        m.insert(ETC_USDT, "USDT_ETC");
-        m.insert(REP_BTC, "BTC_REP");
-        m.insert(REP_USDT, "USDT_REP");
-        m.insert(REP_ETH, "ETH_REP");
-        m.insert(ARDR_BTC, "BTC_ARDR");
-        m.insert(ZEC_BTC, "BTC_ZEC");
-        m.insert(ZEC_ETH, "ETH_ZEC");
-        m.insert(ZEC_USDT, "USDT_ZEC");
-        m.insert(ZEC_XMR, "XMR_ZEC");
-        m.insert(STRAT_BTC, "BTC_STRAT");
-        m.insert(NXC_BTC, "BTC_NXC");
-        m.insert(PASC_BTC, "BTC_PASC");
-        m.insert(GNT_BTC, "BTC_GNT");
-        m.insert(GNT_ETH, "ETH_GNT");
-        m.insert(GNO_BTC, "BTC_GNO");
-        m.insert(GNO_ETH, "ETH_GNO");
-        m.insert(BCH_BTC, "BTC_BCH");
-        m.insert(BCH_ETH, "ETH_BCH");
-        m.insert(BCH_USDT, "USDT_BCH");
-        m.insert(ZRX_BTC, "BTC_ZRX");
-        m.insert(ZRX_ETH, "ETH_ZRX");
-        m.insert(CVC_BTC, "BTC_CVC");
-        m.insert(CVC_ETH, "ETH_CVC");
-        m.insert(OMG_BTC, "BTC_OMG");
-        m.insert(OMG_ETH, "ETH_OMG");
-        m.insert(GAS_BTC, "BTC_GAS");
-        m.insert(GAS_ETH, "ETH_GAS");
-        m.insert(STORJ_BTC, "BTC_STORJ");
-        m
+        m.insert(Pair::BCN_BTC, "BTC_BCN");
+        m.insert(Pair::BELA_BTC, "BTC_BELA");
+        m.insert(Pair::BLK_BTC, "BTC_BLK");
+        m.insert(Pair::BTCD_BTC, "BTC_BTCD");
+        m.insert(Pair::BTM_BTC, "BTC_BTM");
+        m.insert(Pair::BTS_BTC, "BTC_BTS");
+        m.insert(Pair::BURST_BTC, "BTC_BURST");
+        m.insert(Pair::CLAM_BTC, "BTC_CLAM");
+        m.insert(Pair::DASH_BTC, "BTC_DASH");
+        m.insert(Pair::DGB_BTC, "BTC_DGB");
+        m.insert(Pair::DOGE_BTC, "BTC_DOGE");
+        m.insert(Pair::EMC2_BTC, "BTC_EMC2");
+        m.insert(Pair::FLDC_BTC, "BTC_FLDC");
+        m.insert(Pair::FLO_BTC, "BTC_FLO");
+        m.insert(Pair::GAME_BTC, "BTC_GAME");
+        m.insert(Pair::GRC_BTC, "BTC_GRC");
+        m.insert(Pair::HUC_BTC, "BTC_HUC");
+        m.insert(Pair::LTC_BTC, "BTC_LTC");
+        m.insert(Pair::MAID_BTC, "BTC_MAID");
+        m.insert(Pair::OMNI_BTC, "BTC_OMNI");
+        m.insert(Pair::NAUT_BTC, "BTC_NAUT");
+        m.insert(Pair::NAV_BTC, "BTC_NAV");
+        m.insert(Pair::NEOS_BTC, "BTC_NEOS");
+        m.insert(Pair::NMC_BTC, "BTC_NMC");
+        m.insert(Pair::NOTE_BTC, "BTC_NOTE");
+        m.insert(Pair::NXT_BTC, "BTC_NXT");
+        m.insert(Pair::PINK_BTC, "BTC_PINK");
+        m.insert(Pair::POT_BTC, "BTC_POT");
+        m.insert(Pair::PPC_BTC, "BTC_PPC");
+        m.insert(Pair::RIC_BTC, "BTC_RIC");
+        m.insert(Pair::SJCX_BTC, "BTC_SJCX");
+        m.insert(Pair::XLM_BTC, "BTC_STR");
+        m.insert(Pair::SYS_BTC, "BTC_SYS");
+        m.insert(Pair::VIA_BTC, "BTC_VIA");
+        m.insert(Pair::XVC_BTC, "BTC_XVC");
+        m.insert(Pair::VRC_BTC, "BTC_VRC");
+        m.insert(Pair::VTC_BTC, "BTC_VTC");
+        m.insert(Pair::XBC_BTC, "BTC_XBC");
+        m.insert(Pair::XCP_BTC, "BTC_XCP");
+        m.insert(Pair::XEM_BTC, "BTC_XEM");
+        m.insert(Pair::XMR_BTC, "BTC_XMR");
+        m.insert(Pair::XPM_BTC, "BTC_XPM");
+        m.insert(Pair::XRP_BTC, "BTC_XRP");
+        m.insert(Pair::BTC_USDT, "USDT_BTC");
+        m.insert(Pair::DASH_USDT, "USDT_DASH");
+        m.insert(Pair::LTC_USDT, "USDT_LTC");
+        m.insert(Pair::NXT_USDT, "USDT_NXT");
+        m.insert(Pair::XLM_USDT, "USDT_STR");
+        m.insert(Pair::XMR_USDT, "USDT_XMR");
+        m.insert(Pair::XRP_USDT, "USDT_XRP");
+        m.insert(Pair::BCN_XMR, "XMR_BCN");
+        m.insert(Pair::BLK_XMR, "XMR_BLK");
+        m.insert(Pair::BTCD_XMR, "XMR_BTCD");
+        m.insert(Pair::DASH_XMR, "XMR_DASH");
+        m.insert(Pair::LTC_XMR, "XMR_LTC");
+        m.insert(Pair::MAID_XMR, "XMR_MAID");
+        m.insert(Pair::NXT_XMR, "XMR_NXT");
+        m.insert(Pair::ETH_BTC, "BTC_ETH");
+        m.insert(Pair::ETH_USDT, "USDT_ETH");
+        m.insert(Pair::SC_BTC, "BTC_SC");
+        m.insert(Pair::BCY_BTC, "BTC_BCY");
+        m.insert(Pair::EXP_BTC, "BTC_EXP");
+        m.insert(Pair::FCT_BTC, "BTC_FCT");
+        m.insert(Pair::RADS_BTC, "BTC_RADS");
+        m.insert(Pair::AMP_BTC, "BTC_AMP");
+        m.insert(Pair::DCR_BTC, "BTC_DCR");
+        m.insert(Pair::LSK_BTC, "BTC_LSK");
+        m.insert(Pair::LSK_ETH, "ETH_LSK");
+        m.insert(Pair::LBC_BTC, "BTC_LBC");
+        m.insert(Pair::STEEM_BTC, "BTC_STEEM");
+        m.insert(Pair::STEEM_ETH, "ETH_STEEM");
+        m.insert(Pair::SBD_BTC, "BTC_SBD");
+        m.insert(Pair::ETC_BTC, "BTC_ETC");
+        m.insert(Pair::ETC_ETH, "ETH_ETC");
+        m.insert(Pair::ETC_USDT, "USDT_ETC");
+        m.insert(Pair::REP_BTC, "BTC_REP");
+        m.insert(Pair::REP_USDT, "USDT_REP");
+        m.insert(Pair::REP_ETH, "ETH_REP");
+        m.insert(Pair::ARDR_BTC, "BTC_ARDR");
+        m.insert(Pair::ZEC_BTC, "BTC_ZEC");
+        m.insert(Pair::ZEC_ETH, "ETH_ZEC");
+        m.insert(Pair::ZEC_USDT, "USDT_ZEC");
+        m.insert(Pair::ZEC_XMR, "XMR_ZEC");
+        m.insert(Pair::STRAT_BTC, "BTC_STRAT");
+        m.insert(Pair::NXC_BTC, "BTC_NXC");
+        m.insert(Pair::PASC_BTC, "BTC_PASC");
+        m.insert(Pair::GNT_BTC, "BTC_GNT");
+        m.insert(Pair::GNT_ETH, "ETH_GNT");
+        m.insert(Pair::GNO_BTC, "BTC_GNO");
+        m.insert(Pair::GNO_ETH, "ETH_GNO");
+        m.insert(Pair::BCH_BTC, "BTC_BCH");
+        m.insert(Pair::BCH_ETH, "ETH_BCH");
+        m.insert(Pair::BCH_USDT, "USDT_BCH");
+        m.insert(Pair::ZRX_BTC, "BTC_ZRX");
+        m.insert(Pair::ZRX_ETH, "ETH_ZRX");
+        m.insert(Pair::CVC_BTC, "BTC_CVC");
+        m.insert(Pair::CVC_ETH, "ETH_CVC");
+        m.insert(Pair::OMG_BTC, "BTC_OMG");
+        m.insert(Pair::OMG_ETH, "ETH_OMG");
+        m.insert(Pair::GAS_BTC, "BTC_GAS");
+        m.insert(Pair::GAS_ETH, "ETH_GAS");
+        m.insert(Pair::STORJ_BTC, "BTC_STORJ");
+        RwLock::new(m)
     };
 }
 
 /// Return the name associated to pair used by Poloniex
-/// If the Pair is not supported, None is returned.
-pub fn get_pair_string(pair: &Pair) -> Option<&&str> {
-    PAIRS_STRING.get_by_first(pair)
+/// If the Pair is not supported, None is returned. Pairs listed since the last update of this
+/// module are only known once `register_pair` has been called (see `ExchangeApi::markets`).
+pub fn get_pair_string(pair: &Pair) -> Option<&'static str> {
+    helpers::get_pair_name(&PAIRS_STRING, pair)
 }
 
 /// Return the Pair enum associated to the string used by Poloniex
 /// If the Pair is not supported, None is returned.
-pub fn get_pair_enum(pair: &str) -> Option<&Pair> {
-    PAIRS_STRING.get_by_second(&pair)
+pub fn get_pair_enum(pair: &str) -> Option<Pair> {
+    helpers::get_pair_from_name(&PAIRS_STRING, pair)
+}
+
+/// Add a pair listed by Poloniex and the name used by Poloniex for it. Pairs and names that are
+/// already known are left untouched.
+pub fn register_pair(pair: Pair, name: &str) {
+    helpers::register_pair(&PAIRS_STRING, pair, name)
 }
 
 pub fn deserialize_json(json_string: &str) -> Result<Map<String, Value>> {
-    let data: Value = match serde_json::from_str(json_string) {
+    let data: Value = match helpers::parse_json(json_string) {
         Ok(data) => data,
         Err(_) => return Err(ErrorKind::BadParse.into()),
     };
@@ -142,8 +157,9 @@ pub fn deserialize_json(json_string: &str) -> Result<Map<String, Value>> {
 }
 
 /// Convert a JSON array into a map containing a Vec for the "data" key
+/// A JSON object (ie. an error) is returned as is.
 pub fn deserialize_json_array(json_string: &str) -> Result<Map<String, Value>> {
-    let data: Value = match serde_json::from_str(json_string) {
+    let data: Value = match helpers::parse_json(json_string) {
         Ok(data) => data,
         Err(_) => return Err(ErrorKind::BadParse.into()),
     };
@@ -154,15 +170,51 @@ pub fn deserialize_json_array(json_string: &str) -> Result<Map<String, Value>> {
         Ok(map)
     }
 
+    else if let Some(value) = data.as_object() {
+        Ok(value.clone())
+    }
+
     else {
         Err(ErrorKind::BadParse.into())
     }
 }
 
 
+/// Return the ErrorKind matching an error message sent by Poloniex in the "error" field. Some
+/// messages contain values (ie. "Not enough BTC." or "Nonce must be greater than 1508923400000.
+/// You provided 1508923399000."), they are matched on their constant part.
+pub fn get_error_kind(message: &str) -> ErrorKind {
+    match message {
+        "Invalid API key/secret pair." => ErrorKind::BadCredentials,
+        "Permission denied." => ErrorKind::PermissionDenied,
+        "Invalid currency pair." | "Invalid currencyPair parameter." => ErrorKind::PairUnsupported,
+        "Invalid order number, or you are not the person who placed the order." |
+        "Order not found, or you are not the person who placed it." => ErrorKind::OrderNotFound,
+        "This market is frozen." | "This market is disabled." => ErrorKind::MarketInactive,
+        "Internal error. Please try again." => {
+            ErrorKind::ServiceUnavailable(message.to_string())
+        }
+        message if message.starts_with("Nonce must be greater than") => ErrorKind::InvalidNonce,
+        message if message.starts_with("Not enough ") => ErrorKind::InsufficientFunds,
+        message if message.starts_with("Total must be at least") ||
+                   message.starts_with("Amount must be at least") => {
+            ErrorKind::InsufficientOrderSize
+        }
+        message if message.starts_with("Please do not make more than") => {
+            ErrorKind::RateLimitExceeded
+        }
+        message if message == "Invalid command." ||
+                   (message.starts_with("Invalid ") && message.ends_with(" parameter.")) ||
+                   message.starts_with("Required parameter") => ErrorKind::InvalidArguments,
+        other => ErrorKind::ExchangeSpecificError(other.to_string()),
+    }
+}
+
 /// If error array is null, return the result (encoded in a json object)
 /// else return the error string found in array
-pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>> {
+pub fn parse_result(response: &Map<String, Value>,
+                    endpoint: &str)
+                    -> Result<Map<String, Value>> {
     let error_msg = match response.get("error") {
         Some(error) => {
             error
@@ -172,17 +224,313 @@ pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>>
         None => return Ok(response.clone()),
     };
 
-    match error_msg.as_ref() {
-        "Invalid command." => Err(ErrorKind::InvalidArguments.into()),
-        "Invalid API key/secret pair." => Err(ErrorKind::BadCredentials.into()),
-        "Total must be at least 0.0001." => Err(ErrorKind::InsufficientOrderSize.into()),
-        other => Err(ErrorKind::ExchangeSpecificError(other.to_string()).into()),
+    Err(ExchangeError::new(Exchange::Poloniex, endpoint)
+            .message(error_msg)
+            .into_error(get_error_kind(error_msg)))
+}
+
+/// Check the response like `parse_result`, then deserialize it into the typed response of the
+/// endpoint (see the `responses` module).
+pub fn parse_response<T: DeserializeOwned>(response: &Map<String, Value>,
+                                           endpoint: &str)
+                                           -> Result<T> {
+    let result = parse_result(response, endpoint)?;
+    T::deserialize(Value::Object(result)).chain_err(|| ErrorKind::BadParse)
+}
+
+/// Same as `parse_response` for the endpoints returning an array, stored in the "data" field
+/// of the response (see `deserialize_json_array`).
+pub fn parse_array_response<T: DeserializeOwned>(response: &Map<String, Value>,
+                                                 endpoint: &str)
+                                                 -> Result<Vec<T>> {
+    let mut result = parse_result(response, endpoint)?;
+    let data = result
+        .remove("data")
+        .ok_or_else(|| ErrorKind::MissingField("data".to_string()))?;
+    Vec::<T>::deserialize(data).chain_err(|| ErrorKind::BadParse)
+}
+
+/// Same as `parse_response` for the endpoints returning the entries of a market as an array,
+/// or the entries of every market by name when the market is "all". The entries of a single
+/// market are returned under its name, an empty array sent for "all" is an empty map.
+pub fn parse_market_response<T: DeserializeOwned>(response: &Map<String, Value>,
+                                                  endpoint: &str,
+                                                  market: &str)
+                                                  -> Result<HashMap<String, Vec<T>>> {
+    let mut result = parse_result(response, endpoint)?;
+    let entries = match result.remove("data") {
+        Some(Value::Array(ref entries)) if entries.is_empty() && market == "all" => {
+            return Ok(HashMap::new())
+        }
+        Some(entries) => entries,
+        None => {
+            return HashMap::<String, Vec<T>>::deserialize(Value::Object(result))
+                       .chain_err(|| ErrorKind::BadParse)
+        }
+    };
+
+    let entries = Vec::<T>::deserialize(entries).chain_err(|| ErrorKind::BadParse)?;
+    let mut markets = HashMap::new();
+    markets.insert(market.to_string(), entries);
+    Ok(markets)
+}
+
+/// Convert the trades returned by `returnTradeHistory` (most recent first) into a list of
+/// Trade, from the oldest to the most recent.
+pub fn parse_trades(trades: &[Value]) -> Result<Vec<Trade>> {
+    let mut result = Vec::new();
+
+    for trade in trades.iter().rev() {
+        let date = helpers::get_json_string(trade, "date")?;
+
+        result.push(Trade {
+                        identifier: helpers::get_json_id(trade, "tradeID")?,
+                        timestamp: helpers::parse_datetime_ms(date, "date")?,
+                        price: helpers::from_json_bigdecimal(&trade["rate"], "rate")?,
+                        volume: helpers::from_json_bigdecimal(&trade["amount"], "amount")?,
+                        side: parse_side(trade)?,
+                    });
+    }
+
+    Ok(result)
+}
+
+/// Return the candle period (in s) expected by `returnChartData`, if Poloniex supports the
+/// Interval.
+pub fn get_interval_period(interval: Interval) -> Option<String> {
+    match interval {
+        Interval::FiveMinutes |
+        Interval::FifteenMinutes |
+        Interval::ThirtyMinutes |
+        Interval::TwoHours |
+        Interval::FourHours |
+        Interval::OneDay => Some(interval.seconds().to_string()),
+        _ => None,
+    }
+}
+
+/// Convert the candles returned by `returnChartData` into a list of Candle.
+/// Poloniex returns a single candle dated 0 when there is no data for the period requested, it
+/// is skipped.
+pub fn parse_candles(candles: &[Value]) -> Result<Vec<Candle>> {
+    let mut result = Vec::new();
+
+    for candle in candles {
+        let date = candle["date"]
+            .as_i64()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat("date".to_string()))?;
+
+        if date == 0 {
+            continue;
+        }
+
+        result.push(Candle {
+                        timestamp: date * 1000,
+                        open: helpers::from_json_number_bigdecimal(&candle["open"], "open")?,
+                        high: helpers::from_json_number_bigdecimal(&candle["high"], "high")?,
+                        low: helpers::from_json_number_bigdecimal(&candle["low"], "low")?,
+                        close: helpers::from_json_number_bigdecimal(&candle["close"], "close")?,
+                        volume: helpers::from_json_number_bigdecimal(&candle["quoteVolume"],
+                                                                     "quoteVolume")?,
+                    });
+    }
+
+    Ok(result)
+}
+
+/// Poloniex can only filter the trade history by time (inclusive), so the cursor returned by
+/// the generic API is "<UNIX timestamp in s>:<trade id>" of the last trade.
+pub fn build_trade_cursor(trade: &Trade) -> String {
+    format!("{}:{}", trade.timestamp / 1000, trade.identifier)
+}
+
+/// Return the start timestamp (in s) and the last trade id contained in a cursor built by
+/// `build_trade_cursor`.
+pub fn parse_trade_cursor(cursor: &str) -> Result<(String, u64)> {
+    let mut parts = cursor.splitn(2, ':');
+
+    let start = parts.next().ok_or(ErrorKind::InvalidArguments)?;
+    let last_id = parts
+        .next()
+        .and_then(|id| id.parse::<u64>().ok())
+        .ok_or(ErrorKind::InvalidArguments)?;
+
+    if start.parse::<u64>().is_err() {
+        return Err(ErrorKind::InvalidArguments.into());
+    }
+
+    Ok((start.to_string(), last_id))
+}
+
+fn parse_side(order: &Value) -> Result<OrderSide> {
+    match helpers::get_json_string(order, "type")? {
+        "buy" => Ok(OrderSide::Buy),
+        "sell" => Ok(OrderSide::Sell),
+        _ => Err(ErrorKind::InvalidFieldValue("type".to_string()).into()),
+    }
+}
+
+/// Convert the result of `returnTicker` into Markets. The pairs that are not known yet are
+/// registered (see `register_pair`).
+/// Poloniex does not publish the trading rules of its markets, only whether they are frozen.
+pub fn parse_markets(tickers: &Map<String, Value>) -> Result<Markets> {
+    let mut markets = Markets::new();
+
+    for (name, ticker) in tickers {
+        let pair = match get_pair_enum(name) {
+            Some(pair) => pair,
+            None => {
+                // Poloniex puts the right member of the pair first ("BTC_ETH" for ETH_BTC)
+                let mut currencies = name.split('_').map(get_currency_enum);
+                let pair = match (currencies.next(), currencies.next(), currencies.next()) {
+                    (Some(Some(right)), Some(Some(left)), None) => Pair::new(left, right),
+                    _ => return Err(ErrorKind::InvalidFieldValue(name.to_string()).into()),
+                };
+                register_pair(pair, name);
+                pair
+            }
+        };
+
+        markets.insert(pair,
+                       MarketInfo {
+                           pair,
+                           min_order_size: None,
+                           tick_size: None,
+                           lot_decimals: None,
+                           min_notional: None,
+                           active: ticker["isFrozen"].as_str() != Some("1"),
+                       });
+    }
+
+    Ok(markets)
+}
+
+/// Convert an order returned by `returnOpenOrders` into an Order.
+/// Every Poloniex order is a limit order.
+pub fn parse_open_order(pair: Pair, order: &Value) -> Result<Order> {
+    let side = parse_side(order)?;
+
+    let amount = helpers::from_json_bigdecimal(&order["amount"], "amount")?;
+    let quantity = match order.get("startingAmount") {
+        Some(starting_amount) => helpers::from_json_bigdecimal(starting_amount, "startingAmount")?,
+        None => amount.clone(),
+    };
+    let filled_quantity = quantity.clone() - amount;
+
+    Ok(Order {
+           timestamp: helpers::get_unix_timestamp_ms(),
+           identifier: helpers::get_json_string(order, "orderNumber")?.to_string(),
+           pair,
+           side,
+           order_type: match side {
+               OrderSide::Buy => OrderType::BuyLimit,
+               OrderSide::Sell => OrderType::SellLimit,
+           },
+           price: Some(helpers::from_json_bigdecimal(&order["rate"], "rate")?),
+           quantity,
+           status: if filled_quantity.is_zero() {
+               OrderStatus::Open
+           } else {
+               OrderStatus::PartiallyFilled
+           },
+           filled_quantity,
+           average_price: None,
+           opened_at: match order.get("date") {
+               Some(date) => {
+                   let date = date.as_str()
+                       .ok_or_else(|| ErrorKind::InvalidFieldFormat("date".to_string()))?;
+                   Some(helpers::parse_datetime_ms(date, "date")?)
+               }
+               None => None,
+           },
+           closed_at: None,
+       })
+}
+
+/// Build an Order from the trades returned by `returnOrderTrades` for an order which is not
+/// open anymore. Poloniex does not report the original quantity of such an order, so the
+/// quantity, the type and the limit price are the ones of the request (see `OrderInfo`): the
+/// order is Filled if the trades cover its quantity, Canceled otherwise (ie. canceled unfilled
+/// when there is no trade).
+pub fn parse_order_trades(order: &OrderInfo, trades: &[Value]) -> Result<Order> {
+    let order_number = order.identifier.first().ok_or(ErrorKind::OrderNotFound)?;
+    let side = match order.order_type {
+        OrderType::BuyLimit | OrderType::BuyMarket => OrderSide::Buy,
+        OrderType::SellLimit | OrderType::SellMarket => OrderSide::Sell,
+    };
+
+    let mut filled_quantity = BigDecimal::zero();
+    let mut total = BigDecimal::zero();
+    let mut opened_at = None;
+    let mut closed_at = None;
+
+    for trade in trades {
+        filled_quantity += helpers::from_json_bigdecimal(&trade["amount"], "amount")?;
+        total += helpers::from_json_bigdecimal(&trade["total"], "total")?;
+
+        let date = helpers::parse_datetime_ms(helpers::get_json_string(trade, "date")?, "date")?;
+        opened_at = Some(opened_at.map_or(date, |opened: i64| opened.min(date)));
+        closed_at = Some(closed_at.map_or(date, |closed: i64| closed.max(date)));
     }
+
+    let average_price = if filled_quantity.is_zero() {
+        None
+    } else {
+        Some(total / filled_quantity.clone())
+    };
+
+    let status = if filled_quantity >= order.quantity {
+        OrderStatus::Filled
+    } else {
+        OrderStatus::Canceled
+    };
+
+    Ok(Order {
+           timestamp: helpers::get_unix_timestamp_ms(),
+           identifier: order_number.to_string(),
+           pair: order.pair,
+           side,
+           order_type: order.order_type,
+           price: order.price.clone(),
+           quantity: order.quantity.clone(),
+           average_price,
+           filled_quantity,
+           status,
+           opened_at,
+           closed_at,
+       })
+}
+
+/// Convert the "resultingTrades" returned by `buy` and `sell` into a list of Fill.
+pub fn parse_resulting_trades(response: &Map<String, Value>) -> Result<Vec<Fill>> {
+    let trades = match response.get("resultingTrades") {
+        Some(trades) => {
+            trades
+                .as_array()
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat("resultingTrades".to_string()))?
+        }
+        None => return Ok(Vec::new()),
+    };
+
+    let mut fills = Vec::new();
+
+    for trade in trades {
+        fills.push(Fill {
+                       identifier: helpers::get_json_string(trade, "tradeID")?.to_string(),
+                       timestamp: helpers::parse_datetime_ms(helpers::get_json_string(trade,
+                                                                                      "date")?,
+                                                             "date")?,
+                       price: helpers::from_json_bigdecimal(&trade["rate"], "rate")?,
+                       quantity: helpers::from_json_bigdecimal(&trade["amount"], "amount")?,
+                   });
+    }
+
+    Ok(fills)
 }
 
 /// Return the currency enum associated with the
-/// string used by Poloniex. If no currency is found,
-/// return None
+/// string used by Poloniex, see the `normalization` module.
+/// If the string is empty, return None
 /// # Examples
 ///
 /// ```
@@ -193,25 +541,11 @@ pub fn parse_result(response: &Map<String, Value>) -> Result<Map<String, Value>>
 /// assert_eq!(currency, Currency::BTC);
 /// ```
 pub fn get_currency_enum(currency: &str) -> Option<Currency> {
-    match currency {
-        "AMP" => Some(Currency::AMP),
-        "BTC" => Some(Currency::BTC),
-        "ARDR" => Some(Currency::ARDR),
-        "ETH" => Some(Currency::ETH),
-        "ETC" => Some(Currency::ETC),
-        "LBC" => Some(Currency::LBC),
-        "XMR" => Some(Currency::XMR),
-        "XPM" => Some(Currency::XPM),
-        "XRP" => Some(Currency::XRP),
-        "XVC" => Some(Currency::XVC),
-        "ZEC" => Some(Currency::ZEC),
-        _ => None,
-    }
+    normalization::get_currency(Exchange::Poloniex, currency)
 }
 
-/// Return the currency string associated with the
-/// enum used by Poloniex. If no currency is found,
-/// return None
+/// Return the name used by Poloniex for the currency, see
+/// the `normalization` module
 /// # Examples
 ///
 /// ```
@@ -222,18 +556,5 @@ pub fn get_currency_enum(currency: &str) -> Option<Currency> {
 /// assert_eq!(currency, Some("BTC".to_string()));
 /// ```
 pub fn get_currency_string(currency: Currency) -> Option<String> {
-    match currency {
-        Currency::AMP => Some("AMP".to_string()),
-        Currency::BTC => Some("BTC".to_string()),
-        Currency::ARDR => Some("ARDR".to_string()),
-        Currency::ETH => Some("ETH".to_string()),
-        Currency::ETC => Some("ETC".to_string()),
-        Currency::LBC => Some("LBC".to_string()),
-        Currency::XMR => Some("XMR".to_string()),
-        Currency::XPM => Some("XPM".to_string()),
-        Currency::XRP => Some("XRP".to_string()),
-        Currency::XVC => Some("XVC".to_string()),
-        Currency::ZEC => Some("ZEC".to_string()),
-        _ => None,
-    }
+    Some(normalization::get_name(Exchange::Poloniex, currency))
 }
@@ -0,0 +1,275 @@
+//! This module limits the number of requests sent to the exchanges, to avoid a ban.
+//!
+//! Each exchange documents its limits differently: Kraken increments a call counter which decays
+//! over time and costs more for the ledger and trade history calls, GDAX allows a number of
+//! requests per second with bursts, Poloniex and Bittrex have a single limit for all the calls...
+//! All of them are modeled by token buckets: a request takes its cost in tokens from the bucket of
+//! its endpoint, and the buckets are refilled at a constant rate up to their capacity.
+//!
+//! The limits apply to an API key, so by default every client created with the same API key on
+//! the same exchange uses the same `RateLimiter` (see `RateLimiter::shared`).
+
+use tokio::time::sleep;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::error::*;
+use crate::exchange::Exchange;
+
+lazy_static! {
+    static ref SHARED_LIMITERS: Mutex<HashMap<(Exchange, String), RateLimiter>> =
+        Mutex::new(HashMap::new());
+}
+
+/// What to do when a request would exceed the limits of the exchange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    /// Wait until the request can be sent (default).
+    Throttle,
+    /// Send the request immediately. The exchange may reject it or ban the API key.
+    Burst,
+    /// Fail with `ErrorKind::RateLimitExceeded` instead of waiting.
+    Reject,
+}
+
+/// Whether a request is sent to a public or to a private (authenticated) endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Public,
+    Private,
+}
+
+/// The token buckets of an exchange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bucket {
+    /// The public endpoints, or every endpoint when the exchange has a single limit.
+    Public,
+    /// The private endpoints.
+    Private,
+    /// The placement and cancellation of orders, when the exchange limits them separately.
+    Orders,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BucketConfig {
+    /// The maximum number of tokens, ie. the largest burst of requests allowed.
+    pub capacity: f64,
+    /// The number of tokens added to the bucket every second.
+    pub refill_per_sec: f64,
+}
+
+impl BucketConfig {
+    pub fn new(capacity: f64, refill_per_sec: f64) -> BucketConfig {
+        BucketConfig {
+            capacity,
+            refill_per_sec,
+        }
+    }
+}
+
+/// The bucket a request takes its tokens from, and the number of tokens it takes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cost {
+    pub bucket: Bucket,
+    pub tokens: f64,
+}
+
+impl Cost {
+    pub fn new(bucket: Bucket, tokens: f64) -> Cost {
+        Cost { bucket, tokens }
+    }
+}
+
+/// Return the buckets of the exchange, as documented for a new account.
+pub fn get_buckets(exchange: Exchange) -> Vec<(Bucket, BucketConfig)> {
+    match exchange {
+        // 1 public call per second. The private call counter of the Starter tier is limited to 15
+        // and decays by 0.33 per second. The matching engine accepts 60 orders and cancellations
+        // at once and 1 more per second.
+        Exchange::Kraken => {
+            vec![(Bucket::Public, BucketConfig::new(1.0, 1.0)),
+                 (Bucket::Private, BucketConfig::new(15.0, 0.33)),
+                 (Bucket::Orders, BucketConfig::new(60.0, 1.0))]
+        }
+        // 600 requests per 10 minutes for all the endpoints.
+        Exchange::Bitstamp => vec![(Bucket::Public, BucketConfig::new(10.0, 1.0))],
+        // 60 requests per minute for all the endpoints.
+        Exchange::Bittrex => vec![(Bucket::Public, BucketConfig::new(60.0, 1.0))],
+        // 6 requests per second for all the endpoints.
+        Exchange::Poloniex => vec![(Bucket::Public, BucketConfig::new(6.0, 6.0))],
+        // 3 public requests per second in bursts of up to 6, 5 private requests per second in
+        // bursts of up to 10.
+        Exchange::Gdax => {
+            vec![(Bucket::Public, BucketConfig::new(6.0, 3.0)),
+                 (Bucket::Private, BucketConfig::new(10.0, 5.0))]
+        }
+    }
+}
+
+/// Return the cost of a request to the endpoint of the exchange. The endpoint is the name used
+/// by the exchange module for the request (ie. "Balance" for Kraken, "returnTicker" for Poloniex
+/// or "markets" for Bittrex).
+pub fn get_cost(exchange: Exchange, scope: Scope, endpoint: &str) -> Cost {
+    match (exchange, scope) {
+        (_, Scope::Public) => Cost::new(Bucket::Public, 1.0),
+        (Exchange::Kraken, Scope::Private) => {
+            match endpoint {
+                "AddOrder" | "CancelOrder" => Cost::new(Bucket::Orders, 1.0),
+                "Ledgers" | "QueryLedgers" | "TradesHistory" | "QueryTrades" => {
+                    Cost::new(Bucket::Private, 2.0)
+                }
+                _ => Cost::new(Bucket::Private, 1.0),
+            }
+        }
+        (Exchange::Gdax, Scope::Private) => Cost::new(Bucket::Private, 1.0),
+        (Exchange::Bitstamp, Scope::Private) |
+        (Exchange::Bittrex, Scope::Private) |
+        (Exchange::Poloniex, Scope::Private) => Cost::new(Bucket::Public, 1.0),
+    }
+}
+
+#[derive(Debug)]
+struct BucketState {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    config: BucketConfig,
+    state: Mutex<BucketState>,
+}
+
+impl TokenBucket {
+    fn new(config: BucketConfig) -> TokenBucket {
+        TokenBucket {
+            config,
+            state: Mutex::new(BucketState {
+                                  tokens: config.capacity,
+                                  updated: Instant::now(),
+                              }),
+        }
+    }
+
+    /// Take the tokens from the bucket and return how long to wait before they are available.
+    /// When throttling, the tokens are taken in advance so that concurrent callers wait in turn.
+    fn take(&self, tokens: f64, policy: Policy) -> Result<Duration> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.updated).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.config.refill_per_sec)
+            .min(self.config.capacity);
+        state.updated = now;
+
+        match policy {
+            Policy::Throttle => {
+                state.tokens -= tokens;
+                if state.tokens >= 0.0 {
+                    Ok(Duration::from_millis(0))
+                } else {
+                    Ok(Duration::from_secs_f64(-state.tokens / self.config.refill_per_sec))
+                }
+            }
+            Policy::Burst => {
+                state.tokens = (state.tokens - tokens).max(0.0);
+                Ok(Duration::from_millis(0))
+            }
+            Policy::Reject => {
+                if state.tokens < tokens {
+                    return Err(ErrorKind::RateLimitExceeded.into());
+                }
+                state.tokens -= tokens;
+                Ok(Duration::from_millis(0))
+            }
+        }
+    }
+}
+
+struct Limiter {
+    exchange: Exchange,
+    policy: Mutex<Policy>,
+    buckets: HashMap<Bucket, TokenBucket>,
+}
+
+/// The token buckets of an exchange. Cloning a RateLimiter returns a handle on the same buckets,
+/// so that several clients can share it.
+#[derive(Clone)]
+pub struct RateLimiter {
+    inner: Arc<Limiter>,
+}
+
+impl RateLimiter {
+    /// Create a RateLimiter with the buckets documented by the exchange (see `get_buckets`).
+    pub fn new(exchange: Exchange) -> RateLimiter {
+        RateLimiter::with_buckets(exchange, get_buckets(exchange))
+    }
+
+    /// Create a RateLimiter with custom buckets, ie. for an account with higher limits. The
+    /// requests costing tokens from a bucket which is not given are not limited.
+    pub fn with_buckets(exchange: Exchange, buckets: Vec<(Bucket, BucketConfig)>) -> RateLimiter {
+        RateLimiter {
+            inner: Arc::new(Limiter {
+                                exchange,
+                                policy: Mutex::new(Policy::Throttle),
+                                buckets: buckets
+                                    .into_iter()
+                                    .map(|(bucket, config)| (bucket, TokenBucket::new(config)))
+                                    .collect(),
+                            }),
+        }
+    }
+
+    /// Return the RateLimiter shared by all the clients using this API key on the exchange,
+    /// creating it if needed. This is the RateLimiter used by the clients by default.
+    pub fn shared(exchange: Exchange, api_key: &str) -> RateLimiter {
+        let mut limiters = SHARED_LIMITERS.lock().unwrap();
+        limiters
+            .entry((exchange, api_key.to_string()))
+            .or_insert_with(|| RateLimiter::new(exchange))
+            .clone()
+    }
+
+    pub fn exchange(&self) -> Exchange {
+        self.inner.exchange
+    }
+
+    pub fn policy(&self) -> Policy {
+        *self.inner.policy.lock().unwrap()
+    }
+
+    /// Change the policy of the RateLimiter, and thus of all the clients sharing it.
+    pub fn set_policy(&self, policy: Policy) {
+        *self.inner.policy.lock().unwrap() = policy;
+    }
+
+    /// Take the cost of a request to the endpoint from its bucket and return how long to wait
+    /// before sending it.
+    pub fn reserve(&self, scope: Scope, endpoint: &str) -> Result<Duration> {
+        let cost = get_cost(self.inner.exchange, scope, endpoint);
+        match self.inner.buckets.get(&cost.bucket) {
+            Some(bucket) => bucket.take(cost.tokens, self.policy()),
+            None => Ok(Duration::from_millis(0)),
+        }
+    }
+
+    /// Wait until a request to the endpoint can be sent.
+    pub async fn acquire(&self, scope: Scope, endpoint: &str) -> Result<()> {
+        let wait = self.reserve(scope, endpoint)?;
+        if wait > Duration::from_millis(0) {
+            sleep(wait).await;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RateLimiter")
+            .field("exchange", &self.inner.exchange)
+            .field("policy", &self.policy())
+            .finish()
+    }
+}
@@ -0,0 +1,242 @@
+//! This module sends again the requests failing with a transient error.
+//!
+//! A `RetryPolicy` attached to a client (see `set_retry_policy`) decides how many times a request
+//! is sent, how long to wait between two attempts (exponential backoff with jitter) and which
+//! errors are worth a new attempt. When the exchange sends a `Retry-After` header, the next
+//! attempt waits at least as long as it asks.
+//!
+//! Requests reading data are sent again freely. Requests changing the account, like the placement
+//! of an order, are only sent again when the error proves that the exchange rejected them without
+//! executing them (`ErrorKind::InvalidNonce` or `ErrorKind::RateLimitExceeded`): a timeout does
+//! not tell whether the order was placed.
+
+use tokio::time::sleep;
+
+use std::cmp;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::*;
+use crate::exchange::Exchange;
+use crate::rate_limit::Scope;
+
+/// Whether a request can safely be sent again when it failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idempotency {
+    /// The request only reads data: sending it again has no other effect.
+    Idempotent,
+    /// The request changes the account (ie. places an order or a withdrawal).
+    NonIdempotent,
+}
+
+/// Return whether a request to the endpoint of the exchange only reads data. The endpoint is the
+/// name used by the exchange module for the request, as for `rate_limit::get_cost`.
+/// Unknown private endpoints are considered as changing the account.
+pub fn get_idempotency(exchange: Exchange, scope: Scope, endpoint: &str) -> Idempotency {
+    let idempotent = match (exchange, scope) {
+        (_, Scope::Public) => true,
+        (Exchange::Kraken, Scope::Private) => {
+            matches!(endpoint,
+                     "Balance" | "TradeBalance" | "OpenOrders" | "ClosedOrders" | "QueryOrders" |
+                     "TradesHistory" | "QueryTrades" | "OpenPositions" | "Ledgers" |
+                     "QueryLedgers" | "TradeVolume" | "DepositMethods" | "DepositAddresses" |
+                     "DepositStatus" | "WithdrawInfo" | "WithdrawStatus" | "GetWebSocketsToken")
+        }
+        (Exchange::Bitstamp, Scope::Private) => {
+            matches!(endpoint,
+                     "balance" | "user_transactions" | "open_orders" | "order_status" |
+                     "withdrawal-requests" | "fees/trading") ||
+            endpoint.ends_with("_address")
+        }
+        (Exchange::Gdax, Scope::Private) => {
+            matches!(endpoint,
+                     "accounts" | "account" | "account_history" | "account_holds" |
+                     "open_orders" | "order_status" | "fills")
+        }
+        (Exchange::Bittrex, Scope::Private) => {
+            matches!(endpoint,
+                     "balances" | "balance" | "deposit_address" | "open_orders" | "order" |
+                     "order_history" | "withdrawal_history" | "deposit_history")
+        }
+        (Exchange::Poloniex, Scope::Private) => {
+            endpoint.starts_with("return") || endpoint == "getMarginPosition"
+        }
+    };
+
+    if idempotent {
+        Idempotency::Idempotent
+    } else {
+        Idempotency::NonIdempotent
+    }
+}
+
+/// Return true if the error proves that the exchange rejected the request without executing it.
+fn is_rejection(kind: &ErrorKind) -> bool {
+    matches!(*kind, ErrorKind::InvalidNonce | ErrorKind::RateLimitExceeded)
+}
+
+/// The errors retried by default: the exchange could not be reached, the rate limit was exceeded
+/// or the nonce was rejected (see `ErrorKind::is_retryable`).
+pub fn is_transient(kind: &ErrorKind) -> bool {
+    kind.is_retryable()
+}
+
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    retryable: Arc<dyn Fn(&ErrorKind) -> bool + Send + Sync>,
+}
+
+impl RetryPolicy {
+    /// Create a policy sending a request up to `max_attempts` times (the first attempt included)
+    /// when it fails with a transient error (see `is_transient`). The backoff starts at 500ms and
+    /// doubles at each attempt up to 10s, with jitter.
+    pub fn new(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: cmp::max(max_attempts, 1),
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: true,
+            retryable: Arc::new(is_transient),
+        }
+    }
+
+    /// The policy of the clients by default: the requests are sent once.
+    pub fn never() -> RetryPolicy {
+        RetryPolicy::new(1)
+    }
+
+    /// Set the wait before the second attempt and the maximum wait between two attempts.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> RetryPolicy {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the factor applied to the wait after each attempt.
+    pub fn multiplier(mut self, multiplier: f64) -> RetryPolicy {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// With jitter, each wait is randomly chosen between half and all of the computed backoff,
+    /// so that clients failing together do not retry together.
+    pub fn jitter(mut self, jitter: bool) -> RetryPolicy {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the errors worth a new attempt.
+    pub fn retry_on<F>(mut self, retryable: F) -> RetryPolicy
+        where F: Fn(&ErrorKind) -> bool + Send + Sync + 'static
+    {
+        self.retryable = Arc::new(retryable);
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Return how long to wait after the failed attempt number `attempt` (starting at 1).
+    pub fn get_backoff(&self, attempt: u32) -> Duration {
+        let exponent = cmp::min(attempt.saturating_sub(1), 63) as i32;
+        let backoff = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        let backoff = backoff.min(self.max_backoff.as_secs_f64());
+
+        if self.jitter {
+            Duration::from_secs_f64(backoff * (0.5 + fastrand::f64() / 2.0))
+        } else {
+            Duration::from_secs_f64(backoff)
+        }
+    }
+
+    /// Return true if a request failing with this error at the attempt number `attempt` must be
+    /// sent again.
+    pub fn should_retry(&self, kind: &ErrorKind, idempotency: Idempotency, attempt: u32) -> bool {
+        if attempt >= self.max_attempts || !(self.retryable)(kind) {
+            return false;
+        }
+
+        match idempotency {
+            Idempotency::Idempotent => true,
+            Idempotency::NonIdempotent => is_rejection(kind),
+        }
+    }
+
+    /// Run `request` until it succeeds or fails with an error which must not be retried.
+    pub async fn run<T, F, Fut>(&self, idempotency: Idempotency, request: F) -> Result<T>
+        where F: Fn() -> Fut,
+              Fut: Future<Output = Result<T>>
+    {
+        self.run_checked(idempotency, request, |_| Ok(())).await
+    }
+
+    /// Like `run`, but a response is also sent again when `check` finds an error in it, ie. when
+    /// the exchange answered with an error message. The response is returned unchanged once no
+    /// new attempt is allowed.
+    pub async fn run_checked<T, F, Fut, C>(&self,
+                                           idempotency: Idempotency,
+                                           request: F,
+                                           check: C)
+                                           -> Result<T>
+        where F: Fn() -> Fut,
+              Fut: Future<Output = Result<T>>,
+              C: Fn(&T) -> Result<()>
+    {
+        let mut attempt = 1;
+        loop {
+            let wait = match request().await {
+                Err(err) if self.should_retry(err.kind(), idempotency, attempt) => {
+                    self.get_wait(&err, attempt)
+                }
+                Ok(response) if attempt < self.max_attempts => {
+                    match check(&response) {
+                        Err(ref err) if self.should_retry(err.kind(), idempotency, attempt) => {
+                            self.get_wait(err, attempt)
+                        }
+                        _ => return Ok(response),
+                    }
+                }
+                result => return result,
+            };
+            sleep(wait).await;
+            attempt += 1;
+        }
+    }
+
+    /// The backoff, unless the exchange asked to wait longer with a `Retry-After` header.
+    fn get_wait(&self, err: &Error, attempt: u32) -> Duration {
+        let backoff = self.get_backoff(attempt);
+        match err.retry_after() {
+            Some(retry_after) => cmp::max(backoff, retry_after),
+            None => backoff,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// 3 attempts, see `RetryPolicy::new`.
+    fn default() -> RetryPolicy {
+        RetryPolicy::new(3)
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .finish()
+    }
+}
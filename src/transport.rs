@@ -0,0 +1,295 @@
+//! This module contains the HTTP layer used by the exchange APIs.
+//!
+//! Every `*Api` sends its requests through an `HttpTransport` and every `Async*Api` through an
+//! `AsyncHttpTransport`. By default, `HyperTransport` and `ReqwestTransport` are used, but any
+//! transport can be given to the `new_with_transport` constructors: a proxy, another HTTP stack,
+//! or a mock returning canned responses in the tests.
+
+use hyper_native_tls::NativeTlsClient;
+use hyper::Client;
+use hyper::header::Headers;
+use hyper::net::HttpsConnector;
+
+use chrono::{DateTime, Utc};
+use serde::de::IgnoredAny;
+use tokio::task::spawn_blocking;
+
+use std::fmt;
+use std::future::Future;
+use std::io::Read;
+use std::panic;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpRequest {
+    /// Create a request without headers nor body.
+    pub fn new(method: Method, url: &str) -> HttpRequest {
+        HttpRequest {
+            method,
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> HttpRequest {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn body(mut self, body: &str) -> HttpRequest {
+        self.body = body.to_string();
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Return the value of the first header named `name` (case insensitive).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Return the response if its status is successful, or if its body is the JSON error message
+    /// that the exchange APIs parse. Otherwise, return the error matching the status:
+    /// `RateLimitExceeded` for 429, `BadCredentials` for 401, `ServiceUnavailable` for 5xx
+    /// (ie. an HTML page sent by a CDN), `PermissionDenied` for 403, `BadParse` for a successful
+    /// response which is not JSON and `ExchangeSpecificError` for the other statuses. The
+    /// response is kept as an `HttpError`, the cause of the error.
+    pub fn error_for_status(self) -> Result<HttpResponse> {
+        let is_json = serde_json::from_str::<IgnoredAny>(&self.body).is_ok();
+        let kind = match self.status {
+            401 => ErrorKind::BadCredentials,
+            429 => ErrorKind::RateLimitExceeded,
+            500..=599 => ErrorKind::ServiceUnavailable(format!("HTTP status {}", self.status)),
+            _ if is_json => return Ok(self),
+            200..=299 => ErrorKind::BadParse,
+            403 => ErrorKind::PermissionDenied,
+            _ => ErrorKind::ExchangeSpecificError(format!("HTTP status {}", self.status)),
+        };
+
+        let retry_after = self.header("Retry-After")
+            .and_then(|value| parse_retry_after(value, Utc::now()));
+        let cause = HttpError {
+            status: self.status,
+            retry_after,
+            body: self.body,
+        };
+        Err(Error::with_chain(cause, kind))
+    }
+}
+
+/// Parse the value of a `Retry-After` header, either a number of seconds or an HTTP date, into
+/// the time to wait from `now`. A date in the past means that there is no need to wait.
+pub fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.with_timezone(&Utc) - now;
+    Some(wait.to_std().unwrap_or_else(|_| Duration::from_secs(0)))
+}
+
+/// Send an HTTP request and return the response of the server, whatever its status.
+/// Errors are only returned when no response could be received.
+pub trait HttpTransport: fmt::Debug + Send + Sync {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+pub type ResponseFuture<'a> = Pin<Box<dyn Future<Output = Result<HttpResponse>> + Send + 'a>>;
+
+/// Asynchronous counterpart of `HttpTransport`.
+pub trait AsyncHttpTransport: fmt::Debug + Send + Sync {
+    fn send(&self, request: HttpRequest) -> ResponseFuture<'_>;
+}
+
+/// The default transport of the blocking APIs, built on a hyper Client with native TLS.
+pub struct HyperTransport {
+    client: Client,
+}
+
+impl HyperTransport {
+    pub fn new() -> Result<HyperTransport> {
+        // TODO: implement correctly the TLS error in error_chain.
+        let ssl = match NativeTlsClient::new() {
+            Ok(res) => res,
+            Err(_) => return Err(ErrorKind::TlsError.into()),
+        };
+        let connector = HttpsConnector::new(ssl);
+
+        Ok(HyperTransport { client: Client::with_connector(connector) })
+    }
+}
+
+impl From<Client> for HyperTransport {
+    fn from(client: Client) -> HyperTransport {
+        HyperTransport { client }
+    }
+}
+
+impl fmt::Debug for HyperTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.client.fmt(f)
+    }
+}
+
+impl HttpTransport for HyperTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut headers = Headers::new();
+        for (name, value) in request.headers {
+            headers.set_raw(name, vec![value.into_bytes()]);
+        }
+
+        let mut builder = match request.method {
+            Method::Get => self.client.get(&request.url),
+            Method::Post => self.client.post(&request.url),
+            Method::Delete => self.client.delete(&request.url),
+        };
+        builder = builder.headers(headers);
+        if !request.body.is_empty() {
+            builder = builder.body(request.body.as_str());
+        }
+
+        //TODO: Handle correctly http errors with error_chain.
+        let mut response = match builder.send() {
+            Ok(response) => response,
+            Err(err) => return Err(ErrorKind::ServiceUnavailable(err.to_string()).into()),
+        };
+
+        let mut body = String::new();
+        response.read_to_string(&mut body)?;
+
+        Ok(HttpResponse {
+               status: response.status.to_u16(),
+               headers: response
+                   .headers
+                   .iter()
+                   .map(|header| (header.name().to_string(), header.value_string()))
+                   .collect(),
+               body,
+           })
+    }
+}
+
+/// The default transport of the asynchronous APIs, built on a reqwest Client with native TLS.
+/// It must be used from a Tokio runtime.
+#[derive(Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Result<ReqwestTransport> {
+        match reqwest::Client::builder().build() {
+            Ok(client) => Ok(ReqwestTransport { client }),
+            Err(_) => Err(ErrorKind::TlsError.into()),
+        }
+    }
+}
+
+impl From<reqwest::Client> for ReqwestTransport {
+    fn from(client: reqwest::Client) -> ReqwestTransport {
+        ReqwestTransport { client }
+    }
+}
+
+impl AsyncHttpTransport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> ResponseFuture<'_> {
+        Box::pin(async move {
+            let method = match request.method {
+                Method::Get => reqwest::Method::GET,
+                Method::Post => reqwest::Method::POST,
+                Method::Delete => reqwest::Method::DELETE,
+            };
+
+            let mut builder = self.client.request(method, &request.url);
+            for (name, value) in &request.headers {
+                builder = builder.header(name.as_str(), value.as_str());
+            }
+            if !request.body.is_empty() {
+                builder = builder.body(request.body);
+            }
+
+            let response = match builder.send().await {
+                Ok(response) => response,
+                Err(err) => return Err(ErrorKind::ServiceUnavailable(err.to_string()).into()),
+            };
+
+            let status = response.status().as_u16();
+            let headers = response
+                .headers()
+                .iter()
+                .map(|(name, value)| {
+                         (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned())
+                     })
+                .collect();
+            let body = match response.text().await {
+                Ok(body) => body,
+                Err(err) => return Err(ErrorKind::ServiceUnavailable(err.to_string()).into()),
+            };
+
+            Ok(HttpResponse { status, headers, body })
+        })
+    }
+}
+
+/// Run a blocking `HttpTransport` as an `AsyncHttpTransport`: the request is sent on the blocking
+/// thread pool of the runtime (see `tokio::task::spawn_blocking`). This is how the blocking APIs
+/// use their transport: the thread driving the runtime of a client shared between threads is not
+/// blocked by the I/O, so the requests of the other threads waiting for the rate limiter are not
+/// delayed by a slow response.
+pub struct BlockingTransport(Arc<dyn HttpTransport>);
+
+impl From<Box<dyn HttpTransport>> for BlockingTransport {
+    fn from(transport: Box<dyn HttpTransport>) -> BlockingTransport {
+        BlockingTransport(Arc::from(transport))
+    }
+}
+
+impl fmt::Debug for BlockingTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl AsyncHttpTransport for BlockingTransport {
+    fn send(&self, request: HttpRequest) -> ResponseFuture<'_> {
+        let transport = self.0.clone();
+        Box::pin(async move {
+            match spawn_blocking(move || transport.send(request)).await {
+                Ok(response) => response,
+                Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+                Err(err) => Err(ErrorKind::ServiceUnavailable(err.to_string()).into()),
+            }
+        })
+    }
+}
@@ -1,9 +1,14 @@
 //! Types definition used for handling returned data when generic API is used.
 
-use std::collections::HashMap;
-use bigdecimal::BigDecimal;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Mutex;
+use bigdecimal::{BigDecimal, Zero};
 use std::str::FromStr;
 
+use crate::error::*;
+use crate::normalization;
+
 
 pub type Amount = BigDecimal;
 pub type Price = BigDecimal;
@@ -11,6 +16,8 @@ pub type Volume = BigDecimal;
 
 pub type Balances = HashMap<Currency, Amount>;
 
+pub type Markets = HashMap<Pair, MarketInfo>;
+
 #[derive(Debug)]
 pub struct Ticker {
     /// UNIX timestamp in ms (when the response was received)
@@ -56,16 +63,181 @@ impl Orderbook {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Trade {
+    /// Identifier of the trade, as used by the exchange
+    pub identifier: String,
+    /// UNIX timestamp in ms of the trade
+    pub timestamp: i64,
+    pub price: Price,
+    pub volume: Volume,
+    /// Side of the taker, the order that was matched against the Orderbook
+    pub side: OrderSide,
+}
+
 #[derive(Debug)]
+pub struct TradeHistory {
+    /// UNIX timestamp in ms (when the response was received)
+    pub timestamp: i64,
+    /// The Pair corresponding to the trades returned
+    pub pair: Pair,
+    /// Vec containing the trades (from the oldest to the most recent)
+    pub trades: Vec<Trade>,
+    /// Pass this value as `since` to `ExchangeApi::trades` to only get the trades that happened
+    /// after the ones returned. Its format is specific to the exchange.
+    pub cursor: Option<String>,
+}
+
+/// Trading rules of a market, as published by the exchange. The limits that an exchange does not
+/// publish are None.
+#[derive(Debug, Clone)]
+pub struct MarketInfo {
+    pub pair: Pair,
+    /// Minimum quantity of an order (in quote currency, see `ExchangeApi::add_order`)
+    pub min_order_size: Option<Volume>,
+    /// Smallest price increment allowed
+    pub tick_size: Option<Price>,
+    /// Maximum number of decimals allowed for the quantity
+    pub lot_decimals: Option<u32>,
+    /// Minimum value of an order (quantity * price, in base currency)
+    pub min_notional: Option<Price>,
+    /// false if trading is currently suspended on the market
+    pub active: bool,
+}
+
+impl MarketInfo {
+    /// Check an order against the trading rules of the market before it is sent to the exchange.
+    /// Return the quantity rounded down to the lot precision and the limit price rounded to the
+    /// tick size (down for a buy order, up for a sell order), so that the order never costs more
+    /// than requested. The price of a market order is returned untouched and its value is not
+    /// checked against `min_notional`.
+    pub fn check_order(&self,
+                       order_type: OrderType,
+                       quantity: Volume,
+                       price: Option<Price>)
+                       -> Result<(Volume, Option<Price>)> {
+        if !self.active {
+            return Err(ErrorKind::MarketInactive.into());
+        }
+
+        let quantity = match self.lot_decimals {
+            Some(decimals) => quantity.with_scale(i64::from(decimals)),
+            None => quantity,
+        };
+        if quantity <= BigDecimal::zero() {
+            return Err(ErrorKind::InvalidArguments.into());
+        }
+        if let Some(ref min_order_size) = self.min_order_size {
+            if quantity < *min_order_size {
+                return Err(ErrorKind::InsufficientOrderSize.into());
+            }
+        }
+
+        let price = match order_type {
+            OrderType::BuyLimit | OrderType::SellLimit => {
+                let price = price.ok_or(ErrorKind::MissingPrice)?;
+                let price = match self.tick_size {
+                    Some(ref tick_size) if *tick_size > BigDecimal::zero() => {
+                        let rounded = (&price / tick_size).with_scale(0) * tick_size;
+                        if order_type == OrderType::SellLimit && rounded < price {
+                            rounded + tick_size
+                        } else {
+                            rounded
+                        }
+                    }
+                    _ => price,
+                };
+                if price <= BigDecimal::zero() {
+                    return Err(ErrorKind::InvalidArguments.into());
+                }
+                if let Some(ref min_notional) = self.min_notional {
+                    if &quantity * &price < *min_notional {
+                        return Err(ErrorKind::InsufficientOrderSize.into());
+                    }
+                }
+                Some(price)
+            }
+            OrderType::BuyMarket | OrderType::SellMarket => price,
+        };
+
+        Ok((quantity, price))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Candle {
+    /// UNIX timestamp in ms of the beginning of the period
+    pub timestamp: i64,
+    pub open: Price,
+    pub high: Price,
+    pub low: Price,
+    pub close: Price,
+    /// Volume traded during the period (quote-volume)
+    pub volume: Volume,
+}
+
+/// Duration of the period covered by a Candle. Each exchange only supports some of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interval {
+    OneMinute,
+    FiveMinutes,
+    FifteenMinutes,
+    ThirtyMinutes,
+    OneHour,
+    TwoHours,
+    FourHours,
+    OneDay,
+    OneWeek,
+}
+
+impl Interval {
+    /// Duration of the interval in seconds
+    pub fn seconds(&self) -> i64 {
+        match *self {
+            Interval::OneMinute => 60,
+            Interval::FiveMinutes => 300,
+            Interval::FifteenMinutes => 900,
+            Interval::ThirtyMinutes => 1800,
+            Interval::OneHour => 3600,
+            Interval::TwoHours => 7200,
+            Interval::FourHours => 14400,
+            Interval::OneDay => 86400,
+            Interval::OneWeek => 604800,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct OrderInfo {
     /// UNIX timestamp in ms (when the response was received)
     pub timestamp: i64,
     /// This identifiers list is specific to the platform you use. You must store it somewhere if
     /// you want to modify/cancel the order later
     pub identifier: Vec<String>,
+    /// The Pair the order was placed on
+    pub pair: Pair,
+    /// The OrderType passed to `add_order`
+    pub order_type: OrderType,
+    /// The quantity passed to `add_order`
+    pub quantity: Volume,
+    /// The limit price passed to `add_order`
+    pub price: Option<Price>,
+    /// Trades executed as soon as the order was placed. Empty if there was none or if the
+    /// exchange does not report them when an order is placed (use `order_status` in that case).
+    pub fills: Vec<Fill>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Fill {
+    /// Identifier of the trade, as used by the exchange
+    pub identifier: String,
+    /// UNIX timestamp in ms of the trade
+    pub timestamp: i64,
+    pub price: Price,
+    pub quantity: Volume,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OrderType {
     BuyLimit,
     SellLimit,
@@ -73,320 +245,413 @@ pub enum OrderType {
     SellMarket,
 }
 
-/// Currency lists all currencies that can be traded on supported exchanges.
-/// Update date : 27/10/2017.
-/// Note : 1ST, 2GIVE, 8BIT have been renammed "_1ST", "_2GIVE" and "_8BIT" since variables name
-/// cannot start with a number.
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
-#[allow(non_camel_case_types)]
-pub enum Currency {
-    _1ST,
-    _2GIVE,
-    _8BIT,
-    ABY,
-    ADA,
-    ADC,
-    ADT,
-    ADX,
-    AEON,
-    AGRS,
-    AM,
-    AMP,
-    AMS,
-    ANT,
-    APEX,
-    APX,
-    ARB,
-    ARDR,
-    ARK,
-    AUR,
-    BAT,
-    BAY,
-    BCC,
-    BCH,
-    BCN,
-    BCY,
-    BELA,
-    BITB,
-    BITCNY,
-    BITS,
-    BITZ,
-    BLC,
-    BLITZ,
-    BLK,
-    BLOCK,
-    BNT,
-    BOB,
-    BRK,
-    BRX,
-    BSD,
-    BSTY,
-    BTA,
-    BTC,
-    BTCD,
-    BTM,
-    BTS,
-    BURST,
-    BYC,
-    CAD,
-    CANN,
-    CCN,
-    CFI,
-    CLAM,
-    CLOAK,
-    CLUB,
-    COVAL,
-    CPC,
-    CRB,
-    CRBIT,
-    CRW,
-    CRYPT,
-    CURE,
-    CVC,
-    DAR,
-    DASH,
-    DCR,
-    DCT,
-    DGB,
-    DGC,
-    DGD,
-    DMD,
-    DNT,
-    DOGE,
-    DOPE,
-    DRACO,
-    DTB,
-    DTC,
-    DYN,
-    EBST,
-    EDG,
-    EFL,
-    EGC,
-    EMC,
-    EMC2,
-    ENRG,
-    EOS,
-    ERC,
-    ETC,
-    ETH,
-    EUR,
-    EXCL,
-    EXP,
-    FAIR,
-    FC2,
-    FCT,
-    FLDC,
-    FLO,
-    FRK,
-    FSC2,
-    FTC,
-    FUN,
-    GAM,
-    GAME,
-    GBG,
-    GBP,
-    GBYTE,
-    GCR,
-    GEMZ,
-    GEO,
-    GHC,
-    GLD,
-    GNO,
-    GNT,
-    GOLOS,
-    GP,
-    GRC,
-    GRS,
-    GRT,
-    GUP,
-    HKG,
-    HMQ,
-    HUC,
-    HYPER,
-    HZ,
-    ICN,
-    INCNT,
-    INFX,
-    IOC,
-    ION,
-    IOP,
-    J,
-    JPY,
-    KMD,
-    KORE,
-    KR,
-    LBC,
-    LGD,
-    LMC,
-    LSK,
-    LTC,
-    LUN,
-    LXC,
-    MAID,
-    MANA,
-    MAX,
-    MCO,
-    MEC,
-    MEME,
-    METAL,
-    MLN,
-    MND,
-    MONA,
-    MTL,
-    MTR,
-    MUE,
-    MUSIC,
-    MYST,
-    MZC,
-    NAUT,
-    NAV,
-    NBT,
-    NEO,
-    NEOS,
-    NET,
-    NEU,
-    NLG,
-    NMC,
-    NMR,
-    NOTE,
-    NTRN,
-    NXC,
-    NXS,
-    NXT,
-    OC,
-    OK,
-    OMG,
-    OMNI,
-    ORB,
-    PART,
-    PASC,
-    PAY,
-    PDC,
-    PINK,
-    PIVX,
-    PKB,
-    POT,
-    PPC,
-    PRIME,
-    PTC,
-    PTOY,
-    PXI,
-    QRL,
-    QTUM,
-    QWARK,
-    RADS,
-    RBY,
-    RDD,
-    REP,
-    RIC,
-    RISE,
-    RLC,
-    ROOT,
-    SAFEX,
-    SALT,
-    SBD,
-    SC,
-    SCOT,
-    SCRT,
-    SEQ,
-    SFR,
-    SHIFT,
-    SIB,
-    SJCX,
-    SLG,
-    SLING,
-    SLR,
-    SLS,
-    SNGLS,
-    SNRG,
-    SNT,
-    SOON,
-    SPHR,
-    SPR,
-    SPRTS,
-    SSD,
-    START,
-    STEEM,
-    STEPS,
-    STORJ,
-    STR,
-    STRAT,
-    STV,
-    SWIFT,
-    SWING,
-    SWT,
-    SYNX,
-    SYS,
-    TES,
-    THC,
-    TIME,
-    TIT,
-    TIX,
-    TKN,
-    TKS,
-    TRI,
-    TRIG,
-    TRK,
-    TROLL,
-    TRST,
-    TRUST,
-    TX,
-    U,
-    UBQ,
-    UFO,
-    UNB,
-    UNIQ,
-    UNIT,
-    UNO,
-    USD,
-    USDT,
-    UTC,
-    VIA,
-    VIOR,
-    VIRAL,
-    VOX,
-    VPN,
-    VRC,
-    VRM,
-    VTC,
-    VTR,
-    WARP,
-    WAVES,
-    WINGS,
-    XAUR,
-    XBB,
-    XBC,
-    XC,
-    XCO,
-    XCP,
-    XDG,
-    XDN,
-    XDQ,
-    XEL,
-    XEM,
-    XLM,
-    XMG,
-    XMR,
-    XMY,
-    XPM,
-    XPY,
-    XQN,
-    XRP,
-    XSEED,
-    XST,
-    XTC,
-    XVC,
-    XVG,
-    XWC,
-    XZC,
-    YBC,
-    ZCL,
-    ZEC,
-    ZEN,
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrderStatus {
+    /// The order is in the book and nothing has been executed yet
+    Open,
+    /// The order is in the book and has been partially executed
+    PartiallyFilled,
+    /// The order has been fully executed
+    Filled,
+    /// The order has been canceled (it may have been partially executed before)
+    Canceled,
+    /// The order has reached its expiration time
+    Expired,
+}
+
+#[derive(Debug, Clone)]
+pub struct Order {
+    /// UNIX timestamp in ms (when the response was received)
+    pub timestamp: i64,
+    /// Identifier of the order, as used by the exchange (see `OrderInfo`)
+    pub identifier: String,
+    /// The Pair the order was placed on
+    pub pair: Pair,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    /// Limit price of the order, None for market orders or if the exchange does not report it
+    pub price: Option<Price>,
+    /// Original quantity of the order (in quote currency, see `ExchangeApi::add_order`)
+    pub quantity: Volume,
+    /// Quantity executed so far
+    pub filled_quantity: Volume,
+    /// Average price of the executed part, None if nothing has been executed
+    pub average_price: Option<Price>,
+    pub status: OrderStatus,
+    /// UNIX timestamp in ms when the order was placed (if reported by the exchange)
+    pub opened_at: Option<i64>,
+    /// UNIX timestamp in ms when the order was closed (if closed and reported by the exchange)
+    pub closed_at: Option<i64>,
+}
+
+/// Currency represents any asset that can be traded on an exchange.
+/// The currencies listed on the supported exchanges on 27/10/2017 are available as constants
+/// (ie. `Currency::BTC`), the others can be created at runtime with `Currency::new`.
+/// Note : the constants of 1ST, 2GIVE, 8BIT have been renammed "_1ST", "_2GIVE" and "_8BIT" since
+/// constant names cannot start with a number.
+///
+/// Assets known under several names are represented by a single canonical Currency (see the
+/// `normalization` module), so `Currency::XDG == Currency::DOGE`.
+#[derive(Copy, Clone, Hash, Eq, PartialEq)]
+pub struct Currency(&'static str);
+
+impl Currency {
+    /// Return the Currency with the symbol specified (case-insensitive), ie. "BTC". Aliases such as
+    /// "XBT" are converted to their canonical Currency.
+    pub fn new(symbol: &str) -> Currency {
+        Currency(intern(normalization::get_canonical_symbol(&symbol.to_uppercase())))
+    }
+
+    /// Return the symbol of the Currency, ie. "BTC".
+    pub fn symbol(&self) -> &'static str {
+        self.0
+    }
+}
+
+#[allow(non_upper_case_globals)]
+impl Currency {
+    pub const _1ST: Currency = Currency("1ST");
+    pub const _2GIVE: Currency = Currency("2GIVE");
+    pub const _8BIT: Currency = Currency("8BIT");
+    pub const ABY: Currency = Currency("ABY");
+    pub const ADA: Currency = Currency("ADA");
+    pub const ADC: Currency = Currency("ADC");
+    pub const ADT: Currency = Currency("ADT");
+    pub const ADX: Currency = Currency("ADX");
+    pub const AEON: Currency = Currency("AEON");
+    pub const AGRS: Currency = Currency("AGRS");
+    pub const AM: Currency = Currency("AM");
+    pub const AMP: Currency = Currency("AMP");
+    pub const AMS: Currency = Currency("AMS");
+    pub const ANT: Currency = Currency("ANT");
+    pub const APEX: Currency = Currency("APEX");
+    pub const APX: Currency = Currency("APX");
+    pub const ARB: Currency = Currency("ARB");
+    pub const ARDR: Currency = Currency("ARDR");
+    pub const ARK: Currency = Currency("ARK");
+    pub const AUR: Currency = Currency("AUR");
+    pub const BAT: Currency = Currency("BAT");
+    pub const BAY: Currency = Currency("BAY");
+    /// Alias of BCH, the name used by Bittrex
+    pub const BCC: Currency = Currency::BCH;
+    pub const BCH: Currency = Currency("BCH");
+    pub const BCN: Currency = Currency("BCN");
+    pub const BCY: Currency = Currency("BCY");
+    pub const BELA: Currency = Currency("BELA");
+    pub const BITB: Currency = Currency("BITB");
+    pub const BITCNY: Currency = Currency("BITCNY");
+    pub const BITS: Currency = Currency("BITS");
+    pub const BITZ: Currency = Currency("BITZ");
+    pub const BLC: Currency = Currency("BLC");
+    pub const BLITZ: Currency = Currency("BLITZ");
+    pub const BLK: Currency = Currency("BLK");
+    pub const BLOCK: Currency = Currency("BLOCK");
+    pub const BNT: Currency = Currency("BNT");
+    pub const BOB: Currency = Currency("BOB");
+    pub const BRK: Currency = Currency("BRK");
+    pub const BRX: Currency = Currency("BRX");
+    pub const BSD: Currency = Currency("BSD");
+    pub const BSTY: Currency = Currency("BSTY");
+    pub const BTA: Currency = Currency("BTA");
+    pub const BTC: Currency = Currency("BTC");
+    pub const BTCD: Currency = Currency("BTCD");
+    pub const BTM: Currency = Currency("BTM");
+    pub const BTS: Currency = Currency("BTS");
+    pub const BURST: Currency = Currency("BURST");
+    pub const BYC: Currency = Currency("BYC");
+    pub const CAD: Currency = Currency("CAD");
+    pub const CANN: Currency = Currency("CANN");
+    pub const CCN: Currency = Currency("CCN");
+    pub const CFI: Currency = Currency("CFI");
+    pub const CLAM: Currency = Currency("CLAM");
+    pub const CLOAK: Currency = Currency("CLOAK");
+    pub const CLUB: Currency = Currency("CLUB");
+    pub const COVAL: Currency = Currency("COVAL");
+    pub const CPC: Currency = Currency("CPC");
+    pub const CRB: Currency = Currency("CRB");
+    pub const CRBIT: Currency = Currency("CRBIT");
+    pub const CRW: Currency = Currency("CRW");
+    pub const CRYPT: Currency = Currency("CRYPT");
+    pub const CURE: Currency = Currency("CURE");
+    pub const CVC: Currency = Currency("CVC");
+    pub const DAR: Currency = Currency("DAR");
+    pub const DASH: Currency = Currency("DASH");
+    pub const DCR: Currency = Currency("DCR");
+    pub const DCT: Currency = Currency("DCT");
+    pub const DGB: Currency = Currency("DGB");
+    pub const DGC: Currency = Currency("DGC");
+    pub const DGD: Currency = Currency("DGD");
+    pub const DMD: Currency = Currency("DMD");
+    pub const DNT: Currency = Currency("DNT");
+    pub const DOGE: Currency = Currency("DOGE");
+    pub const DOPE: Currency = Currency("DOPE");
+    pub const DRACO: Currency = Currency("DRACO");
+    pub const DTB: Currency = Currency("DTB");
+    pub const DTC: Currency = Currency("DTC");
+    pub const DYN: Currency = Currency("DYN");
+    pub const EBST: Currency = Currency("EBST");
+    pub const EDG: Currency = Currency("EDG");
+    pub const EFL: Currency = Currency("EFL");
+    pub const EGC: Currency = Currency("EGC");
+    pub const EMC: Currency = Currency("EMC");
+    pub const EMC2: Currency = Currency("EMC2");
+    pub const ENRG: Currency = Currency("ENRG");
+    pub const EOS: Currency = Currency("EOS");
+    pub const ERC: Currency = Currency("ERC");
+    pub const ETC: Currency = Currency("ETC");
+    pub const ETH: Currency = Currency("ETH");
+    pub const EUR: Currency = Currency("EUR");
+    pub const EXCL: Currency = Currency("EXCL");
+    pub const EXP: Currency = Currency("EXP");
+    pub const FAIR: Currency = Currency("FAIR");
+    pub const FC2: Currency = Currency("FC2");
+    pub const FCT: Currency = Currency("FCT");
+    pub const FLDC: Currency = Currency("FLDC");
+    pub const FLO: Currency = Currency("FLO");
+    pub const FRK: Currency = Currency("FRK");
+    pub const FSC2: Currency = Currency("FSC2");
+    pub const FTC: Currency = Currency("FTC");
+    pub const FUN: Currency = Currency("FUN");
+    pub const GAM: Currency = Currency("GAM");
+    pub const GAME: Currency = Currency("GAME");
+    pub const GAS: Currency = Currency("GAS");
+    pub const GBG: Currency = Currency("GBG");
+    pub const GBP: Currency = Currency("GBP");
+    pub const GBYTE: Currency = Currency("GBYTE");
+    pub const GCR: Currency = Currency("GCR");
+    pub const GEMZ: Currency = Currency("GEMZ");
+    pub const GEO: Currency = Currency("GEO");
+    pub const GHC: Currency = Currency("GHC");
+    pub const GLD: Currency = Currency("GLD");
+    pub const GNO: Currency = Currency("GNO");
+    pub const GNT: Currency = Currency("GNT");
+    pub const GOLOS: Currency = Currency("GOLOS");
+    pub const GP: Currency = Currency("GP");
+    pub const GRC: Currency = Currency("GRC");
+    pub const GRS: Currency = Currency("GRS");
+    pub const GRT: Currency = Currency("GRT");
+    pub const GUP: Currency = Currency("GUP");
+    pub const HKG: Currency = Currency("HKG");
+    pub const HMQ: Currency = Currency("HMQ");
+    pub const HUC: Currency = Currency("HUC");
+    pub const HYPER: Currency = Currency("HYPER");
+    pub const HZ: Currency = Currency("HZ");
+    pub const ICN: Currency = Currency("ICN");
+    pub const INCNT: Currency = Currency("INCNT");
+    pub const INFX: Currency = Currency("INFX");
+    pub const IOC: Currency = Currency("IOC");
+    pub const ION: Currency = Currency("ION");
+    pub const IOP: Currency = Currency("IOP");
+    pub const J: Currency = Currency("J");
+    pub const JPY: Currency = Currency("JPY");
+    pub const KMD: Currency = Currency("KMD");
+    pub const KORE: Currency = Currency("KORE");
+    pub const KR: Currency = Currency("KR");
+    pub const LBC: Currency = Currency("LBC");
+    pub const LGD: Currency = Currency("LGD");
+    pub const LMC: Currency = Currency("LMC");
+    pub const LSK: Currency = Currency("LSK");
+    pub const LTC: Currency = Currency("LTC");
+    pub const LUN: Currency = Currency("LUN");
+    pub const LXC: Currency = Currency("LXC");
+    pub const MAID: Currency = Currency("MAID");
+    pub const MANA: Currency = Currency("MANA");
+    pub const MAX: Currency = Currency("MAX");
+    pub const MCO: Currency = Currency("MCO");
+    pub const MEC: Currency = Currency("MEC");
+    pub const MEME: Currency = Currency("MEME");
+    pub const METAL: Currency = Currency("METAL");
+    pub const MLN: Currency = Currency("MLN");
+    pub const MND: Currency = Currency("MND");
+    pub const MONA: Currency = Currency("MONA");
+    pub const MTL: Currency = Currency("MTL");
+    pub const MTR: Currency = Currency("MTR");
+    pub const MUE: Currency = Currency("MUE");
+    pub const MUSIC: Currency = Currency("MUSIC");
+    pub const MYST: Currency = Currency("MYST");
+    pub const MZC: Currency = Currency("MZC");
+    pub const NAUT: Currency = Currency("NAUT");
+    pub const NAV: Currency = Currency("NAV");
+    pub const NBT: Currency = Currency("NBT");
+    pub const NEO: Currency = Currency("NEO");
+    pub const NEOS: Currency = Currency("NEOS");
+    pub const NET: Currency = Currency("NET");
+    pub const NEU: Currency = Currency("NEU");
+    pub const NLG: Currency = Currency("NLG");
+    pub const NMC: Currency = Currency("NMC");
+    pub const NMR: Currency = Currency("NMR");
+    pub const NOTE: Currency = Currency("NOTE");
+    pub const NTRN: Currency = Currency("NTRN");
+    pub const NXC: Currency = Currency("NXC");
+    pub const NXS: Currency = Currency("NXS");
+    pub const NXT: Currency = Currency("NXT");
+    pub const OC: Currency = Currency("OC");
+    pub const OK: Currency = Currency("OK");
+    pub const OMG: Currency = Currency("OMG");
+    pub const OMNI: Currency = Currency("OMNI");
+    pub const ORB: Currency = Currency("ORB");
+    pub const PART: Currency = Currency("PART");
+    pub const PASC: Currency = Currency("PASC");
+    pub const PAY: Currency = Currency("PAY");
+    pub const PDC: Currency = Currency("PDC");
+    pub const PINK: Currency = Currency("PINK");
+    pub const PIVX: Currency = Currency("PIVX");
+    pub const PKB: Currency = Currency("PKB");
+    pub const POT: Currency = Currency("POT");
+    pub const PPC: Currency = Currency("PPC");
+    pub const PRIME: Currency = Currency("PRIME");
+    pub const PTC: Currency = Currency("PTC");
+    pub const PTOY: Currency = Currency("PTOY");
+    pub const PXI: Currency = Currency("PXI");
+    pub const QRL: Currency = Currency("QRL");
+    pub const QTUM: Currency = Currency("QTUM");
+    pub const QWARK: Currency = Currency("QWARK");
+    pub const RADS: Currency = Currency("RADS");
+    pub const RBY: Currency = Currency("RBY");
+    pub const RDD: Currency = Currency("RDD");
+    pub const REP: Currency = Currency("REP");
+    pub const RIC: Currency = Currency("RIC");
+    pub const RISE: Currency = Currency("RISE");
+    pub const RLC: Currency = Currency("RLC");
+    pub const ROOT: Currency = Currency("ROOT");
+    pub const SAFEX: Currency = Currency("SAFEX");
+    pub const SALT: Currency = Currency("SALT");
+    pub const SBD: Currency = Currency("SBD");
+    pub const SC: Currency = Currency("SC");
+    pub const SCOT: Currency = Currency("SCOT");
+    pub const SCRT: Currency = Currency("SCRT");
+    pub const SEQ: Currency = Currency("SEQ");
+    pub const SFR: Currency = Currency("SFR");
+    pub const SHIFT: Currency = Currency("SHIFT");
+    pub const SIB: Currency = Currency("SIB");
+    pub const SJCX: Currency = Currency("SJCX");
+    pub const SLG: Currency = Currency("SLG");
+    pub const SLING: Currency = Currency("SLING");
+    pub const SLR: Currency = Currency("SLR");
+    pub const SLS: Currency = Currency("SLS");
+    pub const SNGLS: Currency = Currency("SNGLS");
+    pub const SNRG: Currency = Currency("SNRG");
+    pub const SNT: Currency = Currency("SNT");
+    pub const SOON: Currency = Currency("SOON");
+    pub const SPHR: Currency = Currency("SPHR");
+    pub const SPR: Currency = Currency("SPR");
+    pub const SPRTS: Currency = Currency("SPRTS");
+    pub const SSD: Currency = Currency("SSD");
+    pub const START: Currency = Currency("START");
+    pub const STEEM: Currency = Currency("STEEM");
+    pub const STEPS: Currency = Currency("STEPS");
+    pub const STORJ: Currency = Currency("STORJ");
+    #[deprecated(note = "Poloniex calls XLM \"STR\", use Currency::XLM")]
+    pub const STR: Currency = Currency("STR");
+    pub const STRAT: Currency = Currency("STRAT");
+    pub const STV: Currency = Currency("STV");
+    pub const SWIFT: Currency = Currency("SWIFT");
+    pub const SWING: Currency = Currency("SWING");
+    pub const SWT: Currency = Currency("SWT");
+    pub const SYNX: Currency = Currency("SYNX");
+    pub const SYS: Currency = Currency("SYS");
+    pub const TES: Currency = Currency("TES");
+    pub const THC: Currency = Currency("THC");
+    pub const TIME: Currency = Currency("TIME");
+    pub const TIT: Currency = Currency("TIT");
+    pub const TIX: Currency = Currency("TIX");
+    pub const TKN: Currency = Currency("TKN");
+    pub const TKS: Currency = Currency("TKS");
+    pub const TRI: Currency = Currency("TRI");
+    pub const TRIG: Currency = Currency("TRIG");
+    pub const TRK: Currency = Currency("TRK");
+    pub const TROLL: Currency = Currency("TROLL");
+    pub const TRST: Currency = Currency("TRST");
+    pub const TRUST: Currency = Currency("TRUST");
+    pub const TX: Currency = Currency("TX");
+    pub const U: Currency = Currency("U");
+    pub const UBQ: Currency = Currency("UBQ");
+    pub const UFO: Currency = Currency("UFO");
+    pub const UNB: Currency = Currency("UNB");
+    pub const UNIQ: Currency = Currency("UNIQ");
+    pub const UNIT: Currency = Currency("UNIT");
+    pub const UNO: Currency = Currency("UNO");
+    pub const USD: Currency = Currency("USD");
+    pub const USDT: Currency = Currency("USDT");
+    pub const UTC: Currency = Currency("UTC");
+    pub const VIA: Currency = Currency("VIA");
+    pub const VIOR: Currency = Currency("VIOR");
+    pub const VIRAL: Currency = Currency("VIRAL");
+    pub const VOX: Currency = Currency("VOX");
+    pub const VPN: Currency = Currency("VPN");
+    pub const VRC: Currency = Currency("VRC");
+    pub const VRM: Currency = Currency("VRM");
+    pub const VTC: Currency = Currency("VTC");
+    pub const VTR: Currency = Currency("VTR");
+    pub const WARP: Currency = Currency("WARP");
+    pub const WAVES: Currency = Currency("WAVES");
+    pub const WINGS: Currency = Currency("WINGS");
+    pub const XAUR: Currency = Currency("XAUR");
+    pub const XBB: Currency = Currency("XBB");
+    pub const XBC: Currency = Currency("XBC");
+    pub const XC: Currency = Currency("XC");
+    pub const XCO: Currency = Currency("XCO");
+    pub const XCP: Currency = Currency("XCP");
+    /// Alias of DOGE, the name used by Kraken
+    pub const XDG: Currency = Currency::DOGE;
+    pub const XDN: Currency = Currency("XDN");
+    pub const XDQ: Currency = Currency("XDQ");
+    pub const XEL: Currency = Currency("XEL");
+    pub const XEM: Currency = Currency("XEM");
+    pub const XLM: Currency = Currency("XLM");
+    pub const XMG: Currency = Currency("XMG");
+    pub const XMR: Currency = Currency("XMR");
+    pub const XMY: Currency = Currency("XMY");
+    pub const XPM: Currency = Currency("XPM");
+    pub const XPY: Currency = Currency("XPY");
+    pub const XQN: Currency = Currency("XQN");
+    pub const XRP: Currency = Currency("XRP");
+    pub const XSEED: Currency = Currency("XSEED");
+    pub const XST: Currency = Currency("XST");
+    pub const XTC: Currency = Currency("XTC");
+    pub const XVC: Currency = Currency("XVC");
+    pub const XVG: Currency = Currency("XVG");
+    pub const XWC: Currency = Currency("XWC");
+    pub const XZC: Currency = Currency("XZC");
+    pub const YBC: Currency = Currency("YBC");
+    pub const ZCL: Currency = Currency("ZCL");
+    pub const ZEC: Currency = Currency("ZEC");
+    pub const ZEN: Currency = Currency("ZEN");
+    pub const ZRX: Currency = Currency("ZRX");
+}
+
+impl fmt::Debug for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Currency {
+    type Err = Error;
+
+    fn from_str(symbol: &str) -> ::std::result::Result<Self, Self::Err> {
+        if symbol.is_empty() {
+            return Err(ErrorKind::InvalidArguments.into());
+        }
+        Ok(Currency::new(symbol))
+    }
 }
 
-/// Pair lists all pairs that can be traded on supported exchanges.
-/// Update date : 27/10/2017.
+/// Pair represents any market, trading a quote currency (left member) against a base currency
+/// (right member). The pairs listed on the supported exchanges on 27/10/2017 are available as
+/// constants (ie. `Pair::BTC_EUR`), the others can be created at runtime with `Pair::new`.
 ///
 /// Order of quote currency <-> base currency is important. For example, Kraken supports ZEC_BTC
 /// but Poloniex is doing the opposite inside their API: BTC_ZEC, which equal to 1/ZEC_BTC.
@@ -401,349 +666,449 @@ pub enum Currency {
 /// value accross the 2 exchanges.
 /// Pairs with "_d" at the end : dark pool
 /// 
-/// Note 2 : 1ST and 2GIVE have been renammed "_1ST" and "_2GIVE" since variables name cannot start
-/// with a number.
-#[derive(Debug, Copy, Clone, PartialEq)]
-#[allow(non_camel_case_types)]
-pub enum Pair {
-    _1ST_BTC,
-    _1ST_ETH,
-    _2GIVE_BTC,
-    ABY_BTC,
-    ADA_BTC,
-    ADT_BTC,
-    ADT_ETH,
-    ADX_BTC,
-    ADX_ETH,
-    AEON_BTC,
-    AGRS_BTC,
-    AMP_BTC,
-    ANT_BTC,
-    ANT_ETH,
-    APX_BTC,
-    ARDR_BTC,
-    ARK_BTC,
-    AUR_BTC,
-    BAT_BTC,
-    BAT_ETH,
-    BAY_BTC,
-    BCC_BTC,
-    BCC_ETH,
-    BCC_USDT,
-    BCH_BTC,
-    BCH_ETH,
-    BCH_EUR,
-    BCH_USD,
-    BCH_USDT,
-    BCN_BTC,
-    BCN_XMR,
-    BCY_BTC,
-    BELA_BTC,
-    BITB_BTC,
-    BLITZ_BTC,
-    BLK_BTC,
-    BLK_XMR,
-    BLOCK_BTC,
-    BNT_BTC,
-    BNT_ETH,
-    BRK_BTC,
-    BRX_BTC,
-    BSD_BTC,
-    BTCD_BTC,
-    BTCD_XMR,
-    BTC_CAD,
-    BTC_CAD_d,
-    BTC_EUR,
-    BTC_EUR_d,
-    BTC_GBP,
-    BTC_GBP_d,
-    BTC_JPY,
-    BTC_JPY_d,
-    BTC_USD,
-    BTC_USDT,
-    BTC_USD_d,
-    BTM_BTC,
-    BTS_BTC,
-    BTS_ETH,
-    BURST_BTC,
-    BYC_BTC,
-    CANN_BTC,
-    CFI_BTC,
-    CFI_ETH,
-    CLAM_BTC,
-    CLOAK_BTC,
-    CLUB_BTC,
-    COVAL_BTC,
-    CPC_BTC,
-    CRB_BTC,
-    CRB_ETH,
-    CRW_BTC,
-    CURE_BTC,
-    CVC_BTC,
-    CVC_ETH,
-    DASH_BTC,
-    DASH_ETH,
-    DASH_EUR,
-    DASH_USD,
-    DASH_USDT,
-    DASH_XMR,
-    DCR_BTC,
-    DCT_BTC,
-    DGB_BTC,
-    DGB_ETH,
-    DGD_BTC,
-    DGD_ETH,
-    DMD_BTC,
-    DNT_BTC,
-    DNT_ETH,
-    DOGE_BTC,
-    DOPE_BTC,
-    DTB_BTC,
-    DYN_BTC,
-    EBST_BTC,
-    EDG_BTC,
-    EFL_BTC,
-    EGC_BTC,
-    EMC2_BTC,
-    EMC_BTC,
-    ENRG_BTC,
-    EOS_BTC,
-    EOS_ETH,
-    ERC_BTC,
-    ETC_BTC,
-    ETC_ETH,
-    ETC_EUR,
-    ETC_USD,
-    ETC_USDT,
-    ETH_BTC,
-    ETH_BTC_d,
-    ETH_CAD,
-    ETH_CAD_d,
-    ETH_EUR,
-    ETH_EUR_d,
-    ETH_GBP,
-    ETH_GBP_d,
-    ETH_JPY,
-    ETH_JPY_d,
-    ETH_USD,
-    ETH_USDT,
-    ETH_USD_d,
-    EUR_USD,
-    EXCL_BTC,
-    EXP_BTC,
-    FAIR_BTC,
-    FCT_BTC,
-    FCT_ETH,
-    FLDC_BTC,
-    FLO_BTC,
-    FTC_BTC,
-    FUN_BTC,
-    FUN_ETH,
-    GAME_BTC,
-    GAM_BTC,
-    GAS_BTC,
-    GAS_ETH,
-    GBG_BTC,
-    GBYTE_BTC,
-    GCR_BTC,
-    GEO_BTC,
-    GLD_BTC,
-    GNO_BTC,
-    GNO_ETH,
-    GNT_BTC,
-    GNT_ETH,
-    GOLOS_BTC,
-    GRC_BTC,
-    GRS_BTC,
-    GUP_BTC,
-    GUP_ETH,
-    HMQ_BTC,
-    HMQ_ETH,
-    HUC_BTC,
-    ICN_BTC,
-    ICN_ETH,
-    INCNT_BTC,
-    INFX_BTC,
-    IOC_BTC,
-    ION_BTC,
-    IOP_BTC,
-    KMD_BTC,
-    KORE_BTC,
-    LBC_BTC,
-    LGD_BTC,
-    LGD_ETH,
-    LMC_BTC,
-    LSK_BTC,
-    LSK_ETH,
-    LTC_BTC,
-    LTC_ETH,
-    LTC_EUR,
-    LTC_USD,
-    LTC_USDT,
-    LTC_XMR,
-    LUN_BTC,
-    LUN_ETH,
-    MAID_BTC,
-    MAID_XMR,
-    MANA_BTC,
-    MANA_ETH,
-    MCO_BTC,
-    MCO_ETH,
-    MEME_BTC,
-    MLN_BTC,
-    MLN_ETH,
-    MONA_BTC,
-    MTL_BTC,
-    MTL_ETH,
-    MUE_BTC,
-    MUSIC_BTC,
-    MYST_BTC,
-    MYST_ETH,
-    NAUT_BTC,
-    NAV_BTC,
-    NBT_BTC,
-    NEOS_BTC,
-    NEO_BTC,
-    NEO_ETH,
-    NEO_USDT,
-    NLG_BTC,
-    NMC_BTC,
-    NMR_BTC,
-    NMR_ETH,
-    NOTE_BTC,
-    NXC_BTC,
-    NXS_BTC,
-    NXT_BTC,
-    NXT_USDT,
-    NXT_XMR,
-    OK_BTC,
-    OMG_BTC,
-    OMG_ETH,
-    OMG_USDT,
-    OMNI_BTC,
-    PART_BTC,
-    PASC_BTC,
-    PAY_BTC,
-    PAY_ETH,
-    PDC_BTC,
-    PINK_BTC,
-    PIVX_BTC,
-    PKB_BTC,
-    POT_BTC,
-    PPC_BTC,
-    PTC_BTC,
-    PTOY_BTC,
-    PTOY_ETH,
-    QRL_BTC,
-    QRL_ETH,
-    QTUM_BTC,
-    QTUM_ETH,
-    QWARK_BTC,
-    RADS_BTC,
-    RBY_BTC,
-    RDD_BTC,
-    REP_BTC,
-    REP_ETH,
-    REP_EUR,
-    REP_USDT,
-    RIC_BTC,
-    RISE_BTC,
-    RLC_BTC,
-    RLC_ETH,
-    SAFEX_BTC,
-    SALT_BTC,
-    SALT_ETH,
-    SBD_BTC,
-    SC_BTC,
-    SC_ETH,
-    SEQ_BTC,
-    SHIFT_BTC,
-    SIB_BTC,
-    SJCX_BTC,
-    SLR_BTC,
-    SLS_BTC,
-    SNGLS_BTC,
-    SNGLS_ETH,
-    SNRG_BTC,
-    SNT_BTC,
-    SNT_ETH,
-    SPHR_BTC,
-    SPR_BTC,
-    START_BTC,
-    STEEM_BTC,
-    STEEM_ETH,
-    STORJ_BTC,
-    STORJ_ETH,
-    STRAT_BTC,
-    STRAT_ETH,
-    STR_BTC,
-    STR_USDT,
-    SWIFT_BTC,
-    SWT_BTC,
-    SYNX_BTC,
-    SYS_BTC,
-    THC_BTC,
-    TIME_BTC,
-    TIME_ETH,
-    TIX_BTC,
-    TIX_ETH,
-    TKN_BTC,
-    TKN_ETH,
-    TKS_BTC,
-    TRIG_BTC,
-    TRST_BTC,
-    TRST_ETH,
-    TRUST_BTC,
-    TX_BTC,
-    UBQ_BTC,
-    UNB_BTC,
-    USDT_USD,
-    VIA_BTC,
-    VOX_BTC,
-    VRC_BTC,
-    VRM_BTC,
-    VTC_BTC,
-    VTR_BTC,
-    WAVES_BTC,
-    WAVES_ETH,
-    WINGS_BTC,
-    WINGS_ETH,
-    XAUR_BTC,
-    XBC_BTC,
-    XCP_BTC,
-    XDG_BTC,
-    XDN_BTC,
-    XEL_BTC,
-    XEM_BTC,
-    XEM_ETH,
-    XLM_BTC,
-    XLM_ETH,
-    XMG_BTC,
-    XMR_BTC,
-    XMR_ETH,
-    XMR_EUR,
-    XMR_USD,
-    XMR_USDT,
-    XMY_BTC,
-    XPM_BTC,
-    XRP_BTC,
-    XRP_ETH,
-    XRP_EUR,
-    XRP_USD,
-    XRP_USDT,
-    XST_BTC,
-    XVC_BTC,
-    XVG_BTC,
-    XWC_BTC,
-    XZC_BTC,
-    ZCL_BTC,
-    ZEC_BTC,
-    ZEC_ETH,
-    ZEC_EUR,
-    ZEC_USD,
-    ZEC_USDT,
-    ZEC_XMR,
-    ZEN_BTC,
-    ZRX_BTC,
-    ZRX_ETH,
+/// Note 2 : the constants of 1ST and 2GIVE have been renammed "_1ST" and "_2GIVE" since constant
+/// names cannot start with a number.
+#[derive(Copy, Clone, Hash, Eq, PartialEq)]
+pub struct Pair {
+    quote: Currency,
+    base: Currency,
+    dark_pool: bool,
+}
+
+impl Pair {
+    /// Return the Pair trading `quote` (left member) against `base` (right member).
+    pub const fn new(quote: Currency, base: Currency) -> Pair {
+        Pair {
+            quote,
+            base,
+            dark_pool: false,
+        }
+    }
+
+    /// Return the dark pool of the Pair (Kraken only).
+    pub const fn dark_pool(self) -> Pair {
+        Pair {
+            dark_pool: true,
+            ..self
+        }
+    }
+
+    /// Return the quote currency (left member) of the Pair.
+    pub fn quote(&self) -> Currency {
+        self.quote
+    }
+
+    /// Return the base currency (right member) of the Pair.
+    pub fn base(&self) -> Currency {
+        self.base
+    }
+
+    pub fn is_dark_pool(&self) -> bool {
+        self.dark_pool
+    }
+}
+
+#[allow(non_upper_case_globals)]
+impl Pair {
+    pub const _1ST_BTC: Pair = Pair::new(Currency::_1ST, Currency::BTC);
+    pub const _1ST_ETH: Pair = Pair::new(Currency::_1ST, Currency::ETH);
+    pub const _2GIVE_BTC: Pair = Pair::new(Currency::_2GIVE, Currency::BTC);
+    pub const ABY_BTC: Pair = Pair::new(Currency::ABY, Currency::BTC);
+    pub const ADA_BTC: Pair = Pair::new(Currency::ADA, Currency::BTC);
+    pub const ADT_BTC: Pair = Pair::new(Currency::ADT, Currency::BTC);
+    pub const ADT_ETH: Pair = Pair::new(Currency::ADT, Currency::ETH);
+    pub const ADX_BTC: Pair = Pair::new(Currency::ADX, Currency::BTC);
+    pub const ADX_ETH: Pair = Pair::new(Currency::ADX, Currency::ETH);
+    pub const AEON_BTC: Pair = Pair::new(Currency::AEON, Currency::BTC);
+    pub const AGRS_BTC: Pair = Pair::new(Currency::AGRS, Currency::BTC);
+    pub const AMP_BTC: Pair = Pair::new(Currency::AMP, Currency::BTC);
+    pub const ANT_BTC: Pair = Pair::new(Currency::ANT, Currency::BTC);
+    pub const ANT_ETH: Pair = Pair::new(Currency::ANT, Currency::ETH);
+    pub const APX_BTC: Pair = Pair::new(Currency::APX, Currency::BTC);
+    pub const ARDR_BTC: Pair = Pair::new(Currency::ARDR, Currency::BTC);
+    pub const ARK_BTC: Pair = Pair::new(Currency::ARK, Currency::BTC);
+    pub const AUR_BTC: Pair = Pair::new(Currency::AUR, Currency::BTC);
+    pub const BAT_BTC: Pair = Pair::new(Currency::BAT, Currency::BTC);
+    pub const BAT_ETH: Pair = Pair::new(Currency::BAT, Currency::ETH);
+    pub const BAY_BTC: Pair = Pair::new(Currency::BAY, Currency::BTC);
+    pub const BCC_BTC: Pair = Pair::new(Currency::BCC, Currency::BTC);
+    pub const BCC_ETH: Pair = Pair::new(Currency::BCC, Currency::ETH);
+    pub const BCC_USDT: Pair = Pair::new(Currency::BCC, Currency::USDT);
+    pub const BCH_BTC: Pair = Pair::new(Currency::BCH, Currency::BTC);
+    pub const BCH_ETH: Pair = Pair::new(Currency::BCH, Currency::ETH);
+    pub const BCH_EUR: Pair = Pair::new(Currency::BCH, Currency::EUR);
+    pub const BCH_USD: Pair = Pair::new(Currency::BCH, Currency::USD);
+    pub const BCH_USDT: Pair = Pair::new(Currency::BCH, Currency::USDT);
+    pub const BCN_BTC: Pair = Pair::new(Currency::BCN, Currency::BTC);
+    pub const BCN_XMR: Pair = Pair::new(Currency::BCN, Currency::XMR);
+    pub const BCY_BTC: Pair = Pair::new(Currency::BCY, Currency::BTC);
+    pub const BELA_BTC: Pair = Pair::new(Currency::BELA, Currency::BTC);
+    pub const BITB_BTC: Pair = Pair::new(Currency::BITB, Currency::BTC);
+    pub const BLITZ_BTC: Pair = Pair::new(Currency::BLITZ, Currency::BTC);
+    pub const BLK_BTC: Pair = Pair::new(Currency::BLK, Currency::BTC);
+    pub const BLK_XMR: Pair = Pair::new(Currency::BLK, Currency::XMR);
+    pub const BLOCK_BTC: Pair = Pair::new(Currency::BLOCK, Currency::BTC);
+    pub const BNT_BTC: Pair = Pair::new(Currency::BNT, Currency::BTC);
+    pub const BNT_ETH: Pair = Pair::new(Currency::BNT, Currency::ETH);
+    pub const BRK_BTC: Pair = Pair::new(Currency::BRK, Currency::BTC);
+    pub const BRX_BTC: Pair = Pair::new(Currency::BRX, Currency::BTC);
+    pub const BSD_BTC: Pair = Pair::new(Currency::BSD, Currency::BTC);
+    pub const BTCD_BTC: Pair = Pair::new(Currency::BTCD, Currency::BTC);
+    pub const BTCD_XMR: Pair = Pair::new(Currency::BTCD, Currency::XMR);
+    pub const BTC_CAD: Pair = Pair::new(Currency::BTC, Currency::CAD);
+    pub const BTC_CAD_d: Pair = Pair::new(Currency::BTC, Currency::CAD).dark_pool();
+    pub const BTC_EUR: Pair = Pair::new(Currency::BTC, Currency::EUR);
+    pub const BTC_EUR_d: Pair = Pair::new(Currency::BTC, Currency::EUR).dark_pool();
+    pub const BTC_GBP: Pair = Pair::new(Currency::BTC, Currency::GBP);
+    pub const BTC_GBP_d: Pair = Pair::new(Currency::BTC, Currency::GBP).dark_pool();
+    pub const BTC_JPY: Pair = Pair::new(Currency::BTC, Currency::JPY);
+    pub const BTC_JPY_d: Pair = Pair::new(Currency::BTC, Currency::JPY).dark_pool();
+    pub const BTC_USD: Pair = Pair::new(Currency::BTC, Currency::USD);
+    pub const BTC_USDT: Pair = Pair::new(Currency::BTC, Currency::USDT);
+    pub const BTC_USD_d: Pair = Pair::new(Currency::BTC, Currency::USD).dark_pool();
+    pub const BTM_BTC: Pair = Pair::new(Currency::BTM, Currency::BTC);
+    pub const BTS_BTC: Pair = Pair::new(Currency::BTS, Currency::BTC);
+    pub const BTS_ETH: Pair = Pair::new(Currency::BTS, Currency::ETH);
+    pub const BURST_BTC: Pair = Pair::new(Currency::BURST, Currency::BTC);
+    pub const BYC_BTC: Pair = Pair::new(Currency::BYC, Currency::BTC);
+    pub const CANN_BTC: Pair = Pair::new(Currency::CANN, Currency::BTC);
+    pub const CFI_BTC: Pair = Pair::new(Currency::CFI, Currency::BTC);
+    pub const CFI_ETH: Pair = Pair::new(Currency::CFI, Currency::ETH);
+    pub const CLAM_BTC: Pair = Pair::new(Currency::CLAM, Currency::BTC);
+    pub const CLOAK_BTC: Pair = Pair::new(Currency::CLOAK, Currency::BTC);
+    pub const CLUB_BTC: Pair = Pair::new(Currency::CLUB, Currency::BTC);
+    pub const COVAL_BTC: Pair = Pair::new(Currency::COVAL, Currency::BTC);
+    pub const CPC_BTC: Pair = Pair::new(Currency::CPC, Currency::BTC);
+    pub const CRB_BTC: Pair = Pair::new(Currency::CRB, Currency::BTC);
+    pub const CRB_ETH: Pair = Pair::new(Currency::CRB, Currency::ETH);
+    pub const CRW_BTC: Pair = Pair::new(Currency::CRW, Currency::BTC);
+    pub const CURE_BTC: Pair = Pair::new(Currency::CURE, Currency::BTC);
+    pub const CVC_BTC: Pair = Pair::new(Currency::CVC, Currency::BTC);
+    pub const CVC_ETH: Pair = Pair::new(Currency::CVC, Currency::ETH);
+    pub const DASH_BTC: Pair = Pair::new(Currency::DASH, Currency::BTC);
+    pub const DASH_ETH: Pair = Pair::new(Currency::DASH, Currency::ETH);
+    pub const DASH_EUR: Pair = Pair::new(Currency::DASH, Currency::EUR);
+    pub const DASH_USD: Pair = Pair::new(Currency::DASH, Currency::USD);
+    pub const DASH_USDT: Pair = Pair::new(Currency::DASH, Currency::USDT);
+    pub const DASH_XMR: Pair = Pair::new(Currency::DASH, Currency::XMR);
+    pub const DCR_BTC: Pair = Pair::new(Currency::DCR, Currency::BTC);
+    pub const DCT_BTC: Pair = Pair::new(Currency::DCT, Currency::BTC);
+    pub const DGB_BTC: Pair = Pair::new(Currency::DGB, Currency::BTC);
+    pub const DGB_ETH: Pair = Pair::new(Currency::DGB, Currency::ETH);
+    pub const DGD_BTC: Pair = Pair::new(Currency::DGD, Currency::BTC);
+    pub const DGD_ETH: Pair = Pair::new(Currency::DGD, Currency::ETH);
+    pub const DMD_BTC: Pair = Pair::new(Currency::DMD, Currency::BTC);
+    pub const DNT_BTC: Pair = Pair::new(Currency::DNT, Currency::BTC);
+    pub const DNT_ETH: Pair = Pair::new(Currency::DNT, Currency::ETH);
+    pub const DOGE_BTC: Pair = Pair::new(Currency::DOGE, Currency::BTC);
+    pub const DOPE_BTC: Pair = Pair::new(Currency::DOPE, Currency::BTC);
+    pub const DTB_BTC: Pair = Pair::new(Currency::DTB, Currency::BTC);
+    pub const DYN_BTC: Pair = Pair::new(Currency::DYN, Currency::BTC);
+    pub const EBST_BTC: Pair = Pair::new(Currency::EBST, Currency::BTC);
+    pub const EDG_BTC: Pair = Pair::new(Currency::EDG, Currency::BTC);
+    pub const EFL_BTC: Pair = Pair::new(Currency::EFL, Currency::BTC);
+    pub const EGC_BTC: Pair = Pair::new(Currency::EGC, Currency::BTC);
+    pub const EMC2_BTC: Pair = Pair::new(Currency::EMC2, Currency::BTC);
+    pub const EMC_BTC: Pair = Pair::new(Currency::EMC, Currency::BTC);
+    pub const ENRG_BTC: Pair = Pair::new(Currency::ENRG, Currency::BTC);
+    pub const EOS_BTC: Pair = Pair::new(Currency::EOS, Currency::BTC);
+    pub const EOS_ETH: Pair = Pair::new(Currency::EOS, Currency::ETH);
+    pub const ERC_BTC: Pair = Pair::new(Currency::ERC, Currency::BTC);
+    pub const ETC_BTC: Pair = Pair::new(Currency::ETC, Currency::BTC);
+    pub const ETC_ETH: Pair = Pair::new(Currency::ETC, Currency::ETH);
+    pub const ETC_EUR: Pair = Pair::new(Currency::ETC, Currency::EUR);
+    pub const ETC_USD: Pair = Pair::new(Currency::ETC, Currency::USD);
+    pub const ETC_USDT: Pair = Pair::new(Currency::ETC, Currency::USDT);
+    pub const ETH_BTC: Pair = Pair::new(Currency::ETH, Currency::BTC);
+    pub const ETH_BTC_d: Pair = Pair::new(Currency::ETH, Currency::BTC).dark_pool();
+    pub const ETH_CAD: Pair = Pair::new(Currency::ETH, Currency::CAD);
+    pub const ETH_CAD_d: Pair = Pair::new(Currency::ETH, Currency::CAD).dark_pool();
+    pub const ETH_EUR: Pair = Pair::new(Currency::ETH, Currency::EUR);
+    pub const ETH_EUR_d: Pair = Pair::new(Currency::ETH, Currency::EUR).dark_pool();
+    pub const ETH_GBP: Pair = Pair::new(Currency::ETH, Currency::GBP);
+    pub const ETH_GBP_d: Pair = Pair::new(Currency::ETH, Currency::GBP).dark_pool();
+    pub const ETH_JPY: Pair = Pair::new(Currency::ETH, Currency::JPY);
+    pub const ETH_JPY_d: Pair = Pair::new(Currency::ETH, Currency::JPY).dark_pool();
+    pub const ETH_USD: Pair = Pair::new(Currency::ETH, Currency::USD);
+    pub const ETH_USDT: Pair = Pair::new(Currency::ETH, Currency::USDT);
+    pub const ETH_USD_d: Pair = Pair::new(Currency::ETH, Currency::USD).dark_pool();
+    pub const EUR_USD: Pair = Pair::new(Currency::EUR, Currency::USD);
+    pub const EXCL_BTC: Pair = Pair::new(Currency::EXCL, Currency::BTC);
+    pub const EXP_BTC: Pair = Pair::new(Currency::EXP, Currency::BTC);
+    pub const FAIR_BTC: Pair = Pair::new(Currency::FAIR, Currency::BTC);
+    pub const FCT_BTC: Pair = Pair::new(Currency::FCT, Currency::BTC);
+    pub const FCT_ETH: Pair = Pair::new(Currency::FCT, Currency::ETH);
+    pub const FLDC_BTC: Pair = Pair::new(Currency::FLDC, Currency::BTC);
+    pub const FLO_BTC: Pair = Pair::new(Currency::FLO, Currency::BTC);
+    pub const FTC_BTC: Pair = Pair::new(Currency::FTC, Currency::BTC);
+    pub const FUN_BTC: Pair = Pair::new(Currency::FUN, Currency::BTC);
+    pub const FUN_ETH: Pair = Pair::new(Currency::FUN, Currency::ETH);
+    pub const GAME_BTC: Pair = Pair::new(Currency::GAME, Currency::BTC);
+    pub const GAM_BTC: Pair = Pair::new(Currency::GAM, Currency::BTC);
+    pub const GAS_BTC: Pair = Pair::new(Currency::GAS, Currency::BTC);
+    pub const GAS_ETH: Pair = Pair::new(Currency::GAS, Currency::ETH);
+    pub const GBG_BTC: Pair = Pair::new(Currency::GBG, Currency::BTC);
+    pub const GBYTE_BTC: Pair = Pair::new(Currency::GBYTE, Currency::BTC);
+    pub const GCR_BTC: Pair = Pair::new(Currency::GCR, Currency::BTC);
+    pub const GEO_BTC: Pair = Pair::new(Currency::GEO, Currency::BTC);
+    pub const GLD_BTC: Pair = Pair::new(Currency::GLD, Currency::BTC);
+    pub const GNO_BTC: Pair = Pair::new(Currency::GNO, Currency::BTC);
+    pub const GNO_ETH: Pair = Pair::new(Currency::GNO, Currency::ETH);
+    pub const GNT_BTC: Pair = Pair::new(Currency::GNT, Currency::BTC);
+    pub const GNT_ETH: Pair = Pair::new(Currency::GNT, Currency::ETH);
+    pub const GOLOS_BTC: Pair = Pair::new(Currency::GOLOS, Currency::BTC);
+    pub const GRC_BTC: Pair = Pair::new(Currency::GRC, Currency::BTC);
+    pub const GRS_BTC: Pair = Pair::new(Currency::GRS, Currency::BTC);
+    pub const GUP_BTC: Pair = Pair::new(Currency::GUP, Currency::BTC);
+    pub const GUP_ETH: Pair = Pair::new(Currency::GUP, Currency::ETH);
+    pub const HMQ_BTC: Pair = Pair::new(Currency::HMQ, Currency::BTC);
+    pub const HMQ_ETH: Pair = Pair::new(Currency::HMQ, Currency::ETH);
+    pub const HUC_BTC: Pair = Pair::new(Currency::HUC, Currency::BTC);
+    pub const ICN_BTC: Pair = Pair::new(Currency::ICN, Currency::BTC);
+    pub const ICN_ETH: Pair = Pair::new(Currency::ICN, Currency::ETH);
+    pub const INCNT_BTC: Pair = Pair::new(Currency::INCNT, Currency::BTC);
+    pub const INFX_BTC: Pair = Pair::new(Currency::INFX, Currency::BTC);
+    pub const IOC_BTC: Pair = Pair::new(Currency::IOC, Currency::BTC);
+    pub const ION_BTC: Pair = Pair::new(Currency::ION, Currency::BTC);
+    pub const IOP_BTC: Pair = Pair::new(Currency::IOP, Currency::BTC);
+    pub const KMD_BTC: Pair = Pair::new(Currency::KMD, Currency::BTC);
+    pub const KORE_BTC: Pair = Pair::new(Currency::KORE, Currency::BTC);
+    pub const LBC_BTC: Pair = Pair::new(Currency::LBC, Currency::BTC);
+    pub const LGD_BTC: Pair = Pair::new(Currency::LGD, Currency::BTC);
+    pub const LGD_ETH: Pair = Pair::new(Currency::LGD, Currency::ETH);
+    pub const LMC_BTC: Pair = Pair::new(Currency::LMC, Currency::BTC);
+    pub const LSK_BTC: Pair = Pair::new(Currency::LSK, Currency::BTC);
+    pub const LSK_ETH: Pair = Pair::new(Currency::LSK, Currency::ETH);
+    pub const LTC_BTC: Pair = Pair::new(Currency::LTC, Currency::BTC);
+    pub const LTC_ETH: Pair = Pair::new(Currency::LTC, Currency::ETH);
+    pub const LTC_EUR: Pair = Pair::new(Currency::LTC, Currency::EUR);
+    pub const LTC_USD: Pair = Pair::new(Currency::LTC, Currency::USD);
+    pub const LTC_USDT: Pair = Pair::new(Currency::LTC, Currency::USDT);
+    pub const LTC_XMR: Pair = Pair::new(Currency::LTC, Currency::XMR);
+    pub const LUN_BTC: Pair = Pair::new(Currency::LUN, Currency::BTC);
+    pub const LUN_ETH: Pair = Pair::new(Currency::LUN, Currency::ETH);
+    pub const MAID_BTC: Pair = Pair::new(Currency::MAID, Currency::BTC);
+    pub const MAID_XMR: Pair = Pair::new(Currency::MAID, Currency::XMR);
+    pub const MANA_BTC: Pair = Pair::new(Currency::MANA, Currency::BTC);
+    pub const MANA_ETH: Pair = Pair::new(Currency::MANA, Currency::ETH);
+    pub const MCO_BTC: Pair = Pair::new(Currency::MCO, Currency::BTC);
+    pub const MCO_ETH: Pair = Pair::new(Currency::MCO, Currency::ETH);
+    pub const MEME_BTC: Pair = Pair::new(Currency::MEME, Currency::BTC);
+    pub const MLN_BTC: Pair = Pair::new(Currency::MLN, Currency::BTC);
+    pub const MLN_ETH: Pair = Pair::new(Currency::MLN, Currency::ETH);
+    pub const MONA_BTC: Pair = Pair::new(Currency::MONA, Currency::BTC);
+    pub const MTL_BTC: Pair = Pair::new(Currency::MTL, Currency::BTC);
+    pub const MTL_ETH: Pair = Pair::new(Currency::MTL, Currency::ETH);
+    pub const MUE_BTC: Pair = Pair::new(Currency::MUE, Currency::BTC);
+    pub const MUSIC_BTC: Pair = Pair::new(Currency::MUSIC, Currency::BTC);
+    pub const MYST_BTC: Pair = Pair::new(Currency::MYST, Currency::BTC);
+    pub const MYST_ETH: Pair = Pair::new(Currency::MYST, Currency::ETH);
+    pub const NAUT_BTC: Pair = Pair::new(Currency::NAUT, Currency::BTC);
+    pub const NAV_BTC: Pair = Pair::new(Currency::NAV, Currency::BTC);
+    pub const NBT_BTC: Pair = Pair::new(Currency::NBT, Currency::BTC);
+    pub const NEOS_BTC: Pair = Pair::new(Currency::NEOS, Currency::BTC);
+    pub const NEO_BTC: Pair = Pair::new(Currency::NEO, Currency::BTC);
+    pub const NEO_ETH: Pair = Pair::new(Currency::NEO, Currency::ETH);
+    pub const NEO_USDT: Pair = Pair::new(Currency::NEO, Currency::USDT);
+    pub const NLG_BTC: Pair = Pair::new(Currency::NLG, Currency::BTC);
+    pub const NMC_BTC: Pair = Pair::new(Currency::NMC, Currency::BTC);
+    pub const NMR_BTC: Pair = Pair::new(Currency::NMR, Currency::BTC);
+    pub const NMR_ETH: Pair = Pair::new(Currency::NMR, Currency::ETH);
+    pub const NOTE_BTC: Pair = Pair::new(Currency::NOTE, Currency::BTC);
+    pub const NXC_BTC: Pair = Pair::new(Currency::NXC, Currency::BTC);
+    pub const NXS_BTC: Pair = Pair::new(Currency::NXS, Currency::BTC);
+    pub const NXT_BTC: Pair = Pair::new(Currency::NXT, Currency::BTC);
+    pub const NXT_USDT: Pair = Pair::new(Currency::NXT, Currency::USDT);
+    pub const NXT_XMR: Pair = Pair::new(Currency::NXT, Currency::XMR);
+    pub const OK_BTC: Pair = Pair::new(Currency::OK, Currency::BTC);
+    pub const OMG_BTC: Pair = Pair::new(Currency::OMG, Currency::BTC);
+    pub const OMG_ETH: Pair = Pair::new(Currency::OMG, Currency::ETH);
+    pub const OMG_USDT: Pair = Pair::new(Currency::OMG, Currency::USDT);
+    pub const OMNI_BTC: Pair = Pair::new(Currency::OMNI, Currency::BTC);
+    pub const PART_BTC: Pair = Pair::new(Currency::PART, Currency::BTC);
+    pub const PASC_BTC: Pair = Pair::new(Currency::PASC, Currency::BTC);
+    pub const PAY_BTC: Pair = Pair::new(Currency::PAY, Currency::BTC);
+    pub const PAY_ETH: Pair = Pair::new(Currency::PAY, Currency::ETH);
+    pub const PDC_BTC: Pair = Pair::new(Currency::PDC, Currency::BTC);
+    pub const PINK_BTC: Pair = Pair::new(Currency::PINK, Currency::BTC);
+    pub const PIVX_BTC: Pair = Pair::new(Currency::PIVX, Currency::BTC);
+    pub const PKB_BTC: Pair = Pair::new(Currency::PKB, Currency::BTC);
+    pub const POT_BTC: Pair = Pair::new(Currency::POT, Currency::BTC);
+    pub const PPC_BTC: Pair = Pair::new(Currency::PPC, Currency::BTC);
+    pub const PTC_BTC: Pair = Pair::new(Currency::PTC, Currency::BTC);
+    pub const PTOY_BTC: Pair = Pair::new(Currency::PTOY, Currency::BTC);
+    pub const PTOY_ETH: Pair = Pair::new(Currency::PTOY, Currency::ETH);
+    pub const QRL_BTC: Pair = Pair::new(Currency::QRL, Currency::BTC);
+    pub const QRL_ETH: Pair = Pair::new(Currency::QRL, Currency::ETH);
+    pub const QTUM_BTC: Pair = Pair::new(Currency::QTUM, Currency::BTC);
+    pub const QTUM_ETH: Pair = Pair::new(Currency::QTUM, Currency::ETH);
+    pub const QWARK_BTC: Pair = Pair::new(Currency::QWARK, Currency::BTC);
+    pub const RADS_BTC: Pair = Pair::new(Currency::RADS, Currency::BTC);
+    pub const RBY_BTC: Pair = Pair::new(Currency::RBY, Currency::BTC);
+    pub const RDD_BTC: Pair = Pair::new(Currency::RDD, Currency::BTC);
+    pub const REP_BTC: Pair = Pair::new(Currency::REP, Currency::BTC);
+    pub const REP_ETH: Pair = Pair::new(Currency::REP, Currency::ETH);
+    pub const REP_EUR: Pair = Pair::new(Currency::REP, Currency::EUR);
+    pub const REP_USDT: Pair = Pair::new(Currency::REP, Currency::USDT);
+    pub const RIC_BTC: Pair = Pair::new(Currency::RIC, Currency::BTC);
+    pub const RISE_BTC: Pair = Pair::new(Currency::RISE, Currency::BTC);
+    pub const RLC_BTC: Pair = Pair::new(Currency::RLC, Currency::BTC);
+    pub const RLC_ETH: Pair = Pair::new(Currency::RLC, Currency::ETH);
+    pub const SAFEX_BTC: Pair = Pair::new(Currency::SAFEX, Currency::BTC);
+    pub const SALT_BTC: Pair = Pair::new(Currency::SALT, Currency::BTC);
+    pub const SALT_ETH: Pair = Pair::new(Currency::SALT, Currency::ETH);
+    pub const SBD_BTC: Pair = Pair::new(Currency::SBD, Currency::BTC);
+    pub const SC_BTC: Pair = Pair::new(Currency::SC, Currency::BTC);
+    pub const SC_ETH: Pair = Pair::new(Currency::SC, Currency::ETH);
+    pub const SEQ_BTC: Pair = Pair::new(Currency::SEQ, Currency::BTC);
+    pub const SHIFT_BTC: Pair = Pair::new(Currency::SHIFT, Currency::BTC);
+    pub const SIB_BTC: Pair = Pair::new(Currency::SIB, Currency::BTC);
+    pub const SJCX_BTC: Pair = Pair::new(Currency::SJCX, Currency::BTC);
+    pub const SLR_BTC: Pair = Pair::new(Currency::SLR, Currency::BTC);
+    pub const SLS_BTC: Pair = Pair::new(Currency::SLS, Currency::BTC);
+    pub const SNGLS_BTC: Pair = Pair::new(Currency::SNGLS, Currency::BTC);
+    pub const SNGLS_ETH: Pair = Pair::new(Currency::SNGLS, Currency::ETH);
+    pub const SNRG_BTC: Pair = Pair::new(Currency::SNRG, Currency::BTC);
+    pub const SNT_BTC: Pair = Pair::new(Currency::SNT, Currency::BTC);
+    pub const SNT_ETH: Pair = Pair::new(Currency::SNT, Currency::ETH);
+    pub const SPHR_BTC: Pair = Pair::new(Currency::SPHR, Currency::BTC);
+    pub const SPR_BTC: Pair = Pair::new(Currency::SPR, Currency::BTC);
+    pub const START_BTC: Pair = Pair::new(Currency::START, Currency::BTC);
+    pub const STEEM_BTC: Pair = Pair::new(Currency::STEEM, Currency::BTC);
+    pub const STEEM_ETH: Pair = Pair::new(Currency::STEEM, Currency::ETH);
+    pub const STORJ_BTC: Pair = Pair::new(Currency::STORJ, Currency::BTC);
+    pub const STORJ_ETH: Pair = Pair::new(Currency::STORJ, Currency::ETH);
+    pub const STRAT_BTC: Pair = Pair::new(Currency::STRAT, Currency::BTC);
+    pub const STRAT_ETH: Pair = Pair::new(Currency::STRAT, Currency::ETH);
+    #[deprecated(note = "Poloniex calls XLM \"STR\", use Pair::XLM_BTC")]
+    #[allow(deprecated)]
+    pub const STR_BTC: Pair = Pair::new(Currency::STR, Currency::BTC);
+    #[deprecated(note = "Poloniex calls XLM \"STR\", use Pair::XLM_USDT")]
+    #[allow(deprecated)]
+    pub const STR_USDT: Pair = Pair::new(Currency::STR, Currency::USDT);
+    pub const SWIFT_BTC: Pair = Pair::new(Currency::SWIFT, Currency::BTC);
+    pub const SWT_BTC: Pair = Pair::new(Currency::SWT, Currency::BTC);
+    pub const SYNX_BTC: Pair = Pair::new(Currency::SYNX, Currency::BTC);
+    pub const SYS_BTC: Pair = Pair::new(Currency::SYS, Currency::BTC);
+    pub const THC_BTC: Pair = Pair::new(Currency::THC, Currency::BTC);
+    pub const TIME_BTC: Pair = Pair::new(Currency::TIME, Currency::BTC);
+    pub const TIME_ETH: Pair = Pair::new(Currency::TIME, Currency::ETH);
+    pub const TIX_BTC: Pair = Pair::new(Currency::TIX, Currency::BTC);
+    pub const TIX_ETH: Pair = Pair::new(Currency::TIX, Currency::ETH);
+    pub const TKN_BTC: Pair = Pair::new(Currency::TKN, Currency::BTC);
+    pub const TKN_ETH: Pair = Pair::new(Currency::TKN, Currency::ETH);
+    pub const TKS_BTC: Pair = Pair::new(Currency::TKS, Currency::BTC);
+    pub const TRIG_BTC: Pair = Pair::new(Currency::TRIG, Currency::BTC);
+    pub const TRST_BTC: Pair = Pair::new(Currency::TRST, Currency::BTC);
+    pub const TRST_ETH: Pair = Pair::new(Currency::TRST, Currency::ETH);
+    pub const TRUST_BTC: Pair = Pair::new(Currency::TRUST, Currency::BTC);
+    pub const TX_BTC: Pair = Pair::new(Currency::TX, Currency::BTC);
+    pub const UBQ_BTC: Pair = Pair::new(Currency::UBQ, Currency::BTC);
+    pub const UNB_BTC: Pair = Pair::new(Currency::UNB, Currency::BTC);
+    pub const USDT_USD: Pair = Pair::new(Currency::USDT, Currency::USD);
+    pub const VIA_BTC: Pair = Pair::new(Currency::VIA, Currency::BTC);
+    pub const VOX_BTC: Pair = Pair::new(Currency::VOX, Currency::BTC);
+    pub const VRC_BTC: Pair = Pair::new(Currency::VRC, Currency::BTC);
+    pub const VRM_BTC: Pair = Pair::new(Currency::VRM, Currency::BTC);
+    pub const VTC_BTC: Pair = Pair::new(Currency::VTC, Currency::BTC);
+    pub const VTR_BTC: Pair = Pair::new(Currency::VTR, Currency::BTC);
+    pub const WAVES_BTC: Pair = Pair::new(Currency::WAVES, Currency::BTC);
+    pub const WAVES_ETH: Pair = Pair::new(Currency::WAVES, Currency::ETH);
+    pub const WINGS_BTC: Pair = Pair::new(Currency::WINGS, Currency::BTC);
+    pub const WINGS_ETH: Pair = Pair::new(Currency::WINGS, Currency::ETH);
+    pub const XAUR_BTC: Pair = Pair::new(Currency::XAUR, Currency::BTC);
+    pub const XBC_BTC: Pair = Pair::new(Currency::XBC, Currency::BTC);
+    pub const XCP_BTC: Pair = Pair::new(Currency::XCP, Currency::BTC);
+    pub const XDG_BTC: Pair = Pair::new(Currency::XDG, Currency::BTC);
+    pub const XDN_BTC: Pair = Pair::new(Currency::XDN, Currency::BTC);
+    pub const XEL_BTC: Pair = Pair::new(Currency::XEL, Currency::BTC);
+    pub const XEM_BTC: Pair = Pair::new(Currency::XEM, Currency::BTC);
+    pub const XEM_ETH: Pair = Pair::new(Currency::XEM, Currency::ETH);
+    pub const XLM_BTC: Pair = Pair::new(Currency::XLM, Currency::BTC);
+    pub const XLM_ETH: Pair = Pair::new(Currency::XLM, Currency::ETH);
+    pub const XLM_USDT: Pair = Pair::new(Currency::XLM, Currency::USDT);
+    pub const XMG_BTC: Pair = Pair::new(Currency::XMG, Currency::BTC);
+    pub const XMR_BTC: Pair = Pair::new(Currency::XMR, Currency::BTC);
+    pub const XMR_ETH: Pair = Pair::new(Currency::XMR, Currency::ETH);
+    pub const XMR_EUR: Pair = Pair::new(Currency::XMR, Currency::EUR);
+    pub const XMR_USD: Pair = Pair::new(Currency::XMR, Currency::USD);
+    pub const XMR_USDT: Pair = Pair::new(Currency::XMR, Currency::USDT);
+    pub const XMY_BTC: Pair = Pair::new(Currency::XMY, Currency::BTC);
+    pub const XPM_BTC: Pair = Pair::new(Currency::XPM, Currency::BTC);
+    pub const XRP_BTC: Pair = Pair::new(Currency::XRP, Currency::BTC);
+    pub const XRP_ETH: Pair = Pair::new(Currency::XRP, Currency::ETH);
+    pub const XRP_EUR: Pair = Pair::new(Currency::XRP, Currency::EUR);
+    pub const XRP_USD: Pair = Pair::new(Currency::XRP, Currency::USD);
+    pub const XRP_USDT: Pair = Pair::new(Currency::XRP, Currency::USDT);
+    pub const XST_BTC: Pair = Pair::new(Currency::XST, Currency::BTC);
+    pub const XVC_BTC: Pair = Pair::new(Currency::XVC, Currency::BTC);
+    pub const XVG_BTC: Pair = Pair::new(Currency::XVG, Currency::BTC);
+    pub const XWC_BTC: Pair = Pair::new(Currency::XWC, Currency::BTC);
+    pub const XZC_BTC: Pair = Pair::new(Currency::XZC, Currency::BTC);
+    pub const ZCL_BTC: Pair = Pair::new(Currency::ZCL, Currency::BTC);
+    pub const ZEC_BTC: Pair = Pair::new(Currency::ZEC, Currency::BTC);
+    pub const ZEC_ETH: Pair = Pair::new(Currency::ZEC, Currency::ETH);
+    pub const ZEC_EUR: Pair = Pair::new(Currency::ZEC, Currency::EUR);
+    pub const ZEC_USD: Pair = Pair::new(Currency::ZEC, Currency::USD);
+    pub const ZEC_USDT: Pair = Pair::new(Currency::ZEC, Currency::USDT);
+    pub const ZEC_XMR: Pair = Pair::new(Currency::ZEC, Currency::XMR);
+    pub const ZEN_BTC: Pair = Pair::new(Currency::ZEN, Currency::BTC);
+    pub const ZRX_BTC: Pair = Pair::new(Currency::ZRX, Currency::BTC);
+    pub const ZRX_ETH: Pair = Pair::new(Currency::ZRX, Currency::ETH);
+}
+
+impl fmt::Debug for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}_{}", self.quote, self.base)?;
+        if self.dark_pool {
+            write!(f, "_d")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Pair {
+    type Err = Error;
+
+    /// Parse a Pair formatted like its Display implementation, ie. "BTC_EUR" or "BTC_EUR_d".
+    fn from_str(pair: &str) -> ::std::result::Result<Self, Self::Err> {
+        let (name, dark_pool) = match pair.strip_suffix("_d") {
+            Some(name) => (name, true),
+            None => (pair, false),
+        };
+
+        let mut currencies = name.split('_');
+        let quote = currencies.next().unwrap_or("").parse::<Currency>()?;
+        let base = currencies.next().unwrap_or("").parse::<Currency>()?;
+        if currencies.next().is_some() {
+            return Err(ErrorKind::InvalidArguments.into());
+        }
+
+        let pair = Pair::new(quote, base);
+        Ok(if dark_pool { pair.dark_pool() } else { pair })
+    }
+}
+
+lazy_static! {
+    static ref SYMBOLS: Mutex<HashSet<&'static str>> = Mutex::new(HashSet::new());
+}
+
+/// Return a static copy of the symbol. Each symbol is allocated once and never freed, so that
+/// Currency and Pair can stay Copy.
+fn intern(symbol: &str) -> &'static str {
+    let mut symbols = SYMBOLS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if let Some(interned) = symbols.get(symbol) {
+        return interned;
+    }
+
+    let interned: &'static str = Box::leak(symbol.to_string().into_boxed_str());
+    symbols.insert(interned);
+    interned
 }
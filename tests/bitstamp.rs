@@ -1,17 +1,27 @@
+mod common;
+
 #[cfg(test)]
 mod bitstamp_tests {
     extern crate coinnect;
     extern crate bigdecimal;
+    extern crate serde_json;
 
     use self::bigdecimal::BigDecimal;
     use std::str::FromStr;
 
+    use self::coinnect::bitstamp::responses::{TransactionType, WithdrawalStatus};
     use self::coinnect::bitstamp::utils;
-    use self::coinnect::bitstamp::{BitstampApi, BitstampCreds};
+    use self::coinnect::bitstamp::{AuthMode, BitstampApi, BitstampCreds};
     use self::coinnect::kraken::KrakenCreds;
 
-    use self::coinnect::exchange::ExchangeApi;
-    use self::coinnect::types::Pair;
+    use self::coinnect::exchange::{Exchange, ExchangeApi};
+    use self::coinnect::nonce::{get_resolution, MemoryNonceProvider, NonceProvider};
+    use self::coinnect::transport::{HttpRequest, HttpResponse, Method};
+    use self::coinnect::types::{Currency, OrderSide, OrderStatus, Pair};
+    use self::coinnect::error::ErrorKind;
+
+    use crate::common::{bitstamp_api, decimal, header, recording, FixtureTransport,
+                        BITSTAMP_SECRET};
 
     #[test]
     fn build_url_should_return_the_a_url() {
@@ -36,14 +46,14 @@ mod bitstamp_tests {
     #[test]
     fn can_get_real_bitstamp_tick() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         api.ticker(Pair::BTC_USD).unwrap();
     }
 
     #[test]
     fn ticker_should_have_the_correct_last() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         let result = api.ticker(Pair::BTC_USD);
         assert_ne!(result.unwrap().last_trade_price,
                    BigDecimal::from_str("0.0").unwrap());
@@ -51,7 +61,7 @@ mod bitstamp_tests {
     #[test]
     fn ticker_should_have_the_correct_high() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         let result = api.ticker(Pair::BTC_USD);
         assert_ne!(result.unwrap().highest_bid,
                    BigDecimal::from_str("0.0").unwrap());
@@ -59,7 +69,7 @@ mod bitstamp_tests {
     #[test]
     fn ticker_should_have_the_correct_low() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         let result = api.ticker(Pair::BTC_USD);
         assert_ne!(result.unwrap().lowest_ask,
                    BigDecimal::from_str("0.0").unwrap());
@@ -67,7 +77,7 @@ mod bitstamp_tests {
     #[test]
     fn ticker_should_have_the_correct_volume() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         let result = api.ticker(Pair::BTC_USD);
         assert_ne!(result.unwrap().volume.unwrap(),
                    BigDecimal::from_str("0.0").unwrap());
@@ -76,29 +86,29 @@ mod bitstamp_tests {
     #[test]
     fn should_return_an_order_book() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         let result = api.return_order_book(Pair::BTC_USD);
-        assert_eq!(result.is_ok(), true);
+        assert!(result.is_ok());
     }
 
     #[test]
     fn order_book_should_have_a_timestamp() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         let result = api.return_order_book(Pair::BTC_USD);
         assert!(result.unwrap().contains_key("timestamp"));
     }
     #[test]
     fn order_book_should_have_bids() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         let result = api.return_order_book(Pair::BTC_USD);
         assert!(result.unwrap().contains_key("bids"));
     }
     #[test]
     fn order_book_should_have_asks() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         let result = api.return_order_book(Pair::BTC_USD);
         assert!(result.unwrap().contains_key("bids"));
     }
@@ -106,7 +116,7 @@ mod bitstamp_tests {
     #[test]
     fn order_book_should_have_asks_for_btcusd() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         assert!(api.return_order_book(Pair::BTC_USD)
                     .unwrap()
                     .contains_key("asks"));
@@ -114,19 +124,22 @@ mod bitstamp_tests {
     #[test]
     fn order_book_should_have_asks_for_btceur() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         assert!(api.return_order_book(Pair::BTC_USD)
                     .unwrap()
                     .contains_key("asks"));
     }
 
     #[test]
-    fn should_create_a_fixed_nonce_when_requested() {
-        assert_eq!(utils::generate_nonce(Some("1".to_string())), "1");
+    fn should_create_increasing_nonces() {
+        let provider = MemoryNonceProvider::new(get_resolution(Exchange::Bitstamp));
+        let nonce = provider.next_nonce().unwrap();
+        assert!(provider.next_nonce().unwrap() > nonce);
     }
     #[test]
     fn should_create_a_nonce_bigger_than_2017() {
-        assert!(utils::generate_nonce(None).parse::<i64>().unwrap() > 1483228800);
+        let provider = MemoryNonceProvider::new(get_resolution(Exchange::Bitstamp));
+        assert!(provider.next_nonce().unwrap() > 1483228800000);
     }
     #[test]
     fn should_create_a_correct_signature() {
@@ -143,10 +156,10 @@ mod bitstamp_tests {
     #[test]
     fn should_return_the_trade_history_for_btc_usd() {
         let creds = BitstampCreds::new("", "", "", "");
-        let mut api = BitstampApi::new(creds).unwrap();
-        let result = api.return_trade_history(Pair::BTC_USD);
+        let api = BitstampApi::new(creds).unwrap();
+        let result = api.return_trade_history(Pair::BTC_USD).unwrap();
 
-        assert_eq!(result.is_ok(), false);
+        assert!(result["data"].is_array());
     }
 
     // IMPORTANT: Real keys are needed in order to retrieve the balance
@@ -156,7 +169,7 @@ mod bitstamp_tests {
         use std::path::PathBuf;
         let path = PathBuf::from("./keys_real.json");
         let creds = BitstampCreds::new_from_file("account_bitstamp", path).unwrap();
-        let mut api = BitstampApi::new(creds).unwrap();
+        let api = BitstampApi::new(creds).unwrap();
         let result = api.return_balances().unwrap();
         let result_looking_for_usd = result.clone();
         let result_looking_for_btc = result.clone();
@@ -164,4 +177,332 @@ mod bitstamp_tests {
         assert!(result_looking_for_usd.contains_key("usd_balance"));
         assert!(result_looking_for_btc.contains_key("btc_balance"));
     }
+
+    #[test]
+    fn build_method_url_should_not_have_a_pair() {
+        assert_eq!(utils::build_method_url("cancel_order"),
+                   "https://www.bitstamp.net/api/v2/cancel_order/");
+    }
+
+    #[test]
+    fn unknown_order_should_return_an_order_not_found_error() {
+        let response = utils::deserialize_json(r#"{"error":"Order not found"}"#).unwrap();
+        let res = utils::parse_result(&response, "order_status");
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::OrderNotFound.to_string());
+    }
+
+    #[test]
+    fn parse_order_status_should_return_a_filled_order() {
+        let order: serde_json::Value = serde_json::from_str(r#"{"id": 1453282316,
+            "datetime": "2017-10-27 10:11:12", "type": "0", "status": "Finished",
+            "market": "BTC/USD", "amount_remaining": "0.00000000",
+            "transactions": [{"tid": 24311342, "price": "2100.00", "btc": "0.5",
+            "usd": "1050.00", "fee": "2.10", "datetime": "2017-10-27 10:11:13", "type": 2},
+            {"tid": 24311343, "price": "2200.00", "btc": "0.5", "usd": "1100.00",
+            "fee": "2.20", "datetime": "2017-10-27 10:11:14", "type": 2}]}"#).unwrap();
+
+        let pair = utils::get_pair_enum_from_market("BTC/USD").unwrap();
+        let order = utils::parse_order_status(pair, &order).unwrap();
+
+        assert_eq!(order.identifier, "1453282316");
+        assert_eq!(order.pair, Pair::BTC_USD);
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.quantity, BigDecimal::from_str("1").unwrap());
+        assert_eq!(order.filled_quantity, BigDecimal::from_str("1").unwrap());
+        assert_eq!(order.average_price, Some(BigDecimal::from_str("2150").unwrap()));
+        assert_eq!(order.opened_at, Some(1509099072000));
+        assert_eq!(order.closed_at, Some(1509099074000));
+    }
+
+    #[test]
+    fn parse_trades_should_return_the_trades_from_the_oldest() {
+        let trades: serde_json::Value = serde_json::from_str(r#"[{"date": "1509099074",
+            "tid": "24311343", "price": "5812.99", "type": "1", "amount": "0.04000000"},
+            {"date": "1509099072", "tid": "24311342", "price": "5812.98", "type": "0",
+            "amount": "0.22000000"}]"#).unwrap();
+
+        let trades = utils::parse_trades(trades.as_array().unwrap()).unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].identifier, "24311342");
+        assert_eq!(trades[0].timestamp, 1509099072000);
+        assert_eq!(trades[0].side, OrderSide::Buy);
+        assert_eq!(trades[1].identifier, "24311343");
+        assert_eq!(trades[1].price, BigDecimal::from_str("5812.99").unwrap());
+        assert_eq!(trades[1].side, OrderSide::Sell);
+    }
+
+    #[test]
+    fn markets_should_register_the_pairs_listed_by_bitstamp() {
+        let (api, requests) = recording(bitstamp_api, r#"[{"name": "XLM/USD", "url_symbol": "xlmusd", "base_decimals": 8, "counter_decimals": 5, "instant_order_counter_decimals": 5, "minimum_order": "10.0 USD", "trading": "Enabled", "instant_and_market_orders": "Enabled", "description": "Stellar Lumens / U.S. dollar"}, {"name": "XLM/EUR", "url_symbol": "xlmeur", "base_decimals": 8, "counter_decimals": 5, "instant_order_counter_decimals": 5, "minimum_order": "10.0 EUR", "trading": "Disabled", "instant_and_market_orders": "Disabled", "description": "Stellar Lumens / Euro"}]"#);
+        let xlm_usd = Pair::new(Currency::new("XLM"), Currency::USD);
+        let xlm_eur = Pair::new(Currency::new("XLM"), Currency::EUR);
+
+        let markets = api.markets().unwrap();
+
+        assert_eq!(requests.lock().unwrap()[0].url,
+                   "https://www.bitstamp.net/api/v2/trading-pairs-info/");
+        assert_eq!(markets.len(), 2);
+        let xlm = &markets[&xlm_usd];
+        assert_eq!(xlm.tick_size, Some(BigDecimal::from_str("0.00001").unwrap()));
+        assert_eq!(xlm.lot_decimals, Some(8));
+        assert_eq!(xlm.min_notional, Some(BigDecimal::from_str("10").unwrap()));
+        assert!(xlm.active);
+        assert!(!markets[&xlm_eur].active);
+        assert_eq!(utils::get_pair_string(&xlm_usd), Some("xlmusd"));
+        assert_eq!(utils::get_pair_enum_from_market("XLM/EUR"), Some(xlm_eur));
+    }
+
+    #[test]
+    fn pairs_not_listed_by_bitstamp_should_not_be_supported() {
+        let (api, requests) = recording(bitstamp_api, r#"[{"name": "BTC/USD", "url_symbol": "btcusd", "base_decimals": 8, "counter_decimals": 2, "instant_order_counter_decimals": 2, "minimum_order": "10.0 USD", "trading": "Enabled", "instant_and_market_orders": "Enabled", "description": "Bitcoin / U.S. dollar"}]"#);
+        let unlisted = Pair::new(Currency::new("UNLISTED"), Currency::USD);
+
+        assert_eq!(utils::get_pair_enum_from_market("UNLISTED/USD"), None);
+        assert_eq!(api.ticker(unlisted).unwrap_err().to_string(),
+                   ErrorKind::PairUnsupported.to_string());
+        // Only the markets were requested
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert_eq!(utils::get_pair_string(&unlisted), None);
+        assert_eq!(utils::get_currency_enum("xlm_balance"), Some(Currency::new("XLM")));
+        assert_eq!(utils::get_currency_enum("xlm_available"), None);
+    }
+
+    #[test]
+    fn typed_user_transactions_should_split_the_amounts_and_the_prices() {
+        let (api, requests) = recording(bitstamp_api, r#"[{"id": 24311342, "datetime": "2017-10-27 10:11:13.123456", "type": "2", "fee": "0.11", "order_id": 1453282316, "btc": "0.02035278", "usd": "-42.75", "eur": 0, "btc_usd": "2100.45"}, {"id": 24311300, "datetime": "2017-10-26 08:00:00", "type": "0", "fee": "0.00", "order_id": null, "btc": "0.5", "usd": 0, "eur": 0, "btc_usd": null}]"#);
+
+        let transactions = api.typed()
+            .return_user_transactions(Some(Pair::BTC_USD), "", "2", "desc")
+            .unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].id, "24311342");
+        assert_eq!(transactions[0].transaction_type, TransactionType::MarketTrade);
+        assert_eq!(transactions[0].order_id, Some("1453282316".to_string()));
+        assert_eq!(transactions[0].fee, decimal("0.11"));
+        assert_eq!(transactions[0].amounts["usd"], decimal("-42.75"));
+        assert_eq!(transactions[0].amounts["eur"], decimal("0"));
+        assert_eq!(transactions[0].prices["btc_usd"], decimal("2100.45"));
+        assert_eq!(transactions[0].datetime.timestamp_millis(), 1509099073123);
+        assert_eq!(transactions[1].transaction_type, TransactionType::Deposit);
+        assert_eq!(transactions[1].order_id, None);
+        assert!(transactions[1].prices.is_empty());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].url, "https://www.bitstamp.net/api/v2/user_transactions/btcusd/");
+        assert!(requests[0].body.split('&').any(|param| param == "limit=2"));
+        assert!(!requests[0].body.split('&').any(|param| param.starts_with("offset=")));
+    }
+
+    #[test]
+    fn typed_order_status_should_return_the_amounts_of_the_transactions() {
+        let api = bitstamp_api(r#"{"id": 1453282316, "datetime": "2017-10-27 10:11:12", "type": "0", "status": "Finished", "market": "BTC/USD", "amount_remaining": "0.00000000", "transactions": [{"tid": 24311342, "price": "2100.45", "btc": "0.02035278", "usd": "42.75", "fee": "0.11", "datetime": "2017-10-27 10:11:13", "type": 2}]}"#);
+
+        let order = api.typed().return_order_status("1453282316").unwrap();
+
+        assert_eq!(order.side, Some(OrderSide::Buy));
+        assert_eq!(order.status, "Finished");
+        assert_eq!(order.amount_remaining, Some(decimal("0")));
+        assert_eq!(order.transactions[0].tid, "24311342");
+        assert_eq!(order.transactions[0].transaction_type, TransactionType::MarketTrade);
+        assert_eq!(order.transactions[0].amounts["btc"], decimal("0.02035278"));
+        assert_eq!(order.transactions[0].amounts.len(), 2);
+    }
+
+    #[test]
+    fn typed_cancel_all_orders_should_return_the_canceled_orders() {
+        let (api, requests) = recording(bitstamp_api, r#"{"success": true, "canceled": [{"id": 1453282316, "amount": "0.02035278", "price": "2100.45", "type": 1, "currency_pair": "BTC/USD"}]}"#);
+
+        let canceled = api.typed().cancel_all_orders(None).unwrap();
+
+        assert!(canceled.success);
+        assert_eq!(canceled.canceled[0].id, "1453282316");
+        assert_eq!(canceled.canceled[0].side, OrderSide::Sell);
+        assert_eq!(canceled.canceled[0].currency_pair, Some("BTC/USD".to_string()));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].url, "https://www.bitstamp.net/api/v2/cancel_all_orders/");
+    }
+
+    #[test]
+    fn cancel_order_should_not_send_a_pair() {
+        let (api, requests) = recording(bitstamp_api, r#"{"id": 1453282316, "amount": "0.02035278", "price": "2100.45", "type": 0}"#);
+
+        api.cancel_order("1453282316").unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].url, "https://www.bitstamp.net/api/v2/cancel_order/");
+        let body = &requests[0].body;
+        assert!(body.contains("id=1453282316"), "{}", body);
+        assert!(!body.contains("pair"), "{}", body);
+    }
+
+    #[test]
+    fn typed_withdrawal_requests_and_deposit_addresses_should_be_parsed() {
+        let api = bitstamp_api(r#"[{"id": 1, "datetime": "2017-10-27 10:11:12", "type": 1, "currency": "BTC", "amount": "0.50000000", "status": 2, "address": "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2", "transaction_id": "a2b5c6d1e4f8"}, {"id": 2, "datetime": "2017-10-27 11:00:00", "type": 0, "amount": "100.00", "status": "4"}]"#);
+
+        let withdrawals = api.typed().return_withdrawal_requests("").unwrap();
+
+        assert_eq!(withdrawals[0].status, WithdrawalStatus::Finished);
+        assert_eq!(withdrawals[0].amount, decimal("0.5"));
+        assert_eq!(withdrawals[0].transaction_id, Some("a2b5c6d1e4f8".to_string()));
+        assert_eq!(withdrawals[1].status, WithdrawalStatus::Failed);
+        assert_eq!(withdrawals[1].currency, None);
+
+        let (api, requests) = recording(bitstamp_api, r#"{"address": "rDsbeomae4FXwgQTJp9Rs64Qg9vDiTCdBv", "destination_tag": 89250597}"#);
+
+        let address = api.typed().return_deposit_address(Currency::XRP).unwrap();
+
+        assert_eq!(address.address, "rDsbeomae4FXwgQTJp9Rs64Qg9vDiTCdBv");
+        assert_eq!(address.destination_tag, Some("89250597".to_string()));
+        assert_eq!(address.memo_id, None);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].url, "https://www.bitstamp.net/api/v2/xrp_address/");
+    }
+
+    #[test]
+    fn typed_balances_and_fees_should_be_grouped() {
+        let api = bitstamp_api(r#"{"usd_balance": "102.50", "usd_available": "80.25", "usd_reserved": "22.25", "btc_balance": "0.59098578", "btc_available": "0.59098578", "btc_reserved": "0.00000000", "btc_withdrawal_fee": "0.00050000", "btcusd_fee": "0.250"}"#);
+
+        let balances = api.typed().return_balances().unwrap();
+
+        assert_eq!(balances.balances["usd"].balance, decimal("102.5"));
+        assert_eq!(balances.balances["usd"].available, decimal("80.25"));
+        assert_eq!(balances.balances["usd"].reserved, decimal("22.25"));
+        assert_eq!(balances.withdrawal_fees["btc"], decimal("0.0005"));
+        assert_eq!(balances.trading_fees["btcusd"], decimal("0.25"));
+        assert_eq!(balances.trading_fees.len(), 1);
+
+        let api = bitstamp_api(r#"[{"currency_pair": "btcusd", "market": "btcusd", "fees": {"maker": "0.15000", "taker": "0.25000"}}, {"currency_pair": "btceur", "market": "btceur", "fees": {"maker": "0.15000", "taker": "0.25000"}}]"#);
+        let fees = api.typed().return_trading_fees(None).unwrap();
+        assert_eq!(fees.len(), 2);
+        assert_eq!(fees[1].currency_pair, "btceur");
+
+        let (api, requests) = recording(bitstamp_api, r#"{"currency_pair": "btcusd", "market": "btcusd", "fees": {"maker": "0.15000", "taker": "0.25000"}}"#);
+        let fees = api.typed().return_trading_fees(Some(Pair::BTC_USD)).unwrap();
+        assert_eq!(fees[0].fees.taker, decimal("0.25"));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].url, "https://www.bitstamp.net/api/v2/fees/trading/btcusd/");
+    }
+
+    #[test]
+    fn v2_signatures_should_match_the_reference_vectors() {
+        let nonce = "f93c979d-b00d-43a9-9b9c-fd4cd9547fa6";
+        let request = HttpRequest::new(Method::Post,
+                                       "https://www.bitstamp.net/api/v2/user_transactions/btcusd/")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body("limit=2");
+        let message = utils::build_v2_message("fixture_key", &request, nonce, "1509099072123");
+        assert_eq!(message,
+                   "BITSTAMP fixture_keyPOSTwww.bitstamp.net/api/v2/user_transactions/btcusd/application/x-www-form-urlencodedf93c979d-b00d-43a9-9b9c-fd4cd9547fa61509099072123v2limit=2");
+        assert_eq!(utils::build_v2_signature(&message, "fixture_secret"),
+                   "01e0f41f79b90cfb33644b3c21d6772a60909087ff585524940c70fd7e1c589a");
+
+        let request = HttpRequest::new(Method::Post, "https://www.bitstamp.net/api/v2/balance/");
+        let message = utils::build_v2_message("fixture_key", &request, nonce, "1509099072123");
+        assert_eq!(utils::build_v2_signature(&message, "fixture_secret"),
+                   "2232fad800664e888bbb83189e5e728f536ca1f30634c217475ccc9c8c3666d1");
+    }
+
+    #[test]
+    fn v2_response_signature_should_be_verified() {
+        let nonce = "f93c979d-b00d-43a9-9b9c-fd4cd9547fa6";
+        let mut response = HttpResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string()),
+                          ("X-Server-Auth-Signature".to_string(),
+                           "d36a87816a3afad5e744dab95b6409dcd6fdd781172032c97111b711814ca0ca"
+                               .to_string())],
+            body: r#"{"id": 1}"#.to_string(),
+        };
+        assert!(utils::verify_v2_response(&response, nonce, "1509099072123", "fixture_secret")
+                    .is_ok());
+
+        response.body = r#"{"id": 2}"#.to_string();
+        let err = utils::verify_v2_response(&response, nonce, "1509099072123", "fixture_secret")
+            .unwrap_err();
+        assert_eq!(err.to_string(),
+                   ErrorKind::InvalidFieldValue("X-Server-Auth-Signature".to_string())
+                       .to_string());
+
+        response.headers.pop();
+        let err = utils::verify_v2_response(&response, nonce, "1509099072123", "fixture_secret")
+            .unwrap_err();
+        assert_eq!(err.to_string(),
+                   ErrorKind::MissingField("X-Server-Auth-Signature".to_string()).to_string());
+    }
+
+    #[test]
+    fn header_auth_should_sign_the_request_with_the_x_auth_headers() {
+        let transport = FixtureTransport::new("[]").signed_with(BITSTAMP_SECRET);
+        let (mut api, requests) = recording(bitstamp_api, transport);
+        api.set_auth_mode(AuthMode::Header);
+
+        api.return_user_transactions(Some(Pair::BTC_USD), "", "2", "").unwrap();
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        let nonce = header(request, "X-Auth-Nonce").unwrap();
+        assert_eq!(nonce.len(), 36);
+        assert_eq!(&nonce[14..15], "4");
+        assert_eq!(header(request, "X-Auth"), Some("BITSTAMP fixture_key"));
+        assert_eq!(header(request, "X-Auth-Timestamp"), Some("1509099072123"));
+        assert_eq!(header(request, "X-Auth-Version"), Some("v2"));
+        assert_eq!(request.body, "limit=2");
+
+        let message = utils::build_v2_message("fixture_key", request, nonce, "1509099072123");
+        assert_eq!(header(request, "X-Auth-Signature"),
+                   Some(utils::build_v2_signature(&message, "fixture_secret").as_str()));
+    }
+
+    #[test]
+    fn header_auth_should_not_send_a_content_type_without_body() {
+        let transport = FixtureTransport::new(r#"{"usd_balance": "1.00"}"#)
+            .signed_with(BITSTAMP_SECRET);
+        let (mut api, requests) = recording(bitstamp_api, transport);
+        api.set_auth_mode(AuthMode::Header);
+
+        api.return_balances().unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].body, "");
+        assert_eq!(header(&requests[0], "Content-Type"), None);
+        assert!(header(&requests[0], "X-Auth-Signature").is_some());
+    }
+
+    #[test]
+    fn header_auth_should_reject_the_responses_signed_with_another_secret() {
+        let transport = FixtureTransport::new(r#"{"usd_balance": "1.00"}"#)
+            .signed_with("other_secret");
+        let mut api = bitstamp_api(transport);
+        api.set_auth_mode(AuthMode::Header);
+
+        let err = api.return_balances().unwrap_err();
+        assert_eq!(err.to_string(),
+                   ErrorKind::InvalidFieldValue("X-Server-Auth-Signature".to_string())
+                       .to_string());
+
+        let mut api = bitstamp_api(r#"{"usd_balance": "1.00"}"#);
+        api.set_auth_mode(AuthMode::Header);
+        assert!(api.return_balances().is_err());
+    }
+
+    #[test]
+    fn legacy_auth_should_post_the_key_the_signature_and_the_nonce() {
+        let (api, requests) = recording(bitstamp_api, r#"{"usd_balance": "1.00"}"#);
+        assert_eq!(api.auth_mode(), AuthMode::Legacy);
+
+        api.return_balances().unwrap();
+
+        let requests = requests.lock().unwrap();
+        let params: Vec<&str> = requests[0].body.split('&').collect();
+        assert!(params.contains(&"key=fixture_key"));
+        assert!(params.contains(&"nonce=1509099072123"));
+        assert!(params.iter().any(|param| param.starts_with("signature=")));
+        assert_eq!(header(&requests[0], "X-Auth"), None);
+    }
 }
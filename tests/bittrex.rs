@@ -1,28 +1,47 @@
+mod common;
+
 #[cfg(test)]
 mod bittrex_tests {
     extern crate coinnect;
+    extern crate bigdecimal;
+    extern crate serde_json;
+    extern crate chrono;
+
+    use self::bigdecimal::BigDecimal;
+    use std::str::FromStr;
+    use self::serde_json::Value;
+
+    use self::coinnect::types::{Currency, Interval, OrderSide, OrderStatus, OrderType, Pair};
 
     use self::coinnect::bittrex::{BittrexApi, BittrexCreds};
+    use self::coinnect::bittrex::utils;
+    use self::coinnect::exchange::ExchangeApi;
+    use self::coinnect::error::ErrorKind;
+    use self::coinnect::transport::Method;
+    use self::chrono::{TimeZone, Utc};
+
+    use crate::common::{bittrex_api, decimal, header, recording, BITTREX_SECRET};
+
+    const EMPTY_HASH: &str = "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e";
 
     #[test]
     fn get_markets_should_return_a_result() {
         let creds = BittrexCreds::new("bittrex", "", "");
-        let mut api = BittrexApi::new(creds).unwrap();
+        let api = BittrexApi::new(creds).unwrap();
 
         let result = api.get_markets().unwrap();
 
-        assert!(result.contains_key("success"))
+        assert!(result.contains_key("data"))
     }
 
     #[test]
     fn get_ticker_should_return_a_ticker() {
         let creds = BittrexCreds::new("bittrex", "", "");
-        let mut api = BittrexApi::new(creds).unwrap();
+        let api = BittrexApi::new(creds).unwrap();
 
-        let result = api.get_ticker("BTC-LTC").unwrap();
+        let result = api.get_ticker("LTC-BTC").unwrap();
 
-        let last_price = result.get("result").unwrap().as_object().unwrap().get("Last");
-        assert!(last_price.is_some())
+        assert!(result.get("lastTradeRate").is_some())
     }
 
     /// IMPORTANT: Real keys are needed in order to retrieve the balances
@@ -32,10 +51,465 @@ mod bittrex_tests {
         use std::path::PathBuf;
         let path = PathBuf::from("./keys_real.json");
         let creds = BittrexCreds::new_from_file("account_bittrex", path).unwrap();
-        let mut api = BittrexApi::new(creds).unwrap();
+        let api = BittrexApi::new(creds).unwrap();
 
         let result = api.get_balances().unwrap();
 
-        assert!(result.get("result").is_some())
+        assert!(result.get("data").is_some())
+    }
+
+    #[test]
+    fn build_url_should_return_a_v3_url() {
+        assert_eq!(utils::build_url("/markets/LTC-BTC/ticker"),
+                   "https://api.bittrex.com/v3/markets/LTC-BTC/ticker");
+    }
+
+    #[test]
+    fn build_content_hash_should_return_the_sha512_of_the_body() {
+        assert_eq!(utils::build_content_hash(""), EMPTY_HASH);
+        assert_eq!(utils::build_content_hash(r#"{"direction":"SELL","limit":"0.01","marketSymbol":"LTC-BTC","quantity":"0.5","timeInForce":"GOOD_TIL_CANCELLED","type":"LIMIT"}"#),
+                   "0ff5d9e12c7e71a37b9a9bc321cc8cd069689bd9645eb71824afcd20d0fd13b7071fecb089c3fa9ca733bad8f7899cb1a433360f9625960518aabea84cdbe8d9");
+    }
+
+    #[test]
+    fn should_create_correct_signatures() {
+        // Computed independently with the hashlib and hmac modules of Python
+        let order_hash = "0ff5d9e12c7e71a37b9a9bc321cc8cd069689bd9645eb71824afcd20d0fd13b7071fecb089c3fa9ca733bad8f7899cb1a433360f9625960518aabea84cdbe8d9";
+        let vectors = vec![
+            ("GET", "https://api.bittrex.com/v3/balances", EMPTY_HASH, "",
+             "5735eb18ddf19014dbf943764c56908728742c6de04c3172d600d41d96f3c239e39b5986f31ec43309dc8dc496a80fd92818470f1822ba8f9ad1df69cdbd6182"),
+            ("POST", "https://api.bittrex.com/v3/orders", order_hash, "",
+             "3abe46052a78f2a01600d596dd345a89b760f17c805780b491b61daf4dec1d7eba5488e30f60e0a5db5a0d87de9dd26316cf396fa031c455be46faa75ceef17f"),
+            ("DELETE", "https://api.bittrex.com/v3/orders/open?marketSymbol=LTC-BTC", EMPTY_HASH, "",
+             "a472c7eea8272ff655ee096a0e32b525a5ea3d472ebf4ea1db46cb9e0600328fd3c87bf46f48a7cfeb5905a224afd236feb96528de1e98d2998fafb85329be2d"),
+            ("GET", "https://api.bittrex.com/v3/balances", EMPTY_HASH,
+             "9c2a8bb4-0c68-4b6e-8e2a-64c1f8b6a8f1",
+             "3775d760069934d2f53c21e26049fda82afb84dea651043a78f5be54e1daec5ba7d8c6fc74e8d8d64dc0a1013e0d9af6c0de0c8757632d98163facb6e999b7c0"),
+        ];
+
+        for (method, url, content_hash, subaccount_id, expected) in vectors {
+            assert_eq!(utils::build_signature("1509099072123",
+                                              url,
+                                              method,
+                                              content_hash,
+                                              subaccount_id,
+                                              BITTREX_SECRET),
+                       expected,
+                       "{} {}",
+                       method,
+                       url);
+        }
+    }
+
+    #[test]
+    fn private_requests_should_be_signed() {
+        let (api, requests) = recording(bittrex_api, r#"[{"currencySymbol":"BTC","total":"14.21549076","available":"14.21549076","updatedAt":"2014-07-09T04:01:00.667Z"},{"currencySymbol":"BCH","total":"2.5","available":"2.0","updatedAt":"2017-08-01T12:00:00Z"}]"#);
+
+        let balances = api.balances().unwrap();
+        assert_eq!(balances[&Currency::BTC], decimal("14.21549076"));
+        assert_eq!(balances[&Currency::BCH], decimal("2.0"));
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        assert_eq!(request.method, Method::Get);
+        assert_eq!(request.url, "https://api.bittrex.com/v3/balances");
+        assert_eq!(request.body, "");
+        assert_eq!(header(request, "Api-Key"), Some("fixture_key"));
+        assert_eq!(header(request, "Api-Timestamp"), Some("1509099072123"));
+        assert_eq!(header(request, "Api-Content-Hash"), Some(EMPTY_HASH));
+        assert_eq!(header(request, "Api-Signature"),
+                   Some("5735eb18ddf19014dbf943764c56908728742c6de04c3172d600d41d96f3c239e39b5986f31ec43309dc8dc496a80fd92818470f1822ba8f9ad1df69cdbd6182"));
+        assert_eq!(header(request, "Content-Type"), None);
+    }
+
+    #[test]
+    fn orders_should_be_sent_as_json() {
+        let (api, requests) = recording(bittrex_api, r#"{"id":"e606d53c-8d70-11e3-94b5-425861b86ab6","marketSymbol":"LTC-BTC","direction":"SELL","type":"LIMIT","quantity":"0.50000000","limit":"0.01000000","timeInForce":"GOOD_TIL_CANCELLED","fillQuantity":"0.00000000","commission":"0.00000000","proceeds":"0.00000000","status":"OPEN","createdAt":"2014-07-09T03:55:48.77Z","updatedAt":"2014-07-09T03:55:48.77Z"}"#);
+
+        let order = api.add_order(OrderType::SellLimit,
+                                  Pair::LTC_BTC,
+                                  decimal("0.5"),
+                                  Some(decimal("0.01")))
+            .unwrap();
+        assert_eq!(order.identifier, vec!["e606d53c-8d70-11e3-94b5-425861b86ab6"]);
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        assert_eq!(request.method, Method::Post);
+        assert_eq!(request.url, "https://api.bittrex.com/v3/orders");
+        assert_eq!(request.body,
+                   r#"{"direction":"SELL","limit":"0.01","marketSymbol":"LTC-BTC","quantity":"0.5","timeInForce":"GOOD_TIL_CANCELLED","type":"LIMIT"}"#);
+        assert_eq!(header(request, "Content-Type"), Some("application/json"));
+        assert_eq!(header(request, "Api-Signature"),
+                   Some("3abe46052a78f2a01600d596dd345a89b760f17c805780b491b61daf4dec1d7eba5488e30f60e0a5db5a0d87de9dd26316cf396fa031c455be46faa75ceef17f"));
+    }
+
+    #[test]
+    fn market_orders_should_be_sent_as_market_orders() {
+        let (api, requests) = recording(bittrex_api, r#"{"id":"e606d53c-8d70-11e3-94b5-425861b86ab6","marketSymbol":"LTC-BTC","direction":"BUY","type":"MARKET","quantity":"2.00000000","timeInForce":"IMMEDIATE_OR_CANCEL","fillQuantity":"2.00000000","commission":"0.00005000","proceeds":"0.02000000","status":"CLOSED","createdAt":"2014-07-09T03:55:48.77Z","closedAt":"2014-07-09T03:55:48.77Z"}"#);
+
+        api.add_order(OrderType::BuyMarket, Pair::LTC_BTC, decimal("2"), None).unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].body,
+                   r#"{"direction":"BUY","marketSymbol":"LTC-BTC","quantity":"2","timeInForce":"IMMEDIATE_OR_CANCEL","type":"MARKET"}"#);
+    }
+
+    #[test]
+    fn orders_should_be_canceled_with_a_delete_request() {
+        let (api, requests) = recording(bittrex_api, r#"[{"id":"09aa5bb6-8232-41aa-9b78-a5a1093e0211","statusCode":"SUCCESS","result":{"id":"09aa5bb6-8232-41aa-9b78-a5a1093e0211","marketSymbol":"LTC-BTC","direction":"SELL","type":"LIMIT","quantity":"5.00000000","limit":"2.00000000","timeInForce":"GOOD_TIL_CANCELLED","fillQuantity":"0.00000000","commission":"0.00000000","proceeds":"0.00000000","status":"CLOSED","createdAt":"2014-07-09T03:55:48.77Z","closedAt":"2014-07-09T04:00:00Z"}},{"id":"0cb4c4e4-bdc7-4e13-8c13-430e587d2cc1","statusCode":"ORDER_NOT_OPEN"}]"#);
+
+        let canceled = api.typed().cancel_open_orders("LTC-BTC").unwrap();
+        assert_eq!(canceled[0].status_code, "SUCCESS");
+        assert_eq!(canceled[0].result.as_ref().unwrap().status, "CLOSED");
+        assert_eq!(canceled[1].result, None);
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        assert_eq!(request.method, Method::Delete);
+        assert_eq!(request.url, "https://api.bittrex.com/v3/orders/open?marketSymbol=LTC-BTC");
+        assert_eq!(header(request, "Api-Signature"),
+                   Some("a472c7eea8272ff655ee096a0e32b525a5ea3d472ebf4ea1db46cb9e0600328fd3c87bf46f48a7cfeb5905a224afd236feb96528de1e98d2998fafb85329be2d"));
+    }
+
+    #[test]
+    fn order_not_open_should_return_an_order_already_closed_error() {
+        let response = utils::deserialize_json(r#"{"code":"ORDER_NOT_OPEN"}"#).unwrap();
+        let res = utils::parse_result(&response, "cancel");
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::OrderAlreadyClosed.to_string());
+    }
+
+    #[test]
+    fn not_found_should_return_an_order_not_found_error() {
+        let response = utils::deserialize_json(r#"{"code":"NOT_FOUND"}"#).unwrap();
+        let res = utils::parse_result(&response, "order");
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::OrderNotFound.to_string());
+    }
+
+    #[test]
+    fn documented_errors_should_return_their_error_kind() {
+        let errors = vec![
+            ("APIKEY_NOT_PROVIDED", ErrorKind::BadCredentials),
+            ("APIKEY_INVALID", ErrorKind::BadCredentials),
+            ("APISIGN_NOT_PROVIDED", ErrorKind::BadCredentials),
+            ("INVALID_SIGNATURE", ErrorKind::BadCredentials),
+            ("INVALID_CONTENT_HASH", ErrorKind::BadCredentials),
+            ("UNAUTHORIZED", ErrorKind::BadCredentials),
+            ("NONCE_NOT_PROVIDED", ErrorKind::InvalidNonce),
+            ("INVALID_NONCE", ErrorKind::InvalidNonce),
+            ("INVALID_TIMESTAMP", ErrorKind::InvalidNonce),
+            ("INVALID_PERMISSION", ErrorKind::PermissionDenied),
+            ("FORBIDDEN", ErrorKind::PermissionDenied),
+            ("WHITELIST_VIOLATION_IP", ErrorKind::PermissionDenied),
+            ("WHITELIST_VIOLATION_WITHDRAWAL_ADDRESS", ErrorKind::PermissionDenied),
+            ("INSUFFICIENT_FUNDS", ErrorKind::InsufficientFunds),
+            ("MIN_TRADE_REQUIREMENT_NOT_MET", ErrorKind::InsufficientOrderSize),
+            ("DUST_TRADE_DISALLOWED_MIN_VALUE", ErrorKind::InsufficientOrderSize),
+            ("DUST_TRADE_DISALLOWED_MIN_VALUE_50K_SAT", ErrorKind::InsufficientOrderSize),
+            ("INVALID_MARKET", ErrorKind::PairUnsupported),
+            ("MARKET_DOES_NOT_EXIST", ErrorKind::PairUnsupported),
+            ("MARKET_OFFLINE", ErrorKind::MarketInactive),
+            ("MARKET_NOT_ONLINE", ErrorKind::MarketInactive),
+            ("CURRENCY_OFFLINE", ErrorKind::MarketInactive),
+            ("ORDER_NOT_OPEN", ErrorKind::OrderAlreadyClosed),
+            ("INVALID_ORDER", ErrorKind::OrderNotFound),
+            ("UUID_INVALID", ErrorKind::OrderNotFound),
+            ("ORDER_NOT_FOUND", ErrorKind::OrderNotFound),
+            ("NOT_FOUND", ErrorKind::OrderNotFound),
+            ("MARKET_NOT_PROVIDED", ErrorKind::InvalidArguments),
+            ("CURRENCY_NOT_PROVIDED", ErrorKind::InvalidArguments),
+            ("INVALID_CURRENCY", ErrorKind::InvalidArguments),
+            ("QUANTITY_NOT_PROVIDED", ErrorKind::InvalidArguments),
+            ("QUANTITY_INVALID", ErrorKind::InvalidArguments),
+            ("RATE_NOT_PROVIDED", ErrorKind::InvalidArguments),
+            ("RATE_INVALID", ErrorKind::InvalidArguments),
+            ("UUID_NOT_PROVIDED", ErrorKind::InvalidArguments),
+            ("ADDRESS_NOT_PROVIDED", ErrorKind::InvalidArguments),
+            ("INVALID_ORDER_TYPE", ErrorKind::InvalidArguments),
+            ("INVALID_DEPTH", ErrorKind::InvalidArguments),
+            ("INVALID_TYPE", ErrorKind::InvalidArguments),
+            ("BAD_REQUEST", ErrorKind::InvalidArguments),
+            ("THROTTLED", ErrorKind::RateLimitExceeded),
+            ("TOO_MANY_REQUESTS", ErrorKind::RateLimitExceeded),
+            ("ADDRESS_GENERATING", ErrorKind::ServiceUnavailable("ADDRESS_GENERATING".to_string())),
+            ("INTERNAL_ERROR", ErrorKind::ServiceUnavailable("INTERNAL_ERROR".to_string())),
+            ("NO_API_RESPONSE", ErrorKind::ServiceUnavailable("NO_API_RESPONSE".to_string())),
+            ("SERVICE_UNAVAILABLE", ErrorKind::ServiceUnavailable("SERVICE_UNAVAILABLE".to_string())),
+            ("SOMETHING_NEW", ErrorKind::ExchangeSpecificError("SOMETHING_NEW".to_string())),
+        ];
+
+        for (code, kind) in errors {
+            assert_eq!(format!("{:?}", utils::get_error_kind(code)),
+                       format!("{:?}", kind),
+                       "{}",
+                       code);
+            let body = format!(r#"{{"code":"{}","detail":"for the test"}}"#, code);
+            let response = utils::deserialize_json(&body).unwrap();
+            let err = utils::parse_result(&response, "place_order").unwrap_err();
+            assert_eq!(err.to_string(), kind.to_string(), "{}", code);
+            let exchange_error = err.exchange_error().unwrap();
+            assert_eq!(exchange_error.code.as_deref(), Some(code));
+            assert_eq!(exchange_error.message.as_deref(), Some("for the test"));
+        }
+    }
+
+    #[test]
+    fn parse_order_should_return_a_canceled_order() {
+        let order: Value = serde_json::from_str(r#"{"id":"0cb4c4e4-bdc7-4e13-8c13-430e587d2cc1",
+            "marketSymbol":"LTC-BTC","direction":"BUY","type":"LIMIT","quantity":"1000.00000000",
+            "limit":"0.00000002","timeInForce":"GOOD_TIL_CANCELLED",
+            "fillQuantity":"600.00000000","commission":"0.00000003","proceeds":"0.00001200",
+            "status":"CLOSED","createdAt":"2014-07-13T07:45:46.27Z",
+            "updatedAt":"2014-07-13T08:00:00Z","closedAt":"2014-07-13T08:00:00Z"}"#).unwrap();
+
+        let order = utils::parse_order(Pair::LTC_BTC, &order).unwrap();
+
+        assert_eq!(order.identifier, "0cb4c4e4-bdc7-4e13-8c13-430e587d2cc1");
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(order.order_type, OrderType::BuyLimit);
+        assert_eq!(order.status, OrderStatus::Canceled);
+        assert_eq!(order.quantity, BigDecimal::from_str("1000").unwrap());
+        assert_eq!(order.filled_quantity, BigDecimal::from_str("600").unwrap());
+        assert_eq!(order.average_price, Some(BigDecimal::from_str("0.00000002").unwrap()));
+        assert_eq!(order.opened_at, Some(1405237546270));
+        assert_eq!(order.closed_at, Some(1405238400000));
+    }
+
+    #[test]
+    fn parse_order_should_return_an_open_order_from_open_orders() {
+        let order: Value = serde_json::from_str(r#"{"id":"09aa5bb6-8232-41aa-9b78-a5a1093e0211",
+            "marketSymbol":"LTC-BTC","direction":"SELL","type":"LIMIT","quantity":"5.00000000",
+            "limit":"2.00000000","timeInForce":"GOOD_TIL_CANCELLED",
+            "fillQuantity":"0.00000000","commission":"0.00000000","proceeds":"0.00000000",
+            "status":"OPEN","createdAt":"2014-07-09T03:55:48.77Z",
+            "updatedAt":"2014-07-09T03:55:48.77Z"}"#).unwrap();
+
+        let order = utils::parse_order(Pair::LTC_BTC, &order).unwrap();
+
+        assert_eq!(order.side, OrderSide::Sell);
+        assert_eq!(order.status, OrderStatus::Open);
+        assert_eq!(order.price, Some(BigDecimal::from_str("2").unwrap()));
+        assert_eq!(order.average_price, None);
+        assert_eq!(order.closed_at, None);
+    }
+
+    #[test]
+    fn parse_trades_should_return_the_trades_from_the_oldest() {
+        let trades: Value = serde_json::from_str(r#"[{"id":"8f2e7cbd-7f6a-4d5b-9b0e-7b2b1b1f0b1c",
+            "executedAt":"2014-07-09T03:21:20.08Z","quantity":"0.30802438","rate":"0.01263400",
+            "takerSide":"SELL"},{"id":"2b5c7a1e-3d4f-4e8a-9c0b-1a2b3c4d5e6f",
+            "executedAt":"2014-07-09T03:21:19.5Z","quantity":"0.31820814","rate":"0.01262800",
+            "takerSide":"BUY"}]"#).unwrap();
+
+        let trades = utils::parse_trades(trades.as_array().unwrap()).unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].identifier, "2b5c7a1e-3d4f-4e8a-9c0b-1a2b3c4d5e6f");
+        assert_eq!(trades[0].timestamp, 1404876079500);
+        assert_eq!(trades[0].side, OrderSide::Buy);
+        assert_eq!(trades[1].identifier, "8f2e7cbd-7f6a-4d5b-9b0e-7b2b1b1f0b1c");
+        assert_eq!(trades[1].price, BigDecimal::from_str("0.012634").unwrap());
+        assert_eq!(trades[1].volume, BigDecimal::from_str("0.30802438").unwrap());
+        assert_eq!(trades[1].side, OrderSide::Sell);
+    }
+
+    #[test]
+    fn trades_should_be_filtered_with_the_timestamp_cursor() {
+        let api = bittrex_api(r#"[{"id":"8f2e7cbd-7f6a-4d5b-9b0e-7b2b1b1f0b1c","executedAt":"2014-07-09T03:21:20.08Z","quantity":"0.30802438","rate":"0.01263400","takerSide":"SELL"},{"id":"2b5c7a1e-3d4f-4e8a-9c0b-1a2b3c4d5e6f","executedAt":"2014-07-09T03:21:19.5Z","quantity":"0.31820814","rate":"0.01262800","takerSide":"BUY"}]"#);
+
+        let history = api.trades(Pair::LTC_BTC, None).unwrap();
+        assert_eq!(history.trades.len(), 2);
+        assert_eq!(history.cursor, Some("1404876080080".to_string()));
+
+        let history = api.trades(Pair::LTC_BTC, Some("1404876079500".to_string())).unwrap();
+        assert_eq!(history.trades.len(), 1);
+        assert_eq!(history.trades[0].identifier, "8f2e7cbd-7f6a-4d5b-9b0e-7b2b1b1f0b1c");
+
+        let history = api.trades(Pair::LTC_BTC, Some("1404876080080".to_string())).unwrap();
+        assert!(history.trades.is_empty());
+        assert_eq!(history.cursor, Some("1404876080080".to_string()));
+
+        assert_eq!(api.trades(Pair::LTC_BTC, Some("not a timestamp".to_string()))
+                       .unwrap_err()
+                       .to_string(),
+                   ErrorKind::InvalidArguments.to_string());
+    }
+
+    #[test]
+    fn candles_should_be_returned_from_the_oldest() {
+        let (api, requests) = recording(bittrex_api, r#"[{"startsAt":"2014-07-09T02:00:00Z","open":"0.01250000","high":"0.01290000","low":"0.01240000","close":"0.01263400","volume":"120.5","quoteVolume":"1.5"},{"startsAt":"2014-07-09T03:00:00Z","open":"0.01263400","high":"0.01350000","low":"0.01200000","close":"0.01349998","volume":"383.39761925","quoteVolume":"4.70398702"}]"#);
+
+        let candles = api.candles(Pair::LTC_BTC, Interval::OneHour, None).unwrap();
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].timestamp, 1404871200000);
+        assert_eq!(candles[1].high, decimal("0.0135"));
+        assert_eq!(candles[1].volume, decimal("383.39761925"));
+        assert_eq!(requests.lock().unwrap()[0].url,
+                   "https://api.bittrex.com/v3/markets/LTC-BTC/candles/HOUR_1/recent");
+
+        let candles = api.candles(Pair::LTC_BTC, Interval::OneHour, Some(1404871200000)).unwrap();
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].timestamp, 1404874800000);
+    }
+
+    #[test]
+    fn unsupported_candle_intervals_should_return_an_unsupported_error() {
+        let api = bittrex_api("[]");
+
+        let res = api.candles(Pair::LTC_BTC, Interval::FifteenMinutes, None);
+
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::Unsupported("FifteenMinutes candles".to_string()).to_string());
+    }
+
+    #[test]
+    fn parse_markets_should_return_the_trading_rules() {
+        let markets: Value = serde_json::from_str(r#"[{"symbol":"LTC-BTC",
+            "baseCurrencySymbol":"LTC","quoteCurrencySymbol":"BTC","minTradeSize":"0.01000000",
+            "precision":8,"status":"ONLINE","createdAt":"2014-02-13T00:00:00Z"},
+            {"symbol":"DOGE-BTC","baseCurrencySymbol":"DOGE","quoteCurrencySymbol":"BTC",
+            "minTradeSize":"100.00000000","precision":8,"status":"OFFLINE",
+            "createdAt":"2014-02-13T00:00:00Z"}]"#)
+            .unwrap();
+
+        let markets = utils::parse_markets(markets.as_array().unwrap()).unwrap();
+
+        assert_eq!(markets.len(), 2);
+        let ltc_btc = &markets[&Pair::LTC_BTC];
+        assert_eq!(ltc_btc.min_order_size, Some(BigDecimal::from_str("0.01").unwrap()));
+        assert_eq!(ltc_btc.tick_size, Some(BigDecimal::from_str("0.00000001").unwrap()));
+        assert!(ltc_btc.active);
+        assert!(!markets[&Pair::DOGE_BTC].active);
+    }
+
+    #[test]
+    fn pairs_should_use_the_v3_market_symbols() {
+        assert_eq!(utils::get_pair_string(&Pair::LTC_BTC), Some("LTC-BTC"));
+        assert_eq!(utils::get_pair_enum("ETH-USDT"), Some(Pair::ETH_USDT));
+        assert_eq!(utils::get_pair_enum("BCH-BTC"), Some(Pair::BCH_BTC));
+        assert_eq!(utils::get_market_symbol(&Pair::BCH_BTC).unwrap(), "BCH-BTC");
+    }
+
+    #[test]
+    fn parse_markets_should_register_the_markets_listed_by_bittrex() {
+        let markets: Value = serde_json::from_str(r#"[{"symbol":"DOT-BTC","baseCurrencySymbol":"DOT","quoteCurrencySymbol":"BTC","minTradeSize":"0.5","precision":8,"status":"ONLINE","createdAt":"2020-08-20T00:00:00Z"}]"#).unwrap();
+        let dot_btc = Pair::new(Currency::new("DOT"), Currency::BTC);
+
+        assert_eq!(utils::get_pair_string(&dot_btc), None);
+        let markets = utils::parse_markets(markets.as_array().unwrap()).unwrap();
+
+        assert!(markets[&dot_btc].active);
+        assert_eq!(utils::get_pair_string(&dot_btc), Some("DOT-BTC"));
+        assert_eq!(utils::get_pair_enum("DOT-BTC"), Some(dot_btc));
+    }
+
+    #[test]
+    fn pairs_not_listed_by_bittrex_should_not_be_supported() {
+        let (api, requests) = recording(bittrex_api, r#"[{"symbol":"LTC-BTC","baseCurrencySymbol":"LTC","quoteCurrencySymbol":"BTC","minTradeSize":"0.01","precision":8,"status":"ONLINE","createdAt":"2014-02-13T00:00:00Z"}]"#);
+        let unlisted = Pair::new(Currency::new("UNLISTED"), Currency::BTC);
+
+        assert_eq!(utils::get_pair_enum("UNLISTED-BTC"), None);
+        assert_eq!(api.ticker(unlisted).unwrap_err().to_string(),
+                   ErrorKind::PairUnsupported.to_string());
+        // Only the markets were requested
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert_eq!(utils::get_pair_string(&unlisted), None);
+    }
+
+    #[test]
+    fn ticker_should_use_the_ticker_and_the_summary() {
+        let (api, requests) = recording(bittrex_api, r#"{"symbol":"LTC-BTC","lastTradeRate":"0.01349998","bidRate":"0.01271001","askRate":"0.01291100","high":"0.01350000","low":"0.01200000","volume":"3833.97619253","quoteVolume":"47.03987026","percentChange":"9.80","updatedAt":"2014-07-09T07:22:16.72Z"}"#);
+
+        let ticker = api.ticker(Pair::LTC_BTC).unwrap();
+        assert_eq!(ticker.last_trade_price, decimal("0.01349998"));
+        assert_eq!(ticker.lowest_ask, decimal("0.012911"));
+        assert_eq!(ticker.highest_bid, decimal("0.01271001"));
+        assert_eq!(ticker.volume, Some(decimal("3833.97619253")));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].url, "https://api.bittrex.com/v3/markets/LTC-BTC/ticker");
+        assert_eq!(requests[1].url, "https://api.bittrex.com/v3/markets/LTC-BTC/summary");
+        assert_eq!(header(&requests[0], "Api-Key"), None);
+    }
+
+    #[test]
+    fn orderbook_should_return_the_asks_and_the_bids() {
+        let (api, requests) = recording(bittrex_api, r#"{"bid":[{"quantity":"12.37000000","rate":"0.02525000"}],"ask":[{"quantity":"32.55412402","rate":"0.02540000"},{"quantity":"60.00000000","rate":"0.02550000"}]}"#);
+
+        let orderbook = api.orderbook(Pair::LTC_BTC).unwrap();
+        assert_eq!(orderbook.asks, vec![(decimal("0.0254"), decimal("32.55412402")),
+                                        (decimal("0.0255"), decimal("60"))]);
+        assert_eq!(orderbook.bids, vec![(decimal("0.02525"), decimal("12.37"))]);
+        assert_eq!(requests.lock().unwrap()[0].url,
+                   "https://api.bittrex.com/v3/markets/LTC-BTC/orderbook?depth=500");
+    }
+
+    #[test]
+    fn typed_market_summary_should_return_decimals() {
+        let api = bittrex_api(r#"{"symbol":"LTC-BTC","high":"0.01350000","low":"0.01200000","volume":"3833.97619253","quoteVolume":"47.03987026","percentChange":"9.80","updatedAt":"2014-07-09T07:22:16.72Z"}"#);
+
+        let summary = api.typed().get_market_summary("LTC-BTC").unwrap();
+        assert_eq!(summary.symbol, "LTC-BTC");
+        assert_eq!(summary.high, Some(decimal("0.0135")));
+        assert_eq!(summary.volume, Some(decimal("3833.97619253")));
+        assert_eq!(summary.percent_change, Some(decimal("9.8")));
+        assert_eq!(summary.updated_at.timestamp_millis(), 1404890536720);
+
+        let raw = api.get_market_summary("LTC-BTC").unwrap();
+        assert_eq!(raw["symbol"], Value::String("LTC-BTC".to_string()));
+    }
+
+    #[test]
+    fn typed_order_book_should_return_both_sides() {
+        let api = bittrex_api(r#"{"bid":[{"quantity":"12.37","rate":"0.02525"}],"ask":[{"quantity":"32.55412402","rate":"0.0254"}]}"#);
+
+        let book = api.typed().get_order_book("LTC-BTC", 25).unwrap();
+        assert_eq!(book.bid[0].quantity, decimal("12.37"));
+        assert_eq!(book.ask[0].rate, decimal("0.0254"));
+    }
+
+    #[test]
+    fn typed_open_orders_and_balances_should_return_decimals() {
+        let api = bittrex_api(r#"[{"id":"09aa5bb6-8232-41aa-9b78-a5a1093e0211","marketSymbol":"LTC-BTC","direction":"SELL","type":"LIMIT","quantity":"5.00000000","limit":"2.00000000","timeInForce":"GOOD_TIL_CANCELLED","fillQuantity":"0.00000000","commission":"0.00000000","proceeds":"0.00000000","status":"OPEN","createdAt":"2014-07-09T03:55:48.77Z","updatedAt":"2014-07-09T03:55:48.77Z"}]"#);
+        let orders = api.typed().get_open_orders("LTC-BTC").unwrap();
+        assert_eq!(orders[0].id, "09aa5bb6-8232-41aa-9b78-a5a1093e0211");
+        assert_eq!(orders[0].order_type, "LIMIT");
+        assert_eq!(orders[0].limit, Some(decimal("2")));
+        assert_eq!(orders[0].ceiling, None);
+        assert_eq!(orders[0].closed_at, None);
+
+        let api = bittrex_api(r#"{"currencySymbol":"BTC","total":"4.21549076","available":"4.00000000","updatedAt":"2014-07-09T04:01:00.667Z"}"#);
+        let balance = api.typed().get_balance("BTC").unwrap();
+        assert_eq!(balance.total, decimal("4.21549076"));
+        assert_eq!(balance.available, decimal("4"));
+        assert_eq!(balance.updated_at, Some(Utc.with_ymd_and_hms(2014, 7, 9, 4, 1, 0).unwrap()
+                                                + chrono::Duration::milliseconds(667)));
+    }
+
+    #[test]
+    fn typed_deposit_address_should_wait_for_the_provisioning() {
+        let (api, requests) = recording(bittrex_api, r#"{"status":"REQUESTED","currencySymbol":"VTC"}"#);
+
+        let address = api.typed().provision_deposit_address("VTC").unwrap();
+        assert_eq!(address.status, "REQUESTED");
+        assert_eq!(address.crypto_address, None);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].method, Method::Post);
+        assert_eq!(requests[0].url, "https://api.bittrex.com/v3/addresses");
+        assert_eq!(requests[0].body, r#"{"currencySymbol":"VTC"}"#);
+    }
+
+    #[test]
+    fn typed_errors_should_use_the_error_code() {
+        let api = bittrex_api(r#"{"code":"NOT_FOUND"}"#);
+        assert_eq!(api.typed().cancel("e606d53c").unwrap_err().to_string(),
+                   ErrorKind::OrderNotFound.to_string());
+
+        let api = bittrex_api(r#"{"symbol":"LTC-BTC","bidRate":"none"}"#);
+        assert_eq!(api.typed().get_ticker("LTC-BTC").unwrap_err().to_string(),
+                   ErrorKind::BadParse.to_string());
     }
 }
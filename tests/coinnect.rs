@@ -19,13 +19,18 @@ mod coinnect_tests {
     #[test]
     fn can_create_new_api_connection_to_bitstamp() {
         let creds = BitstampCreds::new("test", "bs_api_key", "bs_api_secret", "bs_cust_id");
-        let api: Box<ExchangeApi> = Coinnect::new(Exchange::Bitstamp, creds).unwrap();
+        let api: Box<dyn ExchangeApi> = Coinnect::new(Exchange::Bitstamp, creds).unwrap();
 
         assert_eq!(format!("{:?}", api),
-                   "BitstampApi { last_request: 0, api_key: \"bs_api_key\", api_secret: \
-        \"bs_api_secret\", customer_id: \"bs_cust_id\", http_client: Client { \
-                    redirect_policy: FollowAll, read_timeout: None, write_timeout: None, proxy: \
-                    None }, burst: false }");
+                   "BitstampApi(AsyncBitstampApi { api_key: \"bs_api_key\", \
+                    api_secret: \"bs_api_secret\", customer_id: \"bs_cust_id\", auth_mode: Legacy, http_client: \
+                    Client { redirect_policy: FollowAll, read_timeout: None, write_timeout: None, \
+                    proxy: None }, config: ClientConfig { rate_limiter: RateLimiter { \
+                    exchange: Bitstamp, policy: Throttle }, retry_policy: RetryPolicy { \
+                    max_attempts: 1, initial_backoff: 500ms, max_backoff: 10s, multiplier: 2.0, \
+                    jitter: true }, nonce_provider: MemoryNonceProvider { \
+                    resolution: Milliseconds, last: 0 } }, \
+                    markets: RwLock { data: None, poisoned: false, .. } })");
     }
     #[test]
     fn can_create_new_api_connection_to_kraken() {
@@ -41,7 +46,7 @@ mod coinnect_tests {
     #[test]
     fn coinnect_can_get_a_ticker_from_bitstamp() {
         let creds = BitstampCreds::new("test", "bs_api_key", "bs_api_secret", "bs_cust_id");
-        let mut api = Coinnect::new(Exchange::Bitstamp, creds).unwrap();
+        let api = Coinnect::new(Exchange::Bitstamp, creds).unwrap();
         let ticker = api.ticker(Pair::BTC_USD);
 
         assert_ne!(ticker.unwrap().last_trade_price, BigDecimal::from_str("0.0").unwrap());
@@ -50,7 +55,7 @@ mod coinnect_tests {
     #[test]
     fn coinnect_can_get_a_ticker_from_kraken() {
         let creds = KrakenCreds::new("test", "api_key", "api_secret");
-        let mut api = Coinnect::new(Exchange::Kraken, creds).unwrap();
+        let api = Coinnect::new(Exchange::Kraken, creds).unwrap();
         let ticker = api.ticker(Pair::BTC_EUR);
 
         assert_ne!(ticker.unwrap().last_trade_price, BigDecimal::from_str("0.0").unwrap());
@@ -59,7 +64,7 @@ mod coinnect_tests {
     #[test]
     fn coinnect_can_get_a_ticker_from_poloniex() {
         let creds = PoloniexCreds::new("test", "api_key", "api_secret");
-        let mut api = Coinnect::new(Exchange::Poloniex, creds).unwrap();
+        let api = Coinnect::new(Exchange::Poloniex, creds).unwrap();
         let ticker = api.ticker(Pair::ETH_BTC);
 
         assert_ne!(ticker.unwrap().last_trade_price, BigDecimal::from_str("0.0").unwrap());
@@ -68,7 +73,7 @@ mod coinnect_tests {
     #[test]
     fn coinnect_can_get_a_ticker_from_bittrex() {
         let creds = BittrexCreds::new("test", "api_key", "api_secret");
-        let mut api = Coinnect::new(Exchange::Bittrex, creds).unwrap();
+        let api = Coinnect::new(Exchange::Bittrex, creds).unwrap();
         let ticker = api.ticker(Pair::ETH_BTC);
 
         assert_ne!(ticker.unwrap().last_trade_price, BigDecimal::from_str("0.0").unwrap());
@@ -77,7 +82,7 @@ mod coinnect_tests {
     #[test]
     fn coinnect_can_get_an_orderbook_from_bitstamp() {
         let creds = BitstampCreds::new("test", "api_key", "api_secret", "customer_id");
-        let mut api = Coinnect::new(Exchange::Bitstamp, creds).unwrap();
+        let api = Coinnect::new(Exchange::Bitstamp, creds).unwrap();
         let orderbook = api.orderbook(Pair::BTC_EUR);
 
         assert_ne!(orderbook.unwrap().avg_price().unwrap(), BigDecimal::from_str("0.0").unwrap())
@@ -86,7 +91,7 @@ mod coinnect_tests {
     #[test]
     fn coinnect_can_get_an_orderbook_from_kraken() {
         let creds = KrakenCreds::new("test", "api_key", "api_secret");
-        let mut api = Coinnect::new(Exchange::Kraken, creds).unwrap();
+        let api = Coinnect::new(Exchange::Kraken, creds).unwrap();
         let orderbook = api.orderbook(Pair::BTC_EUR);
 
         assert_ne!(orderbook.unwrap().avg_price().unwrap(), BigDecimal::from_str("0.0").unwrap())
@@ -95,7 +100,7 @@ mod coinnect_tests {
     #[test]
     fn coinnect_can_get_an_orderbook_from_poloniex() {
         let creds = PoloniexCreds::new("test", "api_key", "api_secret");
-        let mut api = Coinnect::new(Exchange::Poloniex, creds).unwrap();
+        let api = Coinnect::new(Exchange::Poloniex, creds).unwrap();
         let orderbook = api.orderbook(Pair::ETH_BTC);
 
         assert_ne!(orderbook.unwrap().avg_price().unwrap(), BigDecimal::from_str("0.0").unwrap())
@@ -104,7 +109,7 @@ mod coinnect_tests {
     #[test]
     fn coinnect_can_get_an_orderbook_from_bittrex() {
         let creds = BittrexCreds::new("test", "api_key", "api_secret");
-        let mut api = Coinnect::new(Exchange::Bittrex, creds).unwrap();
+        let api = Coinnect::new(Exchange::Bittrex, creds).unwrap();
         let orderbook = api.orderbook(Pair::ETH_BTC);
 
         assert_ne!(orderbook.unwrap().avg_price().unwrap(), BigDecimal::from_str("0.0").unwrap())
@@ -114,29 +119,29 @@ mod coinnect_tests {
     #[cfg_attr(not(feature = "bitstamp_private_tests"), ignore)]
     fn coinnect_can_get_the_balances_from_bitstamp() {
         let path = PathBuf::from("./keys_real.json");
-        let mut api = Coinnect::new_from_file(Exchange::Bitstamp, "account_bitstamp", path)
+        let api = Coinnect::new_from_file(Exchange::Bitstamp, "account_bitstamp", path)
             .unwrap();
         let balances: Balances = api.balances().unwrap();
 
-        assert!(balances.len() > 0)
+        assert!(!balances.is_empty())
     }
 
     #[test]
     #[cfg_attr(not(feature = "poloniex_private_tests"), ignore)]
     fn coinnect_can_get_the_balances_from_poloniex() {
         let path = PathBuf::from("./keys_real.json");
-        let mut api = Coinnect::new_from_file(Exchange::Poloniex, "account_poloniex", path)
+        let api = Coinnect::new_from_file(Exchange::Poloniex, "account_poloniex", path)
             .unwrap();
         let balances: Balances = api.balances().unwrap();
 
-        assert!(balances.len() > 0)
+        assert!(!balances.is_empty())
     }
 
     #[test]
     #[cfg_attr(not(feature = "bitstamp_private_tests"), ignore)]
     fn coinnect_can_get_at_least_a_positive_balance_from_bitstamp() {
         let path = PathBuf::from("./keys_real.json");
-        let mut api = Coinnect::new_from_file(Exchange::Bitstamp, "account_bitstamp", path)
+        let api = Coinnect::new_from_file(Exchange::Bitstamp, "account_bitstamp", path)
             .unwrap();
         let balances: Balances = api.balances().unwrap();
 
@@ -150,11 +155,11 @@ mod coinnect_tests {
     #[cfg_attr(not(feature = "kraken_private_tests"), ignore)]
     fn coinnect_can_get_the_balances_from_kraken() {
         let path = PathBuf::from("./keys_real.json");
-        let mut api = Coinnect::new_from_file(Exchange::Kraken, "account_kraken", path)
+        let api = Coinnect::new_from_file(Exchange::Kraken, "account_kraken", path)
             .unwrap();
         let balances: Balances = api.balances().unwrap();
 
-        assert!(balances.len() > 0);
+        assert!(!balances.is_empty());
         assert!(balances.get(&Currency::BTC).unwrap() >= &BigDecimal::from_str("0.0").unwrap())
     }
 
@@ -162,11 +167,11 @@ mod coinnect_tests {
     #[cfg_attr(not(feature = "poloniex_private_tests"), ignore)]
     fn coinnect_can_get_at_least_a_positive_balance_from_poloniex() {
         let path = PathBuf::from("./keys_real.json");
-        let mut api = Coinnect::new_from_file(Exchange::Poloniex, "account_poloniex", path)
+        let api = Coinnect::new_from_file(Exchange::Poloniex, "account_poloniex", path)
             .unwrap();
         let balances: Balances = api.balances().unwrap();
         let mut is_positive = false;
-        for (_, balance) in &balances {
+        for balance in balances.values() {
             if balance > &BigDecimal::from_str("0.0").unwrap() {
                 is_positive = true;
                 break;
@@ -179,11 +184,11 @@ mod coinnect_tests {
     #[cfg_attr(not(feature = "bittrex_private_tests"), ignore)]
     fn coinnect_can_get_at_least_a_positive_balance_from_bittrex() {
         let path = PathBuf::from("./keys_real.json");
-        let mut api = Coinnect::new_from_file(Exchange::Bittrex, "account_bittrex", path)
+        let api = Coinnect::new_from_file(Exchange::Bittrex, "account_bittrex", path)
             .unwrap();
         let balances: Balances = api.balances().unwrap();
         let mut is_positive = false;
-        for (_, balance) in &balances {
+        for balance in balances.values() {
             if balance >= &BigDecimal::from_str("0.0").unwrap() {
                 is_positive = true;
                 break;
@@ -197,7 +202,7 @@ mod coinnect_tests {
     fn coinnect_can_add_order_from_kraken() {
         let path = PathBuf::from("./keys_real.json");
         let creds = KrakenCreds::new_from_file("account_kraken", path).unwrap();
-        let mut api = Coinnect::new(Exchange::Kraken, creds).unwrap();
+        let api = Coinnect::new(Exchange::Kraken, creds).unwrap();
         // following request should return an error since Kraken minimum order size is BigDecimal::from_str("0.01")?
         let orderinfo = api.add_order(OrderType::BuyLimit,
                                       Pair::BTC_EUR,
@@ -213,7 +218,7 @@ mod coinnect_tests {
     fn coinnect_can_add_order_from_poloniex() {
         let path = PathBuf::from("./keys_real.json");
         let creds = PoloniexCreds::new_from_file("account_poloniex", path).unwrap();
-        let mut api = Coinnect::new(Exchange::Poloniex, creds).unwrap();
+        let api = Coinnect::new(Exchange::Poloniex, creds).unwrap();
         // following request should return an error
         let orderinfo = api.add_order(OrderType::BuyLimit,
                                       Pair::ETH_BTC,
@@ -229,7 +234,7 @@ mod coinnect_tests {
     fn coinnect_can_add_order_from_bitstamp() {
         let path = PathBuf::from("./keys_real.json");
         let creds = BitstampCreds::new_from_file("account_bitstamp", path).unwrap();
-        let mut api = Coinnect::new(Exchange::Bitstamp, creds).unwrap();
+        let api = Coinnect::new(Exchange::Bitstamp, creds).unwrap();
         // following request should return an error
         let orderinfo = api.add_order(OrderType::BuyLimit,
                                       Pair::EUR_USD,
@@ -245,7 +250,7 @@ mod coinnect_tests {
     fn coinnect_can_add_order_from_bittrex() {
         let path = PathBuf::from("./keys_real.json");
         let creds = BittrexCreds::new_from_file("account_bittrex", path).unwrap();
-        let mut api = Coinnect::new(Exchange::Bittrex, creds).unwrap();
+        let api = Coinnect::new(Exchange::Bittrex, creds).unwrap();
         // following request should return an error
         let orderinfo = api.add_order(OrderType::BuyLimit,
                                       Pair::ETH_BTC,
@@ -0,0 +1,236 @@
+//! The helpers shared by the integration tests: a transport answering canned responses instead of
+//! the exchanges and the clients using it.
+
+// Each test crate uses a part of the helpers only
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use bigdecimal::BigDecimal;
+
+use coinnect::bitstamp::{utils as bitstamp_utils, BitstampApi, BitstampCreds};
+use coinnect::bittrex::{BittrexApi, BittrexCreds};
+use coinnect::error::*;
+use coinnect::gdax::{GdaxApi, GdaxCreds};
+use coinnect::kraken::{KrakenApi, KrakenCreds};
+use coinnect::nonce::NonceProvider;
+use coinnect::poloniex::{PoloniexApi, PoloniexCreds};
+use coinnect::rate_limit::Policy;
+use coinnect::transport::{AsyncHttpTransport, HttpRequest, HttpResponse, HttpTransport,
+                          ResponseFuture};
+
+pub const API_KEY: &str = "fixture_key";
+pub const BITTREX_SECRET: &str = "coinnect-bittrex-secret";
+pub const GDAX_SECRET: &str = "Y29pbm5lY3QtZ2RheC1zaWduaW5nLXRlc3Qtc2VjcmV0LTAxMjM0NTY3ODlhYmNkZWY=";
+pub const BITSTAMP_SECRET: &str = "fixture_secret";
+
+/// The nonce returned by `FixedNonceProvider`.
+pub const NONCE: i64 = 1509099072123;
+
+/// The requests sent to a `FixtureTransport`, in order.
+pub type Requests = Arc<Mutex<Vec<HttpRequest>>>;
+
+#[derive(Debug)]
+enum Responses {
+    /// Return the same response to every request.
+    Always(HttpResponse),
+    /// Return the queued responses in order, or fail as if the exchange could not be reached when
+    /// the queued response is None or when the queue is empty.
+    Sequence(Mutex<VecDeque<Option<HttpResponse>>>),
+}
+
+/// Answer the requests with canned responses and record the requests sent.
+#[derive(Debug)]
+pub struct FixtureTransport {
+    responses: Responses,
+    secret: Option<&'static str>,
+    delay: Option<(&'static str, Duration)>,
+    requests: Requests,
+}
+
+impl FixtureTransport {
+    /// Return the body with the status 200 to every request.
+    pub fn new(body: &str) -> FixtureTransport {
+        FixtureTransport::with_status(200, Vec::new(), body)
+    }
+
+    /// Return the same response to every request.
+    pub fn with_status(status: u16, headers: Vec<(&str, &str)>, body: &str) -> FixtureTransport {
+        FixtureTransport::with_responses(Responses::Always(response(status, headers, body)))
+    }
+
+    /// Return each body once with the status 200, or fail as if the exchange could not be
+    /// reached when the body is None.
+    pub fn sequence(bodies: Vec<Option<&str>>) -> FixtureTransport {
+        let responses = bodies
+            .into_iter()
+            .map(|body| body.map(|body| response(200, Vec::new(), body)))
+            .collect();
+        FixtureTransport::with_responses(Responses::Sequence(Mutex::new(responses)))
+    }
+
+    fn with_responses(responses: Responses) -> FixtureTransport {
+        FixtureTransport {
+            responses,
+            secret: None,
+            delay: None,
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Sign the responses with the secret like Bitstamp signs the responses to the v2
+    /// authenticated requests.
+    pub fn signed_with(mut self, secret: &'static str) -> FixtureTransport {
+        self.secret = Some(secret);
+        self
+    }
+
+    /// Wait before answering the requests whose URL contains `url`.
+    pub fn slow_on(mut self, url: &'static str, delay: Duration) -> FixtureTransport {
+        self.delay = Some((url, delay));
+        self
+    }
+
+    /// Return a handle on the requests sent, usable once the transport is moved into a client.
+    pub fn requests(&self) -> Requests {
+        self.requests.clone()
+    }
+}
+
+impl<'a> From<&'a str> for FixtureTransport {
+    fn from(body: &'a str) -> FixtureTransport {
+        FixtureTransport::new(body)
+    }
+}
+
+impl HttpTransport for FixtureTransport {
+    fn send(&self, request: HttpRequest) -> Result<HttpResponse> {
+        if let Some((url, delay)) = self.delay {
+            if request.url.contains(url) {
+                thread::sleep(delay);
+            }
+        }
+
+        let mut response = match self.responses {
+            Responses::Always(ref response) => response.clone(),
+            Responses::Sequence(ref responses) => {
+                match responses.lock().unwrap().pop_front() {
+                    Some(Some(response)) => response,
+                    _ => {
+                        self.requests.lock().unwrap().push(request);
+                        return Err(ErrorKind::ServiceUnavailable("timeout".to_string()).into());
+                    }
+                }
+            }
+        };
+        if let Some(secret) = self.secret {
+            let message = format!("{}{}application/json{}",
+                                  header(&request, "X-Auth-Nonce").unwrap_or(""),
+                                  header(&request, "X-Auth-Timestamp").unwrap_or(""),
+                                  response.body);
+            response.headers.push(("X-Server-Auth-Signature".to_string(),
+                                   bitstamp_utils::build_v2_signature(&message, secret)));
+        }
+        self.requests.lock().unwrap().push(request);
+        Ok(response)
+    }
+}
+
+impl AsyncHttpTransport for FixtureTransport {
+    fn send(&self, request: HttpRequest) -> ResponseFuture<'_> {
+        Box::pin(async move { HttpTransport::send(self, request) })
+    }
+}
+
+fn response(status: u16, headers: Vec<(&str, &str)>, body: &str) -> HttpResponse {
+    let mut headers: Vec<(String, String)> = headers
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+    HttpResponse {
+        status,
+        headers,
+        body: body.to_string(),
+    }
+}
+
+/// Always return the same nonce, so that the signatures are known in advance.
+#[derive(Debug)]
+pub struct FixedNonceProvider;
+
+impl NonceProvider for FixedNonceProvider {
+    fn next_nonce(&self) -> Result<i64> {
+        Ok(NONCE)
+    }
+}
+
+pub fn kraken_api<T: Into<FixtureTransport>>(transport: T) -> KrakenApi {
+    let creds = KrakenCreds::new("test", API_KEY, "c2VjcmV0");
+    let mut api = KrakenApi::new_with_transport(creds, Box::new(transport.into())).unwrap();
+    api.config().rate_limiter().set_policy(Policy::Burst);
+    api.config_mut().set_nonce_provider(Arc::new(FixedNonceProvider));
+    api
+}
+
+pub fn poloniex_api<T: Into<FixtureTransport>>(transport: T) -> PoloniexApi {
+    let creds = PoloniexCreds::new("test", API_KEY, "secret");
+    let mut api = PoloniexApi::new_with_transport(creds, Box::new(transport.into())).unwrap();
+    api.config().rate_limiter().set_policy(Policy::Burst);
+    api.config_mut().set_nonce_provider(Arc::new(FixedNonceProvider));
+    api
+}
+
+pub fn bittrex_api<T: Into<FixtureTransport>>(transport: T) -> BittrexApi {
+    let creds = BittrexCreds::new("test", API_KEY, BITTREX_SECRET);
+    let mut api = BittrexApi::new_with_transport(creds, Box::new(transport.into())).unwrap();
+    api.config().rate_limiter().set_policy(Policy::Burst);
+    api.config_mut().set_nonce_provider(Arc::new(FixedNonceProvider));
+    api
+}
+
+pub fn gdax_api<T: Into<FixtureTransport>>(transport: T) -> GdaxApi {
+    let creds = GdaxCreds::new("test", API_KEY, GDAX_SECRET, "fixture_passphrase");
+    let mut api = GdaxApi::new_with_transport(creds, Box::new(transport.into())).unwrap();
+    api.config().rate_limiter().set_policy(Policy::Burst);
+    api.config_mut().set_nonce_provider(Arc::new(FixedNonceProvider));
+    api
+}
+
+pub fn bitstamp_api<T: Into<FixtureTransport>>(transport: T) -> BitstampApi {
+    let creds = BitstampCreds::new("test", API_KEY, BITSTAMP_SECRET, "123456");
+    let mut api = BitstampApi::new_with_transport(creds, Box::new(transport.into())).unwrap();
+    api.config().rate_limiter().set_policy(Policy::Burst);
+    api.config_mut().set_nonce_provider(Arc::new(FixedNonceProvider));
+    api
+}
+
+/// Create a client with one of the functions above and return it with the requests it sends.
+///
+/// ```ignore
+/// let (api, requests) = recording(gdax_api, "{}");
+/// ```
+pub fn recording<A, F, T>(client: F, transport: T) -> (A, Requests)
+    where F: FnOnce(FixtureTransport) -> A,
+          T: Into<FixtureTransport>
+{
+    let transport = transport.into();
+    let requests = transport.requests();
+    (client(transport), requests)
+}
+
+pub fn header<'a>(request: &'a HttpRequest, name: &str) -> Option<&'a str> {
+    request
+        .headers
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+pub fn decimal(value: &str) -> BigDecimal {
+    BigDecimal::from_str(value).unwrap()
+}
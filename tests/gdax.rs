@@ -1,7 +1,10 @@
+mod common;
+
 #[cfg(test)]
 mod gdax_tests {
     extern crate coinnect;
     extern crate bigdecimal;
+    extern crate serde_json;
 
     use self::bigdecimal::BigDecimal;
     use std::str::FromStr;
@@ -10,18 +13,207 @@ mod gdax_tests {
     use self::coinnect::gdax::{GdaxApi, GdaxCreds};
     use self::coinnect::bitstamp::BitstampCreds;
 
-    use self::coinnect::exchange::ExchangeApi;
-    use self::coinnect::types::Pair;
+    use self::coinnect::exchange::{Exchange, ExchangeApi};
+    use self::coinnect::nonce::{get_resolution, MemoryNonceProvider, NonceProvider};
+    use self::coinnect::transport::Method;
+    use self::coinnect::types::{Currency, Interval, OrderSide, OrderStatus, OrderType, Pair};
+    use self::coinnect::error::ErrorKind;
+
+    use crate::common::{decimal, gdax_api, header, recording, GDAX_SECRET};
+
 
     #[test]
     fn build_url_should_return_the_a_url() {
-        assert_eq!(utils::build_url("ticker", "btc-usd"),
-                   "https://api.gdax.com/products/btc-usd/ticker");
+        assert_eq!(utils::build_url("/products/BTC-USD/ticker"),
+                   "https://api.exchange.coinbase.com/products/BTC-USD/ticker");
+    }
+
+    #[test]
+    fn build_path_should_skip_the_empty_parameters() {
+        assert_eq!(utils::build_path("/orders", &[("product_id", "")]), "/orders");
+        assert_eq!(utils::build_path("/products/BTC-USD/candles",
+                                     &[("granularity", "60"),
+                                       ("start", "2017-10-27T10:00:00Z"),
+                                       ("end", "2017-10-27T15:00:00Z")]),
+                   "/products/BTC-USD/candles?granularity=60&start=2017-10-27T10:00:00Z&\
+                    end=2017-10-27T15:00:00Z");
+    }
+
+    #[test]
+    fn should_create_correct_signatures() {
+        // Computed independently with the hmac module of Python and `openssl dgst -hmac`
+        let vectors = vec![
+            ("1509099072.123", "GET", "/accounts", "",
+             "uyXgaanFf0RsWWW3sJzrLcFZ+8v4geODFSAq1FcQAok="),
+            ("1509099072.123", "POST", "/orders",
+             r#"{"price":"3000.00","product_id":"BTC-USD","side":"sell","size":"0.5","type":"limit"}"#,
+             "pGf4rZg/o9chiNrRpOw+9F0LBigg2Y/nQjE8T2Q2pYM="),
+            ("1509099072", "DELETE", "/orders?product_id=BTC-USD", "",
+             "oEtNFp1nrJSvNFEJSvHjLF3USnZja8Lvty8aI+RRFPs="),
+            ("1509099072.123", "GET",
+             "/fills?order_id=d0c5340b-6d6c-49d9-b567-48c4bfca13d2&product_id=BTC-USD", "",
+             "jPVsRS63bEgVxYLeHFPAFk9mJ3YFbD1896znFXyV7Wc="),
+        ];
+
+        for (timestamp, method, path, body, expected) in vectors {
+            assert_eq!(utils::build_signature(timestamp, method, path, body, GDAX_SECRET)
+                           .unwrap(),
+                       expected,
+                       "{} {}",
+                       method,
+                       path);
+        }
+    }
+
+    #[test]
+    fn a_secret_that_is_not_base64_should_be_an_error() {
+        assert!(utils::build_signature("1509099072", "GET", "/accounts", "", "not base64!")
+                    .is_err());
+    }
+
+    #[test]
+    fn private_requests_should_be_signed() {
+        let (api, requests) = recording(gdax_api, r#"[{"id": "71452118-efc7-4cc4-8780-a5e22d4baa53", "currency": "BTC", "balance": "1.2500000000000000", "available": "1.0000000000000000", "hold": "0.2500000000000000", "profile_id": "75da88c5-05bf-4f54-bc85-5c775bd68254"}, {"id": "e0b3f39a-183d-453e-b754-0c13e5bab0b3", "currency": "USD", "balance": "80.2301373066930000", "available": "79.2266348066930000", "hold": "1.0035025000000000", "profile_id": "75da88c5-05bf-4f54-bc85-5c775bd68254"}]"#);
+
+        let balances = api.balances().unwrap();
+        assert_eq!(balances[&Currency::BTC], decimal("1.25"));
+        assert_eq!(balances[&Currency::USD], decimal("80.230137306693"));
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        assert_eq!(request.method, Method::Get);
+        assert_eq!(request.url, "https://api.exchange.coinbase.com/accounts");
+        assert_eq!(request.body, "");
+        assert_eq!(header(request, "CB-ACCESS-KEY"), Some("fixture_key"));
+        assert_eq!(header(request, "CB-ACCESS-PASSPHRASE"), Some("fixture_passphrase"));
+        assert_eq!(header(request, "CB-ACCESS-TIMESTAMP"), Some("1509099072.123"));
+        assert_eq!(header(request, "CB-ACCESS-SIGN"),
+                   Some("uyXgaanFf0RsWWW3sJzrLcFZ+8v4geODFSAq1FcQAok="));
+        assert_eq!(header(request, "User-Agent"), Some("coinnect"));
+    }
+
+    #[test]
+    fn orders_should_be_sent_as_json() {
+        let (api, requests) = recording(gdax_api, r#"{"id": "d0c5340b-6d6c-49d9-b567-48c4bfca13d2", "price": "3000.00", "size": "0.5", "product_id": "BTC-USD", "side": "sell", "stp": "dc", "type": "limit", "time_in_force": "GTC", "post_only": false, "created_at": "2017-10-27T10:11:12.123Z", "fill_fees": "0.0000000000000000", "filled_size": "0.00000000", "executed_value": "0.0000000000000000", "status": "pending", "settled": false}"#);
+
+        let order = api.add_order(OrderType::SellLimit,
+                                  Pair::BTC_USD,
+                                  decimal("0.5"),
+                                  Some(decimal("3000.00")))
+            .unwrap();
+        assert_eq!(order.identifier, vec!["d0c5340b-6d6c-49d9-b567-48c4bfca13d2"]);
+
+        let requests = requests.lock().unwrap();
+        let request = &requests[0];
+        assert_eq!(request.method, Method::Post);
+        assert_eq!(request.url, "https://api.exchange.coinbase.com/orders");
+        assert_eq!(request.body,
+                   r#"{"price":"3000.00","product_id":"BTC-USD","side":"sell","size":"0.5","type":"limit"}"#);
+        assert_eq!(header(request, "Content-Type"), Some("application/json"));
+        assert_eq!(header(request, "CB-ACCESS-SIGN"),
+                   Some("pGf4rZg/o9chiNrRpOw+9F0LBigg2Y/nQjE8T2Q2pYM="));
+    }
+
+    #[test]
+    fn canceled_orders_should_return_their_ids() {
+        let (api, requests) = recording(gdax_api, r#"["d0c5340b-6d6c-49d9-b567-48c4bfca13d2"]"#);
+
+        let response = api.cancel_all_orders(Some(Pair::BTC_USD)).unwrap();
+        assert_eq!(response["data"][0], "d0c5340b-6d6c-49d9-b567-48c4bfca13d2");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].method, Method::Delete);
+        assert_eq!(requests[0].url,
+                   "https://api.exchange.coinbase.com/orders?product_id=BTC-USD");
+        assert_eq!(header(&requests[0], "CB-ACCESS-SIGN"),
+                   Some("jWUcVCJmvbHOJ5I5CygLcZWInX4v9YeSvm6ynJlDe/s="));
+    }
+
+    #[test]
+    fn fills_should_be_requested_for_an_order_or_a_pair() {
+        let (api, requests) = recording(gdax_api, "[]");
+
+        assert_eq!(api.return_fills(None, None).unwrap_err().to_string(),
+                   ErrorKind::InvalidArguments.to_string());
+
+        let response = api.return_fills(Some("d0c5340b-6d6c-49d9-b567-48c4bfca13d2"),
+                                        Some(Pair::BTC_USD))
+            .unwrap();
+        assert_eq!(response["data"], serde_json::json!([]));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].url,
+                   "https://api.exchange.coinbase.com/fills?\
+                    order_id=d0c5340b-6d6c-49d9-b567-48c4bfca13d2&product_id=BTC-USD");
+        assert_eq!(header(&requests[0], "CB-ACCESS-SIGN"),
+                   Some("jPVsRS63bEgVxYLeHFPAFk9mJ3YFbD1896znFXyV7Wc="));
+    }
+
+    #[test]
+    fn candles_should_be_returned_from_the_oldest() {
+        let (api, requests) = recording(gdax_api, "[[1509099300, 5790.01, 5812.99, 5800.5, 5811.3, 12.25], [1509099000, 5788.1, 5801.2, 5795, 5800.5, 3.5]]");
+
+        let candles = api.candles(Pair::BTC_USD, Interval::FiveMinutes, Some(1509098700000))
+            .unwrap();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].timestamp, 1509099000000);
+        assert_eq!(candles[0].open, decimal("5795"));
+        assert_eq!(candles[0].low, decimal("5788.1"));
+        assert_eq!(candles[1].high, decimal("5812.99"));
+        assert_eq!(candles[1].close, decimal("5811.3"));
+        assert_eq!(candles[1].volume, decimal("12.25"));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].url,
+                   "https://api.exchange.coinbase.com/products/BTC-USD/candles?granularity=300&\
+                    start=2017-10-27T10:05:00Z&end=2017-10-28T11:05:00Z");
+        assert_eq!(header(&requests[0], "CB-ACCESS-SIGN"), None);
+
+        assert_eq!(api.candles(Pair::BTC_USD, Interval::ThirtyMinutes, None)
+                       .unwrap_err()
+                       .to_string(),
+                   ErrorKind::Unsupported("ThirtyMinutes candles".to_string()).to_string());
+    }
+
+    #[test]
+    fn markets_should_register_the_products_listed_by_gdax() {
+        let (api, requests) = recording(gdax_api, r#"[{"id":"DOT-USD","base_currency":"DOT","quote_currency":"USD","base_min_size":"0.1","base_max_size":"50000","quote_increment":"0.0001","base_increment":"0.00100000","display_name":"DOT/USD","min_market_funds":"1","max_market_funds":"1000000","margin_enabled":false,"post_only":false,"limit_only":false,"cancel_only":false,"trading_disabled":false,"status":"online","status_message":""},{"id":"NEWCOIN-EUR","base_currency":"NEWCOIN","quote_currency":"EUR","quote_increment":"0.01","base_increment":"1","trading_disabled":true,"status":"delisted"}]"#);
+        let dot_usd = Pair::new(Currency::new("DOT"), Currency::USD);
+        let newcoin_eur = Pair::new(Currency::new("NEWCOIN"), Currency::EUR);
+
+        let markets = api.markets().unwrap();
+
+        assert_eq!(requests.lock().unwrap()[0].url,
+                   "https://api.exchange.coinbase.com/products");
+        assert_eq!(markets.len(), 2);
+        let dot = &markets[&dot_usd];
+        assert_eq!(dot.min_order_size, Some(decimal("0.1")));
+        assert_eq!(dot.tick_size, Some(decimal("0.0001")));
+        assert_eq!(dot.lot_decimals, Some(3));
+        assert_eq!(dot.min_notional, Some(decimal("1")));
+        assert!(dot.active);
+        let newcoin = &markets[&newcoin_eur];
+        assert_eq!(newcoin.min_order_size, None);
+        assert_eq!(newcoin.lot_decimals, Some(0));
+        assert!(!newcoin.active);
+        assert_eq!(utils::get_pair_string(&dot_usd), Some("dot-usd"));
+        assert_eq!(utils::get_pair_enum("newcoin-eur"), Some(newcoin_eur));
     }
+
     #[test]
-    fn build_url_should_return_the_url_for_transactions_for_btc_usd() {
-        assert_eq!(utils::build_url("transactions", "btc-usd"),
-                   "https://api.gdax.com/accounts/btc-usd/ledger");
+    fn pairs_not_listed_by_gdax_should_not_be_supported() {
+        let (api, requests) = recording(gdax_api, r#"[{"id":"BTC-USD","base_currency":"BTC","quote_currency":"USD","base_min_size":"0.001","quote_increment":"0.01","base_increment":"0.00000001","min_market_funds":"1","trading_disabled":false,"status":"online"}]"#);
+        let unlisted = Pair::new(Currency::new("UNLISTED"), Currency::USD);
+
+        assert_eq!(utils::get_pair_string(&unlisted), None);
+        assert_eq!(utils::get_pair_enum("unlisted-usd"), None);
+        assert_eq!(api.ticker(unlisted).unwrap_err().to_string(),
+                   ErrorKind::PairUnsupported.to_string());
+        // Only the products were requested
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert_eq!(utils::get_pair_string(&unlisted), None);
     }
 
     #[test]
@@ -36,14 +228,14 @@ mod gdax_tests {
     #[test]
     fn can_get_real_gdax_tick() {
         let creds = GdaxCreds::new("", "", "", "");
-        let mut api = GdaxApi::new(creds).unwrap();
+        let api = GdaxApi::new(creds).unwrap();
         api.ticker(Pair::BTC_USD).unwrap();
     }
 
     #[test]
     fn ticker_should_have_the_correct_last() {
         let creds = GdaxCreds::new("", "", "", "");
-        let mut api = GdaxApi::new(creds).unwrap();
+        let api = GdaxApi::new(creds).unwrap();
         let result = api.ticker(Pair::BTC_USD);
         assert_ne!(result.unwrap().last_trade_price,
                    BigDecimal::from_str("0.0").unwrap());
@@ -51,7 +243,7 @@ mod gdax_tests {
     #[test]
     fn ticker_should_have_the_correct_high() {
         let creds = GdaxCreds::new("", "", "", "");
-        let mut api = GdaxApi::new(creds).unwrap();
+        let api = GdaxApi::new(creds).unwrap();
         let result = api.ticker(Pair::BTC_USD);
         assert_ne!(result.unwrap().highest_bid,
                    BigDecimal::from_str("0.0").unwrap());
@@ -59,7 +251,7 @@ mod gdax_tests {
     #[test]
     fn ticker_should_have_the_correct_low() {
         let creds = GdaxCreds::new("", "", "", "");
-        let mut api = GdaxApi::new(creds).unwrap();
+        let api = GdaxApi::new(creds).unwrap();
         let result = api.ticker(Pair::BTC_USD);
         assert_ne!(result.unwrap().lowest_ask,
                    BigDecimal::from_str("0.0").unwrap());
@@ -67,7 +259,7 @@ mod gdax_tests {
     #[test]
     fn ticker_should_have_the_correct_volume() {
         let creds = GdaxCreds::new("", "", "", "");
-        let mut api = GdaxApi::new(creds).unwrap();
+        let api = GdaxApi::new(creds).unwrap();
         let result = api.ticker(Pair::BTC_USD);
         assert_ne!(result.unwrap().volume.unwrap(),
                    BigDecimal::from_str("0.0").unwrap());
@@ -76,22 +268,22 @@ mod gdax_tests {
     #[test]
     fn should_return_an_order_book() {
         let creds = GdaxCreds::new("", "", "", "");
-        let mut api = GdaxApi::new(creds).unwrap();
+        let api = GdaxApi::new(creds).unwrap();
         let result = api.return_order_book(Pair::BTC_USD);
-        assert_eq!(result.is_ok(), true);
+        assert!(result.is_ok());
     }
 
     #[test]
     fn order_book_should_have_a_timestamp() {
         let creds = GdaxCreds::new("", "", "", "");
-        let mut api = GdaxApi::new(creds).unwrap();
+        let api = GdaxApi::new(creds).unwrap();
         let result = api.return_order_book(Pair::BTC_USD);
         assert!(result.unwrap().contains_key("sequence"));
     }
     #[test]
     fn order_book_should_have_asks_for_btcusd() {
         let creds = GdaxCreds::new("", "", "", "");
-        let mut api = GdaxApi::new(creds).unwrap();
+        let api = GdaxApi::new(creds).unwrap();
         assert!(api.return_order_book(Pair::BTC_USD)
                     .unwrap()
                     .contains_key("asks"));
@@ -99,32 +291,120 @@ mod gdax_tests {
     #[test]
     fn order_book_should_have_asks_for_btceur() {
         let creds = GdaxCreds::new("", "", "", "");
-        let mut api = GdaxApi::new(creds).unwrap();
+        let api = GdaxApi::new(creds).unwrap();
         assert!(api.return_order_book(Pair::BTC_USD)
                     .unwrap()
                     .contains_key("asks"));
     }
 
     #[test]
-    fn should_create_a_fixed_nonce_when_requested() {
-        assert_eq!(utils::generate_nonce(Some("1".to_string())), "1");
+    fn should_create_increasing_nonces() {
+        let provider = MemoryNonceProvider::new(get_resolution(Exchange::Gdax));
+        let nonce = provider.next_nonce().unwrap();
+        assert!(provider.next_nonce().unwrap() > nonce);
     }
     #[test]
     fn should_create_a_nonce_bigger_than_2017() {
-        assert!(utils::generate_nonce(None).parse::<i64>().unwrap() > 1483228800);
+        let provider = MemoryNonceProvider::new(get_resolution(Exchange::Gdax));
+        assert!(provider.next_nonce().unwrap() > 1483228800000);
     }
-//    #[test]
-//    fn should_create_a_correct_signature() {
-//        let nonce = "1483228800";
-//        let passphrase = "123456";
-//        let api_key = "1234567890ABCDEF1234567890ABCDEF";
-//        let api_secret = "1234567890ABCDEF1234567890ABCDEF";
-//        let expected_signature = "7D7C4168D49CBC2620A45EF00EAA228C1287561F1C1F94172272E1231A8ADF6B"
-//            .to_string();
-//        assert_eq!(utils::build_signature(nonce, passphrase, api_key, api_secret).unwrap(),
-//                   expected_signature);
-//    }
-//
+
+    #[test]
+    fn order_already_done_should_return_an_order_already_closed_error() {
+        let response = utils::deserialize_json(r#"{"message":"Order already done"}"#).unwrap();
+        let res = utils::parse_result(&response, "cancel_order");
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::OrderAlreadyClosed.to_string());
+    }
+
+    #[test]
+    fn documented_errors_should_return_their_error_kind() {
+        let errors = vec![
+            ("Invalid API Key", ErrorKind::BadCredentials),
+            ("invalid signature", ErrorKind::BadCredentials),
+            ("Invalid Passphrase", ErrorKind::BadCredentials),
+            ("CB-ACCESS-KEY header is required", ErrorKind::BadCredentials),
+            ("CB-ACCESS-SIGN header is required", ErrorKind::BadCredentials),
+            ("CB-ACCESS-PASSPHRASE header is required", ErrorKind::BadCredentials),
+            ("request timestamp expired", ErrorKind::InvalidNonce),
+            ("invalid timestamp", ErrorKind::InvalidNonce),
+            ("Forbidden", ErrorKind::PermissionDenied),
+            ("Insufficient funds", ErrorKind::InsufficientFunds),
+            ("NotFound", ErrorKind::OrderNotFound),
+            ("order not found", ErrorKind::OrderNotFound),
+            ("Order already done", ErrorKind::OrderAlreadyClosed),
+            ("Product not found", ErrorKind::PairUnsupported),
+            ("Invalid product_id", ErrorKind::PairUnsupported),
+            ("Trading is disabled", ErrorKind::MarketInactive),
+            ("Product is in cancel only mode", ErrorKind::MarketInactive),
+            ("Internal server error", ErrorKind::ServiceUnavailable("Internal server error".to_string())),
+            ("Public rate limit exceeded", ErrorKind::RateLimitExceeded),
+            ("Private rate limit exceeded", ErrorKind::RateLimitExceeded),
+            ("size is too small. Minimum size is 0.01", ErrorKind::InsufficientOrderSize),
+            ("funds is too small. Minimum funds is 10", ErrorKind::InsufficientOrderSize),
+            ("BadRequest", ErrorKind::InvalidArguments),
+            ("Invalid order_type", ErrorKind::InvalidArguments),
+            ("price is too small. Minimum price is 0.01", ErrorKind::InvalidArguments),
+            ("size is too accurate. Smallest unit is 0.00000001", ErrorKind::InvalidArguments),
+            ("price is too accurate. Smallest unit is 0.01", ErrorKind::InvalidArguments),
+            ("Something new", ErrorKind::ExchangeSpecificError("Something new".to_string())),
+        ];
+
+        for (message, kind) in errors {
+            assert_eq!(format!("{:?}", utils::get_error_kind(message)),
+                       format!("{:?}", kind),
+                       "{}",
+                       message);
+            let response = utils::deserialize_json(&format!(r#"{{"message":"{}"}}"#, message))
+                .unwrap();
+            let err = utils::parse_result(&response, "buy").unwrap_err();
+            assert_eq!(err.to_string(), kind.to_string(), "{}", message);
+            assert_eq!(err.exchange_error().unwrap().message.as_deref(), Some(message));
+        }
+    }
+
+    #[test]
+    fn parse_order_should_return_a_canceled_order() {
+        let order: serde_json::Value = serde_json::from_str(r#"{
+            "id": "d0c5340b-6d6c-49d9-b567-48c4bfca13d2", "price": "3000.00",
+            "size": "0.50000000", "product_id": "BTC-USD", "side": "sell", "type": "limit",
+            "time_in_force": "GTC", "post_only": false,
+            "created_at": "2017-10-27T10:11:12.123Z", "done_at": "2017-10-27T10:11:14.123Z",
+            "done_reason": "canceled", "fill_fees": "0.0000000000000000",
+            "filled_size": "0.20000000", "executed_value": "600.0000000000000000",
+            "status": "done", "settled": true}"#).unwrap();
+
+        let order = utils::parse_order(Pair::BTC_USD, &order).unwrap();
+
+        assert_eq!(order.order_type, OrderType::SellLimit);
+        assert_eq!(order.status, OrderStatus::Canceled);
+        assert_eq!(order.price, Some(BigDecimal::from_str("3000").unwrap()));
+        assert_eq!(order.quantity, BigDecimal::from_str("0.5").unwrap());
+        assert_eq!(order.filled_quantity, BigDecimal::from_str("0.2").unwrap());
+        assert_eq!(order.average_price, Some(BigDecimal::from_str("3000").unwrap()));
+        assert_eq!(order.opened_at, Some(1509099072123));
+        assert_eq!(order.closed_at, Some(1509099074123));
+    }
+
+    #[test]
+    fn parse_trades_should_return_the_taker_side() {
+        let trades: serde_json::Value = serde_json::from_str(r#"[
+            {"time": "2017-10-27T10:11:14.123Z", "trade_id": 74, "price": "5812.99",
+            "size": "0.04000000", "side": "buy"},
+            {"time": "2017-10-27T10:11:12.123Z", "trade_id": 73, "price": "5812.98",
+            "size": "0.22000000", "side": "sell"}]"#).unwrap();
+
+        let trades = utils::parse_trades(trades.as_array().unwrap()).unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].identifier, "73");
+        assert_eq!(trades[0].timestamp, 1509099072123);
+        assert_eq!(trades[0].side, OrderSide::Buy);
+        assert_eq!(trades[1].identifier, "74");
+        assert_eq!(trades[1].volume, BigDecimal::from_str("0.04").unwrap());
+        assert_eq!(trades[1].side, OrderSide::Sell);
+    }
+
 //    #[test]
 //    fn should_return_the_trade_history_for_btc_usd() {
 //        let creds = GdaxCreds::new("", "", "", "");
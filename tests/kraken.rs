@@ -1,9 +1,24 @@
+mod common;
+
 #[cfg(test)]
 mod kraken_tests {
     extern crate coinnect;
+    extern crate bigdecimal;
+    extern crate serde_json;
+
+    use self::bigdecimal::BigDecimal;
+    use std::str::FromStr;
+    use self::serde_json::Value;
+
+    use self::coinnect::types::{Currency, Interval, OrderSide, OrderStatus, OrderType, Pair};
 
     use self::coinnect::bitstamp::BitstampCreds;
     use self::coinnect::kraken::{KrakenApi, KrakenCreds};
+    use self::coinnect::kraken::utils;
+    use self::coinnect::error::ErrorKind;
+    use self::coinnect::exchange::{Exchange, ExchangeApi};
+
+    use crate::common::{decimal, kraken_api};
 
     #[test]
     fn fail_with_invalid_creds() {
@@ -22,7 +37,7 @@ mod kraken_tests {
         use std::path::PathBuf;
         let path = PathBuf::from("./keys_real.json");
         let creds = KrakenCreds::new_from_file("account_kraken", path).unwrap();
-        let mut api = KrakenApi::new(creds).unwrap();
+        let api = KrakenApi::new(creds).unwrap();
 
         let result = api.get_account_balance().unwrap();
 
@@ -37,7 +52,7 @@ mod kraken_tests {
         use std::path::PathBuf;
         let path = PathBuf::from("./keys_real.json");
         let creds = KrakenCreds::new_from_file("account_kraken", path).unwrap();
-        let mut api = KrakenApi::new(creds).unwrap();
+        let api = KrakenApi::new(creds).unwrap();
 
         let result = api.get_websockets_token().unwrap();
 
@@ -47,4 +62,272 @@ mod kraken_tests {
         assert!(result["result"]["expires"].is_number());
         assert!(result["result"]["token"].is_string())
     }
+
+    #[test]
+    fn unknown_order_should_return_an_order_not_found_error() {
+        let response = utils::deserialize_json(r#"{"error":["EOrder:Unknown order"]}"#).unwrap();
+        let res = utils::parse_result(&response, "QueryOrders");
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::OrderNotFound.to_string());
+    }
+
+    #[test]
+    fn canceling_a_closed_order_should_return_an_order_already_closed_error() {
+        let response = utils::deserialize_json(r#"{"error":[],"result":{"count":0}}"#).unwrap();
+        let res = utils::parse_result(&response, "CancelOrder");
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::OrderAlreadyClosed.to_string());
+
+        let response = utils::deserialize_json(r#"{"error":[],"result":{"count":1}}"#).unwrap();
+        assert!(utils::parse_result(&response, "CancelOrder").is_ok());
+    }
+
+    #[test]
+    fn errors_should_tell_where_they_come_from() {
+        let response = utils::deserialize_json(r#"{"error":["EAPI:Invalid nonce","EGeneral:Internal error"]}"#).unwrap();
+        let err = utils::parse_result(&response, "AddOrder").unwrap_err();
+
+        assert_eq!(err.exchange(), Some(Exchange::Kraken));
+        assert_eq!(err.endpoint(), Some("AddOrder"));
+        assert!(err.is_retryable());
+
+        let cause = err.exchange_error().unwrap();
+        assert_eq!(cause.code, Some("EAPI:Invalid nonce".to_string()));
+        assert_eq!(cause.message,
+                   Some("EAPI:Invalid nonce, EGeneral:Internal error".to_string()));
+    }
+
+    #[test]
+    fn unavailable_service_should_keep_the_reason_and_be_retryable() {
+        let response = utils::deserialize_json(r#"{"error":["EService:Busy"]}"#).unwrap();
+        let err = utils::parse_result(&response, "Balance").unwrap_err();
+
+        assert_eq!(err.to_string(),
+                   ErrorKind::ServiceUnavailable("EService:Busy".to_string()).to_string());
+        assert!(err.is_retryable());
+        assert!(!ErrorKind::InsufficientFunds.is_retryable());
+    }
+
+    #[test]
+    fn altname_should_return_the_pair() {
+        assert_eq!(utils::get_pair_enum_from_altname("XBTEUR"), Some(Pair::BTC_EUR));
+        assert_eq!(utils::get_pair_enum_from_altname("ETHXBT"), Some(Pair::ETH_BTC));
+        assert_eq!(utils::get_pair_enum_from_altname("BCHEUR"), Some(Pair::BCH_EUR));
+        assert_eq!(utils::get_pair_enum_from_altname("FOOBAR"), None);
+    }
+
+    #[test]
+    fn altname_should_not_panic_on_a_non_ascii_name() {
+        // 8 bytes, the fifth one in the middle of "é"
+        utils::register_pair(Pair::new(Currency::new("AAE"), Currency::new("BCD")), "XAA\u{e9}BCD");
+
+        assert_eq!(utils::get_pair_enum_from_altname("AAEBCD"), None);
+    }
+
+    #[test]
+    fn parse_order_should_return_a_partially_filled_order() {
+        let order: Value = serde_json::from_str(r#"{"refid":null,"userref":0,"status":"open",
+            "opentm":1508754150.5,"starttm":0,"expiretm":0,"descr":{"pair":"XBTEUR",
+            "type":"buy","ordertype":"limit","price":"5000.0","price2":"0","leverage":"none",
+            "order":"buy 0.50000000 XBTEUR @ limit 5000.0"},"vol":"0.50000000",
+            "vol_exec":"0.20000000","cost":"1000.00000","fee":"1.60000","price":"5000.0",
+            "misc":"partial","oflags":"fciq"}"#).unwrap();
+
+        let order = utils::parse_order("OQCLML-BW3P3-BUCMWZ", Pair::BTC_EUR, &order).unwrap();
+
+        assert_eq!(order.identifier, "OQCLML-BW3P3-BUCMWZ");
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(order.order_type, OrderType::BuyLimit);
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        assert_eq!(order.price, Some(BigDecimal::from_str("5000").unwrap()));
+        assert_eq!(order.quantity, BigDecimal::from_str("0.5").unwrap());
+        assert_eq!(order.filled_quantity, BigDecimal::from_str("0.2").unwrap());
+        assert_eq!(order.average_price, Some(BigDecimal::from_str("5000").unwrap()));
+        assert_eq!(order.opened_at, Some(1508754150500));
+        assert_eq!(order.closed_at, None);
+    }
+
+    #[test]
+    fn parse_trades_should_return_the_trades() {
+        let trades: Value = serde_json::from_str(r#"[
+            ["5812.90000","0.04000000",1509099072.1234,"b","l","",1001],
+            ["5812.80000","0.22000000",1509099073.5,"s","m",""]]"#).unwrap();
+
+        let trades = utils::parse_trades(trades.as_array().unwrap()).unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].identifier, "1001");
+        assert_eq!(trades[0].timestamp, 1509099072123);
+        assert_eq!(trades[0].price, BigDecimal::from_str("5812.9").unwrap());
+        assert_eq!(trades[0].side, OrderSide::Buy);
+        assert_eq!(trades[1].identifier, "1509099073.5");
+        assert_eq!(trades[1].volume, BigDecimal::from_str("0.22").unwrap());
+        assert_eq!(trades[1].side, OrderSide::Sell);
+    }
+
+    #[test]
+    fn parse_candles_should_return_the_candles() {
+        let candles: Value = serde_json::from_str(r#"[
+            [1509098400,"5800.0","5820.5","5790.1","5812.9","5805.3","12.50000000",42],
+            [1509102000,"5812.9","5830.0","5810.0","5825.0","5820.1","3.25000000",12]]"#)
+            .unwrap();
+
+        let candles = utils::parse_candles(candles.as_array().unwrap()).unwrap();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].timestamp, 1509098400000);
+        assert_eq!(candles[0].open, BigDecimal::from_str("5800").unwrap());
+        assert_eq!(candles[0].high, BigDecimal::from_str("5820.5").unwrap());
+        assert_eq!(candles[0].low, BigDecimal::from_str("5790.1").unwrap());
+        assert_eq!(candles[0].close, BigDecimal::from_str("5812.9").unwrap());
+        assert_eq!(candles[0].volume, BigDecimal::from_str("12.5").unwrap());
+        assert_eq!(candles[1].timestamp, 1509102000000);
+    }
+
+    #[test]
+    fn two_hours_interval_should_not_be_supported() {
+        assert_eq!(utils::get_interval_minutes(Interval::OneHour), Some("60".to_string()));
+        assert_eq!(utils::get_interval_minutes(Interval::OneWeek), Some("10080".to_string()));
+        assert_eq!(utils::get_interval_minutes(Interval::TwoHours), None);
+    }
+
+    #[test]
+    fn parse_markets_should_return_the_trading_rules() {
+        let response = utils::deserialize_json(r#"{"error":[],"result":{
+            "XXBTZEUR":{"altname":"XBTEUR","base":"XXBT","quote":"ZEUR","pair_decimals":1,
+            "lot_decimals":8,"ordermin":"0.0001","costmin":"0.5","tick_size":"0.1",
+            "status":"online"},
+            "XETHZEUR":{"altname":"ETHEUR","base":"XETH","quote":"ZEUR","pair_decimals":2,
+            "lot_decimals":8},
+            "DOTEUR":{"altname":"DOTEUR","base":"DOT","quote":"ZEUR","pair_decimals":4,
+            "lot_decimals":8,"ordermin":"2.5","status":"online"}}}"#)
+            .unwrap();
+        let result = utils::parse_result(&response, "AssetPairs").unwrap();
+
+        let markets = utils::parse_markets(&result).unwrap();
+
+        assert_eq!(markets.len(), 3);
+        let btc_eur = &markets[&Pair::BTC_EUR];
+        assert_eq!(btc_eur.min_order_size, Some(BigDecimal::from_str("0.0001").unwrap()));
+        assert_eq!(btc_eur.tick_size, Some(BigDecimal::from_str("0.1").unwrap()));
+        assert_eq!(btc_eur.lot_decimals, Some(8));
+        assert_eq!(btc_eur.min_notional, Some(BigDecimal::from_str("0.5").unwrap()));
+        assert!(btc_eur.active);
+        let eth_eur = &markets[&Pair::ETH_EUR];
+        assert_eq!(eth_eur.min_order_size, None);
+        assert_eq!(eth_eur.tick_size, Some(BigDecimal::from_str("0.01").unwrap()));
+        assert!(eth_eur.active);
+    }
+
+    #[test]
+    fn parse_markets_should_register_the_new_pairs() {
+        let response = utils::deserialize_json(r#"{"error":[],"result":{
+            "ADAUSD":{"altname":"ADAUSD","base":"ADA","quote":"ZUSD","pair_decimals":6,
+            "lot_decimals":8,"ordermin":"5"}}}"#).unwrap();
+        let result = utils::parse_result(&response, "AssetPairs").unwrap();
+        let ada_usd = Pair::new(Currency::new("ADA"), Currency::USD);
+
+        let markets = utils::parse_markets(&result).unwrap();
+
+        assert_eq!(markets[&ada_usd].min_order_size, Some(BigDecimal::from_str("5").unwrap()));
+        assert_eq!(utils::get_pair_string(&ada_usd), Some("ADAUSD"));
+        assert_eq!(utils::get_pair_enum("ADAUSD"), Some(ada_usd));
+        assert_eq!(utils::get_pair_enum_from_altname("ADAUSD"), Some(ada_usd));
+    }
+
+    #[test]
+    fn unknown_assets_should_be_kept_as_currencies() {
+        assert_eq!(utils::get_currency_enum("XXBT"), Some(Currency::BTC));
+        assert_eq!(utils::get_currency_enum("DOT"), Some(Currency::new("DOT")));
+        assert_eq!(utils::get_currency_enum(""), None);
+        assert_eq!(utils::get_currency_string(Currency::new("DOT")), Some("DOT".to_string()));
+    }
+
+    #[test]
+    fn typed_ticker_should_keep_the_raw_response_available() {
+        let api = kraken_api(r#"{"error":[],"result":{"XXBTZEUR":{"a":["5000.10000","1","1.000"],"b":["4999.90000","2","2.000"],"c":["5000.00000","0.01000000"],"v":["150.5","1200.25"],"p":["4990.1","4980.2"],"t":[120,1500],"l":["4900.0","4850.0"],"h":["5100.0","5150.0"],"o":"4950.00000"}}}"#);
+
+        let tickers = api.typed().get_ticker_information("XXBTZEUR").unwrap();
+        let ticker = &tickers["XXBTZEUR"];
+        assert_eq!(ticker.ask.price, decimal("5000.1"));
+        assert_eq!(ticker.bid.lot_volume, decimal("2"));
+        assert_eq!(ticker.last_trade.lot_volume, decimal("0.01"));
+        assert_eq!(ticker.volume.last_24_hours, decimal("1200.25"));
+        assert_eq!(ticker.trades.today, 120);
+        assert_eq!(ticker.opening_price, decimal("4950"));
+
+        let raw = api.get_ticker_information("XXBTZEUR").unwrap();
+        assert_eq!(raw["result"]["XXBTZEUR"]["o"], Value::String("4950.00000".to_string()));
+    }
+
+    #[test]
+    fn typed_ohlc_should_split_the_pair_and_the_last_id() {
+        let api = kraken_api(r#"{"error":[],"result":{"XXBTZEUR":[[1609459200,"24000.0","24100.5","23900.0","24050.1","24010.3","12.50000000",345]],"last":1609459200}}"#);
+
+        let history = api.typed().get_ohlc_data("XXBTZEUR", "60", "").unwrap();
+        assert_eq!(history.pair, "XXBTZEUR");
+        assert_eq!(history.last, "1609459200");
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].time, 1609459200);
+        assert_eq!(history.entries[0].high, decimal("24100.5"));
+        assert_eq!(history.entries[0].count, 345);
+    }
+
+    #[test]
+    fn balances_should_keep_the_assets_without_a_mapping() {
+        let api = kraken_api(r#"{"error":[],"result":{"ZEUR":"1500.1234","XXBT":"0.5","NEWASSET":"42.0"}}"#);
+
+        let balances = api.balances().unwrap();
+
+        assert_eq!(balances.len(), 3);
+        assert_eq!(balances[&Currency::EUR], decimal("1500.1234"));
+        assert_eq!(balances[&Currency::BTC], decimal("0.5"));
+        assert_eq!(balances[&Currency::new("NEWASSET")], decimal("42"));
+    }
+
+    #[test]
+    fn typed_balance_should_return_the_amounts() {
+        let api = kraken_api(r#"{"error":[],"result":{"ZEUR":"1500.1234","XXBT":"0.0000000123456789"}}"#);
+
+        let balances = api.typed().get_account_balance().unwrap();
+        assert_eq!(balances["ZEUR"], decimal("1500.1234"));
+        assert_eq!(balances["XXBT"], decimal("0.0000000123456789"));
+
+        let api = kraken_api(r#"{"error":[],"result":{"ZEUR":1500.1234}}"#);
+        assert_eq!(api.typed().get_account_balance().unwrap_err().to_string(),
+                   ErrorKind::InvalidFieldFormat("ZEUR".to_string()).to_string());
+    }
+
+    #[test]
+    fn typed_open_orders_should_return_the_orders() {
+        let api = kraken_api(r#"{"error":[],"result":{"open":{"OQCLML-BW3P3-BUCMWZ":{"refid":null,"userref":0,"status":"open","opentm":1609459200.1234,"starttm":0,"expiretm":0,"descr":{"pair":"XBTEUR","type":"buy","ordertype":"limit","price":"24000.0","price2":"0","leverage":"none","order":"buy 1.25 XBTEUR @ limit 24000.0","close":""},"vol":"1.25000000","vol_exec":"0.50000000","cost":"12000.0","fee":"19.2","price":"24000.0","stopprice":"0.00000","limitprice":"0.00000","misc":"","oflags":"fciq"}}}}"#);
+
+        let orders = api.typed().get_open_orders("", "").unwrap();
+        let order = &orders.open["OQCLML-BW3P3-BUCMWZ"];
+        assert_eq!(order.refid, None);
+        assert_eq!(order.status, "open");
+        assert_eq!(order.descr.side, "buy");
+        assert_eq!(order.descr.price, decimal("24000"));
+        assert_eq!(order.vol_exec, decimal("0.5"));
+        assert!(order.trades.is_empty());
+    }
+
+    #[test]
+    fn typed_deposit_methods_should_return_a_list() {
+        let api = kraken_api(r#"{"error":[],"result":[{"method":"Bitcoin","limit":false,"fee":"0.0000000000","gen-address":true},{"method":"SEPA","limit":"10000.00","address-setup-fee":"0.00"}]}"#);
+
+        let methods = api.typed().get_deposit_methods("", "XBT").unwrap();
+        assert_eq!(methods.len(), 2);
+        assert_eq!(methods[0].limit, None);
+        assert_eq!(methods[0].gen_address, Some(true));
+        assert_eq!(methods[1].limit, Some(decimal("10000")));
+        assert_eq!(methods[1].address_setup_fee, Some(decimal("0")));
+    }
+
+    #[test]
+    fn typed_response_with_a_wrong_shape_should_be_a_parse_error() {
+        let api = kraken_api(r#"{"error":[],"result":{"unixtime":"now","rfc1123":""}}"#);
+        assert_eq!(api.typed().get_server_time().unwrap_err().to_string(),
+                   ErrorKind::BadParse.to_string());
+    }
 }
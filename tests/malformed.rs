@@ -0,0 +1,138 @@
+mod common;
+
+#[cfg(test)]
+mod malformed_tests {
+    extern crate coinnect;
+
+    use self::coinnect::error::*;
+    use self::coinnect::exchange::ExchangeApi;
+    use self::coinnect::types::{OrderType, Pair};
+
+    use crate::common::{bitstamp_api, bittrex_api, decimal, gdax_api, kraken_api, poloniex_api};
+
+    fn assert_error<T: std::fmt::Debug>(res: Result<T>, kind: ErrorKind) {
+        assert_eq!(res.unwrap_err().to_string(), kind.to_string());
+    }
+
+    fn missing(field: &str) -> ErrorKind {
+        ErrorKind::MissingField(field.to_string())
+    }
+
+    fn invalid(field: &str) -> ErrorKind {
+        ErrorKind::InvalidFieldFormat(field.to_string())
+    }
+
+    #[test]
+    fn kraken_malformed_responses_should_be_errors() {
+        assert_error(kraken_api(r#"{"result":{}}"#).ticker(Pair::BTC_EUR), ErrorKind::BadParse);
+        assert_error(kraken_api(r#"{"error":[]}"#).ticker(Pair::BTC_EUR), missing("result"));
+        assert_error(kraken_api(r#"{"error":[],"result":{}}"#).ticker(Pair::BTC_EUR), missing("c"));
+        assert_error(kraken_api(r#"{"error":[],"result":{"XXBTZEUR":{"a":["5000.1"],"b":["4999.9"],"c":[5000.0],"v":["1.5"]}}}"#)
+                         .ticker(Pair::BTC_EUR),
+                     invalid("c"));
+        assert_error(kraken_api(r#"{"error":[],"result":{"XXBTZEUR":{"asks":[["5000.1"]],"bids":[]}}}"#)
+                         .orderbook(Pair::BTC_EUR),
+                     missing("ask volume"));
+        assert_error(kraken_api(r#"{"error":[],"result":{}}"#).orderbook(Pair::BTC_EUR),
+                     invalid("asks"));
+        assert_error(kraken_api(r#"{"error":[],"result":{"txid":"OAVY7T-MV5VK-KHDF5X"}}"#)
+                         .add_order(OrderType::BuyMarket, Pair::BTC_EUR, decimal("1.0"), None),
+                     invalid("txid"));
+    }
+
+    #[test]
+    fn poloniex_malformed_responses_should_be_errors() {
+        assert_error(poloniex_api("[]").ticker(Pair::ETH_BTC), ErrorKind::BadParse);
+        assert_error(poloniex_api(r#"{"BTC_ETH":{}}"#).ticker(Pair::ETH_BTC), missing("last"));
+        assert_error(poloniex_api(r#"{"asks":[["0.0417",true]],"bids":[]}"#)
+                         .orderbook(Pair::ETH_BTC),
+                     invalid("ask volume"));
+        assert_error(poloniex_api(r#"{"asks":[],"bids":{}}"#).orderbook(Pair::ETH_BTC),
+                     invalid("bids"));
+        assert_error(poloniex_api(r#"{"resultingTrades":[]}"#)
+                         .add_order(OrderType::BuyMarket, Pair::ETH_BTC, decimal("1.0"), None),
+                     missing("orderNumber"));
+        assert_error(poloniex_api(r#"{"BTC":1.5}"#).balances(), invalid("amount"));
+    }
+
+    #[test]
+    fn bittrex_malformed_responses_should_be_errors() {
+        assert_error(bittrex_api("null").ticker(Pair::ETH_BTC), ErrorKind::BadParse);
+        assert_error(bittrex_api(r#"{"code":1}"#).ticker(Pair::ETH_BTC), invalid("code"));
+        assert_error(bittrex_api(r#"{"lastTradeRate":"0.0417","bidRate":"0.0416","volume":"1.5"}"#)
+                         .ticker(Pair::ETH_BTC),
+                     missing("askRate"));
+        assert_error(bittrex_api(r#"{"bid":[],"ask":[{"rate":"abc","quantity":"1.5"}]}"#)
+                         .orderbook(Pair::ETH_BTC),
+                     invalid("rate"));
+        assert_error(bittrex_api(r#"{"bid":[]}"#).orderbook(Pair::ETH_BTC), invalid("ask"));
+        assert_error(bittrex_api("{}").add_order(OrderType::BuyLimit,
+                                              Pair::ETH_BTC,
+                                              decimal("1.0"),
+                                             Some(decimal("0.04"))),
+                     missing("id"));
+        assert_error(bittrex_api(r#"[{"currencySymbol":"BTC"}]"#).balances(), missing("available"));
+        assert_error(bittrex_api("{}").balances(), missing("data"));
+    }
+
+    #[test]
+    fn bitstamp_malformed_responses_should_be_errors() {
+        assert_error(bitstamp_api(r#"{"last":"6000.00"}"#).ticker(Pair::BTC_USD), missing("ask"));
+        assert_error(bitstamp_api(r#"{"asks":[["6000.00"]],"bids":[]}"#)
+                         .orderbook(Pair::BTC_USD),
+                     missing("ask volume"));
+        assert_error(bitstamp_api(r#"{"asks":[],"bids":null}"#).orderbook(Pair::BTC_USD),
+                     invalid("bids"));
+        assert_error(bitstamp_api(r#"{"price":"6000.00"}"#)
+                         .add_order(OrderType::BuyMarket, Pair::BTC_USD, decimal("1.0"), None),
+                     missing("id"));
+    }
+
+    #[test]
+    fn gdax_malformed_responses_should_be_errors() {
+        assert_error(gdax_api("{}").ticker(Pair::BTC_USD), missing("price"));
+        assert_error(gdax_api(r#"{"price":6000.0}"#).ticker(Pair::BTC_USD), invalid("price"));
+        assert_error(gdax_api(r#"{"asks":"none","bids":[]}"#).orderbook(Pair::BTC_USD),
+                     invalid("asks"));
+    }
+
+    #[test]
+    fn limit_orders_without_a_price_should_be_errors() {
+        assert_error(bittrex_api("{}").add_order(OrderType::SellLimit,
+                                                 Pair::ETH_BTC,
+                                                 decimal("1.0"),
+                                                 None),
+                     ErrorKind::MissingPrice);
+        assert_error(poloniex_api("{}").add_order(OrderType::BuyLimit,
+                                                  Pair::ETH_BTC,
+                                                  decimal("1.0"),
+                                                  None),
+                     ErrorKind::MissingPrice);
+        assert_error(bitstamp_api("{}").add_order(OrderType::BuyLimit,
+                                                  Pair::BTC_USD,
+                                                  decimal("1.0"),
+                                                  None),
+                     ErrorKind::MissingPrice);
+        assert_error(gdax_api("{}").add_order(OrderType::SellLimit,
+                                              Pair::BTC_USD,
+                                              decimal("1.0"),
+                                              None),
+                     ErrorKind::MissingPrice);
+    }
+
+    #[test]
+    fn numbers_should_be_parsed_without_losing_precision() {
+        let ticker = bittrex_api(r#"{"lastTradeRate":0.00000001234567890123456789,"askRate":12345678901234567890.123456789,"bidRate":1e-8,"volume":1.10}"#)
+            .ticker(Pair::ETH_BTC)
+            .unwrap();
+        assert_eq!(ticker.last_trade_price, decimal("0.00000001234567890123456789"));
+        assert_eq!(ticker.lowest_ask, decimal("12345678901234567890.123456789"));
+        assert_eq!(ticker.highest_bid, decimal("0.00000001"));
+        assert_eq!(ticker.volume, Some(decimal("1.10")));
+
+        let orderbook = poloniex_api(r#"{"asks":[["0.04170000",338.87321234567890123]],"bids":[]}"#)
+            .orderbook(Pair::ETH_BTC)
+            .unwrap();
+        assert_eq!(orderbook.asks[0].1, decimal("338.87321234567890123"));
+    }
+}
@@ -0,0 +1,151 @@
+mod common;
+
+#[cfg(test)]
+mod nonce_tests {
+    extern crate coinnect;
+
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process;
+    use std::sync::Arc;
+    use std::thread;
+
+    use self::coinnect::error::*;
+    use self::coinnect::exchange::Exchange;
+    use self::coinnect::kraken::{KrakenApi, KrakenCreds};
+    use self::coinnect::nonce::{get_resolution, FileNonceProvider, MemoryNonceProvider,
+                                NonceProvider, Resolution};
+
+    use crate::common::{kraken_api, recording};
+
+    /// Return a path in the temporary directory, unique to the test, after removing any file left
+    /// by a previous run.
+    fn nonce_file(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("coinnect_{}_{}.nonce", name, process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn nonces_should_be_strictly_increasing() {
+        let provider = MemoryNonceProvider::new(Resolution::Milliseconds);
+        let before = Resolution::Milliseconds.now();
+
+        let mut last = provider.next_nonce().unwrap();
+        assert!(last >= before);
+        for _ in 0..1000 {
+            let nonce = provider.next_nonce().unwrap();
+            assert!(nonce > last);
+            last = nonce;
+        }
+        assert_eq!(provider.last_nonce(), last);
+    }
+
+    #[test]
+    fn poloniex_nonces_should_be_in_microseconds() {
+        assert_eq!(get_resolution(Exchange::Poloniex), Resolution::Microseconds);
+        assert_eq!(get_resolution(Exchange::Kraken), Resolution::Milliseconds);
+        assert!(Resolution::Microseconds.now() / 1000 >= Resolution::Milliseconds.now() - 1);
+    }
+
+    #[test]
+    fn clients_using_the_same_api_key_should_share_their_nonces() {
+        let creds = KrakenCreds::new("test", "nonce_shared_key", "c2VjcmV0");
+        let api = KrakenApi::new(creds).unwrap();
+
+        let nonce = api.config().nonce_provider().next_nonce().unwrap();
+        let shared = MemoryNonceProvider::shared(Exchange::Kraken, "nonce_shared_key");
+        assert_eq!(shared.last_nonce(), nonce);
+        assert_eq!(MemoryNonceProvider::shared(Exchange::Kraken, "nonce_other_key").last_nonce(),
+                   0);
+    }
+
+    #[test]
+    fn file_nonces_should_be_shared_between_providers() {
+        let path = nonce_file("shared");
+        // Each provider opens the file, as two processes would
+        let first = FileNonceProvider::new(&path, Resolution::Milliseconds).unwrap();
+        let second = FileNonceProvider::new(&path, Resolution::Milliseconds).unwrap();
+
+        let mut last = 0;
+        for _ in 0..100 {
+            let nonce = first.next_nonce().unwrap();
+            assert!(nonce > last);
+            let nonce2 = second.next_nonce().unwrap();
+            assert!(nonce2 > nonce);
+            last = nonce2;
+        }
+        assert_eq!(fs::read_to_string(&path).unwrap(), last.to_string());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_nonces_should_be_distinct_between_threads() {
+        let path = nonce_file("threads");
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                thread::spawn(move || {
+                    let provider = FileNonceProvider::new(&path, Resolution::Milliseconds)
+                        .unwrap();
+                    (0..20)
+                        .map(|_| provider.next_nonce().unwrap())
+                        .collect::<Vec<i64>>()
+                })
+            })
+            .collect();
+
+        let mut nonces: Vec<i64> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect();
+        nonces.sort();
+        nonces.dedup();
+        assert_eq!(nonces.len(), 160);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_nonces_should_survive_a_clock_going_backwards() {
+        let path = nonce_file("future");
+        // A nonce sent while the clock was ahead
+        fs::write(&path, "9000000000000000").unwrap();
+
+        let provider = FileNonceProvider::new(&path, Resolution::Milliseconds).unwrap();
+        assert_eq!(provider.next_nonce().unwrap(), 9000000000000001);
+        assert_eq!(provider.next_nonce().unwrap(), 9000000000000002);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn a_corrupted_nonce_file_should_be_an_error() {
+        let path = nonce_file("corrupted");
+        fs::write(&path, "not a nonce").unwrap();
+
+        let provider = FileNonceProvider::new(&path, Resolution::Milliseconds).unwrap();
+        assert_eq!(provider.next_nonce().unwrap_err().to_string(),
+                   ErrorKind::BadParse.to_string());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn private_requests_should_use_the_nonce_provider() {
+        let path = nonce_file("client");
+        fs::write(&path, "9000000000000000").unwrap();
+
+        let (mut api, requests) = recording(kraken_api, r#"{"error":[],"result":{}}"#);
+        let provider = FileNonceProvider::new(&path, Resolution::Milliseconds).unwrap();
+        api.config_mut().set_nonce_provider(Arc::new(provider));
+
+        api.get_account_balance().unwrap();
+        assert_eq!(requests.lock().unwrap()[0].body, "nonce=9000000000000001");
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod normalization_tests {
+    extern crate coinnect;
+
+    use self::coinnect::exchange::Exchange;
+    use self::coinnect::normalization;
+    use self::coinnect::types::{Currency, Pair};
+    use self::coinnect::{bitstamp, bittrex, kraken, poloniex};
+
+    #[test]
+    fn aliases_should_be_converted_to_the_canonical_currency() {
+        assert_eq!(Currency::new("XBT"), Currency::BTC);
+        assert_eq!(Currency::new("xdg"), Currency::DOGE);
+        assert_eq!(Currency::BCC, Currency::BCH);
+        assert_eq!(Pair::XDG_BTC, Pair::DOGE_BTC);
+    }
+
+    #[test]
+    fn the_same_asset_should_have_the_same_currency_on_every_exchange() {
+        assert_eq!(kraken::utils::get_currency_enum("XXBT"), Some(Currency::BTC));
+        assert_eq!(kraken::utils::get_currency_enum("XBT"), Some(Currency::BTC));
+        assert_eq!(poloniex::utils::get_currency_enum("BTC"), Some(Currency::BTC));
+        assert_eq!(bitstamp::utils::get_currency_enum("btc_balance"), Some(Currency::BTC));
+
+        assert_eq!(kraken::utils::get_currency_enum("XXDG"), Some(Currency::DOGE));
+        assert_eq!(bittrex::utils::get_currency_enum("DOGE"), Some(Currency::DOGE));
+
+        assert_eq!(bittrex::utils::get_currency_enum("BCC"), Some(Currency::BCH));
+        assert_eq!(bitstamp::utils::get_currency_enum("bch_balance"), Some(Currency::BCH));
+
+        assert_eq!(poloniex::utils::get_currency_enum("STR"), Some(Currency::XLM));
+        assert_eq!(kraken::utils::get_currency_enum("XXLM"), Some(Currency::XLM));
+    }
+
+    #[test]
+    fn pairs_should_use_the_canonical_currencies() {
+        assert_eq!(kraken::utils::get_pair_enum("XXDGXXBT"), Some(Pair::DOGE_BTC));
+        assert_eq!(bittrex::utils::get_pair_enum("BCH-BTC"), Some(Pair::BCH_BTC));
+        assert_eq!(poloniex::utils::get_pair_enum("BTC_STR"), Some(Pair::XLM_BTC));
+        assert_eq!(poloniex::utils::get_pair_string(&Pair::XLM_USDT), Some("USDT_STR"));
+    }
+
+    #[test]
+    fn currencies_should_use_the_names_of_the_exchange() {
+        assert_eq!(normalization::get_name(Exchange::Kraken, Currency::DOGE), "XXDG");
+        assert_eq!(normalization::get_name(Exchange::Bittrex, Currency::BCH), "BCH");
+        assert_eq!(normalization::get_name(Exchange::Bitstamp, Currency::BCH), "BCH");
+    }
+
+    #[test]
+    fn aliases_should_be_overridable() {
+        let dot = Currency::new("DOT");
+        assert_eq!(normalization::get_currency(Exchange::Gdax, "POLKADOT"),
+                   Some(Currency::new("POLKADOT")));
+
+        normalization::set_alias(Exchange::Gdax, "POLKADOT", dot);
+        assert_eq!(normalization::get_currency(Exchange::Gdax, "POLKADOT"), Some(dot));
+        assert_eq!(normalization::get_name(Exchange::Gdax, dot), "POLKADOT");
+        assert_eq!(normalization::get_currency(Exchange::Bitstamp, "POLKADOT"),
+                   Some(Currency::new("POLKADOT")));
+
+        normalization::remove_alias(Exchange::Gdax, "POLKADOT");
+        assert_eq!(normalization::get_name(Exchange::Gdax, dot), "DOT");
+    }
+}
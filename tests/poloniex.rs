@@ -1,9 +1,27 @@
+mod common;
+
 #[cfg(test)]
 mod poloniex_tests {
     extern crate coinnect;
+    extern crate bigdecimal;
+    extern crate serde_json;
+    extern crate chrono;
+
+    use self::bigdecimal::BigDecimal;
+    use std::str::FromStr;
+    use self::serde_json::Value;
+
+    use self::coinnect::types::{Currency, Interval, OrderInfo, OrderSide, OrderStatus, OrderType,
+                                Pair};
 
     use self::coinnect::poloniex::{PoloniexApi, PoloniexCreds};
     use self::coinnect::bitstamp::BitstampCreds;
+    use self::coinnect::poloniex::utils;
+    use self::coinnect::exchange::ExchangeApi;
+    use self::coinnect::error::ErrorKind;
+
+    use crate::common::{decimal, poloniex_api};
+    use self::chrono::{TimeZone, Utc};
 
     #[test]
     fn fail_with_invalid_creds() {
@@ -20,9 +38,362 @@ mod poloniex_tests {
         use std::path::PathBuf;
         let path = PathBuf::from("./keys_real.json");
         let creds = PoloniexCreds::new_from_file("account_poloniex", path).unwrap();
-        let mut api = PoloniexApi::new(creds).unwrap();
+        let api = PoloniexApi::new(creds).unwrap();
         let result = api.return_balances();
 
         assert!(result.unwrap().contains_key("BTC"));
     }
+
+    #[test]
+    fn invalid_order_number_should_return_an_order_not_found_error() {
+        let response = utils::deserialize_json(r#"{"error":"Invalid order number, or you are not the person who placed the order."}"#).unwrap();
+        let res = utils::parse_result(&response, "cancelOrder");
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::OrderNotFound.to_string());
+    }
+
+    #[test]
+    fn parse_open_order_should_return_a_partially_filled_order() {
+        let order: Value = serde_json::from_str(r#"{"orderNumber":"120466","type":"sell",
+            "rate":"0.025","startingAmount":"100","amount":"60","total":"1.5",
+            "date":"2017-10-27 10:11:12","margin":0}"#).unwrap();
+
+        let order = utils::parse_open_order(Pair::ETH_BTC, &order).unwrap();
+
+        assert_eq!(order.identifier, "120466");
+        assert_eq!(order.side, OrderSide::Sell);
+        assert_eq!(order.order_type, OrderType::SellLimit);
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        assert_eq!(order.quantity, BigDecimal::from_str("100").unwrap());
+        assert_eq!(order.filled_quantity, BigDecimal::from_str("40").unwrap());
+        assert_eq!(order.opened_at, Some(1509099072000));
+    }
+
+    fn order_info(quantity: &str) -> OrderInfo {
+        OrderInfo {
+            timestamp: 1509099070000,
+            identifier: vec!["31226040".to_string()],
+            pair: Pair::ETH_BTC,
+            order_type: OrderType::BuyLimit,
+            quantity: BigDecimal::from_str(quantity).unwrap(),
+            price: Some(BigDecimal::from_str("0.04").unwrap()),
+            fills: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parse_order_trades_should_return_a_filled_order() {
+        let trades: Value = serde_json::from_str(r#"[{"globalTradeID": 20825863,
+            "tradeID": 147142, "currencyPair": "BTC_ETH", "type": "buy", "rate": "0.02",
+            "amount": "1", "total": "0.02", "fee": "0.002", "date": "2017-10-27 10:11:12"},
+            {"globalTradeID": 20825864, "tradeID": 147143, "currencyPair": "BTC_ETH",
+            "type": "buy", "rate": "0.04", "amount": "1", "total": "0.04", "fee": "0.002",
+            "date": "2017-10-27 10:11:14"}]"#).unwrap();
+
+        let order = utils::parse_order_trades(&order_info("2"), trades.as_array().unwrap())
+            .unwrap();
+
+        assert_eq!(order.identifier, "31226040");
+        assert_eq!(order.pair, Pair::ETH_BTC);
+        assert_eq!(order.side, OrderSide::Buy);
+        assert_eq!(order.order_type, OrderType::BuyLimit);
+        assert_eq!(order.status, OrderStatus::Filled);
+        assert_eq!(order.quantity, BigDecimal::from_str("2").unwrap());
+        assert_eq!(order.filled_quantity, BigDecimal::from_str("2").unwrap());
+        assert_eq!(order.price, Some(BigDecimal::from_str("0.04").unwrap()));
+        assert_eq!(order.average_price, Some(BigDecimal::from_str("0.03").unwrap()));
+        assert_eq!(order.opened_at, Some(1509099072000));
+        assert_eq!(order.closed_at, Some(1509099074000));
+    }
+
+    #[test]
+    fn parse_order_trades_should_return_a_partially_filled_order_as_canceled() {
+        let trades: Value = serde_json::from_str(r#"[{"globalTradeID": 20825863,
+            "tradeID": 147142, "currencyPair": "BTC_ETH", "type": "buy", "rate": "0.02",
+            "amount": "1", "total": "0.02", "fee": "0.002", "date": "2017-10-27 10:11:12"}]"#)
+            .unwrap();
+
+        let order = utils::parse_order_trades(&order_info("5"), trades.as_array().unwrap())
+            .unwrap();
+
+        assert_eq!(order.status, OrderStatus::Canceled);
+        assert_eq!(order.quantity, BigDecimal::from_str("5").unwrap());
+        assert_eq!(order.filled_quantity, BigDecimal::from_str("1").unwrap());
+    }
+
+    #[test]
+    fn parse_order_trades_without_trades_should_return_an_unfilled_canceled_order() {
+        let mut order_info = order_info("5");
+        order_info.order_type = OrderType::SellLimit;
+
+        let order = utils::parse_order_trades(&order_info, &[]).unwrap();
+
+        assert_eq!(order.identifier, "31226040");
+        assert_eq!(order.side, OrderSide::Sell);
+        assert_eq!(order.status, OrderStatus::Canceled);
+        assert_eq!(order.quantity, BigDecimal::from_str("5").unwrap());
+        assert_eq!(order.filled_quantity, BigDecimal::from(0));
+        assert_eq!(order.average_price, None);
+    }
+
+    #[test]
+    fn parse_order_trades_without_amount_should_not_have_an_average_price() {
+        let trades: Value = serde_json::from_str(r#"[{"globalTradeID": 20825863,
+            "tradeID": 147142, "currencyPair": "BTC_ETH", "type": "buy", "rate": "0.02",
+            "amount": "0", "total": "0", "fee": "0", "date": "2017-10-27 10:11:12"}]"#)
+            .unwrap();
+
+        let order = utils::parse_order_trades(&order_info("5"), trades.as_array().unwrap())
+            .unwrap();
+
+        assert_eq!(order.average_price, None);
+        assert_eq!(order.status, OrderStatus::Canceled);
+    }
+
+    #[test]
+    fn errors_should_keep_the_message_of_the_exchange() {
+        let response = utils::deserialize_json(r#"{"error":"Not enough BTC."}"#).unwrap();
+        let err = utils::parse_result(&response, "buy").unwrap_err();
+
+        assert_eq!(err.to_string(), ErrorKind::InsufficientFunds.to_string());
+        assert!(!err.is_retryable());
+
+        let cause = err.exchange_error().unwrap();
+        assert_eq!(cause.to_string(), "Poloniex buy: Not enough BTC.");
+        assert_eq!(cause.code, None);
+    }
+
+    #[test]
+    fn documented_errors_should_return_their_error_kind() {
+        let errors = vec![
+            ("Invalid API key/secret pair.", ErrorKind::BadCredentials),
+            ("Permission denied.", ErrorKind::PermissionDenied),
+            ("Invalid currency pair.", ErrorKind::PairUnsupported),
+            ("Invalid currencyPair parameter.", ErrorKind::PairUnsupported),
+            ("Invalid order number, or you are not the person who placed the order.", ErrorKind::OrderNotFound),
+            ("Order not found, or you are not the person who placed it.", ErrorKind::OrderNotFound),
+            ("This market is frozen.", ErrorKind::MarketInactive),
+            ("This market is disabled.", ErrorKind::MarketInactive),
+            ("Internal error. Please try again.", ErrorKind::ServiceUnavailable("Internal error. Please try again.".to_string())),
+            ("Nonce must be greater than 1508923400000. You provided 1508923399000.", ErrorKind::InvalidNonce),
+            ("Not enough ETH.", ErrorKind::InsufficientFunds),
+            ("Total must be at least 0.0001.", ErrorKind::InsufficientOrderSize),
+            ("Amount must be at least 0.000001.", ErrorKind::InsufficientOrderSize),
+            ("Please do not make more than 6 API calls per second.", ErrorKind::RateLimitExceeded),
+            ("Invalid command.", ErrorKind::InvalidArguments),
+            ("Invalid rate parameter.", ErrorKind::InvalidArguments),
+            ("Required parameter missing.", ErrorKind::InvalidArguments),
+            ("Unknown error.", ErrorKind::ExchangeSpecificError("Unknown error.".to_string())),
+        ];
+
+        for (message, kind) in errors {
+            assert_eq!(format!("{:?}", utils::get_error_kind(message)),
+                       format!("{:?}", kind),
+                       "{}",
+                       message);
+            let response = utils::deserialize_json(&format!(r#"{{"error":"{}"}}"#, message))
+                .unwrap();
+            let err = utils::parse_result(&response, "buy").unwrap_err();
+            assert_eq!(err.to_string(), kind.to_string(), "{}", message);
+            assert_eq!(err.exchange_error().unwrap().message.as_deref(), Some(message));
+        }
+    }
+
+    #[test]
+    fn deserialize_json_array_should_keep_errors() {
+        let response = utils::deserialize_json_array(r#"{"error":"Order not found, or you are not the person who placed it."}"#).unwrap();
+        let res = utils::parse_result(&response, "returnOrderTrades");
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::OrderNotFound.to_string());
+    }
+
+    #[test]
+    fn parse_resulting_trades_should_return_the_fills() {
+        let response = utils::deserialize_json(r#"{"orderNumber":31226040,
+            "resultingTrades":[{"amount":"338.8732","date":"2014-10-18 23:03:21",
+            "rate":"0.00000173","total":"0.00058625","tradeID":"16164","type":"buy"}]}"#).unwrap();
+
+        let fills = utils::parse_resulting_trades(&response).unwrap();
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].identifier, "16164");
+        assert_eq!(fills[0].timestamp, 1413673401000);
+        assert_eq!(fills[0].price, BigDecimal::from_str("0.00000173").unwrap());
+        assert_eq!(fills[0].quantity, BigDecimal::from_str("338.8732").unwrap());
+    }
+
+    #[test]
+    fn parse_trades_should_return_the_trades_from_the_oldest() {
+        let trades: Value = serde_json::from_str(r#"[{"globalTradeID":25129732,
+            "tradeID":6325758,"date":"2017-10-27 10:11:14","type":"sell","rate":"0.02565498",
+            "amount":"0.10000000","total":"0.00256549"},{"globalTradeID":25129628,
+            "tradeID":6325741,"date":"2017-10-27 10:11:12","type":"buy","rate":"0.02565499",
+            "amount":"0.20000000","total":"0.00513099"}]"#).unwrap();
+
+        let trades = utils::parse_trades(trades.as_array().unwrap()).unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].identifier, "6325741");
+        assert_eq!(trades[0].timestamp, 1509099072000);
+        assert_eq!(trades[0].side, OrderSide::Buy);
+        assert_eq!(trades[1].identifier, "6325758");
+        assert_eq!(trades[1].price, BigDecimal::from_str("0.02565498").unwrap());
+        assert_eq!(trades[1].side, OrderSide::Sell);
+
+        let cursor = utils::build_trade_cursor(&trades[1]);
+        assert_eq!(cursor, "1509099074:6325758");
+        assert_eq!(utils::parse_trade_cursor(&cursor).unwrap(),
+                   ("1509099074".to_string(), 6325758));
+    }
+
+    #[test]
+    fn parse_trade_cursor_should_reject_an_invalid_cursor() {
+        assert!(utils::parse_trade_cursor("1509099074").is_err());
+        assert!(utils::parse_trade_cursor("abc:123").is_err());
+    }
+
+    #[test]
+    fn parse_candles_should_return_the_candles() {
+        let candles: Value = serde_json::from_str(r#"[{"date":1405699200,"high":0.0045388,
+            "low":0.00403001,"open":0.00404545,"close":0.00427592,"volume":44.11655644,
+            "quoteVolume":10259.29079097,"weightedAverage":0.00430015}]"#).unwrap();
+
+        let candles = utils::parse_candles(candles.as_array().unwrap()).unwrap();
+
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].timestamp, 1405699200000);
+        assert_eq!(candles[0].open, BigDecimal::from_str("0.00404545").unwrap());
+        assert_eq!(candles[0].high, BigDecimal::from_str("0.0045388").unwrap());
+        assert_eq!(candles[0].low, BigDecimal::from_str("0.00403001").unwrap());
+        assert_eq!(candles[0].close, BigDecimal::from_str("0.00427592").unwrap());
+        assert_eq!(candles[0].volume, BigDecimal::from_str("10259.29079097").unwrap());
+    }
+
+    #[test]
+    fn parse_candles_should_skip_the_empty_candle() {
+        let candles: Value = serde_json::from_str(r#"[{"date":0,"high":0,"low":0,"open":0,
+            "close":0,"volume":0,"quoteVolume":0,"weightedAverage":0}]"#).unwrap();
+
+        let candles = utils::parse_candles(candles.as_array().unwrap()).unwrap();
+
+        assert!(candles.is_empty());
+    }
+
+    #[test]
+    fn one_minute_interval_should_not_be_supported() {
+        assert_eq!(utils::get_interval_period(Interval::FiveMinutes), Some("300".to_string()));
+        assert_eq!(utils::get_interval_period(Interval::OneMinute), None);
+    }
+
+    #[test]
+    fn markets_should_register_the_pairs_listed_by_poloniex() {
+        let api = poloniex_api(r#"{"BTC_DOT":{"id":401,"last":"0.00048","lowestAsk":"0.00049","highestBid":"0.00047","percentChange":"0.02","baseVolume":"12.5","quoteVolume":"26000","isFrozen":"0"},"USDT_NEWCOIN":{"id":402,"last":"1.2","lowestAsk":"1.3","highestBid":"1.1","percentChange":"0","baseVolume":"0","quoteVolume":"0","isFrozen":"1"}}"#);
+        let dot_btc = Pair::new(Currency::new("DOT"), Currency::BTC);
+        let newcoin_usdt = Pair::new(Currency::new("NEWCOIN"), Currency::USDT);
+
+        let markets = api.markets().unwrap();
+
+        assert_eq!(markets.len(), 2);
+        assert!(markets[&dot_btc].active);
+        assert!(!markets[&newcoin_usdt].active);
+        assert_eq!(markets[&dot_btc].min_order_size, None);
+        assert_eq!(utils::get_pair_string(&dot_btc), Some("BTC_DOT"));
+        assert_eq!(utils::get_pair_enum("USDT_NEWCOIN"), Some(newcoin_usdt));
+    }
+
+    #[test]
+    fn pairs_not_listed_by_poloniex_should_not_be_supported() {
+        let api = poloniex_api(r#"{"BTC_ETH":{"id":148,"last":"0.0417","lowestAsk":"0.04171","highestBid":"0.04169","percentChange":"0","baseVolume":"120.5","quoteVolume":"2890.1","isFrozen":"0"}}"#);
+        let unlisted = Pair::new(Currency::new("UNLISTED"), Currency::BTC);
+
+        assert_eq!(utils::get_pair_string(&unlisted), None);
+        assert_eq!(utils::get_pair_enum("BTC_UNLISTED"), None);
+        assert_eq!(api.ticker(unlisted).unwrap_err().to_string(),
+                   ErrorKind::PairUnsupported.to_string());
+        assert_eq!(utils::get_pair_string(&unlisted), None);
+        assert_eq!(utils::get_pair_string(&Pair::BTC_EUR_d), None);
+    }
+
+    #[test]
+    fn typed_ticker_and_volumes_should_return_decimals() {
+        let api = poloniex_api(r#"{"BTC_ETH":{"id":148,"last":"0.04170000","lowestAsk":"0.04171000","highestBid":"0.04169999","percentChange":"-0.01","baseVolume":"120.5","quoteVolume":"2890.1","isFrozen":"0","high24hr":"0.0425","low24hr":"0.0410"}}"#);
+        let tickers = api.typed().return_ticker().unwrap();
+        let ticker = &tickers["BTC_ETH"];
+        assert_eq!(ticker.last, decimal("0.0417"));
+        assert_eq!(ticker.percent_change, decimal("-0.01"));
+        assert!(!ticker.is_frozen);
+        assert_eq!(ticker.high_24h, Some(decimal("0.0425")));
+
+        let api = poloniex_api(r#"{"BTC_ETH":{"BTC":"2.23248854","ETH":"87.10381314"},"totalBTC":"81.89657704","totalETH":"78.5"}"#);
+        let volumes = api.typed().return_24_volume().unwrap();
+        assert_eq!(volumes.pairs["BTC_ETH"]["ETH"], decimal("87.10381314"));
+        assert_eq!(volumes.totals["BTC"], decimal("81.89657704"));
+    }
+
+    #[test]
+    fn typed_order_book_and_chart_data_should_keep_the_precision() {
+        let api = poloniex_api(r#"{"asks":[["0.04170000",338.87321234567890123]],"bids":[["0.04160000",1.5]],"isFrozen":"0","seq":18849}"#);
+        let book = api.typed().return_order_book("BTC_ETH", "10").unwrap();
+        assert_eq!(book.asks[0].price, decimal("0.0417"));
+        assert_eq!(book.asks[0].amount, decimal("338.87321234567890123"));
+        assert_eq!(book.seq, Some(18849));
+
+        let api = poloniex_api(r#"[{"date":1405699200,"high":0.0045388,"low":0.00403001,"open":0.00404545,"close":0.00427592,"volume":44.11655644,"quoteVolume":10259.29079097,"weightedAverage":0.00430015}]"#);
+        let candles = api.typed().return_chart_data("BTC_ETH", "", "", "300").unwrap();
+        assert_eq!(candles[0].date, Utc.timestamp_opt(1405699200, 0).unwrap());
+        assert_eq!(candles[0].weighted_average, decimal("0.00430015"));
+    }
+
+    #[test]
+    fn typed_open_orders_should_be_keyed_by_market() {
+        let body = r#"[{"orderNumber":"120466","type":"sell","rate":"0.025","amount":"100","total":"2.5","date":"2018-01-02 03:04:05","margin":0}]"#;
+        let api = poloniex_api(body);
+        let orders = api.typed().return_open_orders("BTC_ETH").unwrap();
+        assert_eq!(orders["BTC_ETH"][0].order_number, "120466");
+        assert_eq!(orders["BTC_ETH"][0].date,
+                   Some(Utc.with_ymd_and_hms(2018, 1, 2, 3, 4, 5).unwrap()));
+        assert!(!orders["BTC_ETH"][0].margin);
+
+        let raw = api.return_open_orders("BTC_ETH").unwrap();
+        assert!(raw["data"].is_array());
+
+        let api = poloniex_api(r#"{"BTC_ETH":[],"BTC_XMR":[{"orderNumber":120467,"type":"buy","rate":"0.01","amount":"1","total":"0.01"}]}"#);
+        let orders = api.typed().return_open_orders("all").unwrap();
+        assert!(orders["BTC_ETH"].is_empty());
+        assert_eq!(orders["BTC_XMR"][0].order_number, "120467");
+
+        let api = poloniex_api("[]");
+        assert!(api.typed().return_open_loan_offers().unwrap().is_empty());
+    }
+
+    #[test]
+    fn typed_margin_position_should_not_have_a_liquidation_price_of_minus_one() {
+        let api = poloniex_api(r#"{"amount":"40.94717831","total":"-0.09671314","basePrice":"0.00236190","liquidationPrice":-1,"pl":"-0.00058655","lendingFees":"-0.00000038","type":"long"}"#);
+        let positions = api.typed().get_margin_position("BTC_XMR").unwrap();
+        let position = &positions["BTC_XMR"];
+        assert_eq!(position.liquidation_price, None);
+        assert_eq!(position.lending_fees, decimal("-0.00000038"));
+        assert_eq!(position.side, "long");
+    }
+
+    #[test]
+    fn typed_buy_should_return_the_order_and_its_trades() {
+        let api = poloniex_api(r#"{"orderNumber":31226040,"resultingTrades":[{"amount":"338.8732","date":"2014-10-18 23:03:21","rate":"0.00000173","total":"0.00058625","tradeID":"16164","type":"buy"}]}"#);
+        let order = api.typed().buy("BTC_ETH", "0.00000173", "338.8732", None).unwrap();
+        assert_eq!(order.order_number, "31226040");
+        assert_eq!(order.resulting_trades[0].trade_id, "16164");
+        assert_eq!(order.resulting_trades[0].date,
+                   Utc.with_ymd_and_hms(2014, 10, 18, 23, 3, 21).unwrap());
+    }
+
+    #[test]
+    fn typed_errors_should_be_the_errors_of_the_raw_api() {
+        let api = poloniex_api(r#"{"error":"Invalid order number, or you are not the person who placed the order."}"#);
+        assert_eq!(api.typed().cancel_order("1").unwrap_err().to_string(),
+                   ErrorKind::OrderNotFound.to_string());
+
+        let api = poloniex_api(r#"{"makerFee":"0.0014","takerFee":true}"#);
+        assert_eq!(api.typed().return_free_info().unwrap_err().to_string(),
+                   ErrorKind::BadParse.to_string());
+    }
 }
@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod rate_limit_tests {
+    extern crate coinnect;
+
+    use std::time::Duration;
+
+    use self::coinnect::error::ErrorKind;
+    use self::coinnect::exchange::Exchange;
+    use self::coinnect::kraken::{AsyncKrakenApi, KrakenApi, KrakenCreds};
+    use self::coinnect::rate_limit::{get_cost, Bucket, BucketConfig, Cost, Policy, RateLimiter,
+                                     Scope};
+
+    fn limiter(policy: Policy) -> RateLimiter {
+        let buckets = vec![(Bucket::Private, BucketConfig::new(2.0, 0.5))];
+        let limiter = RateLimiter::with_buckets(Exchange::Kraken, buckets);
+        limiter.set_policy(policy);
+        limiter
+    }
+
+    #[test]
+    fn kraken_private_calls_should_follow_the_call_counter_rules() {
+        assert_eq!(get_cost(Exchange::Kraken, Scope::Private, "Balance"),
+                   Cost::new(Bucket::Private, 1.0));
+        assert_eq!(get_cost(Exchange::Kraken, Scope::Private, "Ledgers"),
+                   Cost::new(Bucket::Private, 2.0));
+        assert_eq!(get_cost(Exchange::Kraken, Scope::Private, "TradesHistory"),
+                   Cost::new(Bucket::Private, 2.0));
+        assert_eq!(get_cost(Exchange::Kraken, Scope::Private, "AddOrder"),
+                   Cost::new(Bucket::Orders, 1.0));
+        assert_eq!(get_cost(Exchange::Kraken, Scope::Public, "Ticker"),
+                   Cost::new(Bucket::Public, 1.0));
+    }
+
+    #[test]
+    fn exchanges_with_a_single_limit_should_use_a_single_bucket() {
+        assert_eq!(get_cost(Exchange::Poloniex, Scope::Private, "buy"),
+                   Cost::new(Bucket::Public, 1.0));
+        assert_eq!(get_cost(Exchange::Bittrex, Scope::Private, "balances"),
+                   Cost::new(Bucket::Public, 1.0));
+        assert_eq!(get_cost(Exchange::Gdax, Scope::Private, "balance"),
+                   Cost::new(Bucket::Private, 1.0));
+    }
+
+    #[test]
+    fn throttled_requests_should_wait_once_the_bucket_is_empty() {
+        let limiter = limiter(Policy::Throttle);
+
+        assert_eq!(limiter.reserve(Scope::Private, "Balance").unwrap(), Duration::from_millis(0));
+        assert_eq!(limiter.reserve(Scope::Private, "Balance").unwrap(), Duration::from_millis(0));
+
+        // The tokens are taken in advance: each request waits for the previous one
+        let wait = limiter.reserve(Scope::Private, "Balance").unwrap();
+        assert!(wait > Duration::from_millis(1900) && wait <= Duration::from_millis(2000));
+        let wait = limiter.reserve(Scope::Private, "Ledgers").unwrap();
+        assert!(wait > Duration::from_millis(5900) && wait <= Duration::from_millis(6000));
+    }
+
+    #[test]
+    fn rejected_requests_should_fail_without_taking_tokens() {
+        let limiter = limiter(Policy::Reject);
+
+        limiter.reserve(Scope::Private, "Ledgers").unwrap();
+        assert_eq!(limiter.reserve(Scope::Private, "Balance").unwrap_err().to_string(),
+                   ErrorKind::RateLimitExceeded.to_string());
+
+        limiter.set_policy(Policy::Throttle);
+        let wait = limiter.reserve(Scope::Private, "Balance").unwrap();
+        assert!(wait > Duration::from_millis(1900) && wait <= Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn burst_requests_should_never_wait() {
+        let limiter = limiter(Policy::Burst);
+
+        for _ in 0..10 {
+            assert_eq!(limiter.reserve(Scope::Private, "Ledgers").unwrap(),
+                       Duration::from_millis(0));
+        }
+    }
+
+    #[test]
+    fn requests_without_bucket_should_not_be_limited() {
+        let limiter = limiter(Policy::Reject);
+
+        for _ in 0..10 {
+            assert_eq!(limiter.reserve(Scope::Public, "Ticker").unwrap(),
+                       Duration::from_millis(0));
+        }
+    }
+
+    #[test]
+    fn clients_using_the_same_api_key_should_share_their_limiter() {
+        let first = AsyncKrakenApi::new(KrakenCreds::new("test", "shared_key", "")).unwrap();
+        let second = KrakenApi::new(KrakenCreds::new("test", "shared_key", "")).unwrap();
+        let other = AsyncKrakenApi::new(KrakenCreds::new("test", "other_key", "")).unwrap();
+
+        first.config().rate_limiter().set_policy(Policy::Burst);
+        assert_eq!(second.config().rate_limiter().policy(), Policy::Burst);
+        assert_eq!(other.config().rate_limiter().policy(), Policy::Throttle);
+
+        second.config().rate_limiter().set_policy(Policy::Throttle);
+        assert_eq!(first.config().rate_limiter().policy(), Policy::Throttle);
+    }
+
+    #[test]
+    fn a_limiter_should_be_replaceable() {
+        let mut api = KrakenApi::new(KrakenCreds::new("test", "replaced_key", "")).unwrap();
+        let buckets = vec![(Bucket::Private, BucketConfig::new(20.0, 1.0))];
+        let limiter = RateLimiter::with_buckets(Exchange::Kraken, buckets);
+        api.config_mut().set_rate_limiter(limiter.clone());
+
+        limiter.set_policy(Policy::Reject);
+        assert_eq!(api.config().rate_limiter().policy(), Policy::Reject);
+        assert_eq!(RateLimiter::shared(Exchange::Kraken, "replaced_key").policy(),
+                   Policy::Throttle);
+    }
+}
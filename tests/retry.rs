@@ -0,0 +1,172 @@
+mod common;
+
+#[cfg(test)]
+mod retry_tests {
+    extern crate coinnect;
+
+    use std::time::Duration;
+
+    use self::coinnect::error::*;
+    use self::coinnect::exchange::Exchange;
+    use self::coinnect::kraken::{AsyncKrakenApi, KrakenCreds};
+    use self::coinnect::rate_limit::{RateLimiter, Scope};
+    use self::coinnect::retry::{get_idempotency, Idempotency, RetryPolicy};
+
+    use crate::common::{self, recording, FixtureTransport, Requests};
+
+    const SERVER_TIME: &str = r#"{"error":[],"result":{"unixtime":1508923400,"rfc1123":"Wed, 25 Oct 17 09:23:20 +0000"}}"#;
+    const ORDER_ADDED: &str = r#"{"error":[],"result":{"descr":{"order":"buy 1.0 XBTEUR @ limit 5000"},"txid":["OAVY7T-MV5VK-KHDF5X"]}}"#;
+    const INVALID_NONCE: &str = r#"{"error":["EAPI:Invalid nonce"]}"#;
+
+    fn fast_policy(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::new(max_attempts).backoff(Duration::from_millis(1), Duration::from_millis(1))
+    }
+
+    fn kraken_api(bodies: Vec<Option<&'static str>>,
+                  policy: RetryPolicy)
+                  -> (AsyncKrakenApi, Requests) {
+        let transport = FixtureTransport::sequence(bodies);
+        let requests = transport.requests();
+        let creds = KrakenCreds::new("test", "retry_key", "c2VjcmV0");
+        let mut api = AsyncKrakenApi::new_with_transport(creds, Box::new(transport)).unwrap();
+        api.config_mut().set_rate_limiter(RateLimiter::with_buckets(Exchange::Kraken, Vec::new()));
+        api.config_mut().set_retry_policy(policy);
+        (api, requests)
+    }
+
+    async fn add_order(api: &AsyncKrakenApi) -> Result<()> {
+        api.add_standard_order("XXBTZEUR", "buy", "limit", "5000", "", "1.0", "", "", "", "",
+                                "", "")
+            .await
+            .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn public_requests_should_be_retried_after_a_transient_error() {
+        let (api, requests) = kraken_api(vec![None, None, Some(SERVER_TIME)], fast_policy(3));
+
+        assert!(api.get_server_time().await.is_ok());
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn requests_should_fail_after_the_last_attempt() {
+        let (api, requests) = kraken_api(vec![None, None, Some(SERVER_TIME)], fast_policy(2));
+
+        assert_eq!(api.get_server_time().await.unwrap_err().to_string(),
+                   ErrorKind::ServiceUnavailable("timeout".to_string()).to_string());
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn requests_should_be_sent_once_by_default() {
+        let (api, requests) = kraken_api(vec![None, Some(SERVER_TIME)], RetryPolicy::never());
+
+        assert!(api.get_server_time().await.is_err());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn errors_which_are_not_transient_should_not_be_retried() {
+        let (api, requests) = kraken_api(vec![Some(r#"{"error":["EAPI:Invalid key"]}"#),
+                                              Some(SERVER_TIME)],
+                                         fast_policy(3));
+
+        // The raw response is returned unchanged
+        let response = api.get_account_balance().await.unwrap();
+        assert_eq!(response["error"][0], "EAPI:Invalid key");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn orders_should_not_be_retried_after_a_timeout() {
+        let (api, requests) = kraken_api(vec![None, Some(ORDER_ADDED)], fast_policy(3));
+
+        assert!(add_order(&api).await.is_err());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn orders_rejected_by_the_exchange_should_be_retried_with_a_new_nonce() {
+        let (api, requests) = kraken_api(vec![Some(INVALID_NONCE), Some(ORDER_ADDED)],
+                                         fast_policy(3));
+
+        assert!(add_order(&api).await.is_ok());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_ne!(requests[0].body, requests[1].body);
+    }
+
+    #[tokio::test]
+    async fn retryable_errors_should_be_configurable() {
+        let policy = fast_policy(3).retry_on(|kind| matches!(*kind, ErrorKind::BadCredentials));
+        let (api, requests) = kraken_api(vec![Some(r#"{"error":["EAPI:Invalid key"]}"#),
+                                              None,
+                                              Some(SERVER_TIME)],
+                                         policy);
+
+        assert!(api.get_server_time().await.is_err());
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn blocking_clients_should_use_the_retry_policy() {
+        let transport = FixtureTransport::sequence(vec![None, Some(SERVER_TIME)]);
+        let (mut api, requests) = recording(common::kraken_api, transport);
+        api.config_mut().set_retry_policy(fast_policy(2));
+
+        assert!(api.get_server_time().is_ok());
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn backoff_should_grow_exponentially_up_to_the_maximum() {
+        let policy = RetryPolicy::new(10)
+            .backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .jitter(false);
+
+        assert_eq!(policy.get_backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.get_backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.get_backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.get_backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.get_backoff(1000), Duration::from_secs(1));
+
+        let policy = policy.multiplier(3.0);
+        assert_eq!(policy.get_backoff(2), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn jitter_should_keep_the_backoff_between_half_and_all_of_it() {
+        let policy = RetryPolicy::new(3)
+            .backoff(Duration::from_millis(100), Duration::from_secs(1));
+
+        for _ in 0..100 {
+            let backoff = policy.get_backoff(2);
+            assert!(backoff >= Duration::from_millis(100));
+            assert!(backoff <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn only_reading_requests_should_be_idempotent() {
+        assert_eq!(get_idempotency(Exchange::Kraken, Scope::Public, "Ticker"),
+                   Idempotency::Idempotent);
+        assert_eq!(get_idempotency(Exchange::Kraken, Scope::Private, "Balance"),
+                   Idempotency::Idempotent);
+        assert_eq!(get_idempotency(Exchange::Kraken, Scope::Private, "AddOrder"),
+                   Idempotency::NonIdempotent);
+        assert_eq!(get_idempotency(Exchange::Poloniex, Scope::Private, "returnBalances"),
+                   Idempotency::Idempotent);
+        assert_eq!(get_idempotency(Exchange::Poloniex, Scope::Private, "buy"),
+                   Idempotency::NonIdempotent);
+        assert_eq!(get_idempotency(Exchange::Bittrex, Scope::Private, "place_order"),
+                   Idempotency::NonIdempotent);
+        assert_eq!(get_idempotency(Exchange::Bittrex, Scope::Private, "open_orders"),
+                   Idempotency::Idempotent);
+        assert_eq!(get_idempotency(Exchange::Bitstamp, Scope::Private, "cancel_order"),
+                   Idempotency::NonIdempotent);
+        assert_eq!(get_idempotency(Exchange::Gdax, Scope::Private, "open_orders"),
+                   Idempotency::Idempotent);
+    }
+}
@@ -0,0 +1,347 @@
+mod common;
+
+#[cfg(test)]
+mod transport_tests {
+    extern crate coinnect;
+    extern crate bigdecimal;
+    extern crate chrono;
+
+    use self::bigdecimal::BigDecimal;
+    use std::str::FromStr;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use self::chrono::{TimeZone, Utc};
+
+    use self::coinnect::coinnect::Coinnect;
+    use self::coinnect::error::*;
+    use self::coinnect::exchange::{AsyncExchangeApi, Exchange, ExchangeApi};
+    use self::coinnect::bitstamp::{AsyncBitstampApi, BitstampApi};
+    use self::coinnect::bittrex::{AsyncBittrexApi, BittrexApi, BittrexCreds};
+    use self::coinnect::gdax::{AsyncGdaxApi, GdaxApi, GdaxCreds};
+    use self::coinnect::kraken::{AsyncKrakenApi, KrakenApi, KrakenCreds};
+    use self::coinnect::poloniex::{AsyncPoloniexApi, PoloniexApi, PoloniexCreds};
+    use self::coinnect::rate_limit::{Bucket, BucketConfig, RateLimiter};
+    use self::coinnect::transport::{parse_retry_after, HttpResponse, Method};
+    use self::coinnect::types::{Currency, Pair};
+
+    use crate::common::{header, FixtureTransport};
+
+    #[test]
+    fn kraken_private_requests_should_be_signed() {
+        let transport = FixtureTransport::new(r#"{"error":[],"result":{"XXBT":"1.5","ZEUR":"200.25"}}"#);
+        let requests = transport.requests();
+        let creds = KrakenCreds::new("test", "my_key", "c2VjcmV0");
+        let api = Coinnect::new_with_transport(Exchange::Kraken, creds, Box::new(transport))
+            .unwrap();
+
+        let balances = api.balances().unwrap();
+        assert_eq!(balances[&Currency::BTC], BigDecimal::from_str("1.5").unwrap());
+        assert_eq!(balances[&Currency::EUR], BigDecimal::from_str("200.25").unwrap());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, Method::Post);
+        assert_eq!(requests[0].url, "https://api.kraken.com/0/private/Balance");
+        assert_eq!(header(&requests[0], "API-Key"), Some("my_key"));
+        assert!(!header(&requests[0], "API-Sign").unwrap_or("").is_empty());
+        assert!(requests[0].body.contains("nonce="));
+    }
+
+    #[test]
+    fn kraken_errors_should_be_parsed_from_the_response() {
+        let transport = FixtureTransport::new(r#"{"error":["EAPI:Invalid key"]}"#);
+        let creds = KrakenCreds::new("test", "my_key", "c2VjcmV0");
+        let api = Coinnect::new_with_transport(Exchange::Kraken, creds, Box::new(transport))
+            .unwrap();
+
+        assert_eq!(api.balances().unwrap_err().to_string(),
+                   ErrorKind::BadCredentials.to_string());
+    }
+
+    #[test]
+    fn poloniex_ticker_should_be_parsed_from_the_response() {
+        let transport = FixtureTransport::new(r#"{"BTC_ETH":{"last":"0.0251","lowestAsk":"0.0252","highestBid":"0.0250","quoteVolume":"1234.5"}}"#);
+        let requests = transport.requests();
+        let creds = PoloniexCreds::new("test", "", "");
+        let api = PoloniexApi::new_with_transport(creds, Box::new(transport)).unwrap();
+
+        let ticker = api.ticker(Pair::ETH_BTC).unwrap();
+        assert_eq!(ticker.last_trade_price, BigDecimal::from_str("0.0251").unwrap());
+        assert_eq!(ticker.lowest_ask, BigDecimal::from_str("0.0252").unwrap());
+        assert_eq!(ticker.highest_bid, BigDecimal::from_str("0.0250").unwrap());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].method, Method::Get);
+        assert!(requests[0].url.starts_with("https://poloniex.com/public?command=returnTicker"));
+    }
+
+    #[test]
+    fn bittrex_private_requests_should_be_signed() {
+        let transport = FixtureTransport::new(r#"[{"currencySymbol":"BCH","total":"2.5","available":"2.5"}]"#);
+        let requests = transport.requests();
+        let creds = BittrexCreds::new("test", "my_key", "my_secret");
+        let api = BittrexApi::new_with_transport(creds, Box::new(transport)).unwrap();
+
+        let balances = api.balances().unwrap();
+        assert_eq!(balances[&Currency::BCH], BigDecimal::from_str("2.5").unwrap());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(header(&requests[0], "Api-Key"), Some("my_key"));
+        assert_eq!(header(&requests[0], "Api-Signature").map(|sign| sign.len()), Some(128));
+    }
+
+    #[test]
+    fn gdax_public_requests_should_send_a_user_agent() {
+        let transport = FixtureTransport::new(r#"{"price":"6512.3","ask":"6512.4","bid":"6512.2","volume":"1024.7"}"#);
+        let requests = transport.requests();
+        let creds = GdaxCreds::new("test", "", "", "");
+        let api = GdaxApi::new_with_transport(creds, Box::new(transport)).unwrap();
+
+        let ticker = api.ticker(Pair::BTC_EUR).unwrap();
+        assert_eq!(ticker.last_trade_price, BigDecimal::from_str("6512.3").unwrap());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(header(&requests[0], "User-Agent"), Some("coinnect"));
+    }
+
+    #[test]
+    fn the_exchange_of_the_credentials_should_still_be_checked() {
+        let transport = FixtureTransport::new("{}");
+        let creds = KrakenCreds::new("test", "", "");
+        let res = Coinnect::new_with_transport(Exchange::Bitstamp, creds, Box::new(transport));
+
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::InvalidConfigType(Exchange::Bitstamp, Exchange::Kraken).to_string());
+    }
+
+    #[test]
+    fn response_headers_should_be_case_insensitive() {
+        let response = HttpResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: String::new(),
+        };
+
+        assert_eq!(response.header("content-type"), Some("application/json"));
+        assert_eq!(response.header("Retry-After"), None);
+    }
+
+    #[test]
+    fn too_many_requests_should_be_a_rate_limit_error() {
+        let body = r#"{"error":["EGeneral:Too many requests"]}"#;
+        let transport = FixtureTransport::with_status(429, vec![("Retry-After", "30")], body);
+        let creds = KrakenCreds::new("test", "my_key", "c2VjcmV0");
+        let api = KrakenApi::new_with_transport(creds, Box::new(transport)).unwrap();
+
+        let err = api.get_account_balance().unwrap_err();
+        assert_eq!(err.to_string(), ErrorKind::RateLimitExceeded.to_string());
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(30)));
+        assert_eq!(err.exchange(), Some(Exchange::Kraken));
+        assert_eq!(err.endpoint(), Some("Balance"));
+        assert!(err.is_retryable());
+
+        let http_error = err.http_error().unwrap();
+        assert_eq!(http_error.status, 429);
+        assert_eq!(http_error.body, body);
+    }
+
+    #[test]
+    fn an_html_page_from_a_proxy_should_be_a_service_error() {
+        let page = "<html><body><h1>502 Bad Gateway</h1></body></html>";
+        let transport = FixtureTransport::with_status(502, Vec::new(), page);
+        let creds = PoloniexCreds::new("test", "", "");
+        let api = Coinnect::new_with_transport(Exchange::Poloniex, creds, Box::new(transport))
+            .unwrap();
+
+        let err = api.ticker(Pair::ETH_BTC).unwrap_err();
+        assert_eq!(err.to_string(),
+                   ErrorKind::ServiceUnavailable("HTTP status 502".to_string()).to_string());
+        assert_eq!(err.retry_after(), None);
+        assert_eq!(err.http_error().unwrap().body, page);
+    }
+
+    #[test]
+    fn an_html_page_with_a_forbidden_status_should_be_a_permission_error() {
+        let page = "<html><body><h1>403 Forbidden</h1></body></html>";
+        let transport = FixtureTransport::with_status(403, Vec::new(), page);
+        let creds = KrakenCreds::new("test", "", "");
+        let api = Coinnect::new_with_transport(Exchange::Kraken, creds, Box::new(transport))
+            .unwrap();
+
+        let err = api.ticker(Pair::BTC_EUR).unwrap_err();
+        assert_eq!(err.to_string(), ErrorKind::PermissionDenied.to_string());
+        assert_eq!(err.http_error().unwrap().status, 403);
+        assert_eq!(err.http_error().unwrap().body, page);
+    }
+
+    #[test]
+    fn an_html_page_with_a_not_found_status_should_keep_the_response() {
+        let page = "<html><body><h1>404 Not Found</h1></body></html>";
+        let transport = FixtureTransport::with_status(404, Vec::new(), page);
+        let creds = BittrexCreds::new("test", "", "");
+        let api = BittrexApi::new_with_transport(creds, Box::new(transport)).unwrap();
+
+        let err = api.get_ticker("BTC-ETH").unwrap_err();
+        assert_eq!(err.to_string(),
+                   ErrorKind::ExchangeSpecificError("HTTP status 404".to_string()).to_string());
+        assert_eq!(err.http_error().unwrap().status, 404);
+        assert_eq!(err.http_error().unwrap().body, page);
+    }
+
+    #[test]
+    fn a_successful_response_which_is_not_json_should_keep_the_response() {
+        let transport = FixtureTransport::with_status(200, Vec::new(), "maintenance");
+        let creds = GdaxCreds::new("test", "", "", "");
+        let api = GdaxApi::new_with_transport(creds, Box::new(transport)).unwrap();
+
+        let err = api.return_ticker(Pair::BTC_USD).unwrap_err();
+        assert_eq!(err.to_string(), ErrorKind::BadParse.to_string());
+        assert_eq!(err.http_error().unwrap().status, 200);
+        assert_eq!(err.http_error().unwrap().body, "maintenance");
+    }
+
+    #[test]
+    fn unauthorized_requests_should_be_a_credentials_error() {
+        let transport = FixtureTransport::with_status(401, Vec::new(), r#"{"message":"invalid signature"}"#);
+        let creds = GdaxCreds::new("test", "my_key", "c2VjcmV0", "passphrase");
+        let api = Coinnect::new_with_transport(Exchange::Gdax, creds, Box::new(transport))
+            .unwrap();
+
+        assert_eq!(api.balances().unwrap_err().to_string(),
+                   ErrorKind::BadCredentials.to_string());
+    }
+
+    #[test]
+    fn other_statuses_should_be_left_to_the_exchange_apis() {
+        let transport = FixtureTransport::with_status(400, Vec::new(), r#"{"code":"MARKET_DOES_NOT_EXIST"}"#);
+        let creds = BittrexCreds::new("test", "", "");
+        let api = BittrexApi::new_with_transport(creds, Box::new(transport)).unwrap();
+
+        let response = api.get_ticker("XYZ-BTC").unwrap();
+        assert_eq!(response["code"], "MARKET_DOES_NOT_EXIST");
+    }
+
+    #[test]
+    fn retry_after_should_accept_seconds_and_dates() {
+        let now = Utc.with_ymd_and_hms(2015, 10, 21, 7, 28, 0).unwrap();
+
+        assert_eq!(parse_retry_after("120", now), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:45 GMT", now),
+                   Some(Duration::from_secs(45)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:27:00 GMT", now),
+                   Some(Duration::from_secs(0)));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[tokio::test]
+    async fn async_kraken_private_requests_should_be_signed() {
+        let transport = FixtureTransport::new(r#"{"error":[],"result":{"XXBT":"1.5"}}"#);
+        let requests = transport.requests();
+        let creds = KrakenCreds::new("test", "my_key", "c2VjcmV0");
+        let api =
+            Coinnect::new_async_with_transport(Exchange::Kraken, creds, Box::new(transport))
+                .unwrap();
+
+        let balances = api.balances().await.unwrap();
+        assert_eq!(balances[&Currency::BTC], BigDecimal::from_str("1.5").unwrap());
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].url, "https://api.kraken.com/0/private/Balance");
+        assert_eq!(header(&requests[0], "API-Key"), Some("my_key"));
+    }
+
+    #[tokio::test]
+    async fn async_tickers_should_be_fetched_concurrently() {
+        let transport = FixtureTransport::new(r#"{"BTC_ETH":{"last":"0.0251","lowestAsk":"0.0252","highestBid":"0.0250","quoteVolume":"1234.5"}}"#);
+        let creds = PoloniexCreds::new("test", "", "");
+        let poloniex = AsyncPoloniexApi::new_with_transport(creds, Box::new(transport)).unwrap();
+
+        let transport = FixtureTransport::new(r#"{"price":"6512.3","ask":"6512.4","bid":"6512.2","volume":"1024.7"}"#);
+        let creds = GdaxCreds::new("test", "", "", "");
+        let gdax = AsyncGdaxApi::new_with_transport(creds, Box::new(transport)).unwrap();
+
+        let (poloniex_ticker, gdax_ticker) = tokio::join!(poloniex.ticker(Pair::ETH_BTC),
+                                                          gdax.ticker(Pair::BTC_EUR));
+
+        assert_eq!(poloniex_ticker.unwrap().last_trade_price,
+                   BigDecimal::from_str("0.0251").unwrap());
+        assert_eq!(gdax_ticker.unwrap().last_trade_price,
+                   BigDecimal::from_str("6512.3").unwrap());
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn clients_should_be_send_and_sync() {
+        assert_send_sync::<KrakenApi>();
+        assert_send_sync::<AsyncKrakenApi>();
+        assert_send_sync::<BitstampApi>();
+        assert_send_sync::<AsyncBitstampApi>();
+        assert_send_sync::<BittrexApi>();
+        assert_send_sync::<AsyncBittrexApi>();
+        assert_send_sync::<PoloniexApi>();
+        assert_send_sync::<AsyncPoloniexApi>();
+        assert_send_sync::<GdaxApi>();
+        assert_send_sync::<AsyncGdaxApi>();
+        assert_send_sync::<Box<dyn ExchangeApi>>();
+        assert_send_sync::<Box<dyn AsyncExchangeApi>>();
+    }
+
+    #[test]
+    fn a_client_shared_between_threads_should_send_distinct_nonces() {
+        let transport = FixtureTransport::new(r#"{"error":[],"result":{"XXBT":"1.5"}}"#);
+        let requests = transport.requests();
+        let creds = KrakenCreds::new("test", "my_key", "c2VjcmV0");
+        let api: Arc<dyn ExchangeApi> =
+            Coinnect::new_with_transport(Exchange::Kraken, creds, Box::new(transport))
+                .unwrap()
+                .into();
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                     let api = api.clone();
+                     thread::spawn(move || api.balances().unwrap())
+                 })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap()[&Currency::BTC],
+                       BigDecimal::from_str("1.5").unwrap());
+        }
+
+        let requests = requests.lock().unwrap();
+        let nonces: HashSet<&str> = requests
+            .iter()
+            .flat_map(|request| request.body.split('&'))
+            .filter(|param| param.starts_with("nonce="))
+            .collect();
+        assert_eq!(requests.len(), 8);
+        assert_eq!(nonces.len(), 8);
+    }
+
+    #[test]
+    fn a_slow_request_should_not_delay_the_throttled_requests_of_other_threads() {
+        let creds = KrakenCreds::new("test", "slow_key", "");
+        let transport = FixtureTransport::new(r#"{"error":[],"result":{}}"#)
+            .slow_on("/Time", Duration::from_millis(1500));
+        let mut api = KrakenApi::new_with_transport(creds, Box::new(transport)).unwrap();
+        let buckets = vec![(Bucket::Public, BucketConfig::new(1.0, 5.0))];
+        api.config_mut().set_rate_limiter(RateLimiter::with_buckets(Exchange::Kraken, buckets));
+        let api = Arc::new(api);
+
+        let slow = {
+            let api = api.clone();
+            thread::spawn(move || api.get_server_time().unwrap())
+        };
+        thread::sleep(Duration::from_millis(100));
+
+        // The bucket is still empty: the request waits about 100ms, not for the slow request
+        let start = Instant::now();
+        api.get_ticker_information("XXBTZEUR").unwrap();
+        assert!(start.elapsed() < Duration::from_millis(1000));
+
+        slow.join().unwrap();
+    }
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod types_tests {
+    extern crate coinnect;
+    extern crate bigdecimal;
+
+    use self::bigdecimal::BigDecimal;
+    use std::str::FromStr;
+
+    use self::coinnect::types::{Currency, MarketInfo, OrderType, Pair};
+    use self::coinnect::error::ErrorKind;
+
+    fn btc_eur_market() -> MarketInfo {
+        MarketInfo {
+            pair: Pair::BTC_EUR,
+            min_order_size: Some(BigDecimal::from_str("0.002").unwrap()),
+            tick_size: Some(BigDecimal::from_str("0.1").unwrap()),
+            lot_decimals: Some(8),
+            min_notional: Some(BigDecimal::from_str("10").unwrap()),
+            active: true,
+        }
+    }
+
+    #[test]
+    fn check_order_should_round_a_buy_order_down() {
+        let (quantity, price) = btc_eur_market()
+            .check_order(OrderType::BuyLimit,
+                         BigDecimal::from_str("0.0123456789").unwrap(),
+                         Some(BigDecimal::from_str("5812.97").unwrap()))
+            .unwrap();
+
+        assert_eq!(quantity, BigDecimal::from_str("0.01234567").unwrap());
+        assert_eq!(price, Some(BigDecimal::from_str("5812.9").unwrap()));
+    }
+
+    #[test]
+    fn check_order_should_round_a_sell_price_up() {
+        let (_, price) = btc_eur_market()
+            .check_order(OrderType::SellLimit,
+                         BigDecimal::from_str("0.01").unwrap(),
+                         Some(BigDecimal::from_str("5812.91").unwrap()))
+            .unwrap();
+
+        assert_eq!(price, Some(BigDecimal::from_str("5813.0").unwrap()));
+    }
+
+    #[test]
+    fn check_order_should_keep_a_price_on_the_tick() {
+        let (_, price) = btc_eur_market()
+            .check_order(OrderType::SellLimit,
+                         BigDecimal::from_str("0.01").unwrap(),
+                         Some(BigDecimal::from_str("5812.9").unwrap()))
+            .unwrap();
+
+        assert_eq!(price, Some(BigDecimal::from_str("5812.9").unwrap()));
+    }
+
+    #[test]
+    fn check_order_should_reject_an_order_under_the_minimum_size() {
+        let res = btc_eur_market().check_order(OrderType::BuyLimit,
+                                               BigDecimal::from_str("0.001").unwrap(),
+                                               Some(BigDecimal::from_str("5812.9").unwrap()));
+
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::InsufficientOrderSize.to_string());
+    }
+
+    #[test]
+    fn check_order_should_reject_an_order_under_the_minimum_value() {
+        let res = btc_eur_market().check_order(OrderType::BuyLimit,
+                                               BigDecimal::from_str("0.003").unwrap(),
+                                               Some(BigDecimal::from_str("3000").unwrap()));
+
+        assert_eq!(res.unwrap_err().to_string(),
+                   ErrorKind::InsufficientOrderSize.to_string());
+    }
+
+    #[test]
+    fn check_order_should_reject_a_limit_order_without_price() {
+        let res = btc_eur_market().check_order(OrderType::SellLimit,
+                                               BigDecimal::from_str("0.01").unwrap(),
+                                               None);
+
+        assert_eq!(res.unwrap_err().to_string(), ErrorKind::MissingPrice.to_string());
+    }
+
+    #[test]
+    fn check_order_should_reject_a_quantity_rounded_to_zero() {
+        let res = btc_eur_market().check_order(OrderType::BuyMarket,
+                                               BigDecimal::from_str("0.000000001").unwrap(),
+                                               None);
+
+        assert_eq!(res.unwrap_err().to_string(), ErrorKind::InvalidArguments.to_string());
+    }
+
+    #[test]
+    fn check_order_should_accept_a_market_order_without_price() {
+        let (quantity, price) = btc_eur_market()
+            .check_order(OrderType::BuyMarket, BigDecimal::from_str("0.01").unwrap(), None)
+            .unwrap();
+
+        assert_eq!(quantity, BigDecimal::from_str("0.01").unwrap());
+        assert_eq!(price, None);
+    }
+
+    #[test]
+    fn check_order_should_reject_an_inactive_market() {
+        let mut market = btc_eur_market();
+        market.active = false;
+
+        let res = market.check_order(OrderType::BuyLimit,
+                                     BigDecimal::from_str("0.01").unwrap(),
+                                     Some(BigDecimal::from_str("5812.9").unwrap()));
+
+        assert_eq!(res.unwrap_err().to_string(), ErrorKind::MarketInactive.to_string());
+    }
+
+    #[test]
+    fn currency_should_be_created_from_any_symbol() {
+        assert_eq!(Currency::new("btc"), Currency::BTC);
+        assert_eq!(Currency::new("1ST"), Currency::_1ST);
+        assert_eq!(Currency::new("dot").symbol(), "DOT");
+        assert_eq!(format!("{:?}", Currency::new("dot")), "DOT");
+        assert!("".parse::<Currency>().is_err());
+    }
+
+    #[test]
+    fn pair_should_be_created_from_any_currencies() {
+        let dot_eur = Pair::new(Currency::new("DOT"), Currency::EUR);
+
+        assert_eq!(Pair::new(Currency::BTC, Currency::EUR), Pair::BTC_EUR);
+        assert_eq!(dot_eur.quote(), Currency::new("DOT"));
+        assert_eq!(dot_eur.base(), Currency::EUR);
+        assert_eq!(dot_eur.to_string(), "DOT_EUR");
+        assert_eq!(Pair::BTC_EUR_d.to_string(), "BTC_EUR_d");
+        assert_eq!(format!("{:?}", Pair::_1ST_BTC), "1ST_BTC");
+    }
+
+    #[test]
+    fn pair_should_be_parsed_from_its_name() {
+        assert_eq!("DOT_EUR".parse::<Pair>().unwrap(),
+                   Pair::new(Currency::new("DOT"), Currency::EUR));
+        assert_eq!("BTC_EUR_d".parse::<Pair>().unwrap(), Pair::BTC_EUR_d);
+        assert!("BTCEUR".parse::<Pair>().is_err());
+        assert!("BTC_EUR_USD".parse::<Pair>().is_err());
+    }
+}